@@ -1,3 +1,4 @@
 pub mod backend;
+pub mod client_config;
 pub mod gui;
 pub mod server_communication;
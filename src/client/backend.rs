@@ -2,9 +2,14 @@ pub mod login_state;
 pub mod pop_up_manager;
 pub mod profile_stat_manager;
 
+pub mod account_editor;
+pub mod challenge_editor;
+pub mod custom_exercise_editor;
+pub mod exercise_catalog_cache;
 pub mod exercise_create;
 pub mod exercise_manager;
 pub mod image_manager;
+pub mod log_entry_editor;
 pub mod mascot_manager;
 pub mod recent_workouts;
 pub mod user_manager;
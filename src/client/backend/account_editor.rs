@@ -0,0 +1,83 @@
+use crate::common::user_mod::account::{AccountDeletion, PasswordChange, UsernameChange};
+
+/// Inputs of the account section in the settings. Every change needs the current password
+#[derive(Debug, Default)]
+pub struct AccountEditor {
+    pub current_password: String,
+    pub new_username: String,
+    pub new_password: String,
+    /// The account is only deleted once the deletion is confirmed with a second press
+    pub deletion_requested: bool,
+}
+
+const CURRENT_PASSWORD_MISSING: &str = "Enter your current password first";
+
+impl AccountEditor {
+    pub fn parse_password_change(&self) -> Result<PasswordChange, &'static str> {
+        let password_change = PasswordChange {
+            current_password: self.current_password_or_error()?,
+            new_password: self.new_password.clone(),
+        };
+        match password_change.validation_error() {
+            Some(reason) => Err(reason),
+            None => Ok(password_change),
+        }
+    }
+
+    pub fn parse_username_change(&self) -> Result<UsernameChange, &'static str> {
+        let username_change = UsernameChange {
+            password: self.current_password_or_error()?,
+            new_username: self.new_username.trim().to_string(),
+        };
+        match username_change.validation_error() {
+            Some(reason) => Err(reason),
+            None => Ok(username_change),
+        }
+    }
+
+    pub fn parse_account_deletion(&self) -> Result<AccountDeletion, &'static str> {
+        Ok(AccountDeletion {
+            password: self.current_password_or_error()?,
+        })
+    }
+
+    fn current_password_or_error(&self) -> Result<String, &'static str> {
+        if self.current_password.is_empty() {
+            Err(CURRENT_PASSWORD_MISSING)
+        } else {
+            Ok(self.current_password.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_need_the_current_password() {
+        let mut account_editor = AccountEditor {
+            new_username: " anna ".to_string(),
+            new_password: "new".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            account_editor.parse_account_deletion().err(),
+            Some(CURRENT_PASSWORD_MISSING)
+        );
+        assert!(account_editor.parse_username_change().is_err());
+
+        account_editor.current_password = "old".to_string();
+        assert_eq!(
+            account_editor.parse_username_change().unwrap().new_username,
+            "anna"
+        );
+        assert_eq!(
+            account_editor.parse_password_change().unwrap().new_password,
+            "new"
+        );
+
+        account_editor.new_password = "old".to_string();
+        assert!(account_editor.parse_password_change().is_err());
+    }
+}
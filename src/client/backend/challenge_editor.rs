@@ -0,0 +1,185 @@
+use crate::common::challenge::{ChallengeGoal, NewChallenge};
+use chrono::{Duration, NaiveDate};
+use strum_macros::{Display, EnumIter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter)]
+pub enum ChallengeGoalKind {
+    #[default]
+    #[strum(to_string = "Most workouts")]
+    MostWorkouts,
+    #[strum(to_string = "Most volume")]
+    MostVolume,
+    #[strum(to_string = "First to lift")]
+    FirstToLift,
+}
+
+/// Inputs of the Social tab to challenge a friend, the challenge starts on the day it is sent
+pub struct ChallengeEditor {
+    pub opponent: Option<String>,
+    pub goal_kind: ChallengeGoalKind,
+    /// Only used by `FirstToLift`
+    pub exercise: Option<String>,
+    pub weight_input: String,
+    pub days_input: String,
+    pub stake_input: String,
+}
+
+impl Default for ChallengeEditor {
+    fn default() -> Self {
+        ChallengeEditor {
+            opponent: None,
+            goal_kind: ChallengeGoalKind::default(),
+            exercise: None,
+            weight_input: String::new(),
+            days_input: "7".to_string(),
+            stake_input: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChallengeEditorError {
+    InvalidWeight,
+    InvalidDays,
+    InvalidStake,
+    NotEnoughCoins,
+    Rejected(&'static str),
+}
+
+impl ChallengeEditorError {
+    pub fn to_error_message(&self) -> String {
+        match self {
+            ChallengeEditorError::InvalidWeight => "Enter the weight as a number".to_string(),
+            ChallengeEditorError::InvalidDays => {
+                "Enter how many days the challenge lasts".to_string()
+            }
+            ChallengeEditorError::InvalidStake => {
+                "Enter the stake as a whole number of coins".to_string()
+            }
+            ChallengeEditorError::NotEnoughCoins => "Not enough coins for the stake".to_string(),
+            ChallengeEditorError::Rejected(reason) => reason.to_string(),
+        }
+    }
+}
+
+impl ChallengeEditor {
+    /// An empty stake means the challenge is just for fun
+    pub fn parse_challenge(
+        &self,
+        today: NaiveDate,
+        coin_balance: u32,
+    ) -> Result<NewChallenge, ChallengeEditorError> {
+        let days = self
+            .days_input
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|days| *days > 0)
+            .ok_or(ChallengeEditorError::InvalidDays)?;
+        let stake = match self.stake_input.trim() {
+            "" => 0,
+            stake => stake
+                .parse::<u32>()
+                .map_err(|_| ChallengeEditorError::InvalidStake)?,
+        };
+        if stake > coin_balance {
+            return Err(ChallengeEditorError::NotEnoughCoins);
+        }
+        let goal = match self.goal_kind {
+            ChallengeGoalKind::MostWorkouts => ChallengeGoal::MostWorkouts,
+            ChallengeGoalKind::MostVolume => ChallengeGoal::MostVolume,
+            ChallengeGoalKind::FirstToLift => ChallengeGoal::FirstToLift {
+                exercise_name: self.exercise.clone().unwrap_or_default(),
+                weight: self
+                    .weight_input
+                    .trim()
+                    .replace(',', ".")
+                    .parse::<f32>()
+                    .map_err(|_| ChallengeEditorError::InvalidWeight)?,
+            },
+        };
+
+        let new_challenge = NewChallenge {
+            opponent: self.opponent.clone().unwrap_or_default(),
+            goal,
+            start_date: today,
+            end_date: today + Duration::days(days as i64 - 1),
+            stake,
+        };
+        match new_challenge.validation_error(today) {
+            Some(reason) => Err(ChallengeEditorError::Rejected(reason)),
+            None => Ok(new_challenge),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+    }
+
+    #[test]
+    fn parses_challenge_starting_today() {
+        let editor = ChallengeEditor {
+            opponent: Some("anna".to_string()),
+            goal_kind: ChallengeGoalKind::FirstToLift,
+            exercise: Some("Squat".to_string()),
+            weight_input: "102,5".to_string(),
+            stake_input: "20".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            editor.parse_challenge(today(), 20),
+            Ok(NewChallenge {
+                opponent: "anna".to_string(),
+                goal: ChallengeGoal::FirstToLift {
+                    exercise_name: "Squat".to_string(),
+                    weight: 102.5,
+                },
+                start_date: today(),
+                end_date: NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+                stake: 20,
+            })
+        );
+        assert_eq!(
+            editor.parse_challenge(today(), 19),
+            Err(ChallengeEditorError::NotEnoughCoins)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let mut editor = ChallengeEditor::default();
+        assert!(matches!(
+            editor.parse_challenge(today(), 0),
+            Err(ChallengeEditorError::Rejected(_))
+        ));
+
+        editor.opponent = Some("anna".to_string());
+        assert!(editor.parse_challenge(today(), 0).is_ok());
+
+        editor.days_input = "0".to_string();
+        assert_eq!(
+            editor.parse_challenge(today(), 0),
+            Err(ChallengeEditorError::InvalidDays)
+        );
+
+        editor.days_input = "7".to_string();
+        editor.stake_input = "-5".to_string();
+        assert_eq!(
+            editor.parse_challenge(today(), 0),
+            Err(ChallengeEditorError::InvalidStake)
+        );
+
+        editor.stake_input.clear();
+        editor.goal_kind = ChallengeGoalKind::FirstToLift;
+        assert_eq!(
+            editor.parse_challenge(today(), 0),
+            Err(ChallengeEditorError::InvalidWeight)
+        );
+    }
+}
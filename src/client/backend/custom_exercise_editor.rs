@@ -0,0 +1,94 @@
+use crate::common::exercise_mod::custom_exercise::NewCustomExercise;
+use crate::common::exercise_mod::exercise::Exercise;
+use crate::common::exercise_mod::general_exercise::{
+    ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, Muscle,
+};
+use iced::widget::combo_box;
+use strum::IntoEnumIterator;
+
+/// Inputs of the form in the exercise browser to create a custom exercise
+pub struct CustomExerciseEditor {
+    pub is_open: bool,
+    pub new_custom_exercise: NewCustomExercise,
+    pub muscle_state: combo_box::State<Muscle>,
+    pub equipment_state: combo_box::State<ExerciseEquipment>,
+    pub force_state: combo_box::State<ExerciseForce>,
+    pub level_state: combo_box::State<ExerciseLevel>,
+    pub category_state: combo_box::State<ExerciseCategory>,
+}
+
+impl Default for CustomExerciseEditor {
+    fn default() -> Self {
+        CustomExerciseEditor {
+            is_open: false,
+            new_custom_exercise: NewCustomExercise {
+                name: String::new(),
+                primary_muscle: Muscle::Quadriceps,
+                equipment: ExerciseEquipment::Machine,
+                force: ExerciseForce::Push,
+                level: ExerciseLevel::Beginner,
+                category: ExerciseCategory::Strength,
+                shared_with_friends: false,
+            },
+            muscle_state: combo_box::State::new(Muscle::iter().collect()),
+            equipment_state: combo_box::State::new(ExerciseEquipment::iter().collect()),
+            force_state: combo_box::State::new(ExerciseForce::iter().collect()),
+            level_state: combo_box::State::new(ExerciseLevel::iter().collect()),
+            category_state: combo_box::State::new(ExerciseCategory::iter().collect()),
+        }
+    }
+}
+
+impl CustomExerciseEditor {
+    pub fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    /// Closes the form and resets its inputs
+    pub fn close(&mut self) {
+        *self = CustomExerciseEditor::default();
+    }
+
+    /// Returns the exercise to send to the server with a trimmed name.
+    /// `exercises` are all exercises the user can already track
+    pub fn parse(&self, exercises: &[Exercise]) -> Result<NewCustomExercise, &'static str> {
+        let existing_names = exercises
+            .iter()
+            .map(|exercise| exercise.general_exercise_info.name.as_str());
+        match self.new_custom_exercise.validation_error(existing_names) {
+            Some(reason) => Err(reason),
+            None => Ok(NewCustomExercise {
+                name: self.new_custom_exercise.name.trim().to_string(),
+                ..self.new_custom_exercise.clone()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::exercise_mod::general_exercise::GeneralExerciseInfo;
+
+    #[test]
+    fn parse_trims_name_and_rejects_existing_exercises() {
+        let mut general_exercise_info = GeneralExerciseInfo::test_obj();
+        general_exercise_info.name = "Leg Press".to_string();
+        let exercises = vec![Exercise::new(general_exercise_info)];
+        let mut editor = CustomExerciseEditor::default();
+        editor.open();
+
+        editor.new_custom_exercise.name = " Pendulum squat ".to_string();
+        assert_eq!(
+            editor.parse(&exercises).map(|exercise| exercise.name),
+            Ok("Pendulum squat".to_string())
+        );
+
+        editor.new_custom_exercise.name = "leg press".to_string();
+        assert!(editor.parse(&exercises).is_err());
+
+        editor.close();
+        assert!(!editor.is_open);
+        assert!(editor.new_custom_exercise.name.is_empty());
+    }
+}
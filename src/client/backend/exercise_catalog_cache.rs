@@ -0,0 +1,74 @@
+use crate::common::exercise_mod::general_exercise::GeneralExerciseInfo;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Exercise catalog of the server stored on disk together with its ETag,
+/// so it only has to be downloaded again after it changed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedExerciseCatalog {
+    pub etag: String,
+    pub exercises: Vec<GeneralExerciseInfo>,
+}
+
+impl CachedExerciseCatalog {
+    /// None if there is no cache yet or it can't be read
+    pub async fn load(path: &Path) -> Option<Self> {
+        let content = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Writes to a temporary file first, so an interrupted write never leaves a broken cache
+    pub async fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(folder) = path.parent()
+            && !folder.as_os_str().is_empty()
+        {
+            tokio::fs::create_dir_all(folder).await?;
+        }
+        let content = serde_json::to_vec(self)?;
+        let temporary_path = path.with_extension("tmp");
+        tokio::fs::write(&temporary_path, content).await?;
+        tokio::fs::rename(&temporary_path, path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_cache_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("buff_buddies_{}", std::process::id()))
+            .join(name)
+    }
+
+    #[tokio::test]
+    async fn saved_catalog_is_loaded_again() {
+        let path = test_cache_path("saved_catalog.json");
+        let cached_catalog = CachedExerciseCatalog {
+            etag: "\"abc\"".to_string(),
+            exercises: vec![GeneralExerciseInfo::test_obj()],
+        };
+
+        cached_catalog.save(&path).await.unwrap();
+        let loaded_catalog = CachedExerciseCatalog::load(&path).await.unwrap();
+
+        assert_eq!(loaded_catalog.etag, "\"abc\"");
+        assert_eq!(loaded_catalog.exercises.len(), 1);
+        assert_eq!(loaded_catalog.exercises[0].name, "Test exercise");
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_or_broken_cache_is_ignored() {
+        let path = test_cache_path("broken_catalog.json");
+        assert!(CachedExerciseCatalog::load(&path).await.is_none());
+
+        tokio::fs::create_dir_all(path.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(&path, "not json").await.unwrap();
+        assert!(CachedExerciseCatalog::load(&path).await.is_none());
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use crate::client::backend::custom_exercise_editor::CustomExerciseEditor;
 use crate::client::backend::exercise_create::{
     ExerciseCreate, ExerciseCreateString, StrengthSetCreate, WorkoutCreate,
 };
@@ -10,18 +11,16 @@ use crate::client::gui::bb_theme::combo_box::{
     get_combo_box_all_exercises_state, get_combo_box_tracked_exercise_state,
 };
 use crate::common::exercise_mod::exercise::{DateWeightPoints, Exercise};
-use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::exercise_mod::general_exercise::{GeneralExerciseInfo, Id};
 use crate::common::exercise_mod::set::{Reps, StrengthSet};
 use crate::common::exercise_mod::weight::Kg;
 use crate::common::user_mod::user::UserInformation;
+use crate::common::workout::{PresetTraining, is_plausible_workout_duration};
 use crate::common::workout_preset::WorkoutPreset;
-use chrono::{Local, NaiveDate};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
 use iced::widget::combo_box;
 use std::collections::{BTreeMap, HashSet};
 
-///coins you receive each day you have done a workout
-const DAILY_COIN_REWARD: u32 = 5;
-
 pub enum CreateWorkoutError {
     WorkoutAlreadyInCreation,
 }
@@ -52,12 +51,23 @@ pub struct ExerciseManager {
 
     /// Needed for exercise creation menu
     pub workout_in_creation: Option<WorkoutCreate>,
+    /// Date the workout in creation is saved with, can lie in the past to log earlier workouts
+    pub workout_date: NaiveDate,
+    /// When the workout creation was started
+    pub workout_started_at: NaiveDateTime,
+    /// Id of the saved workout which is edited in the exercise creation menu.
+    /// None if a new workout is created
+    pub workout_in_edit_id: Option<Id>,
+    /// Id of the preset the workout in creation was started from
+    pub workout_preset_id: Option<Id>,
     /// shows which exercise is being edited during workout creation
     pub exercise_in_edit_number: Option<ExerciseNumber>,
     /// used for iced to be able to show and edit the current workout in creation
     pub exercise_in_edit_strings: Option<ExerciseCreateString>,
     /// Contains the exercise data for the visualization of up to three most recent workouts
     pub recent_workouts: Vec<RecentWorkoutVisualization>,
+    /// Form of the exercise browser to create custom exercises
+    pub custom_exercise_editor: CustomExerciseEditor,
 }
 impl Default for ExerciseManager {
     fn default() -> Self {
@@ -77,8 +87,13 @@ impl Default for ExerciseManager {
             weight_personal_record: 0.0,
             set_with_most_total_lifted_weight: (Default::default(), 0.0),
             workout_in_creation: None,
+            workout_date: Local::now().date_naive(),
+            workout_started_at: Local::now().naive_local(),
+            workout_in_edit_id: None,
+            workout_preset_id: None,
             exercise_in_edit_number: None,
             exercise_in_edit_strings: None,
+            custom_exercise_editor: CustomExerciseEditor::default(),
         };
 
         exercise_manager.all_exercise_state =
@@ -105,6 +120,11 @@ impl ExerciseManager {
 
         self.update_selected_exercise(selected_exercise)
     }
+    /// Adds a newly created custom exercise, which has no sets yet
+    pub fn add_custom_exercise(&mut self, general_exercise_info: GeneralExerciseInfo) {
+        self.exercises.push(Exercise::new(general_exercise_info));
+        self.all_exercise_state = get_combo_box_all_exercises_state(&self.exercises);
+    }
     /// Returns a reference to an Exercise, if the name inside
     /// ExerciseManager.selected_exercise_name exists
     /// If the exercise name doesn't exist it returns None
@@ -162,19 +182,43 @@ impl ExerciseManager {
         hash_map
     }
 
-    /// Saves the workout supplied as an input and the current date.
-    /// As a side effect gives the user DAILY_COIN_REWARD coins if it's their
-    /// first workout of the day.
-    /// It also updates data after workout is safed
+    /// Saves the workout supplied as an input on the given date.
+    /// It also updates data after workout is safed.
+    /// The daily coin reward is paid by the server
     pub fn save_workout(
         &mut self,
         workout: &WorkoutCreate,
         workout_id: Id,
+        date: NaiveDate,
         user_info: &mut UserInformation,
     ) {
-        let local_time = Local::now().date_naive();
-        let first_workout_today: bool =
-            !self.is_set_tracked_on_date(&local_time) && !workout.is_empty();
+        self.add_workout_sets(workout, workout_id, date);
+        self.update_app_data_after_save_workout(user_info);
+    }
+
+    /// Replaces the sets of a saved workout with the edited ones, keeping the date of the workout
+    pub fn replace_workout(
+        &mut self,
+        workout_id: Id,
+        workout: &WorkoutCreate,
+        user_info: &mut UserInformation,
+    ) {
+        let date = self
+            .exercises
+            .iter()
+            .find_map(|exercise| exercise.get_date_of_workout_id(workout_id))
+            .unwrap_or_else(|| Local::now().date_naive());
+        self.remove_workout_sets(workout_id);
+        self.add_workout_sets(workout, workout_id, date);
+        self.update_app_data_after_save_workout(user_info);
+    }
+
+    pub fn delete_workout(&mut self, workout_id: Id, user_info: &mut UserInformation) {
+        self.remove_workout_sets(workout_id);
+        self.update_app_data_after_save_workout(user_info);
+    }
+
+    fn add_workout_sets(&mut self, workout: &WorkoutCreate, workout_id: Id, date: NaiveDate) {
         for exercise_data in &mut self.exercises {
             for exercise_create in workout {
                 if exercise_create.name == exercise_data.general_exercise_info.name
@@ -191,24 +235,58 @@ impl ExerciseManager {
                     for workout_set in workout_sets {
                         exercise_data
                             .sets
-                            .entry(local_time)
+                            .entry(date)
                             .or_default()
                             .push(workout_set);
                     }
                 }
             }
         }
-        self.update_app_data_after_save_workout(user_info, first_workout_today);
     }
 
-    fn update_app_data_after_save_workout(
-        &mut self,
-        user_info: &mut UserInformation,
-        first_workout_today: bool,
-    ) {
-        if first_workout_today {
-            user_info.coin_balance += DAILY_COIN_REWARD;
+    /// Days without any sets left are removed, so exercises without sets count as untracked
+    fn remove_workout_sets(&mut self, workout_id: Id) {
+        for exercise_data in &mut self.exercises {
+            exercise_data.sets.retain(|_date, sets| {
+                sets.retain(|set| set.workout_id != workout_id);
+                !sets.is_empty()
+            });
         }
+    }
+
+    /// Builds the editable version of a saved workout, None if no set has this workout_id
+    pub fn get_workout_create(&self, workout_id: Id) -> Option<WorkoutCreate> {
+        let workout: WorkoutCreate = self
+            .exercises
+            .iter()
+            .filter(|exercise| exercise.contains_set_with_workout_id(workout_id))
+            .map(|exercise| ExerciseCreate {
+                name: exercise.general_exercise_info.name.clone(),
+                sets: exercise
+                    .sets
+                    .values()
+                    .flatten()
+                    .filter(|set| set.workout_id == workout_id)
+                    .map(|set| set.clone().into())
+                    .collect(),
+            })
+            .collect();
+        if workout.is_empty() {
+            None
+        } else {
+            Some(workout)
+        }
+    }
+
+    /// Id of the most recent workout containing the selected exercise
+    pub fn latest_workout_id_of_selected_exercise(&self) -> Option<Id> {
+        self.get_selected_exercise()?
+            .get_up_to_three_largest_workout_ids()
+            .first()
+            .copied()
+    }
+
+    fn update_app_data_after_save_workout(&mut self, user_info: &mut UserInformation) {
         self.update_selected_exercise(self.selected_exercise_name.clone());
         user_info.profile_stat_manager =
             ProfileStatManager::new(&self.exercises, user_info.user_goals.weekly_workouts as u32);
@@ -254,6 +332,8 @@ impl ExerciseManager {
     /// clears the current workout in creation
     pub fn clear_workout(&mut self) {
         self.workout_in_creation = None;
+        self.workout_in_edit_id = None;
+        self.workout_preset_id = None;
         self.exercise_in_edit_strings = None;
         self.exercise_in_edit_number = None;
     }
@@ -262,9 +342,43 @@ impl ExerciseManager {
     pub fn start_workout(&mut self) {
         if self.workout_in_creation.is_none() {
             self.workout_in_creation = Some(Vec::new());
+            self.workout_preset_id = None;
+            self.reset_workout_date();
         }
     }
 
+    /// A new workout is logged for today until another date is picked
+    fn reset_workout_date(&mut self) {
+        self.workout_started_at = Local::now().naive_local();
+        self.workout_date = self.workout_started_at.date();
+    }
+
+    /// Moves the date of the workout in creation by `days`, but never past today
+    pub fn shift_workout_date(&mut self, days: i64) {
+        let today = Local::now().date_naive();
+        self.workout_date = (self.workout_date + Duration::days(days)).min(today);
+    }
+
+    /// The start time is only known for workouts logged on the day they were started
+    pub fn workout_start_time(&self) -> Option<NaiveTime> {
+        (self.workout_date == self.workout_started_at.date())
+            .then(|| self.workout_started_at.time())
+    }
+
+    /// Preset the new workout was started from, with its duration if it is logged on the day it was started.
+    /// Implausible durations, like a workout left open for hours, are left out
+    pub fn preset_training(&self, now: NaiveDateTime) -> Option<PresetTraining> {
+        let preset_id = self.workout_preset_id?;
+        let duration_minutes = self
+            .workout_start_time()
+            .map(|_| (now - self.workout_started_at).num_minutes().max(0) as u32)
+            .filter(|&duration_minutes| is_plausible_workout_duration(duration_minutes));
+        Some(PresetTraining {
+            preset_id,
+            duration_minutes,
+        })
+    }
+
     /// setup workout creation with exercises of a preset already being added
     pub fn start_workout_with_preset(
         &mut self,
@@ -278,6 +392,25 @@ impl ExerciseManager {
         }
     }
 
+    /// setup workout creation with the exercises and sets of a saved workout
+    pub fn start_workout_edit(&mut self, workout_id: Id) -> Result<(), CreateWorkoutError> {
+        if self.workout_in_creation.is_none() {
+            self.force_workout_edit(workout_id);
+            Ok(())
+        } else {
+            Err(CreateWorkoutError::WorkoutAlreadyInCreation)
+        }
+    }
+
+    /// edits the saved workout even if the workout in creation gets replaced
+    pub fn force_workout_edit(&mut self, workout_id: Id) {
+        self.clear_workout();
+        if let Some(workout) = self.get_workout_create(workout_id) {
+            self.workout_in_creation = Some(workout);
+            self.workout_in_edit_id = Some(workout_id);
+        }
+    }
+
     /// makes new workout with preset even if the old one gets replaced
     pub fn force_workout_with_preset(&mut self, preset: &WorkoutPreset) {
         let mut exercises = Vec::new();
@@ -285,6 +418,9 @@ impl ExerciseManager {
             exercises.push(ExerciseCreate::new(exercise.clone()))
         }
         self.workout_in_creation = Some(exercises);
+        self.workout_in_edit_id = None;
+        self.workout_preset_id = preset.id;
+        self.reset_workout_date();
     }
 
     /// Checks the historically latest done set for an exercise.
@@ -310,14 +446,16 @@ mod tests {
     use crate::client::backend::exercise_create::{
         ExerciseCreate, StrengthSetCreate, WorkoutCreate,
     };
-    use crate::client::backend::exercise_manager::{DAILY_COIN_REWARD, ExerciseManager};
+    use crate::client::backend::exercise_manager::ExerciseManager;
     use crate::common::exercise_mod::exercise::tests::{MOCK_DATES, mock_exercise};
     use crate::common::exercise_mod::{
         exercise::Exercise, general_exercise::GeneralExerciseInfo, set::StrengthSet,
         weight::ExerciseWeight,
     };
     use crate::common::user_mod::user::UserInformation;
-    use chrono::{Local, NaiveDate};
+    use crate::common::workout::PresetTraining;
+    use crate::common::workout_preset::WorkoutPreset;
+    use chrono::{Duration, Local, NaiveDate};
 
     #[test]
     fn select_invalid_exercise() {
@@ -467,7 +605,7 @@ mod tests {
     }
 
     #[test]
-    fn adding_two_exercises_only_one_daily_reward() {
+    fn saving_workout_leaves_coins_to_server() {
         let mut ex_manager = ExerciseManager::default();
         let mut exercise_create_example = ExerciseCreate::test_case(0);
         exercise_create_example
@@ -478,10 +616,11 @@ mod tests {
         ex_manager.save_workout(
             &vec![exercise_create_example.clone()],
             0,
+            Local::now().date_naive(),
             &mut user_information,
         );
         assert_eq!(
-            UserInformation::default(&Vec::new()).coin_balance + DAILY_COIN_REWARD,
+            UserInformation::default(&Vec::new()).coin_balance,
             user_information.coin_balance
         );
     }
@@ -492,7 +631,12 @@ mod tests {
         let exercise_create_example = ExerciseCreate::test_case(0);
         let mut user_information =
             UserInformation::default(&vec![Exercise::new(GeneralExerciseInfo::test_obj())]);
-        ex_manager.save_workout(&vec![exercise_create_example], 0, &mut user_information);
+        ex_manager.save_workout(
+            &vec![exercise_create_example],
+            0,
+            Local::now().date_naive(),
+            &mut user_information,
+        );
         for exercise in ex_manager.exercises {
             assert_ne!(
                 exercise.general_exercise_info.name,
@@ -516,9 +660,14 @@ mod tests {
             weight_personal_record: 0.0,
             set_with_most_total_lifted_weight: (Default::default(), 0.0),
             workout_in_creation: None,
+            workout_date: Local::now().date_naive(),
+            workout_started_at: Local::now().naive_local(),
+            workout_in_edit_id: None,
+            workout_preset_id: None,
             exercise_in_edit_number: None,
             exercise_in_edit_strings: None,
             recent_workouts: Vec::new(),
+            custom_exercise_editor: Default::default(),
         };
         assert!(ex_manager.is_set_tracked_on_date(&MOCK_DATES[0]));
     }
@@ -543,9 +692,14 @@ mod tests {
             weight_personal_record: 0.0,
             set_with_most_total_lifted_weight: (Default::default(), 0.0),
             workout_in_creation: Some(WorkoutCreate::default()),
+            workout_date: Local::now().date_naive(),
+            workout_started_at: Local::now().naive_local(),
+            workout_in_edit_id: None,
+            workout_preset_id: None,
             exercise_in_edit_number: None,
             exercise_in_edit_strings: None,
             recent_workouts: Vec::new(),
+            custom_exercise_editor: Default::default(),
         };
         ex_manager.clear_workout();
         assert_eq!(ex_manager.workout_in_creation, None);
@@ -566,11 +720,157 @@ mod tests {
             weight_personal_record: 0.0,
             set_with_most_total_lifted_weight: (Default::default(), 0.0),
             workout_in_creation: Some(workout.clone()),
+            workout_date: Local::now().date_naive(),
+            workout_started_at: Local::now().naive_local(),
+            workout_in_edit_id: None,
+            workout_preset_id: None,
             exercise_in_edit_number: None,
             exercise_in_edit_strings: None,
             recent_workouts: Vec::new(),
+            custom_exercise_editor: Default::default(),
         };
         ex_manager.start_workout();
         assert_eq!(ex_manager.workout_in_creation, Some(workout));
     }
+
+    fn manager_with_mock_exercise() -> ExerciseManager {
+        let mut ex_manager = ExerciseManager::default();
+        ex_manager.update_exercise_manager_on_login(
+            vec![mock_exercise()],
+            mock_exercise().general_exercise_info.name,
+        );
+        ex_manager
+    }
+
+    #[test]
+    fn edit_workout_loads_its_sets() {
+        let mut ex_manager = manager_with_mock_exercise();
+        ex_manager.start_workout_edit(1).ok().unwrap();
+
+        assert_eq!(ex_manager.workout_in_edit_id, Some(1));
+        let workout = ex_manager.workout_in_creation.unwrap();
+        assert_eq!(workout.len(), 1);
+        assert_eq!(
+            workout[0].sets,
+            vec![
+                StrengthSetCreate::new(ExerciseWeight::Kg(55.0), 15),
+                StrengthSetCreate::new(ExerciseWeight::Kg(60.0), 15),
+                StrengthSetCreate::new(ExerciseWeight::Kg(57.5), 11),
+            ]
+        );
+    }
+    #[test]
+    fn replace_workout_keeps_date_and_updates_stats() {
+        let mut ex_manager = manager_with_mock_exercise();
+        let mut user_information = UserInformation::default(&ex_manager.exercises);
+        let edited_workout = vec![ExerciseCreate {
+            name: mock_exercise().general_exercise_info.name,
+            sets: vec![StrengthSetCreate::new(ExerciseWeight::Kg(100.0), 1)],
+        }];
+
+        ex_manager.replace_workout(1, &edited_workout, &mut user_information);
+
+        let sets_of_day = &ex_manager.exercises[0].sets[&MOCK_DATES[1]];
+        assert_eq!(
+            sets_of_day,
+            &vec![StrengthSet::new(1, ExerciseWeight::Kg(100.0), 1)]
+        );
+        assert_eq!(ex_manager.weight_personal_record, 100.0);
+        assert_eq!(ex_manager.all_time_sets, 4);
+    }
+    #[test]
+    fn delete_workout_removes_empty_days() {
+        let mut ex_manager = manager_with_mock_exercise();
+        let mut user_information = UserInformation::default(&ex_manager.exercises);
+
+        ex_manager.delete_workout(0, &mut user_information);
+
+        assert!(!ex_manager.exercises[0].sets.contains_key(&MOCK_DATES[0]));
+        assert_eq!(ex_manager.all_time_sets, 3);
+        assert_eq!(ex_manager.latest_workout_id_of_selected_exercise(), Some(1));
+        assert_eq!(ex_manager.recent_workouts.len(), 1);
+    }
+    #[test]
+    fn backdated_workout_is_saved_on_its_date() {
+        let mut ex_manager = manager_with_mock_exercise();
+        let mut user_information = UserInformation::default(&ex_manager.exercises);
+        let backdated_workout = vec![ExerciseCreate {
+            name: mock_exercise().general_exercise_info.name,
+            sets: vec![StrengthSetCreate::new(ExerciseWeight::Kg(40.0), 8)],
+        }];
+        let date_before_mock_dates = MOCK_DATES[0] - chrono::Duration::days(1);
+
+        ex_manager.save_workout(
+            &backdated_workout,
+            5,
+            date_before_mock_dates,
+            &mut user_information,
+        );
+
+        assert!(ex_manager.is_set_tracked_on_date(&date_before_mock_dates));
+        assert_eq!(
+            user_information
+                .profile_stat_manager
+                .activity_data
+                .get(&date_before_mock_dates),
+            Some(&1)
+        );
+    }
+    #[test]
+    fn workout_date_can_not_be_shifted_into_the_future() {
+        let mut ex_manager = ExerciseManager::default();
+        ex_manager.start_workout();
+        let today = Local::now().date_naive();
+
+        ex_manager.shift_workout_date(1);
+        assert_eq!(ex_manager.workout_date, today);
+        assert!(ex_manager.workout_start_time().is_some());
+
+        ex_manager.shift_workout_date(-2);
+        assert_eq!(ex_manager.workout_date, today - chrono::Duration::days(2));
+        assert_eq!(ex_manager.workout_start_time(), None);
+    }
+
+    #[test]
+    fn preset_training_of_workout_started_with_preset() {
+        let mut ex_manager = ExerciseManager::default();
+        let preset = WorkoutPreset {
+            id: Some(4),
+            exercises: vec!["Squat".to_string()],
+            ..Default::default()
+        };
+        ex_manager.force_workout_with_preset(&preset);
+        let now = ex_manager.workout_started_at + Duration::minutes(45);
+        assert_eq!(
+            ex_manager.preset_training(now),
+            Some(PresetTraining {
+                preset_id: 4,
+                duration_minutes: Some(45),
+            })
+        );
+
+        // a workout left open for days has no plausible duration
+        let much_later = ex_manager.workout_started_at + Duration::days(2);
+        assert_eq!(
+            ex_manager.preset_training(much_later),
+            Some(PresetTraining {
+                preset_id: 4,
+                duration_minutes: None,
+            })
+        );
+
+        // the duration of backdated workouts is unknown
+        ex_manager.shift_workout_date(-1);
+        assert_eq!(
+            ex_manager.preset_training(now),
+            Some(PresetTraining {
+                preset_id: 4,
+                duration_minutes: None,
+            })
+        );
+
+        ex_manager.clear_workout();
+        ex_manager.start_workout();
+        assert_eq!(ex_manager.preset_training(now), None);
+    }
 }
@@ -0,0 +1,136 @@
+use crate::common::user_mod::user_goals::GoalType;
+use crate::common::user_mod::user_log::UserLogEntry;
+use chrono::NaiveDate;
+
+/// Format of the date text input in the health tab's log list
+pub const LOG_DATE_FORMAT: &str = "%d.%m.%Y";
+
+/// Inputs of the health tab's log list, used to add or edit the entry of any past date
+pub struct LogEntryEditor {
+    pub selected_goal_type: GoalType,
+    pub date_input: String,
+    pub value_input: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogEntryError {
+    InvalidDate,
+    InvalidValue,
+    Rejected(&'static str),
+}
+
+impl LogEntryError {
+    pub fn to_error_message(&self) -> String {
+        match self {
+            LogEntryError::InvalidDate => "Enter the date like 31.12.2025".to_string(),
+            LogEntryError::InvalidValue => "Enter the value as a number".to_string(),
+            LogEntryError::Rejected(reason) => reason.to_string(),
+        }
+    }
+}
+
+impl LogEntryEditor {
+    pub fn new(today: NaiveDate) -> Self {
+        LogEntryEditor {
+            selected_goal_type: GoalType::Weight,
+            date_input: today.format(LOG_DATE_FORMAT).to_string(),
+            value_input: String::new(),
+        }
+    }
+
+    pub fn select_goal_type(&mut self, goal_type: GoalType, today: NaiveDate) {
+        *self = LogEntryEditor {
+            selected_goal_type: goal_type,
+            ..LogEntryEditor::new(today)
+        };
+    }
+
+    /// Fills the inputs with an existing entry so saving overwrites it
+    pub fn start_edit(&mut self, entry: UserLogEntry) {
+        self.date_input = entry.date.format(LOG_DATE_FORMAT).to_string();
+        self.value_input = entry.value.to_string();
+    }
+
+    pub fn reset(&mut self, today: NaiveDate) {
+        self.select_goal_type(self.selected_goal_type, today);
+    }
+
+    pub fn parse_entry(&self, today: NaiveDate) -> Result<UserLogEntry, LogEntryError> {
+        let date = NaiveDate::parse_from_str(self.date_input.trim(), LOG_DATE_FORMAT)
+            .map_err(|_| LogEntryError::InvalidDate)?;
+        let value = self
+            .value_input
+            .trim()
+            .replace(',', ".")
+            .parse::<f32>()
+            .map_err(|_| LogEntryError::InvalidValue)?;
+
+        let entry = UserLogEntry { date, value };
+        match entry.validation_error(&self.selected_goal_type, today) {
+            Some(reason) => Err(LogEntryError::Rejected(reason)),
+            None => Ok(entry),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+    }
+
+    #[test]
+    fn parses_entry_of_past_date() {
+        let mut editor = LogEntryEditor::new(today());
+        editor.select_goal_type(GoalType::Sleep, today());
+        editor.date_input = "03.03.2025".to_string();
+        editor.value_input = "7,5".to_string();
+
+        assert_eq!(
+            editor.parse_entry(today()),
+            Ok(UserLogEntry {
+                date: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+                value: 7.5,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let mut editor = LogEntryEditor::new(today());
+        editor.value_input = "abc".to_string();
+        assert_eq!(
+            editor.parse_entry(today()),
+            Err(LogEntryError::InvalidValue)
+        );
+
+        editor.value_input = "70".to_string();
+        editor.date_input = "2025-03-01".to_string();
+        assert_eq!(editor.parse_entry(today()), Err(LogEntryError::InvalidDate));
+
+        editor.date_input = "11.03.2025".to_string();
+        assert!(matches!(
+            editor.parse_entry(today()),
+            Err(LogEntryError::Rejected(_))
+        ));
+    }
+
+    #[test]
+    fn edit_fills_inputs_and_reset_keeps_goal_type() {
+        let mut editor = LogEntryEditor::new(today());
+        editor.select_goal_type(GoalType::Water, today());
+        editor.start_edit(UserLogEntry {
+            date: NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+            value: 2.25,
+        });
+        assert_eq!(editor.date_input, "28.02.2025");
+        assert_eq!(editor.value_input, "2.25");
+
+        editor.reset(today());
+        assert_eq!(editor.selected_goal_type, GoalType::Water);
+        assert_eq!(editor.date_input, "10.03.2025");
+        assert!(editor.value_input.is_empty());
+    }
+}
@@ -1,15 +1,19 @@
+use crate::client::client_config::normalize_server_url;
+use crate::client::server_communication::api_client::DEFAULT_SERVER_URL;
 use crate::client::server_communication::user_communicator::LoginRequest;
 
 #[derive(Debug, PartialEq)]
 pub enum LoginStateError {
     UsernameEmpty,
     PasswordEmpty,
+    InvalidServerUrl,
 }
 impl LoginStateError {
     pub fn to_error_message(&self) -> String {
         let slice = match self {
             LoginStateError::UsernameEmpty => "Username can't be empty!",
             LoginStateError::PasswordEmpty => "Password can't be empty!",
+            LoginStateError::InvalidServerUrl => "Server address is invalid!",
         };
         slice.to_string()
     }
@@ -21,15 +25,38 @@ pub enum LoginStates {
     LoggedIn,
 }
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct LoginState {
+    /// Address of the server as entered by the user, see normalize_server_url
+    pub server_url: String,
     pub username: String,
     pub password: String,
     pub state: LoginStates,
     pub error_text: String,
 }
 
+impl Default for LoginState {
+    fn default() -> Self {
+        LoginState::new(DEFAULT_SERVER_URL.to_string())
+    }
+}
+
 impl LoginState {
+    pub fn new(server_url: String) -> Self {
+        LoginState {
+            server_url,
+            username: String::new(),
+            password: String::new(),
+            state: LoginStates::default(),
+            error_text: String::new(),
+        }
+    }
+
+    /// checks if the entered server address is a valid http(s) url and returns it normalized
+    pub fn try_server_url(&self) -> Result<String, LoginStateError> {
+        normalize_server_url(&self.server_url).map_err(|_| LoginStateError::InvalidServerUrl)
+    }
+
     /// checks if the current login state has a non-empty username and password
     pub fn try_login(&self) -> Result<LoginRequest, LoginStateError> {
         if self.username.is_empty() {
@@ -65,6 +92,7 @@ mod tests {
     #[test]
     fn try_login_no_username() {
         let login_state = LoginState {
+            server_url: DEFAULT_SERVER_URL.to_string(),
             username: "".to_string(),
             password: "123".to_string(),
             state: Default::default(),
@@ -75,6 +103,7 @@ mod tests {
     #[test]
     fn try_login_no_password() {
         let login_state = LoginState {
+            server_url: DEFAULT_SERVER_URL.to_string(),
             username: "123".to_string(),
             password: "".to_string(),
             state: Default::default(),
@@ -86,6 +115,7 @@ mod tests {
     #[test]
     fn try_login_all_filled() {
         let login_state = LoginState {
+            server_url: DEFAULT_SERVER_URL.to_string(),
             username: "123".to_string(),
             password: "1234".to_string(),
             state: Default::default(),
@@ -97,4 +127,20 @@ mod tests {
         };
         assert_eq!(login_state.try_login(), Ok(login_request));
     }
+    #[test]
+    fn try_server_url_invalid() {
+        let login_state = LoginState::new("ftp://127.0.0.1".to_string());
+        assert_eq!(
+            login_state.try_server_url(),
+            Err(LoginStateError::InvalidServerUrl)
+        );
+    }
+    #[test]
+    fn try_server_url_without_scheme() {
+        let login_state = LoginState::new("192.168.0.10:3000".to_string());
+        assert_eq!(
+            login_state.try_server_url(),
+            Ok("http://192.168.0.10:3000".to_string())
+        );
+    }
 }
@@ -1,14 +1,22 @@
-use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::mascot_mod::mascot::{Mascot, MascotRarity};
 use crate::common::mascot_mod::mascot_data_transfer::MascotDataServerClientTransfer;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use iced::widget::combo_box::State;
 use iced::widget::{Image, image};
+use iced_anim::{Animated, Motion};
 use rand::random_range;
+use std::time::Duration;
+
+const ROLL_REVEAL_MILLIS: u64 = 1500;
 
 pub struct MascotManager {
     pub selected_mascot: Mascot,
     pub owned_mascots: Vec<Mascot>,
     pub owned_mascots_state: State<Mascot>,
+    /// Mascot the server picked in the last shop roll, revealed in the shop widget of its rarity
+    pub last_roll: Option<(MascotRarity, Mascot)>,
+    /// Goes from 0 to 1 while the rolled mascot is revealed
+    pub roll_reveal_animation: Animated<f32>,
 }
 
 impl Default for MascotManager {
@@ -23,9 +31,24 @@ impl MascotManager {
             selected_mascot: Mascot::default(),
             owned_mascots: vec![Mascot::default()],
             owned_mascots_state: State::with_selection(vec![Mascot::default()], None),
+            last_roll: None,
+            roll_reveal_animation: Animated::new(
+                0.0,
+                Motion {
+                    response: Duration::from_millis(ROLL_REVEAL_MILLIS),
+                    damping: Motion::SMOOTH.damping(),
+                },
+            ),
         }
     }
 
+    /// Restarts the reveal animation for a freshly rolled mascot
+    pub fn start_roll_reveal(&mut self, rarity: MascotRarity, mascot: Mascot) {
+        self.last_roll = Some((rarity, mascot));
+        self.roll_reveal_animation.settle_at(0.0);
+        self.roll_reveal_animation.set_target(1.0);
+    }
+
     pub fn owns_mascot(&self, mascot: impl Into<Mascot>) -> bool {
         self.owned_mascots.contains(&mascot.into())
     }
@@ -76,6 +99,17 @@ mod tests {
         assert!(mascot_manager.owns_mascot(EpicMascot::Reindeer));
     }
     #[test]
+    fn start_roll_reveal() {
+        let mut mascot_manager = MascotManager::default();
+        mascot_manager.start_roll_reveal(MascotRarity::Epic, Mascot::Epic(EpicMascot::Shark));
+        assert_eq!(
+            mascot_manager.last_roll,
+            Some((MascotRarity::Epic, Mascot::Epic(EpicMascot::Shark)))
+        );
+        assert_eq!(*mascot_manager.roll_reveal_animation.value(), 0.0);
+        assert_eq!(*mascot_manager.roll_reveal_animation.target(), 1.0);
+    }
+    #[test]
     fn update_on_login() {
         let mut mascot_manager = MascotManager::default();
         mascot_manager.update_mascot_manager_on_login(MascotDataServerClientTransfer {
@@ -1,5 +1,6 @@
 use crate::client::gui::bb_widget::activity_widget::activity::{ActivityData, AmountOfSets};
 use crate::client::gui::bb_widget::activity_widget::date_utils;
+use crate::client::gui::bb_widget::body_map_widget::MuscleVolume;
 use crate::common::exercise_mod::exercise::Exercise;
 use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::exercise_mod::weight::Kg;
@@ -7,6 +8,9 @@ use chrono::{Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+const PRIMARY_MUSCLE_SET_VOLUME: f32 = 1.0;
+const SECONDARY_MUSCLE_SET_VOLUME: f32 = 0.5;
+
 /// The data included in this struct is only there for performance enhancement purposes
 /// so that these values don't have to be calculated with every frame inside the view function
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +54,34 @@ pub fn calculate_activity_data(exercise_data: &[Exercise]) -> ActivityData {
     }
     map
 }
+/// Calculates the sets per muscle tracked in the week from Monday until Sunday the given `date` falls in. <br>
+/// A set counts as one set for the primary muscle and as half a set for every secondary muscle
+pub fn calculate_weekly_muscle_volume(exercise_data: &[Exercise], date: NaiveDate) -> MuscleVolume {
+    let dates_of_week = date_utils::get_dates_of_week_belonging_to_date(date);
+    let mut muscle_volume: MuscleVolume = HashMap::new();
+
+    for exercise in exercise_data {
+        let sets_this_week: usize = exercise
+            .sets
+            .iter()
+            .filter(|(date_of_sets, _)| dates_of_week.contains(date_of_sets))
+            .map(|(_, sets)| sets.len())
+            .sum();
+        if sets_this_week == 0 {
+            continue;
+        }
+        let general_exercise_info = &exercise.general_exercise_info;
+
+        *muscle_volume
+            .entry(general_exercise_info.primary_muscle)
+            .or_default() += sets_this_week as f32 * PRIMARY_MUSCLE_SET_VOLUME;
+        for muscle in &general_exercise_info.secondary_muscles {
+            *muscle_volume.entry(*muscle).or_default() +=
+                sets_this_week as f32 * SECONDARY_MUSCLE_SET_VOLUME;
+        }
+    }
+    muscle_volume
+}
 /// Calculate the total amount of sets across all exercises up to `u64::MAX`
 pub fn total_sets(exercise_data: &Vec<Exercise>) -> u64 {
     let mut result: u64 = 0;
@@ -131,11 +163,11 @@ pub fn calculate_weekly_workout_streak(exercises: &Vec<Exercise>, weekly_workout
 #[cfg(test)]
 mod tests {
     use crate::client::backend::profile_stat_manager::{
-        amount_of_workouts_in_week, calculate_weekly_workout_streak,
+        amount_of_workouts_in_week, calculate_weekly_muscle_volume, calculate_weekly_workout_streak,
     };
     use crate::common::exercise_mod::exercise;
     use crate::common::exercise_mod::exercise::Exercise;
-    use crate::common::exercise_mod::general_exercise::{GeneralExerciseInfo, Id};
+    use crate::common::exercise_mod::general_exercise::{GeneralExerciseInfo, Id, Muscle};
     use crate::common::exercise_mod::set::StrengthSet;
     use crate::common::exercise_mod::weight::ExerciseWeight;
     use chrono::{Duration, Local, NaiveDate};
@@ -244,4 +276,38 @@ mod tests {
         let exercises = vec![exercise1, exercise2];
         assert_eq!(amount_of_workouts_in_week(&exercises, date), 0);
     }
+
+    #[test]
+    fn weekly_muscle_volume_counts_secondary_muscles_half() {
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let set = StrengthSet::new(1, ExerciseWeight::Kg(60.0), 5);
+        let mut sets = BTreeMap::new();
+        sets.insert(monday, vec![set.clone(), set.clone()]);
+        sets.insert(monday + Duration::days(6), vec![set.clone()]);
+        sets.insert(monday - Duration::days(1), vec![set.clone()]);
+        sets.insert(monday + Duration::days(7), vec![set]);
+
+        let squat = Exercise {
+            general_exercise_info: GeneralExerciseInfo {
+                primary_muscle: Muscle::Quadriceps,
+                secondary_muscles: vec![Muscle::Glutes, Muscle::Hamstrings],
+                ..GeneralExerciseInfo::test_obj()
+            },
+            sets,
+        };
+        let untrained_exercise = Exercise::new(GeneralExerciseInfo {
+            primary_muscle: Muscle::Chest,
+            ..GeneralExerciseInfo::test_obj()
+        });
+
+        let muscle_volume = calculate_weekly_muscle_volume(
+            &[squat, untrained_exercise],
+            monday + Duration::days(3),
+        );
+
+        assert_eq!(muscle_volume.len(), 3);
+        assert_eq!(muscle_volume[&Muscle::Quadriceps], 3.0);
+        assert_eq!(muscle_volume[&Muscle::Glutes], 1.5);
+        assert_eq!(muscle_volume[&Muscle::Hamstrings], 1.5);
+    }
 }
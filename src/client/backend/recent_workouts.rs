@@ -5,6 +5,7 @@ use chrono::NaiveDate;
 /// This struct contains the necessary data to display exercise data
 #[derive(Debug, PartialEq)]
 pub struct RecentWorkoutVisualization {
+    pub workout_id: Id,
     pub date: NaiveDate,
     pub exercise_names: Vec<String>,
 }
@@ -40,6 +41,7 @@ fn get_exercise_names_in_workout_and_date_by_workout_id(
     }
 
     RecentWorkoutVisualization {
+        workout_id,
         date: date_of_workout.unwrap_or_default(),
         exercise_names: exercises_in_workout,
     }
@@ -116,6 +118,7 @@ mod tests {
 
         for (i, date) in MOCK_DATES.iter().enumerate() {
             let expected_data_for_date = RecentWorkoutVisualization {
+                workout_id: i as Id,
                 date: *date,
                 exercise_names: vec![mock_exercise().general_exercise_info.name],
             };
@@ -133,6 +136,7 @@ mod tests {
 
         for i in 0..=2 {
             let expected_data_for_date = RecentWorkoutVisualization {
+                workout_id: CUSTOM_TRACKED_DAYS - 1 - i,
                 date: Local::now().date_naive()
                     + Duration::days((CUSTOM_TRACKED_DAYS - 1 - i) as i64),
                 exercise_names: vec![custom_exercise_preset().general_exercise_info.name],
@@ -1,8 +1,19 @@
+use crate::client::backend::account_editor::AccountEditor;
+use crate::client::backend::challenge_editor::ChallengeEditor;
+use crate::client::backend::log_entry_editor::LogEntryEditor;
+use crate::common::challenge::{Challenge, ChallengeOverview};
 use crate::common::exercise_mod::exercise::Exercise;
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::leaderboard::{LeaderboardEntry, LeaderboardMetric, LeaderboardQuery};
+use crate::common::social_feed::FeedPage;
+use crate::common::user_mod::friend_request::FriendshipStatus;
 use crate::common::user_mod::user::{
-    ForeignUser, Gender, UserInformation, UserInformationStrings, UserType,
+    ForeignUser, Gender, ProfileVisibility, UserInformation, UserInformationStrings, UserType,
 };
+use crate::common::workout_reaction::{WorkoutComment, WorkoutReactions, WorkoutSummary};
+use chrono::Local;
 use iced::widget::combo_box;
+use std::collections::HashMap;
 use strum::IntoEnumIterator;
 
 pub struct UserManager {
@@ -10,22 +21,62 @@ pub struct UserManager {
     pub user_info: UserInformation,
     /// Contains information if the user is currently inside of edit mode of the user_info in the settings
     pub pending_user_info_changes: Option<(UserInformation, UserInformationStrings)>,
+    /// Inputs to add or edit health log entries of any date
+    pub log_entry_editor: LogEntryEditor,
+    pub account_editor: AccountEditor,
 
     /// Info about all loaded non-logged-in users
     pub loaded_users: Vec<ForeignUser>,
     pub most_recently_viewed_user: UserType,
+    /// Loaded events of the friends feed, newest first
+    pub feed: FeedPage,
+    /// Kudos and comments on the workouts of the logged-in user, shown on the recent workouts
+    pub own_workout_reactions: Vec<WorkoutReactions>,
+    /// Latest workouts of the most recently viewed profile
+    pub profile_workouts: Vec<WorkoutSummary>,
+    /// Unsent comment for each workout
+    pub comment_inputs: HashMap<Id, String>,
+    /// Selected metric, period and exercise of the leaderboard in the Social tab
+    pub leaderboard_query: LeaderboardQuery,
+    /// Entries loaded for `leaderboard_query`
+    pub leaderboard: Vec<LeaderboardEntry>,
+    /// Challenges the logged-in user sent or received, newest first
+    pub challenges: Vec<Challenge>,
+    pub challenge_editor: ChallengeEditor,
+    /// Users the logged-in user blocked, most recently blocked first
+    pub blocked_users: Vec<String>,
+    /// Reason for reporting the most recently viewed profile
+    pub report_reason_input: String,
 
     //Utils
     pub gender_combo_box_state: combo_box::State<Gender>,
+    pub profile_visibility_combo_box_state: combo_box::State<ProfileVisibility>,
+    pub leaderboard_metric_state: combo_box::State<LeaderboardMetric>,
 }
 impl UserManager {
     pub fn new(exercise_data: &Vec<Exercise>) -> Self {
         UserManager {
             user_info: UserInformation::default(exercise_data),
             pending_user_info_changes: None,
+            log_entry_editor: LogEntryEditor::new(Local::now().date_naive()),
+            account_editor: AccountEditor::default(),
             loaded_users: vec![],
             most_recently_viewed_user: UserType::Own,
+            feed: FeedPage::default(),
+            own_workout_reactions: vec![],
+            profile_workouts: vec![],
+            comment_inputs: HashMap::new(),
+            leaderboard_query: LeaderboardQuery::default(),
+            leaderboard: vec![],
+            challenges: vec![],
+            challenge_editor: ChallengeEditor::default(),
+            blocked_users: vec![],
+            report_reason_input: String::new(),
             gender_combo_box_state: combo_box::State::new(Gender::iter().collect()),
+            profile_visibility_combo_box_state: combo_box::State::new(
+                ProfileVisibility::iter().collect(),
+            ),
+            leaderboard_metric_state: combo_box::State::new(LeaderboardMetric::iter().collect()),
         }
     }
 }
@@ -42,39 +93,327 @@ impl UserManager {
             .find(|user| user.username.eq_ignore_ascii_case(username))
     }
     pub fn get_friends(&self) -> Vec<&ForeignUser> {
-        self.loaded_users
-            .iter()
-            .filter(|user| user.friends_with_active_user)
-            .collect()
+        self.get_users_with_status(&[FriendshipStatus::Friends])
+    }
+    /// Users who sent the logged-in user a pending friend request
+    pub fn get_friend_requests(&self) -> Vec<&ForeignUser> {
+        self.get_users_with_status(&[FriendshipStatus::RequestReceived])
     }
     pub fn get_non_friend_users(&self) -> Vec<&ForeignUser> {
+        self.get_users_with_status(&[FriendshipStatus::NotFriends, FriendshipStatus::RequestSent])
+    }
+    fn get_users_with_status(&self, statuses: &[FriendshipStatus]) -> Vec<&ForeignUser> {
         self.loaded_users
             .iter()
-            .filter(|user| !user.friends_with_active_user)
+            .filter(|user| statuses.contains(&user.friendship_status))
             .collect()
     }
 
-    /// Returns whether adding a friend was successful or not
-    pub fn add_user_as_friend(&mut self, username: &str) -> bool {
+    /// Returns whether the user was found
+    pub fn set_friendship_status(&mut self, username: &str, status: FriendshipStatus) -> bool {
         let user_opt = self.get_user_by_username_mut(username);
 
         if let Some(user) = user_opt {
-            user.friends_with_active_user = true;
+            user.friendship_status = status;
             true
         } else {
             false
         }
     }
 
+    /// Returns whether sending the friend request was successful or not.
+    /// A pending request of the other user is accepted instead, like the server does
+    pub fn send_friend_request(&mut self, username: &str) -> bool {
+        let status = match self.get_user_by_username(username) {
+            Some(user) if user.friendship_status == FriendshipStatus::RequestReceived => {
+                FriendshipStatus::Friends
+            }
+            Some(_) => FriendshipStatus::RequestSent,
+            None => return false,
+        };
+        self.set_friendship_status(username, status)
+    }
+
     /// Returns whether deletion was successful or not
     pub fn remove_user_as_friend(&mut self, username: &str) -> bool {
-        let user_opt = self.get_user_by_username_mut(username);
+        self.feed
+            .events
+            .retain(|event| !event.username.eq_ignore_ascii_case(username));
+        if self
+            .challenge_editor
+            .opponent
+            .as_ref()
+            .is_some_and(|opponent| opponent.eq_ignore_ascii_case(username))
+        {
+            self.challenge_editor.opponent = None;
+        }
+        self.set_friendship_status(username, FriendshipStatus::NotFriends)
+    }
 
-        if let Some(user) = user_opt {
-            user.friends_with_active_user = false;
-            true
+    /// Removes everything of the user from the social data, like the server does
+    pub fn block_user(&mut self, username: &str) {
+        self.remove_user_as_friend(username);
+        self.loaded_users
+            .retain(|user| !user.username.eq_ignore_ascii_case(username));
+        self.leaderboard
+            .retain(|entry| !entry.username.eq_ignore_ascii_case(username));
+        if !self.is_blocked(username) {
+            self.blocked_users.insert(0, username.to_string());
+        }
+    }
+
+    pub fn unblock_user(&mut self, username: &str) {
+        self.blocked_users
+            .retain(|blocked| !blocked.eq_ignore_ascii_case(username));
+    }
+
+    pub fn is_blocked(&self, username: &str) -> bool {
+        self.blocked_users
+            .iter()
+            .any(|blocked| blocked.eq_ignore_ascii_case(username))
+    }
+
+    /// The server moves coins whenever challenges are created, answered or resolved
+    pub fn apply_challenge_overview(&mut self, overview: ChallengeOverview) {
+        self.challenges = overview.challenges;
+        self.user_info.coin_balance = overview.coin_balance;
+    }
+
+    pub fn get_own_workout_reactions(&self, workout_id: Id) -> Option<&WorkoutReactions> {
+        self.own_workout_reactions
+            .iter()
+            .find(|reactions| reactions.workout_id == workout_id)
+    }
+
+    /// Shows the workouts if the profile of `username` is still the most recently viewed one
+    pub fn set_profile_workouts(&mut self, username: &str, profile_workouts: Vec<WorkoutSummary>) {
+        let viewed_username = match &self.most_recently_viewed_user {
+            UserType::Own => &self.user_info.username,
+            UserType::Other(viewed_username) => viewed_username,
+        };
+        if !viewed_username.eq_ignore_ascii_case(username) {
+            return;
+        }
+        self.profile_workouts = profile_workouts;
+        for index in 0..self.profile_workouts.len() {
+            self.sync_own_workout_reactions(index);
+        }
+    }
+
+    /// Gives kudos to a workout of the viewed profile or takes them back.
+    /// Returns whether the user gives kudos now, None if the workout isn't shown
+    pub fn toggle_kudos(&mut self, workout_id: Id) -> Option<bool> {
+        let index = self.get_profile_workout_index(workout_id)?;
+        let gives_kudos = self.profile_workouts[index]
+            .reactions
+            .toggle_kudos(&self.user_info.username);
+        self.sync_own_workout_reactions(index);
+        Some(gives_kudos)
+    }
+
+    pub fn add_workout_comment(&mut self, comment: WorkoutComment) {
+        if let Some(index) = self.get_profile_workout_index(comment.workout_id) {
+            self.profile_workouts[index]
+                .reactions
+                .comments
+                .push(comment);
+            self.sync_own_workout_reactions(index);
+        }
+    }
+
+    pub fn remove_workout_comment(&mut self, workout_id: Id, comment_id: i64) {
+        if let Some(index) = self.get_profile_workout_index(workout_id) {
+            self.profile_workouts[index]
+                .reactions
+                .comments
+                .retain(|comment| comment.id != comment_id);
+            self.sync_own_workout_reactions(index);
+        }
+    }
+
+    /// Whether the logged-in user can delete the comment of a workout of `owner`
+    pub fn can_delete_comment(&self, owner: &str, comment: &WorkoutComment) -> bool {
+        let username = &self.user_info.username;
+        comment.author.eq_ignore_ascii_case(username) || owner.eq_ignore_ascii_case(username)
+    }
+
+    fn get_profile_workout_index(&self, workout_id: Id) -> Option<usize> {
+        self.profile_workouts
+            .iter()
+            .position(|summary| summary.workout_id == workout_id)
+    }
+
+    /// Copies the reactions of an own workout shown on the profile to the recent workouts
+    fn sync_own_workout_reactions(&mut self, profile_workout_index: usize) {
+        let summary = &self.profile_workouts[profile_workout_index];
+        if !summary.owner.eq_ignore_ascii_case(&self.user_info.username) {
+            return;
+        }
+        let reactions = summary.reactions.clone();
+        self.own_workout_reactions
+            .retain(|own_reactions| own_reactions.workout_id != reactions.workout_id);
+        if !reactions.kudos.is_empty() || !reactions.comments.is_empty() {
+            self.own_workout_reactions.push(reactions);
+        }
+    }
+
+    /// Replaces the feed with the newest page if `before` is None, else appends the older page
+    pub fn add_feed_page(&mut self, before: Option<i64>, feed_page: FeedPage) {
+        if before.is_none() {
+            self.feed = feed_page;
         } else {
-            false
+            self.feed.events.extend(feed_page.events);
+            self.feed.next_before = feed_page.next_before;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager_with_user(friendship_status: FriendshipStatus) -> UserManager {
+        let mut user_manager = UserManager::new(&vec![]);
+        user_manager.loaded_users.push(ForeignUser {
+            username: "felix".to_string(),
+            friendship_status,
+            ..Default::default()
+        });
+        user_manager
+    }
+
+    #[test]
+    fn friend_request_is_pending_until_answered() {
+        let mut user_manager = manager_with_user(FriendshipStatus::NotFriends);
+
+        assert!(user_manager.send_friend_request("felix"));
+        assert_eq!(user_manager.get_non_friend_users().len(), 1);
+        assert!(user_manager.get_friends().is_empty());
+        assert!(!user_manager.send_friend_request("nobody"));
+    }
+
+    #[test]
+    fn sending_request_to_sender_makes_users_friends() {
+        let mut user_manager = manager_with_user(FriendshipStatus::RequestReceived);
+        assert_eq!(user_manager.get_friend_requests().len(), 1);
+
+        user_manager.send_friend_request("felix");
+        assert_eq!(user_manager.get_friends().len(), 1);
+        assert!(user_manager.get_friend_requests().is_empty());
+    }
+
+    #[test]
+    fn older_feed_pages_are_appended_and_refresh_replaces_feed() {
+        use crate::common::mascot_mod::mascot::Mascot;
+        use crate::common::social_feed::{FeedEvent, FeedEventKind};
+
+        let event = |id| FeedEvent {
+            id,
+            username: "felix".to_string(),
+            profile_picture_path: String::new(),
+            favorite_mascot: Mascot::default(),
+            created_at: chrono::NaiveDateTime::default(),
+            kind: FeedEventKind::MascotBought(Mascot::default()),
+        };
+        let mut user_manager = manager_with_user(FriendshipStatus::Friends);
+
+        user_manager.add_feed_page(
+            None,
+            FeedPage {
+                events: vec![event(3)],
+                next_before: Some(3),
+            },
+        );
+        user_manager.add_feed_page(
+            Some(3),
+            FeedPage {
+                events: vec![event(1)],
+                next_before: None,
+            },
+        );
+        let ids = |user_manager: &UserManager| {
+            user_manager
+                .feed
+                .events
+                .iter()
+                .map(|event| event.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&user_manager), vec![3, 1]);
+        assert_eq!(user_manager.feed.next_before, None);
+
+        user_manager.add_feed_page(None, FeedPage::default());
+        assert!(ids(&user_manager).is_empty());
+
+        user_manager.add_feed_page(
+            None,
+            FeedPage {
+                events: vec![event(4)],
+                next_before: None,
+            },
+        );
+        user_manager.remove_user_as_friend("felix");
+        assert!(user_manager.feed.events.is_empty());
+    }
+
+    #[test]
+    fn blocked_user_is_removed_until_unblocked() {
+        let mut user_manager = manager_with_user(FriendshipStatus::Friends);
+        user_manager.challenge_editor.opponent = Some("felix".to_string());
+
+        user_manager.block_user("felix");
+        assert!(user_manager.get_user_by_username("felix").is_none());
+        assert_eq!(user_manager.challenge_editor.opponent, None);
+        assert_eq!(user_manager.blocked_users, vec!["felix".to_string()]);
+
+        user_manager.block_user("Felix");
+        assert_eq!(user_manager.blocked_users.len(), 1);
+
+        user_manager.unblock_user("felix");
+        assert!(!user_manager.is_blocked("felix"));
+    }
+
+    #[test]
+    fn reactions_on_own_profile_workouts_reach_the_recent_workouts() {
+        let mut user_manager = manager_with_user(FriendshipStatus::Friends);
+        user_manager.user_info.username = "anna".to_string();
+        let summary = |workout_id, owner: &str| WorkoutSummary {
+            workout_id,
+            owner: owner.to_string(),
+            date: chrono::NaiveDate::default(),
+            exercise_names: vec!["Squat".to_string()],
+            reactions: WorkoutReactions::new(workout_id),
+        };
+
+        user_manager.most_recently_viewed_user = UserType::Other("felix".to_string());
+        user_manager.set_profile_workouts("felix", vec![summary(2, "felix")]);
+        assert_eq!(user_manager.toggle_kudos(2), Some(true));
+        assert_eq!(user_manager.toggle_kudos(7), None);
+        assert!(user_manager.own_workout_reactions.is_empty());
+
+        // Workouts of a profile which isn't viewed anymore are ignored
+        user_manager.most_recently_viewed_user = UserType::Own;
+        user_manager.set_profile_workouts("felix", vec![]);
+        assert_eq!(user_manager.profile_workouts.len(), 1);
+
+        user_manager.set_profile_workouts("anna", vec![summary(5, "anna")]);
+        user_manager.add_workout_comment(WorkoutComment {
+            id: 1,
+            workout_id: 5,
+            author: "felix".to_string(),
+            text: "Nice".to_string(),
+            created_at: chrono::NaiveDateTime::default(),
+        });
+        let comment = &user_manager.profile_workouts[0].reactions.comments[0];
+        assert!(user_manager.can_delete_comment("anna", comment));
+        assert_eq!(
+            user_manager
+                .get_own_workout_reactions(5)
+                .map(|reactions| reactions.summary()),
+            Some("0 kudos | 1 comment".to_string())
+        );
+
+        user_manager.remove_workout_comment(5, 1);
+        assert!(user_manager.get_own_workout_reactions(5).is_none());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::client::gui::bb_widget::progress_bar::ProgressBarState;
 use crate::common::user_mod::user::UserInformation;
+use crate::common::user_mod::user_goals::GoalType;
+use crate::common::user_mod::user_log::UserLog;
 
 pub struct ProgressBarStateManager {
     pub water_progress_bar_state: ProgressBarState,
@@ -9,10 +11,20 @@ pub struct ProgressBarStateManager {
 
 impl ProgressBarStateManager {
     pub(crate) fn new(user_information: &UserInformation) -> Self {
+        let user_logs = &user_information.user_logs;
         Self {
-            water_progress_bar_state: ProgressBarState::new(0.0, user_information.user_goals.water),
-            steps_progress_bar_state: ProgressBarState::new(0.0, user_information.user_goals.steps),
-            sleep_progress_bar_state: ProgressBarState::new(0.0, user_information.user_goals.sleep),
+            water_progress_bar_state: ProgressBarState::new(
+                user_logs.todays_value(&GoalType::Water),
+                user_information.user_goals.water,
+            ),
+            steps_progress_bar_state: ProgressBarState::new(
+                user_logs.todays_value(&GoalType::Steps),
+                user_information.user_goals.steps,
+            ),
+            sleep_progress_bar_state: ProgressBarState::new(
+                user_logs.todays_value(&GoalType::Sleep),
+                user_information.user_goals.sleep,
+            ),
         }
     }
 
@@ -29,4 +41,78 @@ impl ProgressBarStateManager {
         self.steps_progress_bar_state.goal_value = user_information.user_goals.steps;
         self.sleep_progress_bar_state.goal_value = user_information.user_goals.sleep;
     }
+
+    /// Sets the current values to what was logged today, e.g. after the logs were fetched at login
+    pub(crate) fn update_current_values(&mut self, user_logs: &UserLog) {
+        for goal_type in GoalType::DAILY_LOG_TYPES {
+            self.state_mut(goal_type).current_value = user_logs.todays_value(&goal_type);
+        }
+    }
+
+    pub(crate) fn state(&self, goal_type: GoalType) -> &ProgressBarState {
+        match goal_type {
+            GoalType::Water => &self.water_progress_bar_state,
+            GoalType::Steps => &self.steps_progress_bar_state,
+            GoalType::Sleep => &self.sleep_progress_bar_state,
+            GoalType::WeeklyWorkouts | GoalType::Weight => {
+                panic!("There are no progress bars of these types yet!")
+            }
+        }
+    }
+
+    fn state_mut(&mut self, goal_type: GoalType) -> &mut ProgressBarState {
+        match goal_type {
+            GoalType::Water => &mut self.water_progress_bar_state,
+            GoalType::Steps => &mut self.steps_progress_bar_state,
+            GoalType::Sleep => &mut self.sleep_progress_bar_state,
+            GoalType::WeeklyWorkouts | GoalType::Weight => {
+                panic!("There are no progress bars of these types yet!")
+            }
+        }
+    }
+
+    /// Goal types whose current value differs from the ones in `previous`
+    pub(crate) fn changed_values(&self, previous: &Self) -> Vec<(GoalType, f32)> {
+        GoalType::DAILY_LOG_TYPES
+            .into_iter()
+            .map(|goal_type| (goal_type, self.state(goal_type).current_value))
+            .filter(|(goal_type, value)| previous.state(*goal_type).current_value != *value)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    #[test]
+    fn current_values_are_loaded_from_todays_logs() {
+        let mut user_information = UserInformation::default(&vec![]);
+        let today = Local::now().date_naive();
+        let yesterday = today.pred_opt().unwrap();
+        user_information.user_logs.water_log = vec![(yesterday, 3.0), (today, 1.5)];
+        user_information.user_logs.step_log = vec![(yesterday, 9000.0)];
+
+        let manager = ProgressBarStateManager::new(&user_information);
+
+        assert_eq!(manager.water_progress_bar_state.current_value, 1.5);
+        assert_eq!(manager.steps_progress_bar_state.current_value, 0.0);
+        assert_eq!(manager.sleep_progress_bar_state.current_value, 0.0);
+    }
+
+    #[test]
+    fn only_changed_values_are_reported() {
+        let user_information = UserInformation::default(&vec![]);
+        let previous = ProgressBarStateManager::new(&user_information);
+        let mut pending = previous.duplicate_states();
+        pending.water_progress_bar_state.increment(GoalType::Water);
+        pending.sleep_progress_bar_state.increment(GoalType::Sleep);
+
+        assert_eq!(
+            pending.changed_values(&previous),
+            vec![(GoalType::Water, 0.25), (GoalType::Sleep, 0.5)]
+        );
+        assert!(previous.changed_values(&previous).is_empty());
+    }
 }
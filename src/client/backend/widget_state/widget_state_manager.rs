@@ -7,9 +7,11 @@ use crate::client::gui::bb_widget::animated_background_mod::animated_background:
     BackgroundAnimationMessage, BackgroundAnimationState,
 };
 use crate::client::gui::bb_widget::bmi_calculator::{BMIMessage, BMIWidgetState};
+use crate::client::gui::bb_widget::body_map_widget::BodyMapWidgetState;
 use crate::client::gui::bb_widget::chart_widget::chart::{ChartMessage, DataPointsType};
 use crate::client::gui::bb_widget::chart_widget::graph::GraphWidgetState;
 use crate::client::gui::bb_widget::circle_widget::{CircleMessage, CircleWidgetState};
+use crate::client::gui::bb_widget::general_exercise_info_elements::CustomExerciseMessage;
 use crate::client::gui::bb_widget::progress_bar::ProgressBarMessage;
 use crate::client::gui::user_interface::Message;
 use crate::common::mascot_mod::mascot::Mascot;
@@ -22,6 +24,7 @@ pub struct WidgetManager {
     pub health_graph_widget_state: GraphWidgetState,
     pub circle_widget_state: CircleWidgetState,
     pub bmi_widget_state: BMIWidgetState,
+    pub body_map_widget_state: BodyMapWidgetState,
     pub progress_bar_state_manager: ProgressBarStateManager,
     pub pending_progress_bar_state_manager: Option<ProgressBarStateManager>,
     pub background_animation_state: BackgroundAnimationState,
@@ -47,6 +50,7 @@ impl WidgetManager {
             )),
             circle_widget_state: CircleWidgetState::new(),
             bmi_widget_state: BMIWidgetState::new(),
+            body_map_widget_state: BodyMapWidgetState::default(),
             progress_bar_state_manager: ProgressBarStateManager::new(user_information),
             pending_progress_bar_state_manager: None,
             background_animation_state: BackgroundAnimationState::default(),
@@ -74,6 +78,7 @@ pub enum WidgetMessage {
     ProgressBar(ProgressBarMessage),
     BackgroundAnimation(BackgroundAnimationMessage),
     ToggleGeneralExerciseInfo(u32),
+    CustomExercise(CustomExerciseMessage),
 }
 
 impl WidgetMessage {
@@ -106,6 +111,9 @@ impl WidgetMessage {
                     &mut app.widget_manager.background_animation_state,
                 )
             }
+            WidgetMessage::CustomExercise(custom_exercise_message) => {
+                custom_exercise_message.update(app)
+            }
             WidgetMessage::ToggleGeneralExerciseInfo(id) => {
                 let extended_set = &mut app.exercise_manager.extended_general_exercise_infos;
                 if extended_set.contains(&id) {
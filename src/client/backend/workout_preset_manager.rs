@@ -1,4 +1,5 @@
-use crate::common::workout_preset::{PresetCreation, WorkoutPreset};
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::workout_preset::{PresetCreation, PresetStats, WorkoutPreset};
 
 #[derive(Debug, PartialEq)]
 pub enum PresetSafeError {
@@ -8,10 +9,16 @@ pub enum PresetSafeError {
     PresetEmpty,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum EditPresetError {
+    PresetAlreadyInCreation,
+    PresetNotFound,
+}
+
 #[derive(Default)]
 pub struct WorkoutPresetManager {
     pub presets: Vec<WorkoutPreset>,
-    /// used for preset creation
+    /// used for preset creation and for editing a saved preset, which has an id
     pub preset_in_creation: Option<PresetCreation>,
 }
 
@@ -22,10 +29,62 @@ impl WorkoutPresetManager {
         }
     }
 
-    pub fn add_preset(&mut self) {
+    pub fn add_preset(&mut self, preset_id: Id) {
         if let Some(new_preset) = &self.preset_in_creation {
+            let mut new_preset = new_preset.workout_preset.clone();
+            new_preset.id = Some(preset_id);
             //add preset to front, so the newest created is the first to be shown
-            self.presets.insert(0, new_preset.workout_preset.clone());
+            self.presets.insert(0, new_preset);
+            self.preset_in_creation = None;
+        }
+    }
+
+    /// Id of the saved preset that is currently edited
+    pub fn preset_in_edit_id(&self) -> Option<Id> {
+        self.preset_in_creation
+            .as_ref()
+            .and_then(|preset_creation| preset_creation.workout_preset.id)
+    }
+
+    /// Opens a saved preset in the preset creation, unless another preset is already in creation
+    pub fn start_preset_edit(&mut self, preset_id: Id) -> Result<(), EditPresetError> {
+        match &self.preset_in_creation {
+            Some(preset_creation) if preset_creation.workout_preset.id == Some(preset_id) => Ok(()),
+            Some(_) => Err(EditPresetError::PresetAlreadyInCreation),
+            None => self.force_preset_edit(preset_id),
+        }
+    }
+
+    pub fn force_preset_edit(&mut self, preset_id: Id) -> Result<(), EditPresetError> {
+        let preset = self
+            .presets
+            .iter()
+            .find(|preset| preset.id == Some(preset_id))
+            .ok_or(EditPresetError::PresetNotFound)?;
+        self.preset_in_creation = Some(PresetCreation {
+            workout_preset: preset.clone(),
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    /// Replaces the saved preset with its edited version
+    pub fn replace_preset(&mut self) {
+        if let Some(preset_creation) = self.preset_in_creation.take() {
+            let edited_preset = preset_creation.workout_preset;
+            if let Some(preset) = self
+                .presets
+                .iter_mut()
+                .find(|preset| preset.id.is_some() && preset.id == edited_preset.id)
+            {
+                *preset = edited_preset;
+            }
+        }
+    }
+
+    pub fn delete_preset(&mut self, preset_id: Id) {
+        self.presets.retain(|preset| preset.id != Some(preset_id));
+        if self.preset_in_edit_id() == Some(preset_id) {
             self.preset_in_creation = None;
         }
     }
@@ -39,7 +98,10 @@ impl WorkoutPresetManager {
                 return Err(PresetSafeError::NameEmpty);
             }
             for preset in &self.presets {
-                if preset.name == new_preset.workout_preset.name {
+                // an edited preset may keep its own name
+                if preset.name == new_preset.workout_preset.name
+                    && (preset.id.is_none() || preset.id != new_preset.workout_preset.id)
+                {
                     return Err(PresetSafeError::NameAlreadyExists);
                 }
             }
@@ -49,16 +111,35 @@ impl WorkoutPresetManager {
         }
     }
 
-    pub fn move_preset_to_front(&mut self, preset: &WorkoutPreset) {
+    /// Returns true if the order of the presets changed
+    pub fn move_preset_to_front(&mut self, preset: &WorkoutPreset) -> bool {
         if let Some(index) = self
             .presets
             .iter()
             .position(|preset_in_vec| -> bool { preset.name == preset_in_vec.name })
         {
             self.presets.remove(index);
-            self.presets.insert(0, preset.clone())
+            self.presets.insert(0, preset.clone());
+            index != 0
+        } else {
+            false
+        }
+    }
+
+    pub fn update_preset_stats(&mut self, preset_id: Id, stats: PresetStats) {
+        if let Some(preset) = self
+            .presets
+            .iter_mut()
+            .find(|preset| preset.id == Some(preset_id))
+        {
+            preset.stats = stats;
         }
     }
+
+    /// Ids of the saved presets in the order they are shown
+    pub fn preset_order(&self) -> Vec<Id> {
+        self.presets.iter().filter_map(|preset| preset.id).collect()
+    }
 }
 
 #[cfg(test)]
@@ -80,9 +161,11 @@ mod tests {
         let mut workout_preset_manager = WorkoutPresetManager::default();
         let preset_creation = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "Preset1".to_string(),
                 image: Default::default(),
                 exercises: vec![],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
@@ -98,7 +181,7 @@ mod tests {
     #[test]
     fn add_preset_none() {
         let mut workout_preset_manager = WorkoutPresetManager::default();
-        workout_preset_manager.add_preset();
+        workout_preset_manager.add_preset(1);
         assert!(workout_preset_manager.presets.is_empty())
     }
 
@@ -107,17 +190,19 @@ mod tests {
         let mut workout_preset_manager = WorkoutPresetManager::default();
         let preset_creation = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "Preset1".to_string(),
                 image: Default::default(),
                 exercises: vec![],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
         };
         workout_preset_manager.preset_in_creation = Some(preset_creation.clone());
-        workout_preset_manager.add_preset();
+        workout_preset_manager.add_preset(1);
         assert!(workout_preset_manager.preset_in_creation.is_none());
-        assert!(!workout_preset_manager.presets.is_empty());
+        assert_eq!(workout_preset_manager.presets[0].id, Some(1));
     }
 
     #[test]
@@ -134,9 +219,11 @@ mod tests {
         let mut workout_preset_manager = WorkoutPresetManager::default();
         let preset_creation = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "".to_string(),
                 image: Default::default(),
                 exercises: vec!["Exercise".to_string()],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
@@ -162,9 +249,11 @@ mod tests {
         let mut workout_preset_manager = WorkoutPresetManager::default();
         let preset_creation = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "123".to_string(),
                 image: Default::default(),
                 exercises: vec!["Exercise".to_string()],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
@@ -182,9 +271,11 @@ mod tests {
         let mut workout_preset_manager = WorkoutPresetManager::default();
         let preset_creation = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "123".to_string(),
                 image: Default::default(),
                 exercises: vec!["Exercise".to_string()],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
@@ -198,18 +289,22 @@ mod tests {
         let mut workout_preset_manager = WorkoutPresetManager::default();
         let preset_creation1 = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "123".to_string(),
                 image: Default::default(),
                 exercises: vec!["Exercise".to_string()],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
         };
         let preset_creation2 = PresetCreation {
             workout_preset: WorkoutPreset {
+                id: None,
                 name: "123".to_string(),
                 image: Default::default(),
                 exercises: vec!["Exercise".to_string()],
+                stats: Default::default(),
             },
             edit_title: false,
             edit_image: false,
@@ -224,4 +319,86 @@ mod tests {
             preset_creation2.workout_preset
         );
     }
+
+    fn saved_preset(id: Id, name: &str) -> WorkoutPreset {
+        WorkoutPreset {
+            id: Some(id),
+            name: name.to_string(),
+            image: Default::default(),
+            exercises: vec!["Exercise".to_string()],
+            stats: Default::default(),
+        }
+    }
+
+    #[test]
+    fn edit_preset_and_replace() {
+        let mut workout_preset_manager = WorkoutPresetManager {
+            presets: vec![saved_preset(1, "Push"), saved_preset(2, "Pull")],
+            preset_in_creation: None,
+        };
+        assert_eq!(
+            workout_preset_manager.start_preset_edit(3),
+            Err(EditPresetError::PresetNotFound)
+        );
+        workout_preset_manager.start_preset_edit(2).unwrap();
+        assert_eq!(workout_preset_manager.preset_in_edit_id(), Some(2));
+        // keeping its own name is fine
+        assert!(workout_preset_manager.check_preset().is_ok());
+
+        let preset_creation = workout_preset_manager.preset_in_creation.as_mut().unwrap();
+        preset_creation.workout_preset.name = "Push".to_string();
+        assert_eq!(
+            workout_preset_manager.check_preset(),
+            Err(PresetSafeError::NameAlreadyExists)
+        );
+
+        let preset_creation = workout_preset_manager.preset_in_creation.as_mut().unwrap();
+        preset_creation.workout_preset.name = "Back".to_string();
+        workout_preset_manager.replace_preset();
+        assert!(workout_preset_manager.preset_in_creation.is_none());
+        assert_eq!(
+            workout_preset_manager.presets,
+            vec![saved_preset(1, "Push"), saved_preset(2, "Back")]
+        );
+    }
+
+    #[test]
+    fn start_preset_edit_with_preset_in_creation() {
+        let mut workout_preset_manager = WorkoutPresetManager {
+            presets: vec![saved_preset(1, "Push")],
+            preset_in_creation: None,
+        };
+        workout_preset_manager.start_preset_creation();
+        assert_eq!(
+            workout_preset_manager.start_preset_edit(1),
+            Err(EditPresetError::PresetAlreadyInCreation)
+        );
+        workout_preset_manager.force_preset_edit(1).unwrap();
+        assert_eq!(workout_preset_manager.preset_in_edit_id(), Some(1));
+        // continuing the same edit is no conflict
+        assert!(workout_preset_manager.start_preset_edit(1).is_ok());
+    }
+
+    #[test]
+    fn delete_preset_stops_its_edit() {
+        let mut workout_preset_manager = WorkoutPresetManager {
+            presets: vec![saved_preset(1, "Push"), saved_preset(2, "Pull")],
+            preset_in_creation: None,
+        };
+        workout_preset_manager.start_preset_edit(1).unwrap();
+        workout_preset_manager.delete_preset(1);
+        assert!(workout_preset_manager.preset_in_creation.is_none());
+        assert_eq!(workout_preset_manager.preset_order(), vec![2]);
+    }
+
+    #[test]
+    fn move_preset_to_front_reports_changed_order() {
+        let mut workout_preset_manager = WorkoutPresetManager {
+            presets: vec![saved_preset(1, "Push"), saved_preset(2, "Pull")],
+            preset_in_creation: None,
+        };
+        assert!(!workout_preset_manager.move_preset_to_front(&saved_preset(1, "Push")));
+        assert!(workout_preset_manager.move_preset_to_front(&saved_preset(2, "Pull")));
+        assert_eq!(workout_preset_manager.preset_order(), vec![2, 1]);
+    }
 }
@@ -0,0 +1,214 @@
+use crate::client::server_communication::api_client::DEFAULT_SERVER_URL;
+use clap::Parser;
+use reqwest::Url;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Config file which is loaded if no other path is given via `--config` or `BUFFBUDDIES_CLIENT_CONFIG`
+pub const DEFAULT_CLIENT_CONFIG_FILE_PATH: &str = "client_config.toml";
+/// File the exercise catalog of the server is cached in, see CachedExerciseCatalog
+pub const DEFAULT_EXERCISE_CATALOG_CACHE_PATH: &str = "exercise_catalog_cache.json";
+
+/// Command line flags of the client binary.
+/// Every flag can alternatively be set through the named environment variable.
+/// Flags take precedence over environment variables, which take precedence over the config file.
+#[derive(Parser, Debug, Default)]
+#[command(name = "client", about = "Runs the BuffBuddies desktop client")]
+pub struct ClientArgs {
+    /// Path to a TOML config file
+    #[arg(long, env = "BUFFBUDDIES_CLIENT_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// Address of the BuffBuddies server, e.g. http://192.168.0.10:3000
+    #[arg(long, env = "BUFFBUDDIES_SERVER_URL")]
+    pub server_url: Option<String>,
+    /// File the downloaded exercise catalog is cached in
+    #[arg(long, env = "BUFFBUDDIES_EXERCISE_CATALOG_CACHE")]
+    pub exercise_catalog_cache: Option<PathBuf>,
+}
+
+/// Content of the TOML config file
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfigFile {
+    pub server_url: Option<String>,
+    pub exercise_catalog_cache: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum ClientConfigError {
+    /// The config file was explicitly requested but doesn't exist
+    ConfigFileNotFound(PathBuf),
+    CouldNotReadConfigFile(PathBuf, std::io::Error),
+    InvalidConfigFile(PathBuf, toml::de::Error),
+    InvalidServerUrl(String),
+}
+impl ClientConfigError {
+    pub fn to_error_message(&self) -> String {
+        match self {
+            ClientConfigError::ConfigFileNotFound(path) => {
+                format!("Config file {} doesn't exist!", path.display())
+            }
+            ClientConfigError::CouldNotReadConfigFile(path, err) => {
+                format!("Could not read config file {}: {err}", path.display())
+            }
+            ClientConfigError::InvalidConfigFile(path, err) => {
+                format!("Config file {} is invalid: {err}", path.display())
+            }
+            ClientConfigError::InvalidServerUrl(url) => {
+                format!("{url} is not a valid server address! Expected e.g. http://127.0.0.1:3000")
+            }
+        }
+    }
+}
+
+/// Validated configuration the client is started with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientConfig {
+    /// Normalized server url, see normalize_server_url
+    pub server_url: String,
+    pub exercise_catalog_cache_path: PathBuf,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            server_url: DEFAULT_SERVER_URL.to_string(),
+            exercise_catalog_cache_path: PathBuf::from(DEFAULT_EXERCISE_CATALOG_CACHE_PATH),
+        }
+    }
+}
+
+impl ClientConfigFile {
+    /// Loads the config file at `path`. <br>
+    /// If no path is given the DEFAULT_CLIENT_CONFIG_FILE_PATH is used, but only if that file exists
+    pub fn load(path: Option<&Path>) -> Result<Self, ClientConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path, true),
+            None => (Path::new(DEFAULT_CLIENT_CONFIG_FILE_PATH), false),
+        };
+
+        if !path.exists() {
+            return if required {
+                Err(ClientConfigError::ConfigFileNotFound(path.to_path_buf()))
+            } else {
+                Ok(ClientConfigFile::default())
+            };
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| ClientConfigError::CouldNotReadConfigFile(path.to_path_buf(), err))?;
+
+        toml::from_str(&content)
+            .map_err(|err| ClientConfigError::InvalidConfigFile(path.to_path_buf(), err))
+    }
+}
+
+impl ClientConfig {
+    /// Loads the config file referenced by `args` and merges it with the flags/environment variables
+    pub fn load(args: ClientArgs) -> Result<Self, ClientConfigError> {
+        let config_file = ClientConfigFile::load(args.config.as_deref())?;
+        Self::from_sources(args, config_file)
+    }
+
+    /// Merges both sources, `args` overriding `config_file`, and validates the result
+    pub fn from_sources(
+        args: ClientArgs,
+        config_file: ClientConfigFile,
+    ) -> Result<Self, ClientConfigError> {
+        let server_url = args
+            .server_url
+            .or(config_file.server_url)
+            .unwrap_or(DEFAULT_SERVER_URL.to_string());
+        let exercise_catalog_cache_path = args
+            .exercise_catalog_cache
+            .or(config_file.exercise_catalog_cache)
+            .unwrap_or(PathBuf::from(DEFAULT_EXERCISE_CATALOG_CACHE_PATH));
+
+        Ok(ClientConfig {
+            server_url: normalize_server_url(&server_url)?,
+            exercise_catalog_cache_path,
+        })
+    }
+}
+
+/// Validates a server address entered by the user. <br>
+/// "http://" is added if the scheme is missing and trailing slashes are removed,
+/// e.g. "192.168.0.10:3000/" becomes "http://192.168.0.10:3000"
+pub fn normalize_server_url(server_url: &str) -> Result<String, ClientConfigError> {
+    let trimmed_url = server_url.trim();
+    let url_with_scheme = if trimmed_url.contains("://") {
+        trimmed_url.to_string()
+    } else {
+        format!("http://{trimmed_url}")
+    };
+
+    let invalid_url_error = || ClientConfigError::InvalidServerUrl(server_url.to_string());
+
+    let url = Url::parse(&url_with_scheme).map_err(|_| invalid_url_error())?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(invalid_url_error());
+    }
+
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_server_url_without_other_sources() {
+        let config =
+            ClientConfig::from_sources(ClientArgs::default(), ClientConfigFile::default()).unwrap();
+        assert_eq!(config, ClientConfig::default());
+    }
+    #[test]
+    fn args_override_config_file() {
+        let args = ClientArgs {
+            server_url: Some("http://10.0.0.2:3000".to_string()),
+            ..Default::default()
+        };
+        let config_file = ClientConfigFile {
+            server_url: Some("http://10.0.0.3:3000".to_string()),
+            exercise_catalog_cache: Some(PathBuf::from("cache/catalog.json")),
+        };
+        let config = ClientConfig::from_sources(args, config_file).unwrap();
+        assert_eq!(config.server_url, "http://10.0.0.2:3000");
+        assert_eq!(
+            config.exercise_catalog_cache_path,
+            PathBuf::from("cache/catalog.json")
+        );
+    }
+    #[test]
+    fn config_file_is_used_without_args() {
+        let config_file: ClientConfigFile =
+            toml::from_str("server_url = \"https://buffbuddies.example\"").unwrap();
+        let config = ClientConfig::from_sources(ClientArgs::default(), config_file).unwrap();
+        assert_eq!(config.server_url, "https://buffbuddies.example");
+    }
+    #[test]
+    fn normalize_adds_scheme_and_removes_trailing_slash() {
+        assert_eq!(
+            normalize_server_url(" 192.168.0.10:3000/ ").unwrap(),
+            "http://192.168.0.10:3000"
+        );
+        assert_eq!(
+            normalize_server_url("https://buffbuddies.example/").unwrap(),
+            "https://buffbuddies.example"
+        );
+    }
+    #[test]
+    fn normalize_rejects_invalid_urls() {
+        assert!(normalize_server_url("").is_err());
+        assert!(normalize_server_url("ftp://192.168.0.10").is_err());
+        assert!(normalize_server_url("http://").is_err());
+    }
+    #[test]
+    fn explicit_missing_config_file_is_an_error() {
+        let result = ClientConfigFile::load(Some(Path::new("does/not/exist.toml")));
+        assert!(matches!(
+            result,
+            Err(ClientConfigError::ConfigFileNotFound(_))
+        ));
+    }
+}
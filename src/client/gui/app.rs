@@ -3,16 +3,21 @@ use crate::client::backend::image_manager::ImageManager;
 use crate::client::backend::login_state::{LoginState, LoginStates};
 use crate::client::backend::mascot_manager::MascotManager;
 use crate::client::backend::pop_up_manager::PopUpManager;
+use crate::client::backend::profile_stat_manager::calculate_weekly_muscle_volume;
 use crate::client::backend::user_manager::UserManager;
 use crate::client::backend::widget_state::widget_state_manager::WidgetManager;
 use crate::client::backend::workout_preset_manager::WorkoutPresetManager;
+use crate::client::client_config::ClientConfig;
 use crate::client::gui::bb_tab::tab::Tab;
+use crate::client::server_communication::api_client::ApiClient;
 use crate::client::server_communication::request_data::LoginServerRequestData;
+use chrono::Local;
+use std::path::PathBuf;
 
 pub struct App {
-    /// Every connection to the server after the login has to contain this jwt in its JSON header
-    /// to qualify as a valid request. E.g. see user_communicator::get_user_information_from_server
-    pub jsonwebtoken: Option<String>,
+    /// Sends every request to the server and holds the jwt of the logged-in user.
+    /// E.g. see user_communicator::get_user_information_from_server
+    pub api_client: ApiClient,
     /// Contains the currently viewed tab in logged-in view
     pub screen: Tab,
     pub widget_manager: WidgetManager,
@@ -23,16 +28,23 @@ pub struct App {
     pub user_manager: UserManager,
     pub image_manager: ImageManager,
     pub pop_up_manager: PopUpManager,
+    /// Kept when logging out, see ClientConfig
+    pub exercise_catalog_cache_path: PathBuf,
 }
 
 impl Default for App {
     fn default() -> Self {
+        App::new(ClientConfig::default())
+    }
+}
+impl App {
+    pub fn new(client_config: ClientConfig) -> Self {
         let exercise_manager = ExerciseManager::default();
         let user_manager = UserManager::new(&exercise_manager.exercises);
         App {
-            jsonwebtoken: None,
+            api_client: ApiClient::new(client_config.server_url.clone()),
             screen: Tab::Home,
-            login_state: LoginState::default(),
+            login_state: LoginState::new(client_config.server_url),
             widget_manager: WidgetManager::new(&user_manager.user_info),
             user_manager,
             mascot_manager: MascotManager::default(),
@@ -40,15 +52,29 @@ impl Default for App {
             workout_preset_manager: WorkoutPresetManager::default(),
             image_manager: ImageManager::default(),
             pop_up_manager: PopUpManager::default(),
+            exercise_catalog_cache_path: client_config.exercise_catalog_cache_path,
         }
     }
-}
-impl App {
+
+    /// Logs the user out by resetting the whole app. <br>
+    /// The server the user connected to and the catalog cache are kept
+    pub fn reset_to_login(&mut self) {
+        *self = App::new(ClientConfig {
+            server_url: self.api_client.base_url().to_string(),
+            exercise_catalog_cache_path: self.exercise_catalog_cache_path.clone(),
+        });
+    }
+
     pub fn update_app_on_login(&mut self, data: LoginServerRequestData) {
         // Update user info
         self.user_manager.user_info = data.user_information;
         // Update foreign users
         self.user_manager.loaded_users = data.foreign_users;
+        self.user_manager.feed = data.feed;
+        self.user_manager.own_workout_reactions = data.own_workout_reactions;
+        self.user_manager.leaderboard = data.leaderboard;
+        self.user_manager.challenges = data.challenges;
+        self.user_manager.blocked_users = data.blocked_users;
         // Update mascot manager
         self.mascot_manager
             .update_mascot_manager_on_login(data.mascot_data);
@@ -65,6 +91,19 @@ impl App {
         // Update preset manager
         self.workout_preset_manager.presets = data.presets;
         // Update activity_widget state
+        self.refresh_activity_widget();
+        //Update widget manager goals and today's logged values
+        self.widget_manager
+            .progress_bar_state_manager
+            .update_goals(&self.user_manager.user_info);
+        self.widget_manager
+            .progress_bar_state_manager
+            .update_current_values(&self.user_manager.user_info.user_logs);
+    }
+
+    /// Shows the current activity data and sets per muscle of the user,
+    /// e.g. after a (backdated) workout was saved
+    pub fn refresh_activity_widget(&mut self) {
         self.widget_manager.activity_widget.update_data(
             self.mascot_manager.selected_mascot,
             self.user_manager
@@ -73,10 +112,12 @@ impl App {
                 .activity_data
                 .clone(),
         );
-        //Update widget manager goals
         self.widget_manager
-            .progress_bar_state_manager
-            .update_goals(&self.user_manager.user_info)
+            .body_map_widget_state
+            .update_data(calculate_weekly_muscle_volume(
+                &self.exercise_manager.exercises,
+                Local::now().date_naive(),
+            ));
     }
 
     /// This function updates the screen and login state <br>
@@ -1,10 +1,15 @@
+use crate::client::backend::log_entry_editor::LOG_DATE_FORMAT;
+use crate::client::backend::pop_up_manager::PopUpType;
 use crate::client::backend::widget_state::progress_bar_manager::ProgressBarStateManager;
 use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::health::HealthMessage::{SaveProgressBarChanges, SwitchEditMode};
 use crate::client::gui::bb_tab::tab::FRAME_PADDING;
 use crate::client::gui::bb_theme::color;
 use crate::client::gui::bb_theme::color::TEXT_COLOR;
-use crate::client::gui::bb_theme::container::DEFAULT_CONTAINER_RADIUS;
+use crate::client::gui::bb_theme::combo_box::create_text_input_style;
+use crate::client::gui::bb_theme::container::{
+    ContainerStyle, DEFAULT_CONTAINER_RADIUS, create_container_style,
+};
 use crate::client::gui::bb_theme::custom_button::ButtonStyle::InactiveTab;
 use crate::client::gui::bb_theme::custom_button::{
     ButtonStyle, create_element_button, create_text_button,
@@ -13,7 +18,9 @@ use crate::client::gui::bb_theme::scrollable::{
     ScrollableExtension, ScrollableStyle, TAB_SCROLLBAR_PADDING, TAB_SCROLLBAR_WIDTH,
     create_scrollable,
 };
-use crate::client::gui::bb_theme::text_format::FIRA_SANS_EXTRABOLD;
+use crate::client::gui::bb_theme::text_format::{
+    FIRA_SANS_EXTRABOLD, format_button_text, format_description_text,
+};
 use crate::client::gui::bb_widget::bmi_calculator;
 use crate::client::gui::bb_widget::chart_widget::chart::health_chart_environment_widget;
 use crate::client::gui::bb_widget::circle_widget::{CircleStart, CircleWidget};
@@ -24,33 +31,59 @@ use crate::client::gui::bb_widget::stats::health_stat_container;
 use crate::client::gui::bb_widget::widget_utils::{INDENT, LARGE_INDENT};
 use crate::client::gui::user_interface::Message;
 use crate::client::gui::user_interface::Message::HealthTab;
+use crate::client::server_communication::user_communicator::{
+    delete_user_log_on_server, save_user_log_on_server,
+};
 use crate::common::mascot_mod::epic_mascot::EpicMascot::Capybara;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::mascot_mod::rare_mascot::RareMascot::{Chameleon, Whale};
 use crate::common::user_mod::user_goals::GoalType;
-use iced::widget::{Column, Row, Space, container, image, row, text};
+use crate::common::user_mod::user_log::UserLogEntry;
+use chrono::{Local, NaiveDate};
+use iced::widget::{Column, Row, Space, container, image, row, text, text_input};
 use iced::{Element, Task};
 use iced_core::alignment::Vertical;
 use iced_core::image::Handle;
 use iced_core::{Length, Padding};
 
+const LOG_HEADER_SIZE: f32 = 30.0;
+const LOG_TEXT_SIZE: f32 = 18.0;
+const LOG_INPUT_WIDTH: f32 = 150.0;
+const LOG_CONTAINER_WIDTH: f32 = 700.0;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum HealthMessage {
     SwitchEditMode,
     SaveProgressBarChanges,
+
+    // Log list
+    SelectLogType(GoalType),
+    EditLogDate(String),
+    EditLogValue(String),
+    EditLogEntry(UserLogEntry),
+    SaveLogEntry,
+    DeleteLogEntry(NaiveDate),
 }
 
 impl HealthMessage {
     pub fn update_health_tab(health_tab_message: HealthMessage, app: &mut App) -> Task<Message> {
         match health_tab_message {
             SaveProgressBarChanges => {
-                app.widget_manager.progress_bar_state_manager = app
+                let pending_state = app
                     .widget_manager
                     .pending_progress_bar_state_manager
                     .take() //now pending_progress_bar_state_manager is None and I get Option<ProgressBarState>
-                    .unwrap() //take() is going to return Some(progressbar_state) since SaveProgressBarChanges can only be sent in edit_mode so it can't fail
-
+                    .unwrap(); //take() is going to return Some(progressbar_state) since SaveProgressBarChanges can only be sent in edit_mode so it can't fail
+                let changed_values =
+                    pending_state.changed_values(&app.widget_manager.progress_bar_state_manager);
+                app.widget_manager.progress_bar_state_manager = pending_state;
                 //MODE IS AUTOMATICALLY SWITCHED AT THIS POINT SINCE PENDING STATE IS NONE
+
+                let today = Local::now().date_naive();
+                let save_tasks = changed_values.into_iter().map(|(goal_type, value)| {
+                    app.save_log_entry(goal_type, UserLogEntry { date: today, value })
+                });
+                return Task::batch(save_tasks.collect::<Vec<_>>());
             }
 
             SwitchEditMode => {
@@ -67,12 +100,87 @@ impl HealthMessage {
                         ))
                 }
             }
+
+            HealthMessage::SelectLogType(goal_type) => app
+                .user_manager
+                .log_entry_editor
+                .select_goal_type(goal_type, Local::now().date_naive()),
+            HealthMessage::EditLogDate(date_input) => {
+                app.user_manager.log_entry_editor.date_input = date_input
+            }
+            HealthMessage::EditLogValue(value_input) => {
+                app.user_manager.log_entry_editor.value_input = value_input
+            }
+            HealthMessage::EditLogEntry(entry) => {
+                app.user_manager.log_entry_editor.start_edit(entry)
+            }
+            HealthMessage::SaveLogEntry => {
+                let today = Local::now().date_naive();
+                let log_entry_editor = &mut app.user_manager.log_entry_editor;
+                match log_entry_editor.parse_entry(today) {
+                    Ok(entry) => {
+                        let goal_type = log_entry_editor.selected_goal_type;
+                        log_entry_editor.reset(today);
+                        return app.save_log_entry(goal_type, entry);
+                    }
+                    Err(err) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Invalid log entry".to_string(),
+                        err.to_error_message(),
+                    ),
+                }
+            }
+            HealthMessage::DeleteLogEntry(date) => {
+                let goal_type = app.user_manager.log_entry_editor.selected_goal_type;
+                return app.delete_log_entry(goal_type, date);
+            }
         }
 
         Task::none()
     }
 }
 
+impl App {
+    /// Writes the entry into the local log and sends it to the server
+    fn save_log_entry(&mut self, goal_type: GoalType, entry: UserLogEntry) -> Task<Message> {
+        let _ = self.user_manager.user_info.user_logs.update_log_on_date(
+            &goal_type,
+            entry.date,
+            entry.value,
+        );
+        self.widget_manager
+            .progress_bar_state_manager
+            .update_current_values(&self.user_manager.user_info.user_logs);
+
+        if !self.api_client.is_logged_in() {
+            return Task::none();
+        }
+        Task::perform(
+            save_user_log_on_server(self.api_client.clone(), goal_type, entry),
+            move |result| Message::UpdateInfoOnServerResult(result, format!("{goal_type} log")),
+        )
+    }
+
+    fn delete_log_entry(&mut self, goal_type: GoalType, date: NaiveDate) -> Task<Message> {
+        let _ = self
+            .user_manager
+            .user_info
+            .user_logs
+            .remove_log_on_date(&goal_type, date);
+        self.widget_manager
+            .progress_bar_state_manager
+            .update_current_values(&self.user_manager.user_info.user_logs);
+
+        if !self.api_client.is_logged_in() {
+            return Task::none();
+        }
+        Task::perform(
+            delete_user_log_on_server(self.api_client.clone(), goal_type, date),
+            move |result| Message::UpdateInfoOnServerResult(result, format!("{goal_type} log")),
+        )
+    }
+}
+
 impl App {
     pub fn health_screen(&self) -> Element<'_, Message> {
         let edit_mode: bool = self
@@ -226,6 +334,7 @@ impl App {
         content = content
             .push(circles_with_stats_with_graph)
             .push(progress_bars)
+            .push(self.view_log_entries())
             .padding(Padding {
                 top: LARGE_INDENT,
                 bottom: FRAME_PADDING,
@@ -241,4 +350,129 @@ impl App {
         .add_vertical_scrollbar(TAB_SCROLLBAR_WIDTH, TAB_SCROLLBAR_PADDING)
         .into()
     }
+
+    /// Lists every entry of the selected log with inputs to add, edit or delete entries of any date
+    fn view_log_entries(&self) -> Element<'_, Message> {
+        let mascot = &self.mascot_manager.selected_mascot;
+        let log_entry_editor = &self.user_manager.log_entry_editor;
+        let goal_type = log_entry_editor.selected_goal_type;
+
+        let log_header = text("Logs")
+            .font(FIRA_SANS_EXTRABOLD)
+            .color(color::TEXT_COLOR)
+            .size(LOG_HEADER_SIZE);
+
+        let mut goal_type_buttons = Row::new().spacing(10.0);
+        for log_type in GoalType::LOGGED_TYPES {
+            let button_style = if log_type == goal_type {
+                ButtonStyle::Active
+            } else {
+                InactiveTab
+            };
+            goal_type_buttons = goal_type_buttons.push(
+                create_text_button(
+                    mascot,
+                    log_type.to_string(),
+                    button_style,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .height(35.0)
+                .on_press(HealthTab(HealthMessage::SelectLogType(log_type))),
+            );
+        }
+
+        let date_input = text_input("dd.mm.yyyy", &log_entry_editor.date_input)
+            .on_input(|date_input| HealthTab(HealthMessage::EditLogDate(date_input)))
+            .on_submit(HealthTab(HealthMessage::SaveLogEntry));
+        let value_input = text_input(
+            &format!("{goal_type} {}", goal_type.get_unit()),
+            &log_entry_editor.value_input,
+        )
+        .on_input(|value_input| HealthTab(HealthMessage::EditLogValue(value_input)))
+        .on_submit(HealthTab(HealthMessage::SaveLogEntry));
+        let save_entry_button = create_text_button(
+            mascot,
+            "Save entry".to_string(),
+            ButtonStyle::Active,
+            Some(DEFAULT_CONTAINER_RADIUS.into()),
+        )
+        .height(35.0)
+        .on_press(HealthTab(HealthMessage::SaveLogEntry));
+
+        let mut input_row = Row::new().spacing(10.0).align_y(Vertical::Center);
+        for input in [date_input, value_input] {
+            input_row = input_row.push(
+                input
+                    .style(create_text_input_style(mascot, color::BACKGROUND_COLOR))
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .width(LOG_INPUT_WIDTH),
+            );
+        }
+        input_row = input_row.push(save_entry_button);
+
+        let mut entries_column = Column::new().spacing(5.0);
+        let log = self
+            .user_manager
+            .user_info
+            .user_logs
+            .get_log_by_goal_type(&goal_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if log.is_empty() {
+            entries_column = entries_column
+                .push(format_description_text(text("Nothing logged yet")).size(LOG_TEXT_SIZE));
+        }
+        // newest entries first
+        for (date, value) in log.iter().rev() {
+            let entry = UserLogEntry {
+                date: *date,
+                value: *value,
+            };
+            let edit_button = create_text_button(
+                mascot,
+                "Edit".to_string(),
+                InactiveTab,
+                Some(DEFAULT_CONTAINER_RADIUS.into()),
+            )
+            .on_press(HealthTab(HealthMessage::EditLogEntry(entry)));
+            let delete_button = create_text_button(
+                mascot,
+                "Delete".to_string(),
+                InactiveTab,
+                Some(DEFAULT_CONTAINER_RADIUS.into()),
+            )
+            .on_press(HealthTab(HealthMessage::DeleteLogEntry(*date)));
+
+            entries_column = entries_column.push(
+                Row::new()
+                    .push(
+                        format_description_text(text(date.format(LOG_DATE_FORMAT).to_string()))
+                            .size(LOG_TEXT_SIZE)
+                            .width(LOG_INPUT_WIDTH),
+                    )
+                    .push(
+                        format_button_text(text(format!("{value} {}", goal_type.get_unit())))
+                            .size(LOG_TEXT_SIZE),
+                    )
+                    .push(Space::new().width(Length::Fill))
+                    .push(edit_button)
+                    .push(delete_button)
+                    .spacing(10.0)
+                    .align_y(Vertical::Center),
+            );
+        }
+
+        let contents = Column::new()
+            .push(log_header)
+            .push(goal_type_buttons)
+            .push(input_row)
+            .push(entries_column)
+            .spacing(INDENT)
+            .padding(INDENT);
+
+        container(contents)
+            .style(create_container_style(ContainerStyle::Default, None, None))
+            .width(LOG_CONTAINER_WIDTH)
+            .into()
+    }
 }
@@ -8,6 +8,7 @@ use crate::client::gui::bb_theme::scrollable::{
 use crate::client::gui::bb_theme::text_format::{FIRA_SANS_EXTRABOLD, format_button_text};
 use crate::client::gui::bb_widget::activity_widget::date_utils::DateScope;
 use crate::client::gui::bb_widget::bmi_calculator::BMIWidget;
+use crate::client::gui::bb_widget::body_map_widget::BodyMapWidget;
 use crate::client::gui::bb_widget::chart_widget::chart::chart_environment_widget;
 use crate::client::gui::bb_widget::circle_widget;
 use crate::client::gui::bb_widget::circle_widget::CircleStart;
@@ -42,8 +43,12 @@ impl App {
             .push(activity_widget)
             .spacing(SPACING);
 
+        let body_map_widget = BodyMapWidget::new(self).view();
+
         let mut activity_widget_with_mascot = Row::new()
             .push(activity_widget_with_welcome)
+            .push(Space::new().width(Length::Fixed(SPACING)))
+            .push(body_map_widget)
             .align_y(Vertical::Center)
             .height(HEIGHT_MASCOT);
 
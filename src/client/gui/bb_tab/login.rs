@@ -1,4 +1,4 @@
-use crate::client::backend::login_state::LoginState;
+use crate::client::backend::login_state::{LoginState, LoginStateError};
 use crate::client::backend::pop_up_manager::PopUpType;
 use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::tab::Tab;
@@ -13,66 +13,81 @@ use crate::client::gui::bb_widget::animated_background_mod::animated_background:
 use crate::client::gui::bb_widget::widget_utils::INDENT;
 use crate::client::gui::user_interface::Message;
 use crate::client::server_communication::request_data::request_login_data;
-use crate::client::server_communication::user_communicator::{valid_login, valid_register};
-use crate::common::login::{RequestValidRegisterError, RequestValidUserError};
+use crate::client::server_communication::user_communicator::{
+    LoginRequest, valid_login, valid_register,
+};
+use crate::common::login::{AuthTokens, RequestValidRegisterError, RequestValidUserError};
 use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::user_mod::account::{MAX_PASSWORD_LENGTH, MAX_USERNAME_LENGTH};
 use iced::widget::{Column, Space, container, stack, text, text_input};
 use iced::{Element, Task};
 use iced_core::Length::Fill;
 
-const MAX_USERNAME_LENGTH: usize = 15;
-const MAX_PASSWORD_LENGTH: usize = 100;
-
 #[derive(Clone, Debug)]
 pub enum LoginMessage {
     TryRegister,
     TryLogin,
     /// used to handle result from server after trying to log in
-    RequestValidUser(Result<String, RequestValidUserError>),
+    RequestValidUser(Result<AuthTokens, RequestValidUserError>),
     /// used to handle result from server after trying to register
-    RequestValidRegister(Result<String, RequestValidRegisterError>),
+    RequestValidRegister(Result<AuthTokens, RequestValidRegisterError>),
+    ServerUrlEntered(String),
     UsernameEntered(String),
     PasswordEntered(String),
 }
 
+/// Validates the entered login data and points the api client to the entered server
+fn prepare_login_request(app: &mut App) -> Result<LoginRequest, LoginStateError> {
+    let server_url = app.login_state.try_server_url()?;
+    let login_request = app.login_state.try_login()?;
+    app.api_client.set_base_url(server_url);
+    Ok(login_request)
+}
+
 impl LoginMessage {
     pub fn update(&self, app: &mut App) -> Task<Message> {
         match self {
-            LoginMessage::TryRegister => match app.login_state.try_login() {
+            LoginMessage::TryRegister => match prepare_login_request(app) {
                 Err(err) => {
                     app.login_state.error_text = err.to_error_message();
                     Task::none()
                 }
-                Ok(login_request) => {
-                    Task::perform(valid_register(login_request), |result| -> Message {
+                Ok(login_request) => Task::perform(
+                    valid_register(app.api_client.clone(), login_request),
+                    |result| -> Message {
                         Message::Login(LoginMessage::RequestValidRegister(result))
-                    })
-                }
+                    },
+                ),
             },
-            LoginMessage::TryLogin => match app.login_state.try_login() {
+            LoginMessage::TryLogin => match prepare_login_request(app) {
                 Err(err) => {
                     app.login_state.error_text = err.to_error_message();
                     Task::none()
                 }
-                Ok(login_request) => {
-                    Task::perform(valid_login(login_request), |result| -> Message {
-                        Message::Login(LoginMessage::RequestValidUser(result))
-                    })
-                }
+                Ok(login_request) => Task::perform(
+                    valid_login(app.api_client.clone(), login_request),
+                    |result| -> Message { Message::Login(LoginMessage::RequestValidUser(result)) },
+                ),
             },
-            LoginMessage::RequestValidUser(Ok(jwt)) => {
-                app.jsonwebtoken = Some(jwt.clone());
+            LoginMessage::RequestValidUser(Ok(auth_tokens)) => {
+                app.api_client.set_auth_tokens(Some(auth_tokens.clone()));
                 app.screen = Tab::Loading;
                 Task::perform(
-                    request_login_data(app.jsonwebtoken.clone()),
+                    request_login_data(
+                        app.api_client.clone(),
+                        app.exercise_catalog_cache_path.clone(),
+                    ),
                     Message::RequestLoginData,
                 )
             }
-            LoginMessage::RequestValidRegister(Ok(jwt)) => {
-                app.jsonwebtoken = Some(jwt.clone());
+            LoginMessage::RequestValidRegister(Ok(auth_tokens)) => {
+                app.api_client.set_auth_tokens(Some(auth_tokens.clone()));
                 app.screen = Tab::Loading;
                 Task::perform(
-                    request_login_data(app.jsonwebtoken.clone()),
+                    request_login_data(
+                        app.api_client.clone(),
+                        app.exercise_catalog_cache_path.clone(),
+                    ),
                     Message::RequestLoginData,
                 )
             }
@@ -110,6 +125,10 @@ impl LoginMessage {
                 }
                 Task::none()
             }
+            LoginMessage::ServerUrlEntered(new_server_url) => {
+                app.login_state.server_url = new_server_url.clone();
+                Task::none()
+            }
             LoginMessage::UsernameEntered(new_username) => {
                 if new_username.clone().len() <= MAX_USERNAME_LENGTH {
                     app.login_state.username = new_username.clone();
@@ -165,6 +184,15 @@ fn view_login_container<'a>(
         .color(ERROR_COLOR)
         .into();
 
+    let server_url_field: Element<Message> =
+        text_input("Enter server address...", &login_state.server_url)
+            .style(create_text_input_style(mascot, BACKGROUND_COLOR))
+            .font(FIRA_SANS_EXTRABOLD)
+            .on_input(|new_server_url| -> Message {
+                Message::Login(LoginMessage::ServerUrlEntered(new_server_url))
+            })
+            .into();
+
     let username_field: Element<Message> = text_input("Enter username...", &login_state.username)
         .style(create_text_input_style(mascot, BACKGROUND_COLOR))
         .font(FIRA_SANS_EXTRABOLD)
@@ -198,6 +226,7 @@ fn view_login_container<'a>(
     let login_elements = Column::new()
         .push(login_text)
         .push(error_text)
+        .push(server_url_field)
         .push(username_field)
         .push(password_field)
         .push(Space::new().height(INDENT))
@@ -15,15 +15,17 @@ use crate::client::gui::bb_widget::shop;
 use crate::client::gui::bb_widget::widget_utils::INDENT;
 use crate::client::gui::user_interface::Message;
 use crate::client::server_communication::mascot_communicator::{
-    buy_mascot, update_selected_mascot_on_server,
+    roll_mascot, update_selected_mascot_on_server,
 };
 use crate::client::server_communication::server_communicator::ServerRequestError;
 use crate::common::mascot_mod::epic_mascot::EpicMascot;
 use crate::common::mascot_mod::mascot::{Mascot, MascotRarity};
+use crate::common::mascot_mod::mascot_data_transfer::MascotRollAnswer;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use crate::common::mascot_mod::rare_mascot::RareMascot;
 use iced::widget::{Column, Row, Space, container, image, row, text};
 use iced::{Element, Task};
+use iced_anim::Animation;
 use iced_core::Length::Fill;
 use iced_core::alignment::{Horizontal, Vertical};
 use iced_core::image::{Handle, Image};
@@ -40,7 +42,8 @@ const HEADERS_TEXT_SIZE: f32 = 30.0;
 #[derive(Clone, Debug)]
 pub enum MascotMessage {
     BuyMascot(MascotRarity),
-    SaveMascot(Result<Mascot, ServerRequestError>),
+    RollResult(MascotRarity, Result<MascotRollAnswer, ServerRequestError>),
+    AnimateRoll(iced_anim::Event<f32>),
     SelectMascot(Mascot),
 }
 
@@ -48,66 +51,46 @@ impl MascotMessage {
     pub fn update(&self, app: &mut App) -> Task<Message> {
         match self {
             MascotMessage::BuyMascot(rarity) => {
-                if match rarity {
-                    MascotRarity::Rare => app.user_manager.user_info.coin_balance >= 50,
-                    MascotRarity::Epic => app.user_manager.user_info.coin_balance >= 100,
-                } {
-                    let mut mascot_maybe: Option<Mascot> = None;
-                    match rarity {
-                        MascotRarity::Rare => {
-                            match RareMascot::random_new_rare(&app.mascot_manager) {
-                                Ok(mascot) => mascot_maybe = Some(mascot.into()),
-                                Err(_err) => app.pop_up_manager.new_pop_up(
-                                    PopUpType::Minor,
-                                    "Failed to buy mascot!".to_string(),
-                                    "All mascots of this rarity have already been purchased!"
-                                        .to_string(),
-                                ),
-                            }
-                        }
-                        MascotRarity::Epic => {
-                            match EpicMascot::random_new_epic(&app.mascot_manager) {
-                                Ok(mascot) => mascot_maybe = Some(mascot.into()),
-                                Err(_err) => app.pop_up_manager.new_pop_up(
-                                    PopUpType::Minor,
-                                    "Failed to buy mascot!".to_string(),
-                                    "All mascots of this rarity have already been purchased!"
-                                        .to_string(),
-                                ),
-                            }
-                        }
-                    };
-                    if let Some(mascot) = mascot_maybe {
-                        if let Some(jwt) = &app.jsonwebtoken {
-                            Task::perform(buy_mascot(jwt.clone(), mascot), |result| {
-                                Message::Mascot(MascotMessage::SaveMascot(result))
-                            })
-                        } else {
-                            app.pop_up_manager.new_pop_up(
-                                PopUpType::Minor,
-                                "Buying Mascot failed!".to_string(),
-                                "Log in to buy mascots!".to_string(),
-                            );
-                            Task::none()
-                        }
-                    } else {
-                        Task::none()
-                    }
+                if app.user_manager.user_info.coin_balance < rarity.get_prize() {
+                    show_funds_lacking_pop_up(app);
+                    Task::none()
+                } else if rarity
+                    .mascots()
+                    .into_iter()
+                    .all(|mascot| app.mascot_manager.owns_mascot(mascot))
+                {
+                    show_all_bought_pop_up(app);
+                    Task::none()
+                } else if app.api_client.is_logged_in() {
+                    let rarity = *rarity;
+                    Task::perform(roll_mascot(app.api_client.clone(), rarity), move |result| {
+                        Message::Mascot(MascotMessage::RollResult(rarity, result))
+                    })
                 } else {
                     app.pop_up_manager.new_pop_up(
                         PopUpType::Minor,
-                        "Funds lacking!".to_string(),
-                        "You do not have enough money to buy a mascot of this type".to_string(),
+                        "Buying Mascot failed!".to_string(),
+                        "Log in to buy mascots!".to_string(),
                     );
                     Task::none()
                 }
             }
-            MascotMessage::SaveMascot(Ok(mascot)) => {
-                app.user_manager.user_info.coin_balance -= mascot.get_prize();
-                app.mascot_manager.add_mascot(*mascot);
+            MascotMessage::RollResult(rarity, Ok(answer)) => {
+                match answer {
+                    MascotRollAnswer::Rolled {
+                        mascot,
+                        coin_balance,
+                    } => {
+                        app.user_manager.user_info.coin_balance = *coin_balance;
+                        app.mascot_manager.add_mascot(*mascot);
+                        app.mascot_manager.start_roll_reveal(*rarity, *mascot);
+                    }
+                    MascotRollAnswer::NotEnoughCoins => show_funds_lacking_pop_up(app),
+                    MascotRollAnswer::AllMascotsOwned => show_all_bought_pop_up(app),
+                }
                 Task::none()
             }
-            MascotMessage::SaveMascot(Err(_err)) => {
+            MascotMessage::RollResult(_, Err(_err)) => {
                 app.pop_up_manager.new_pop_up(
                     PopUpType::Minor,
                     "Server error!".to_string(),
@@ -115,6 +98,10 @@ impl MascotMessage {
                 );
                 Task::none()
             }
+            MascotMessage::AnimateRoll(event) => {
+                app.mascot_manager.roll_reveal_animation.update(*event);
+                Task::none()
+            }
             MascotMessage::SelectMascot(mascot) => {
                 let active_mascot = &mut app.mascot_manager.selected_mascot;
                 *active_mascot = *mascot;
@@ -122,10 +109,13 @@ impl MascotMessage {
                     .activity_widget
                     .update_active_mascot(*active_mascot);
 
-                if let Some(jwt) = app.jsonwebtoken.clone() {
-                    Task::perform(update_selected_mascot_on_server(jwt, *mascot), |result| {
-                        Message::UpdateInfoOnServerResult(result, "selected Mascot".to_string())
-                    })
+                if app.api_client.is_logged_in() {
+                    Task::perform(
+                        update_selected_mascot_on_server(app.api_client.clone(), *mascot),
+                        |result| {
+                            Message::UpdateInfoOnServerResult(result, "selected Mascot".to_string())
+                        },
+                    )
                 } else {
                     println!("Log in to select a Mascot!");
                     Task::none()
@@ -135,6 +125,22 @@ impl MascotMessage {
     }
 }
 
+fn show_funds_lacking_pop_up(app: &mut App) {
+    app.pop_up_manager.new_pop_up(
+        PopUpType::Minor,
+        "Funds lacking!".to_string(),
+        "You do not have enough money to buy a mascot of this type".to_string(),
+    );
+}
+
+fn show_all_bought_pop_up(app: &mut App) {
+    app.pop_up_manager.new_pop_up(
+        PopUpType::Minor,
+        "Failed to buy mascot!".to_string(),
+        "All mascots of this rarity have already been purchased!".to_string(),
+    );
+}
+
 impl App {
     pub fn mascot_screen(&self) -> Element<'_, Message> {
         let current_mascot_image = self
@@ -240,25 +246,17 @@ impl App {
         )
         .into();
 
-        let rare_shop_widget = shop::ShopWidget::new(
-            "Random Rare Mascot".to_string(),
-            50,
-            &self.mascot_manager.selected_mascot,
-            Message::Mascot(MascotMessage::BuyMascot(MascotRarity::Rare)),
-        )
-        .set_image(Image::new(Handle::from_path(
+        let rare_shop_widget = self.view_shop_widget(
+            MascotRarity::Rare,
+            "Random Rare Mascot",
             "assets/images/rare_gacha.png",
-        )));
+        );
 
-        let epic_shop_widget = shop::ShopWidget::new(
-            "Random Epic Mascot".to_string(),
-            100,
-            &self.mascot_manager.selected_mascot,
-            Message::Mascot(MascotMessage::BuyMascot(MascotRarity::Epic)),
-        )
-        .set_image(Image::new(Handle::from_path(
+        let epic_shop_widget = self.view_shop_widget(
+            MascotRarity::Epic,
+            "Random Epic Mascot",
             "assets/images/epic_gacha.png",
-        )));
+        );
 
         let shop_widgets: Element<Message> = Row::new()
             .push(rare_shop_widget)
@@ -299,6 +297,35 @@ impl App {
     }
 }
 
+impl App {
+    /// The shop widget of the last rolled rarity reveals the bought mascot
+    fn view_shop_widget(
+        &self,
+        rarity: MascotRarity,
+        title: &str,
+        gacha_image_path: &str,
+    ) -> Element<'_, Message> {
+        let mut shop_widget = shop::ShopWidget::new(
+            title.to_string(),
+            rarity.get_prize() as usize,
+            &self.mascot_manager.selected_mascot,
+            Message::Mascot(MascotMessage::BuyMascot(rarity)),
+        )
+        .set_image(Image::new(Handle::from_path(gacha_image_path)));
+
+        match self.mascot_manager.last_roll {
+            Some((rolled_rarity, mascot)) if rolled_rarity == rarity => {
+                shop_widget = shop_widget
+                    .reveal_mascot(mascot, *self.mascot_manager.roll_reveal_animation.value());
+                Animation::new(&self.mascot_manager.roll_reveal_animation, shop_widget)
+                    .on_update(|event| Message::Mascot(MascotMessage::AnimateRoll(event)))
+                    .into()
+            }
+            _ => shop_widget.into(),
+        }
+    }
+}
+
 #[derive(PartialEq)]
 enum BoxType {
     Selectable,
@@ -1,5 +1,5 @@
 use crate::client::backend::pop_up_manager::PopUpType;
-use crate::client::backend::workout_preset_manager::PresetSafeError;
+use crate::client::backend::workout_preset_manager::{EditPresetError, PresetSafeError};
 use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::tab::Tab;
 use crate::client::gui::bb_tab::workout_creation::ExerciseNumber;
@@ -12,8 +12,11 @@ use crate::client::gui::bb_theme::separator::separator_line;
 use crate::client::gui::bb_theme::text_format::{FIRA_SANS_EXTRABOLD, format_button_text};
 use crate::client::gui::bb_widget::widget_utils::INDENT;
 use crate::client::gui::user_interface::Message;
-use crate::client::server_communication::preset_communicator::save_preset;
+use crate::client::server_communication::preset_communicator::{
+    delete_preset, save_preset, save_preset_order, update_preset,
+};
 use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::workout_preset::PresetImage;
 use iced::Color;
@@ -24,6 +27,7 @@ use iced_core::alignment::{Horizontal, Vertical};
 use iced_core::image::Handle;
 use iced_core::text::LineHeight;
 use iced_core::{Alignment, Length, Pixels};
+use std::rc::Rc;
 use strum::IntoEnumIterator;
 
 const ADD_EXERCISE_HEIGHT: f32 = 30.0;
@@ -43,7 +47,16 @@ pub enum PresetCreationMessage {
     AddExercise(String),
     DeleteExercise(ExerciseNumber),
     FinishPresetCreation,
-    SavePreset(Result<(), ServerRequestError>),
+    SavePreset(Result<Id, ServerRequestError>),
+    UpdatePreset(Result<(), ServerRequestError>),
+    DiscardPresetEdit,
+
+    // Preset overview
+    EditPreset(Id),
+    ForcePresetEdit(Id),
+    DeletePreset(Id),
+    ConfirmDeletePreset(Id),
+    PresetDeleted(Result<(), ServerRequestError>, Id),
 }
 
 impl PresetCreationMessage {
@@ -110,17 +123,24 @@ impl PresetCreationMessage {
                             app.pop_up_manager.new_pop_up(PopUpType::Minor, "Preset needs to have a name!".to_string(), "Please add a name to your preset".to_string())
                         }
                     }
-                } else if let Some(jwt) = &app.jsonwebtoken.clone() {
+                } else if app.api_client.is_logged_in() {
+                    let preset = app
+                        .workout_preset_manager
+                        .preset_in_creation
+                        .as_ref()
+                        .unwrap()
+                        .workout_preset
+                        .clone();
+                    if let Some(preset_id) = app.workout_preset_manager.preset_in_edit_id() {
+                        return Task::perform(
+                            update_preset(app.api_client.clone(), preset_id, preset),
+                            |result| {
+                                Message::PresetCreation(PresetCreationMessage::UpdatePreset(result))
+                            },
+                        );
+                    }
                     return Task::perform(
-                        save_preset(
-                            jwt.clone(),
-                            app.workout_preset_manager
-                                .preset_in_creation
-                                .as_ref()
-                                .unwrap()
-                                .workout_preset
-                                .clone(),
-                        ),
+                        save_preset(app.api_client.clone(), preset),
                         |result| -> Message {
                             Message::PresetCreation(PresetCreationMessage::SavePreset(result))
                         },
@@ -133,10 +153,101 @@ impl PresetCreationMessage {
                     );
                 }
             }
-            PresetCreationMessage::SavePreset(Ok(())) => {
-                app.workout_preset_manager.add_preset();
+            PresetCreationMessage::SavePreset(Ok(preset_id)) => {
+                app.workout_preset_manager.add_preset(*preset_id);
                 app.screen = Tab::Workout;
             }
+            PresetCreationMessage::UpdatePreset(Ok(())) => {
+                app.workout_preset_manager.replace_preset();
+                app.screen = Tab::PresetOverview;
+            }
+            PresetCreationMessage::DiscardPresetEdit => {
+                app.workout_preset_manager.preset_in_creation = None;
+                app.screen = Tab::PresetOverview;
+            }
+            PresetCreationMessage::EditPreset(preset_id) => {
+                match app.workout_preset_manager.start_preset_edit(*preset_id) {
+                    Ok(()) => app.screen = Tab::CreatePreset,
+                    Err(EditPresetError::PresetAlreadyInCreation) => {
+                        let preset_id = *preset_id;
+                        app.pop_up_manager.new_pop_up(
+                            PopUpType::Question(Rc::new(move |overwrite| {
+                                if overwrite {
+                                    Message::PresetCreation(PresetCreationMessage::ForcePresetEdit(
+                                        preset_id,
+                                    ))
+                                } else {
+                                    Message::ResetPopUp
+                                }
+                            })),
+                            "You already have a preset in creation!".to_string(),
+                            "Do you want to discard it and edit this preset?".to_string(),
+                        )
+                    }
+                    Err(EditPresetError::PresetNotFound) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Preset not found!".to_string(),
+                        "Please try again or report bug".to_string(),
+                    ),
+                }
+            }
+            PresetCreationMessage::ForcePresetEdit(preset_id) => {
+                app.pop_up_manager.reset();
+                if app
+                    .workout_preset_manager
+                    .force_preset_edit(*preset_id)
+                    .is_ok()
+                {
+                    app.screen = Tab::CreatePreset;
+                }
+            }
+            PresetCreationMessage::DeletePreset(preset_id) => {
+                let preset_id = *preset_id;
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Question(Rc::new(move |delete| {
+                        if delete {
+                            Message::PresetCreation(PresetCreationMessage::ConfirmDeletePreset(
+                                preset_id,
+                            ))
+                        } else {
+                            Message::ResetPopUp
+                        }
+                    })),
+                    "Delete preset?".to_string(),
+                    "Workouts you already did with it stay in your history".to_string(),
+                );
+            }
+            PresetCreationMessage::ConfirmDeletePreset(preset_id) => {
+                app.pop_up_manager.reset();
+                if !app.api_client.is_logged_in() {
+                    app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Log in to delete preset!".to_string(),
+                        "You need to be logged in to delete a preset.".to_string(),
+                    );
+                    return Task::none();
+                }
+                let preset_id = *preset_id;
+                return Task::perform(
+                    delete_preset(app.api_client.clone(), preset_id),
+                    move |result| {
+                        Message::PresetCreation(PresetCreationMessage::PresetDeleted(
+                            result, preset_id,
+                        ))
+                    },
+                );
+            }
+            PresetCreationMessage::PresetDeleted(Ok(()), preset_id) => {
+                app.workout_preset_manager.delete_preset(*preset_id);
+            }
+            PresetCreationMessage::UpdatePreset(Err(err))
+            | PresetCreationMessage::PresetDeleted(Err(err), _) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    err.to_error_message().to_string(),
+                    "".to_string(),
+                );
+            }
             PresetCreationMessage::SavePreset(Err(err)) => {
                 app.pop_up_manager.new_pop_up(
                     PopUpType::Minor,
@@ -194,6 +305,20 @@ fn view_exercise_preset(
         .into()
 }
 impl App {
+    /// Sends the order of the presets, e.g. after a preset was moved to the front
+    pub fn save_preset_order_on_server(&self) -> Task<Message> {
+        if !self.api_client.is_logged_in() {
+            return Task::none();
+        }
+        Task::perform(
+            save_preset_order(
+                self.api_client.clone(),
+                self.workout_preset_manager.preset_order(),
+            ),
+            |result| Message::UpdateInfoOnServerResult(result, "preset order".to_string()),
+        )
+    }
+
     pub fn preset_creation_screen(&self) -> Element<'_, Message> {
         let preset_name =
             if let Some(current_preset) = &self.workout_preset_manager.preset_in_creation {
@@ -298,14 +423,14 @@ impl App {
         let mut exercises = Column::new().spacing(INDENT);
 
         if let Some(current_preset) = &self.workout_preset_manager.preset_in_creation {
-            let mut counter: ExerciseNumber = 1;
-            for exercise in current_preset.workout_preset.exercises.clone() {
+            for (counter, exercise) in
+                (1 as ExerciseNumber..).zip(current_preset.workout_preset.exercises.clone())
+            {
                 exercises = exercises.push(view_exercise_preset(
                     exercise,
                     counter,
                     &self.mascot_manager.selected_mascot,
                 ));
-                counter += 1;
             }
         }
 
@@ -333,9 +458,14 @@ impl App {
         .line_height(LineHeight::Absolute(Pixels(ADD_EXERCISE_HEIGHT)))
         .into();
 
-        let finish_preset_text = format_button_text(text("Finish Preset Creation"))
-            .size(25)
-            .center();
+        let editing_preset = self.workout_preset_manager.preset_in_edit_id().is_some();
+        let finish_preset_text = format_button_text(text(if editing_preset {
+            "Save Changes"
+        } else {
+            "Finish Preset Creation"
+        }))
+        .size(25)
+        .center();
 
         let check_box_image = image(Handle::from_path("assets/images/check_box.png"));
 
@@ -354,9 +484,23 @@ impl App {
         ))
         .into();
 
+        let mut finish_buttons = Row::new().push(finish_preset_button).spacing(10);
+        if editing_preset {
+            let discard_changes_button = create_element_button(
+                &self.mascot_manager.selected_mascot,
+                format_button_text(text("Discard Changes")).size(25).into(),
+                ButtonStyle::InactiveTab,
+                None,
+            )
+            .on_press(Message::PresetCreation(
+                PresetCreationMessage::DiscardPresetEdit,
+            ));
+            finish_buttons = finish_buttons.push(discard_changes_button);
+        }
+
         let add_exercise_and_finish: Column<Message> = Column::new()
             .push(add_exercise_search_bar)
-            .push(finish_preset_button)
+            .push(finish_buttons)
             .spacing(10)
             .height(Fixed(BOTTOM_SEGMENT_HEIGHT))
             .width(Fill);
@@ -1,4 +1,6 @@
+use crate::client::gui::bb_tab::preset_creation::PresetCreationMessage;
 use crate::client::gui::bb_theme::color::TEXT_COLOR;
+use crate::client::gui::bb_theme::custom_button::{ButtonStyle, create_element_button};
 use crate::client::gui::bb_theme::scrollable::{
     ScrollableExtension, ScrollableStyle, TAB_SCROLLBAR_PADDING, TAB_SCROLLBAR_WIDTH,
     create_scrollable,
@@ -8,15 +10,18 @@ use crate::client::gui::bb_theme::text_format::FIRA_SANS_EXTRABOLD;
 use crate::client::gui::bb_widget::widget_utils::{INDENT, LARGE_INDENT};
 use crate::client::gui::bb_widget::workout::{DEFAULT_WORKOUT_WIDGET_WIDTH, WorkoutWidget};
 use crate::client::gui::user_interface::Message;
+use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::workout_preset::WorkoutPreset;
 use iced::Element;
-use iced::widget::{Column, Row, Space, text};
+use iced::widget::{Column, Row, Space, image, text};
 use iced_core::Length;
 use iced_core::alignment::Horizontal;
+use iced_core::image::Handle;
 
 const PRESET_TITLE_FONT_SIZE: f32 = 50.0;
 const PRESETS_PER_ROW: usize = 4;
+const PRESET_ACTION_ICON_SIZE: f32 = 25.0;
 pub fn preset_overview_screen<'a>(
     mascot: &'a Mascot,
     presets: &[WorkoutPreset],
@@ -56,9 +61,48 @@ fn preset_grid_column<'a>(mascot: &Mascot, presets: &[WorkoutPreset]) -> Column<
         let mut row: Row<Message> = Row::new().spacing(INDENT);
 
         for preset in preset_chunk {
-            row = row.push(WorkoutWidget::new_workout_preset_widget(preset, mascot));
+            let mut preset_column = Column::new()
+                .push(WorkoutWidget::new_workout_preset_widget(preset, mascot))
+                .spacing(5.0);
+            if let Some(preset_id) = preset.id {
+                preset_column = preset_column.push(preset_action_row(mascot, preset_id));
+            }
+            row = row.push(preset_column);
         }
         column = column.push(row);
     }
     column
 }
+
+/// Edit and delete buttons below a saved preset
+fn preset_action_row<'a>(mascot: &Mascot, preset_id: Id) -> Row<'a, Message> {
+    let edit_button = create_element_button(
+        mascot,
+        image(Handle::from_path("assets/images/edit.png"))
+            .height(PRESET_ACTION_ICON_SIZE)
+            .into(),
+        ButtonStyle::InactiveTransparent,
+        None,
+    )
+    .on_press(Message::PresetCreation(PresetCreationMessage::EditPreset(
+        preset_id,
+    )));
+    let delete_button = create_element_button(
+        mascot,
+        image(Handle::from_path("assets/images/trash_black.png"))
+            .height(PRESET_ACTION_ICON_SIZE)
+            .into(),
+        ButtonStyle::InactiveTransparent,
+        None,
+    )
+    .on_press(Message::PresetCreation(
+        PresetCreationMessage::DeletePreset(preset_id),
+    ));
+
+    Row::new()
+        .push(Space::new().width(Length::Fill))
+        .push(edit_button)
+        .push(delete_button)
+        .spacing(5.0)
+        .width(DEFAULT_WORKOUT_WIDGET_WIDTH)
+}
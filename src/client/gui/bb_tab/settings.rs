@@ -1,5 +1,8 @@
+use crate::client::backend::account_editor::AccountEditor;
+use crate::client::backend::pop_up_manager::PopUpType;
 use crate::client::backend::widget_state::widget_state_manager::update_progress_bar_goals_after_updated_user_info;
 use crate::client::gui::app::App;
+use crate::client::gui::bb_tab::tab::Tab;
 use crate::client::gui::bb_theme::color;
 use crate::client::gui::bb_theme::color::{BACKGROUND_COLOR, TEXT_COLOR};
 use crate::client::gui::bb_theme::combo_box::{create_menu_style, create_text_input_style};
@@ -19,14 +22,22 @@ use crate::client::gui::bb_widget::widget_utils::{
     descriptor_space_fill_element_row, descriptor_space_fill_text_row,
 };
 use crate::client::gui::user_interface::Message;
-use crate::client::server_communication::user_communicator::update_user_info_on_server;
+use crate::client::server_communication::request_data::request_login_data;
+use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::client::server_communication::user_communicator::{
+    change_password_on_server, change_username_on_server, delete_account_on_server,
+    logout_on_server, update_user_info_on_server,
+};
 use crate::common::exercise_mod::weight::Kg;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::profile_picture::{
     LARGE_PROFILE_PICTURE_DIMENSION, profile_picture_selection_row,
 };
+use crate::common::user_mod::account::{
+    AccountChangeAnswer, AccountDeletionAnswer, MAX_PASSWORD_LENGTH, MAX_USERNAME_LENGTH,
+};
 use crate::common::user_mod::user::{
-    Gender, MAX_DESCRIPTION_CHARACTERS, UserInformation, UserInformationStrings,
+    Gender, MAX_DESCRIPTION_CHARACTERS, ProfileVisibility, UserInformation, UserInformationStrings,
 };
 use crate::common::user_mod::user_goals::GoalType;
 use iced::widget::{
@@ -44,15 +55,18 @@ const TITLE_SIZE: f32 = 30.0;
 const USER_DATA_TITLE: &str = "General info";
 const GOAL_DATA_TITLE: &str = "Goals";
 const PROFILE_PICTURE_TITLE: &str = "Profile picture";
+const ACCOUNT_TITLE: &str = "Account";
 
 impl App {
     pub fn settings_screen(&self) -> Element<'_, Message> {
         let user_info_container = user_settings(self).map(Message::Settings);
+        let account_container = account_settings(self).map(Message::Settings);
         let log_out_button =
             log_out_button(&self.mascot_manager.selected_mascot).map(Message::Settings);
 
         let content = Column::new()
             .push(user_info_container)
+            .push(account_container)
             .push(log_out_button)
             .spacing(INDENT);
 
@@ -197,6 +211,10 @@ fn preview_user_info_column(app: &App) -> Column<'_, SettingsMessage> {
             "Height:".to_string(),
             cm_to_string(user_info.height),
         ))
+        .push(descriptor_space_fill_text_row(
+            "Profile visibility:".to_string(),
+            user_info.profile_visibility.to_string(),
+        ))
         .push(description)
         .spacing(SETTINGS_ENTRY_SPACING)
         .width(Length::FillPortion(15));
@@ -223,7 +241,7 @@ fn edit_user_info_column(app: &App) -> Column<'_, SettingsMessage> {
     let user_data_title =
         create_settings_sub_header(&app.mascot_manager.selected_mascot, USER_DATA_TITLE);
 
-    //Username should currently not be changeable
+    //The username is changed in the account settings, as it needs the password
     let username = text(&pending_info.username)
         .font(FIRA_SANS_EXTRABOLD)
         .color(TEXT_COLOR)
@@ -243,6 +261,20 @@ fn edit_user_info_column(app: &App) -> Column<'_, SettingsMessage> {
     ))
     .menu_style(create_menu_style(&app.mascot_manager.selected_mascot));
 
+    let profile_visibility_combo_box = combo_box(
+        &app.user_manager.profile_visibility_combo_box_state,
+        "Select visibility...",
+        Some(&pending_info.profile_visibility),
+        SettingsMessage::SelectProfileVisibility,
+    )
+    .font(FIRA_SANS_EXTRABOLD)
+    .width(SETTINGS_TEXT_INPUT_WIDTH)
+    .input_style(create_text_input_style(
+        &app.mascot_manager.selected_mascot,
+        BACKGROUND_COLOR,
+    ))
+    .menu_style(create_menu_style(&app.mascot_manager.selected_mascot));
+
     let height_text_input = text_input("Enter your height in cm", &pending_info_strings.height)
         .on_input(SettingsMessage::EditHeight);
 
@@ -283,6 +315,10 @@ fn edit_user_info_column(app: &App) -> Column<'_, SettingsMessage> {
         .push(descriptor_space_fill_element_row(
             "Gender:".to_string(),
             gender_combo_box.into(),
+        ))
+        .push(descriptor_space_fill_element_row(
+            "Profile visibility:".to_string(),
+            profile_visibility_combo_box.into(),
         ));
 
     for (description_text, mut text_input) in text_input_data_fields {
@@ -428,6 +464,94 @@ fn save_or_discard_pending_user_info(mascot: &Mascot) -> Row<'_, SettingsMessage
         .push(discard_changes_button)
         .spacing(INDENT)
 }
+/// Changing the username or password and deleting the account, each needs the current password
+fn account_settings(app: &App) -> Element<'_, SettingsMessage> {
+    let active_mascot = &app.mascot_manager.selected_mascot;
+    let account_editor = &app.user_manager.account_editor;
+
+    let styled_text_input = |placeholder, value| {
+        text_input(placeholder, value)
+            .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+            .font(FIRA_SANS_EXTRABOLD)
+            .width(SETTINGS_TEXT_INPUT_WIDTH)
+    };
+    let account_button = |button_text: &str, message| {
+        create_text_button(
+            active_mascot,
+            button_text.to_string(),
+            ButtonStyle::InactiveSolid,
+            None,
+        )
+        .on_press(message)
+    };
+
+    let current_password_input =
+        styled_text_input("Current password", &account_editor.current_password)
+            .secure(true)
+            .on_input(SettingsMessage::EditCurrentPassword);
+    let new_username_input = styled_text_input("New username", &account_editor.new_username)
+        .on_input(SettingsMessage::EditNewUsername)
+        .on_submit(SettingsMessage::ChangeUsername);
+    let new_password_input = styled_text_input("New password", &account_editor.new_password)
+        .secure(true)
+        .on_input(SettingsMessage::EditNewPassword)
+        .on_submit(SettingsMessage::ChangePassword);
+
+    let delete_text = if account_editor.deletion_requested {
+        "Press again to delete your account and all its data"
+    } else {
+        "Delete account"
+    };
+    let delete_button = create_element_button(
+        active_mascot,
+        text(delete_text)
+            .font(FIRA_SANS_EXTRABOLD)
+            .color(color::ERROR_COLOR)
+            .into(),
+        ButtonStyle::InactiveTab,
+        None,
+    )
+    .on_press(SettingsMessage::DeleteAccount)
+    .width(Length::Fill);
+
+    let account_column = Column::new()
+        .push(format_button_text(text(ACCOUNT_TITLE)).size(TITLE_SIZE))
+        .push(descriptor_space_fill_element_row(
+            "Current password:".to_string(),
+            current_password_input.into(),
+        ))
+        .push(descriptor_space_fill_element_row(
+            "Username:".to_string(),
+            Row::new()
+                .push(new_username_input)
+                .push(account_button(
+                    "Change username",
+                    SettingsMessage::ChangeUsername,
+                ))
+                .spacing(INDENT)
+                .into(),
+        ))
+        .push(descriptor_space_fill_element_row(
+            "Password:".to_string(),
+            Row::new()
+                .push(new_password_input)
+                .push(account_button(
+                    "Change password",
+                    SettingsMessage::ChangePassword,
+                ))
+                .spacing(INDENT)
+                .into(),
+        ))
+        .push(delete_button)
+        .spacing(SETTINGS_ENTRY_SPACING);
+
+    container(account_column)
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .width(Length::FillPortion(10))
+        .padding(LARGE_INDENT)
+        .into()
+}
+
 fn log_out_button(active_mascot: &Mascot) -> Element<'_, SettingsMessage> {
     let log_out_button_text = text("Log out")
         .font(FIRA_SANS_EXTRABOLD)
@@ -451,6 +575,7 @@ fn log_out_button(active_mascot: &Mascot) -> Element<'_, SettingsMessage> {
 pub enum SettingsMessage {
     StartEditingProfile,
     SelectGender(Gender),
+    SelectProfileVisibility(ProfileVisibility),
     SelectProfilePicture(String),
     EditHeight(String),
     EditWeight(String),
@@ -461,9 +586,19 @@ pub enum SettingsMessage {
     SavePendingUserInfoChanges,
     DiscardPendingUserInfoChanges,
     LogOut,
+    EditCurrentPassword(String),
+    EditNewUsername(String),
+    EditNewPassword(String),
+    ChangeUsername,
+    UsernameChanged(Result<AccountChangeAnswer, ServerRequestError>),
+    ChangePassword,
+    PasswordChanged(Result<AccountChangeAnswer, ServerRequestError>),
+    /// Only asks for a confirmation on the first press
+    DeleteAccount,
+    AccountDeleted(Result<AccountDeletionAnswer, ServerRequestError>),
 }
 impl SettingsMessage {
-    pub fn update(self, app: &mut App, opt_jwt: Option<String>) -> Task<Message> {
+    pub fn update(self, app: &mut App) -> Task<Message> {
         let existing_user_info = &app.user_manager.user_info;
         let pending_user_info_changes = &mut app.user_manager.pending_user_info_changes;
         match self {
@@ -481,6 +616,11 @@ impl SettingsMessage {
                     user_info.gender = new_gender;
                 }
             }
+            SettingsMessage::SelectProfileVisibility(new_profile_visibility) => {
+                if let Some((user_info, _)) = pending_user_info_changes {
+                    user_info.profile_visibility = new_profile_visibility;
+                }
+            }
             SettingsMessage::SelectProfilePicture(new_profile_picture_path) => {
                 if let Some((user_info, _)) = pending_user_info_changes {
                     user_info.profile_picture_path = new_profile_picture_path;
@@ -575,6 +715,7 @@ impl SettingsMessage {
                     user_info.weight = pending_user_info.weight;
                     user_info.height = pending_user_info.height;
                     user_info.description = pending_user_info.description;
+                    user_info.profile_visibility = pending_user_info.profile_visibility;
                     //Goals
                     user_info.user_goals = pending_user_info.user_goals;
                     // Profile Picture
@@ -586,9 +727,12 @@ impl SettingsMessage {
                         .expect("Update log expect");
                     update_progress_bar_goals_after_updated_user_info(app);
 
-                    if let Some(jwt) = opt_jwt {
+                    if app.api_client.is_logged_in() {
                         return Task::perform(
-                            update_user_info_on_server(jwt, app.user_manager.user_info.clone()),
+                            update_user_info_on_server(
+                                app.api_client.clone(),
+                                app.user_manager.user_info.clone(),
+                            ),
                             |result| Message::UpdateInfoOnServerResult(result, "user".to_string()),
                         );
                     } else {
@@ -600,9 +744,146 @@ impl SettingsMessage {
                 app.user_manager.pending_user_info_changes = None;
             }
             SettingsMessage::LogOut => {
-                *app = App::default();
+                let logout_task = if app.api_client.is_logged_in() {
+                    Task::perform(logout_on_server(app.api_client.clone()), |result| {
+                        Message::UpdateInfoOnServerResult(result, "logout".to_string())
+                    })
+                } else {
+                    Task::none()
+                };
+                app.reset_to_login();
+                return logout_task;
+            }
+            SettingsMessage::EditCurrentPassword(current_password) => {
+                if current_password.len() <= MAX_PASSWORD_LENGTH {
+                    app.user_manager.account_editor.current_password = current_password;
+                }
+            }
+            SettingsMessage::EditNewUsername(new_username) => {
+                if new_username.len() <= MAX_USERNAME_LENGTH {
+                    app.user_manager.account_editor.new_username = new_username;
+                }
+            }
+            SettingsMessage::EditNewPassword(new_password) => {
+                if new_password.len() <= MAX_PASSWORD_LENGTH {
+                    app.user_manager.account_editor.new_password = new_password;
+                }
+            }
+            SettingsMessage::ChangeUsername => {
+                match app.user_manager.account_editor.parse_username_change() {
+                    Err(reason) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Changing Username Failed!".to_string(),
+                        reason.to_string(),
+                    ),
+                    Ok(username_change) if app.api_client.is_logged_in() => {
+                        return Task::perform(
+                            change_username_on_server(app.api_client.clone(), username_change),
+                            |result| Message::Settings(SettingsMessage::UsernameChanged(result)),
+                        );
+                    }
+                    Ok(_) => {}
+                }
+            }
+            SettingsMessage::UsernameChanged(Ok(AccountChangeAnswer::Valid(auth_tokens))) => {
+                // Everything is loaded again like after a login, so every view shows the new name
+                app.reset_to_login();
+                app.api_client.set_auth_tokens(Some(auth_tokens));
+                app.screen = Tab::Loading;
+                return Task::perform(
+                    request_login_data(
+                        app.api_client.clone(),
+                        app.exercise_catalog_cache_path.clone(),
+                    ),
+                    Message::RequestLoginData,
+                );
+            }
+            SettingsMessage::UsernameChanged(answer) => {
+                account_change_failed(app, "Changing Username Failed!", answer);
+            }
+            SettingsMessage::ChangePassword => {
+                match app.user_manager.account_editor.parse_password_change() {
+                    Err(reason) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Changing Password Failed!".to_string(),
+                        reason.to_string(),
+                    ),
+                    Ok(password_change) if app.api_client.is_logged_in() => {
+                        return Task::perform(
+                            change_password_on_server(app.api_client.clone(), password_change),
+                            |result| Message::Settings(SettingsMessage::PasswordChanged(result)),
+                        );
+                    }
+                    Ok(_) => {}
+                }
+            }
+            SettingsMessage::PasswordChanged(Ok(AccountChangeAnswer::Valid(_))) => {
+                app.user_manager.account_editor = AccountEditor::default();
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Password Changed".to_string(),
+                    "Your other devices were logged out".to_string(),
+                );
+            }
+            SettingsMessage::PasswordChanged(answer) => {
+                account_change_failed(app, "Changing Password Failed!", answer);
+            }
+            SettingsMessage::DeleteAccount => {
+                let account_editor = &mut app.user_manager.account_editor;
+                match account_editor.parse_account_deletion() {
+                    Err(reason) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Deleting Account Failed!".to_string(),
+                        reason.to_string(),
+                    ),
+                    Ok(_) if !account_editor.deletion_requested => {
+                        account_editor.deletion_requested = true;
+                    }
+                    Ok(account_deletion) if app.api_client.is_logged_in() => {
+                        return Task::perform(
+                            delete_account_on_server(app.api_client.clone(), account_deletion),
+                            |result| Message::Settings(SettingsMessage::AccountDeleted(result)),
+                        );
+                    }
+                    Ok(_) => {}
+                }
+            }
+            SettingsMessage::AccountDeleted(Ok(AccountDeletionAnswer::Deleted)) => {
+                app.reset_to_login();
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Account Deleted".to_string(),
+                    "Your account and all its data were deleted".to_string(),
+                );
+            }
+            SettingsMessage::AccountDeleted(answer) => {
+                app.user_manager.account_editor.deletion_requested = false;
+                let message = match answer {
+                    Ok(_) => "Wrong password!".to_string(),
+                    Err(err) => err.to_error_message(),
+                };
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Deleting Account Failed!".to_string(),
+                    message,
+                );
             }
         }
         Task::none()
     }
 }
+
+fn account_change_failed(
+    app: &mut App,
+    title: &str,
+    answer: Result<AccountChangeAnswer, ServerRequestError>,
+) {
+    let message = match answer {
+        Ok(AccountChangeAnswer::WrongPassword) => "Wrong password!".to_string(),
+        Ok(AccountChangeAnswer::UsernameAlreadyExists) => "Username already exists!".to_string(),
+        Ok(AccountChangeAnswer::Valid(_)) => return,
+        Err(err) => err.to_error_message(),
+    };
+    app.pop_up_manager
+        .new_pop_up(PopUpType::Minor, title.to_string(), message);
+}
@@ -1,29 +1,63 @@
+use crate::client::backend::challenge_editor::{ChallengeEditor, ChallengeGoalKind};
 use crate::client::backend::pop_up_manager::PopUpType;
 use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::tab::{FRAME_PADDING, Tab};
-use crate::client::gui::bb_theme::color::TEXT_COLOR;
-use crate::client::gui::bb_theme::container::{ContainerStyle, create_container_style};
+use crate::client::gui::bb_theme::color::{BACKGROUND_COLOR, TEXT_COLOR};
+use crate::client::gui::bb_theme::combo_box::{create_menu_style, create_text_input_style};
+use crate::client::gui::bb_theme::container::{
+    ContainerStyle, DEFAULT_CONTAINER_RADIUS, create_container_style,
+};
+use crate::client::gui::bb_theme::custom_button::{ButtonStyle, create_text_button};
 use crate::client::gui::bb_theme::scrollable::{
     ScrollableExtension, ScrollableStyle, TAB_SCROLLBAR_PADDING, TAB_SCROLLBAR_WIDTH,
     create_scrollable,
 };
 use crate::client::gui::bb_theme::text_format::FIRA_SANS_EXTRABOLD;
-use crate::client::gui::bb_widget::social_elements::{friend_user_button, user_profile_button};
+use crate::client::gui::bb_widget::social_elements::{
+    challenge_element, feed_event_element, friend_user_button, leaderboard_entry_element,
+    user_profile_button,
+};
 use crate::client::gui::bb_widget::widget_utils::INDENT;
 use crate::client::gui::user_interface::Message;
+use crate::client::server_communication::challenge_communicator::{
+    answer_challenge_on_server, get_challenges_from_server, send_challenge_to_server,
+};
+use crate::client::server_communication::server_communicator::ServerRequestError;
 use crate::client::server_communication::user_communicator::{
-    add_foreign_user_as_friend_on_server, remove_foreign_user_as_friend_on_server,
+    answer_friend_request_on_server, block_user_on_server, get_friends_feed_from_server,
+    get_leaderboard_from_server, remove_foreign_user_as_friend_on_server, report_user_on_server,
+    send_friend_request_on_server, unblock_user_on_server,
+};
+use crate::client::server_communication::workout_reaction_communicator::{
+    add_comment_on_server, delete_comment_on_server, get_profile_workouts_from_server,
+    set_kudos_on_server,
+};
+use crate::common::challenge::{ChallengeAnswer, ChallengeOverview};
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::leaderboard::{
+    LeaderboardEntry, LeaderboardMetric, LeaderboardPeriod, LeaderboardQuery,
+};
+use crate::common::social_feed::FeedPage;
+use crate::common::user_mod::friend_request::{
+    FriendRequest, FriendRequestAnswer, FriendshipStatus,
 };
-use crate::common::user_mod::friend_request::FriendRequest;
 use crate::common::user_mod::user::UserType;
-use iced::widget::{Column, Row, Space, container, text};
+use crate::common::user_mod::user_report::UserReport;
+use crate::common::workout_reaction::{NewWorkoutComment, WorkoutComment, WorkoutSummary};
+use chrono::Local;
+use iced::widget::{Column, Row, Space, combo_box, container, text, text_input};
 use iced::{Element, Task};
-use iced_core::alignment::Horizontal;
+use iced_core::alignment::{Horizontal, Vertical};
 use iced_core::{Length, Padding};
+use strum::IntoEnumIterator;
+
+const LEADERBOARD_SELECTOR_WIDTH: f32 = 200.0;
+const CHALLENGE_NUMBER_INPUT_WIDTH: f32 = 120.0;
 
 impl App {
     pub fn social_screen(&self) -> Element<'_, Message> {
         let friends = self.user_manager.get_friends();
+        let friend_requests = self.user_manager.get_friend_requests();
         let non_friend_users = self.user_manager.get_non_friend_users();
 
         let mut friend_buttons = Row::new().spacing(INDENT).padding(INDENT);
@@ -53,6 +87,80 @@ impl App {
             .width(Length::Shrink)
             .padding(INDENT);
 
+        let mut friend_request_buttons = Column::new().spacing(INDENT).padding(INDENT);
+
+        for user in &friend_requests {
+            friend_request_buttons = friend_request_buttons.push(user_profile_button(
+                &self.mascot_manager.selected_mascot,
+                user,
+            ))
+        }
+
+        let friend_request_container = container(
+            Column::new()
+                .push(
+                    text("Friend requests")
+                        .font(FIRA_SANS_EXTRABOLD)
+                        .color(TEXT_COLOR)
+                        .size(24),
+                )
+                .push(friend_request_buttons),
+        )
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .padding(INDENT);
+
+        let active_mascot = &self.mascot_manager.selected_mascot;
+        let feed_navigation = Row::new()
+            .push(
+                text("Buddy feed")
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .color(TEXT_COLOR)
+                    .size(24),
+            )
+            .push(Space::new().width(Length::Fill))
+            .push(
+                create_text_button(
+                    active_mascot,
+                    "Refresh".to_string(),
+                    ButtonStyle::InactiveSolid,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::LoadFeed(None))),
+            )
+            .align_y(Vertical::Center);
+
+        let mut feed_events = Column::new().spacing(INDENT).padding(INDENT);
+        for event in &self.user_manager.feed.events {
+            feed_events = feed_events.push(feed_event_element(event));
+        }
+        if self.user_manager.feed.events.is_empty() {
+            feed_events =
+                feed_events.push(text("No activity of your buddies yet").color(TEXT_COLOR));
+        }
+        if let Some(next_before) = self.user_manager.feed.next_before {
+            feed_events = feed_events.push(
+                create_text_button(
+                    active_mascot,
+                    "Load more".to_string(),
+                    ButtonStyle::Active,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::LoadFeed(Some(next_before)))),
+            );
+        }
+
+        let feed_container = container(
+            Column::new()
+                .push(feed_navigation)
+                .push(feed_events)
+                .align_x(Horizontal::Center),
+        )
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .padding(INDENT);
+
+        let leaderboard_container = self.leaderboard_view();
+        let challenges_container = self.challenges_view();
+
         let mut user_buttons = Column::new().spacing(INDENT).padding(INDENT);
 
         for user in &non_friend_users {
@@ -76,6 +184,41 @@ impl App {
             .style(create_container_style(ContainerStyle::Default, None, None))
             .padding(INDENT);
 
+        let mut blocked_users = Column::new().spacing(INDENT).padding(INDENT);
+        for username in &self.user_manager.blocked_users {
+            blocked_users = blocked_users.push(
+                Row::new()
+                    .push(text(username).color(TEXT_COLOR))
+                    .push(Space::new().width(Length::Fill))
+                    .push(
+                        create_text_button(
+                            active_mascot,
+                            "Unblock".to_string(),
+                            ButtonStyle::InactiveSolid,
+                            Some(DEFAULT_CONTAINER_RADIUS.into()),
+                        )
+                        .on_press(Message::Social(
+                            SocialMessage::UnblockUser(username.clone()),
+                        )),
+                    )
+                    .spacing(INDENT)
+                    .align_y(Vertical::Center),
+            );
+        }
+
+        let blocked_users_container = container(
+            Column::new()
+                .push(
+                    text("Blocked users")
+                        .font(FIRA_SANS_EXTRABOLD)
+                        .color(TEXT_COLOR)
+                        .size(24),
+                )
+                .push(blocked_users),
+        )
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .padding(INDENT);
+
         let mut content = Column::new()
             .align_x(Horizontal::Center)
             .spacing(INDENT)
@@ -83,12 +226,24 @@ impl App {
                 bottom: FRAME_PADDING,
                 ..20.into()
             });
+        if !friend_requests.is_empty() {
+            content = content.push(friend_request_container)
+        }
         if !friends.is_empty() {
             content = content.push(friend_container)
         }
+        if !friends.is_empty() {
+            content = content
+                .push(challenges_container)
+                .push(leaderboard_container)
+                .push(feed_container)
+        }
         if !non_friend_users.is_empty() {
             content = content.push(user_container)
         }
+        if !self.user_manager.blocked_users.is_empty() {
+            content = content.push(blocked_users_container)
+        }
 
         let aligned_content = Row::new()
             .push(Space::new().width(Length::Fill))
@@ -105,44 +260,371 @@ impl App {
     }
 }
 
+/// Clears the leaderboard until the entries of the selected query arrive
+fn load_leaderboard(app: &mut App) -> Task<Message> {
+    app.user_manager.leaderboard.clear();
+    let query = app.user_manager.leaderboard_query.clone();
+    if query.validation_error().is_some() || !app.api_client.is_logged_in() {
+        return Task::none();
+    }
+    Task::perform(
+        get_leaderboard_from_server(app.api_client.clone(), query.clone()),
+        move |result| Message::Social(SocialMessage::LeaderboardLoaded(query, result)),
+    )
+}
+
+/// Only the workouts of the own profile and of friends are loaded
+fn load_profile_workouts(app: &mut App) -> Task<Message> {
+    app.user_manager.profile_workouts.clear();
+    let username = match &app.user_manager.most_recently_viewed_user {
+        UserType::Own => app.user_manager.user_info.username.clone(),
+        UserType::Other(username) => match app.user_manager.get_user_by_username(username) {
+            Some(user) if user.friendship_status == FriendshipStatus::Friends => username.clone(),
+            _ => return Task::none(),
+        },
+    };
+    if !app.api_client.is_logged_in() {
+        return Task::none();
+    }
+    Task::perform(
+        get_profile_workouts_from_server(app.api_client.clone(), username.clone()),
+        move |result| Message::Social(SocialMessage::ProfileWorkoutsLoaded(username, result)),
+    )
+}
+
+impl App {
+    /// Ranking of the user and their friends with selectors for the period and metric
+    fn leaderboard_view(&self) -> Element<'_, Message> {
+        let active_mascot = &self.mascot_manager.selected_mascot;
+        let query = &self.user_manager.leaderboard_query;
+
+        let mut selectors = Row::new().spacing(INDENT).align_y(Vertical::Center);
+        for period in LeaderboardPeriod::iter() {
+            let style = if period == query.period {
+                ButtonStyle::Active
+            } else {
+                ButtonStyle::InactiveSolid
+            };
+            selectors = selectors.push(
+                create_text_button(
+                    active_mascot,
+                    period.to_string(),
+                    style,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::SelectLeaderboardPeriod(
+                    period,
+                ))),
+            );
+        }
+        selectors = selectors.push(
+            combo_box(
+                &self.user_manager.leaderboard_metric_state,
+                "Select metric...",
+                Some(&query.metric),
+                |metric| Message::Social(SocialMessage::SelectLeaderboardMetric(metric)),
+            )
+            .font(FIRA_SANS_EXTRABOLD)
+            .width(LEADERBOARD_SELECTOR_WIDTH)
+            .input_style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+            .menu_style(create_menu_style(active_mascot)),
+        );
+        if query.metric == LeaderboardMetric::BestSet {
+            selectors = selectors.push(
+                combo_box(
+                    &self.exercise_manager.all_exercise_state,
+                    "Select exercise...",
+                    query.exercise.as_ref(),
+                    |exercise| Message::Social(SocialMessage::SelectLeaderboardExercise(exercise)),
+                )
+                .font(FIRA_SANS_EXTRABOLD)
+                .width(LEADERBOARD_SELECTOR_WIDTH)
+                .input_style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+                .menu_style(create_menu_style(active_mascot)),
+            );
+        }
+
+        let mut entries = Column::new().spacing(INDENT).padding(INDENT);
+        if let Some(reason) = query.validation_error() {
+            entries = entries.push(text(reason).color(TEXT_COLOR));
+        }
+        for entry in &self.user_manager.leaderboard {
+            entries = entries.push(leaderboard_entry_element(self, entry, query.metric));
+        }
+
+        container(
+            Column::new()
+                .push(
+                    text("Leaderboard")
+                        .font(FIRA_SANS_EXTRABOLD)
+                        .color(TEXT_COLOR)
+                        .size(24),
+                )
+                .push(selectors)
+                .push(entries)
+                .spacing(INDENT),
+        )
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .padding(INDENT)
+        .into()
+    }
+
+    /// Challenges of the user and the inputs to challenge a friend
+    fn challenges_view(&self) -> Element<'_, Message> {
+        let active_mascot = &self.mascot_manager.selected_mascot;
+        let editor = &self.user_manager.challenge_editor;
+
+        let title = Row::new()
+            .push(
+                text("Challenges")
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .color(TEXT_COLOR)
+                    .size(24),
+            )
+            .push(Space::new().width(Length::Fill))
+            .push(
+                create_text_button(
+                    active_mascot,
+                    "Refresh".to_string(),
+                    ButtonStyle::InactiveSolid,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::LoadChallenges)),
+            )
+            .align_y(Vertical::Center);
+
+        let mut opponents = Row::new().spacing(INDENT).align_y(Vertical::Center);
+        for friend in self.user_manager.get_friends() {
+            let style = if editor.opponent.as_ref() == Some(&friend.username) {
+                ButtonStyle::Active
+            } else {
+                ButtonStyle::InactiveSolid
+            };
+            opponents = opponents.push(
+                create_text_button(
+                    active_mascot,
+                    friend.username.clone(),
+                    style,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::SelectChallengeOpponent(
+                    friend.username.clone(),
+                ))),
+            );
+        }
+
+        let mut goals = Row::new().spacing(INDENT).align_y(Vertical::Center);
+        for goal_kind in ChallengeGoalKind::iter() {
+            let style = if goal_kind == editor.goal_kind {
+                ButtonStyle::Active
+            } else {
+                ButtonStyle::InactiveSolid
+            };
+            goals = goals.push(
+                create_text_button(
+                    active_mascot,
+                    goal_kind.to_string(),
+                    style,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::SelectChallengeGoal(
+                    goal_kind,
+                ))),
+            );
+        }
+        if editor.goal_kind == ChallengeGoalKind::FirstToLift {
+            goals = goals
+                .push(
+                    combo_box(
+                        &self.exercise_manager.all_exercise_state,
+                        "Select exercise...",
+                        editor.exercise.as_ref(),
+                        |exercise| {
+                            Message::Social(SocialMessage::SelectChallengeExercise(exercise))
+                        },
+                    )
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .width(LEADERBOARD_SELECTOR_WIDTH)
+                    .input_style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+                    .menu_style(create_menu_style(active_mascot)),
+                )
+                .push(
+                    text_input("kg", &editor.weight_input)
+                        .on_input(|weight| {
+                            Message::Social(SocialMessage::EditChallengeWeight(weight))
+                        })
+                        .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+                        .font(FIRA_SANS_EXTRABOLD)
+                        .width(CHALLENGE_NUMBER_INPUT_WIDTH),
+                );
+        }
+
+        let terms = Row::new()
+            .push(text("Days").color(TEXT_COLOR))
+            .push(
+                text_input("7", &editor.days_input)
+                    .on_input(|days| Message::Social(SocialMessage::EditChallengeDays(days)))
+                    .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .width(CHALLENGE_NUMBER_INPUT_WIDTH),
+            )
+            .push(text("Stake").color(TEXT_COLOR))
+            .push(
+                text_input("0 coins", &editor.stake_input)
+                    .on_input(|stake| Message::Social(SocialMessage::EditChallengeStake(stake)))
+                    .on_submit(Message::Social(SocialMessage::SendChallenge))
+                    .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .width(CHALLENGE_NUMBER_INPUT_WIDTH),
+            )
+            .push(Space::new().width(Length::Fill))
+            .push(
+                create_text_button(
+                    active_mascot,
+                    "Challenge".to_string(),
+                    ButtonStyle::Active,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::SendChallenge)),
+            )
+            .spacing(INDENT)
+            .align_y(Vertical::Center);
+
+        let mut challenges = Column::new().spacing(INDENT).padding(INDENT);
+        for challenge in &self.user_manager.challenges {
+            challenges = challenges.push(challenge_element(self, challenge));
+        }
+        if self.user_manager.challenges.is_empty() {
+            challenges = challenges.push(text("No challenges yet").color(TEXT_COLOR));
+        }
+
+        container(
+            Column::new()
+                .push(title)
+                .push(opponents)
+                .push(goals)
+                .push(terms)
+                .push(challenges)
+                .spacing(INDENT),
+        )
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .padding(INDENT)
+        .into()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum SocialMessage {
-    AddUserAsFriend(String),
+    SendFriendRequest(String),
+    FriendRequestSent(String, Result<FriendshipStatus, ServerRequestError>),
+    /// Username of the sender and whether the request is accepted
+    AnswerFriendRequest(String, bool),
     RemoveUserAsFriend(String),
     ViewProfile(UserType),
+    /// Loads the events older than the given feed event id, or the newest ones if None
+    LoadFeed(Option<i64>),
+    FeedLoaded(Option<i64>, Result<FeedPage, ServerRequestError>),
+    /// Username of the profile and its latest workouts
+    ProfileWorkoutsLoaded(String, Result<Vec<WorkoutSummary>, ServerRequestError>),
+    ToggleKudos(Id),
+    EditComment(Id, String),
+    SendComment(Id),
+    CommentSent(Id, Result<WorkoutComment, ServerRequestError>),
+    /// Workout id and comment id
+    DeleteComment(Id, i64),
+    SelectLeaderboardMetric(LeaderboardMetric),
+    SelectLeaderboardPeriod(LeaderboardPeriod),
+    SelectLeaderboardExercise(String),
+    LeaderboardLoaded(
+        LeaderboardQuery,
+        Result<Vec<LeaderboardEntry>, ServerRequestError>,
+    ),
+    LoadChallenges,
+    ChallengesLoaded(Result<ChallengeOverview, ServerRequestError>),
+    SelectChallengeOpponent(String),
+    SelectChallengeGoal(ChallengeGoalKind),
+    SelectChallengeExercise(String),
+    EditChallengeWeight(String),
+    EditChallengeDays(String),
+    EditChallengeStake(String),
+    SendChallenge,
+    ChallengeSent(Result<ChallengeOverview, ServerRequestError>),
+    /// Challenge id and whether it is accepted
+    AnswerChallenge(i64, bool),
+    BlockUser(String),
+    UnblockUser(String),
+    EditReportReason(String),
+    ReportUser(String),
+    ReportSent(Result<(), ServerRequestError>),
 }
 
 impl SocialMessage {
     pub fn update(self, app: &mut App) -> Task<Message> {
         match self {
-            SocialMessage::AddUserAsFriend(username) => {
-                app.user_manager.add_user_as_friend(&username);
-                if let Some(jwt) = app.jsonwebtoken.clone() {
+            SocialMessage::SendFriendRequest(username) => {
+                app.user_manager.send_friend_request(&username);
+                if app.api_client.is_logged_in() {
                     return Task::perform(
-                        add_foreign_user_as_friend_on_server(
-                            jwt,
+                        send_friend_request_on_server(
+                            app.api_client.clone(),
                             FriendRequest {
                                 username: username.clone(),
                             },
                         ),
-                        |result| {
-                            Message::UpdateInfoOnServerResult(result, "added-Friend".to_string())
+                        move |result| {
+                            Message::Social(SocialMessage::FriendRequestSent(username, result))
                         },
                     );
                 } else {
                     app.pop_up_manager.new_pop_up(
                         PopUpType::Minor,
-                        "Adding Friend Failed!".to_string(),
-                        "Log in to add a friend persistently!".to_string(),
+                        "Sending Friend Request Failed!".to_string(),
+                        "Log in to send a friend request!".to_string(),
+                    );
+                }
+            }
+            SocialMessage::FriendRequestSent(username, Ok(friendship_status)) => {
+                app.user_manager
+                    .set_friendship_status(&username, friendship_status);
+            }
+            SocialMessage::FriendRequestSent(username, Err(err)) => {
+                app.user_manager
+                    .set_friendship_status(&username, FriendshipStatus::NotFriends);
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Sending Friend Request Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::AnswerFriendRequest(username, accept) => {
+                let friendship_status = if accept {
+                    FriendshipStatus::Friends
+                } else {
+                    FriendshipStatus::NotFriends
+                };
+                app.user_manager
+                    .set_friendship_status(&username, friendship_status);
+                if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        answer_friend_request_on_server(
+                            app.api_client.clone(),
+                            FriendRequestAnswer { username, accept },
+                        ),
+                        |result| {
+                            Message::UpdateInfoOnServerResult(
+                                result,
+                                "answered-Friend-Request".to_string(),
+                            )
+                        },
                     );
                 }
             }
             SocialMessage::RemoveUserAsFriend(username) => {
                 app.user_manager.remove_user_as_friend(&username);
-                if let Some(jwt) = app.jsonwebtoken.clone() {
+                if app.api_client.is_logged_in() {
                     return Task::perform(
                         remove_foreign_user_as_friend_on_server(
-                            jwt,
+                            app.api_client.clone(),
                             FriendRequest {
                                 username: username.clone(),
                             },
@@ -159,6 +641,268 @@ impl SocialMessage {
                     );
                 }
             }
+            SocialMessage::LoadFeed(before) => {
+                if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        get_friends_feed_from_server(app.api_client.clone(), before),
+                        move |result| Message::Social(SocialMessage::FeedLoaded(before, result)),
+                    );
+                }
+            }
+            SocialMessage::FeedLoaded(before, Ok(feed_page)) => {
+                app.user_manager.add_feed_page(before, feed_page);
+            }
+            SocialMessage::FeedLoaded(_, Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Loading Feed Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::ProfileWorkoutsLoaded(username, Ok(profile_workouts)) => {
+                app.user_manager
+                    .set_profile_workouts(&username, profile_workouts);
+            }
+            SocialMessage::ProfileWorkoutsLoaded(_, Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Loading Workouts Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::ToggleKudos(workout_id) => {
+                if let Some(give_kudos) = app.user_manager.toggle_kudos(workout_id)
+                    && app.api_client.is_logged_in()
+                {
+                    return Task::perform(
+                        set_kudos_on_server(app.api_client.clone(), workout_id, give_kudos),
+                        |result| Message::UpdateInfoOnServerResult(result, "Kudos".to_string()),
+                    );
+                }
+            }
+            SocialMessage::EditComment(workout_id, text) => {
+                app.user_manager.comment_inputs.insert(workout_id, text);
+            }
+            SocialMessage::SendComment(workout_id) => {
+                let new_comment = NewWorkoutComment {
+                    text: app
+                        .user_manager
+                        .comment_inputs
+                        .get(&workout_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                };
+                if let Some(reason) = new_comment.validation_error() {
+                    app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Sending Comment Failed!".to_string(),
+                        reason.to_string(),
+                    );
+                } else if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        add_comment_on_server(app.api_client.clone(), workout_id, new_comment),
+                        move |result| {
+                            Message::Social(SocialMessage::CommentSent(workout_id, result))
+                        },
+                    );
+                }
+            }
+            SocialMessage::CommentSent(workout_id, Ok(comment)) => {
+                app.user_manager.comment_inputs.remove(&workout_id);
+                app.user_manager.add_workout_comment(comment);
+            }
+            SocialMessage::CommentSent(_, Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Sending Comment Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::DeleteComment(workout_id, comment_id) => {
+                app.user_manager
+                    .remove_workout_comment(workout_id, comment_id);
+                if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        delete_comment_on_server(app.api_client.clone(), workout_id, comment_id),
+                        |result| {
+                            Message::UpdateInfoOnServerResult(result, "deleted-Comment".to_string())
+                        },
+                    );
+                }
+            }
+            SocialMessage::SelectLeaderboardMetric(metric) => {
+                app.user_manager.leaderboard_query.metric = metric;
+                return load_leaderboard(app);
+            }
+            SocialMessage::SelectLeaderboardPeriod(period) => {
+                app.user_manager.leaderboard_query.period = period;
+                return load_leaderboard(app);
+            }
+            SocialMessage::SelectLeaderboardExercise(exercise) => {
+                app.user_manager.leaderboard_query.exercise = Some(exercise);
+                return load_leaderboard(app);
+            }
+            SocialMessage::LeaderboardLoaded(query, Ok(leaderboard)) => {
+                // Results of a query the user already changed are outdated
+                if query == app.user_manager.leaderboard_query {
+                    app.user_manager.leaderboard = leaderboard;
+                }
+            }
+            SocialMessage::LeaderboardLoaded(_, Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Loading Leaderboard Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::LoadChallenges => {
+                if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        get_challenges_from_server(app.api_client.clone()),
+                        |result| Message::Social(SocialMessage::ChallengesLoaded(result)),
+                    );
+                }
+            }
+            SocialMessage::ChallengesLoaded(Ok(overview)) => {
+                app.user_manager.apply_challenge_overview(overview);
+            }
+            SocialMessage::ChallengesLoaded(Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Loading Challenges Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::SelectChallengeOpponent(username) => {
+                app.user_manager.challenge_editor.opponent = Some(username);
+            }
+            SocialMessage::SelectChallengeGoal(goal_kind) => {
+                app.user_manager.challenge_editor.goal_kind = goal_kind;
+            }
+            SocialMessage::SelectChallengeExercise(exercise) => {
+                app.user_manager.challenge_editor.exercise = Some(exercise);
+            }
+            SocialMessage::EditChallengeWeight(weight) => {
+                app.user_manager.challenge_editor.weight_input = weight;
+            }
+            SocialMessage::EditChallengeDays(days) => {
+                app.user_manager.challenge_editor.days_input = days;
+            }
+            SocialMessage::EditChallengeStake(stake) => {
+                app.user_manager.challenge_editor.stake_input = stake;
+            }
+            SocialMessage::SendChallenge => {
+                let parsed_challenge = app.user_manager.challenge_editor.parse_challenge(
+                    Local::now().date_naive(),
+                    app.user_manager.user_info.coin_balance,
+                );
+                match parsed_challenge {
+                    Err(err) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Sending Challenge Failed!".to_string(),
+                        err.to_error_message(),
+                    ),
+                    Ok(new_challenge) if app.api_client.is_logged_in() => {
+                        return Task::perform(
+                            send_challenge_to_server(app.api_client.clone(), new_challenge),
+                            |result| Message::Social(SocialMessage::ChallengeSent(result)),
+                        );
+                    }
+                    Ok(_) => app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Sending Challenge Failed!".to_string(),
+                        "Log in to challenge your friends!".to_string(),
+                    ),
+                }
+            }
+            SocialMessage::ChallengeSent(Ok(overview)) => {
+                app.user_manager.challenge_editor = ChallengeEditor::default();
+                app.user_manager.apply_challenge_overview(overview);
+            }
+            SocialMessage::ChallengeSent(Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Sending Challenge Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
+            SocialMessage::AnswerChallenge(challenge_id, accept) => {
+                if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        answer_challenge_on_server(
+                            app.api_client.clone(),
+                            ChallengeAnswer {
+                                challenge_id,
+                                accept,
+                            },
+                        ),
+                        |result| Message::Social(SocialMessage::ChallengesLoaded(result)),
+                    );
+                }
+            }
+            SocialMessage::BlockUser(username) => {
+                if !app.api_client.is_logged_in() {
+                    app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Blocking Failed!".to_string(),
+                        "Log in to block a user!".to_string(),
+                    );
+                    return Task::none();
+                }
+                app.user_manager.block_user(&username);
+                app.screen = Tab::Social;
+                return Task::perform(
+                    block_user_on_server(app.api_client.clone(), FriendRequest { username }),
+                    |result| Message::UpdateInfoOnServerResult(result, "blocked-User".to_string()),
+                );
+            }
+            SocialMessage::UnblockUser(username) => {
+                app.user_manager.unblock_user(&username);
+                if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        unblock_user_on_server(app.api_client.clone(), FriendRequest { username }),
+                        |result| {
+                            Message::UpdateInfoOnServerResult(result, "unblocked-User".to_string())
+                        },
+                    );
+                }
+            }
+            SocialMessage::EditReportReason(reason) => {
+                app.user_manager.report_reason_input = reason;
+            }
+            SocialMessage::ReportUser(username) => {
+                let report = UserReport {
+                    username,
+                    reason: app.user_manager.report_reason_input.clone(),
+                };
+                if let Some(reason) = report.validation_error() {
+                    app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Reporting Failed!".to_string(),
+                        reason.to_string(),
+                    );
+                } else if app.api_client.is_logged_in() {
+                    return Task::perform(
+                        report_user_on_server(app.api_client.clone(), report),
+                        |result| Message::Social(SocialMessage::ReportSent(result)),
+                    );
+                }
+            }
+            SocialMessage::ReportSent(Ok(())) => {
+                app.user_manager.report_reason_input.clear();
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Report Sent".to_string(),
+                    "Thank you, the moderators will look at the profile".to_string(),
+                );
+            }
+            SocialMessage::ReportSent(Err(err)) => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Reporting Failed!".to_string(),
+                    err.to_error_message(),
+                );
+            }
             SocialMessage::ViewProfile(user_type) => {
                 match user_type {
                     UserType::Own => {
@@ -182,9 +926,11 @@ impl SocialMessage {
                         }
                         app.user_manager.most_recently_viewed_user =
                             UserType::Other(username.clone());
+                        app.user_manager.report_reason_input.clear();
                     }
                 }
                 app.screen = Tab::ViewProfile;
+                return load_profile_workouts(app);
             }
         }
         Task::none()
@@ -1,19 +1,29 @@
 use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::social::SocialMessage;
-use crate::client::gui::bb_theme::color::{HIGHLIGHTED_CONTAINER_COLOR, TEXT_COLOR};
-use crate::client::gui::bb_theme::container::{ContainerStyle, create_container_style};
+use crate::client::gui::bb_theme::color::{
+    BACKGROUND_COLOR, HIGHLIGHTED_CONTAINER_COLOR, TEXT_COLOR,
+};
+use crate::client::gui::bb_theme::combo_box::create_text_input_style;
+use crate::client::gui::bb_theme::container::{
+    ContainerStyle, DEFAULT_CONTAINER_RADIUS, create_container_style,
+};
 use crate::client::gui::bb_theme::custom_button::{ButtonStyle, create_text_button};
 use crate::client::gui::bb_theme::text_format::{
     FIRA_SANS_EXTRABOLD, format_description_text, kg_to_string,
 };
+use crate::client::gui::bb_widget::social_elements::workout_with_reactions;
 use crate::client::gui::bb_widget::stats::{PROFILE_STAT_CONTAINER_HEIGHT, profile_stat_container};
 use crate::client::gui::bb_widget::widget_utils::{INDENT, LARGE_INDENT};
 use crate::client::gui::user_interface::Message;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use crate::common::profile_picture::LARGE_PROFILE_PICTURE_DIMENSION;
-use crate::common::user_mod::user::DisplayUserProfileData;
+use crate::common::user_mod::friend_request::FriendshipStatus;
+use crate::common::user_mod::user::{DisplayUserProfileData, UserType};
+use crate::common::user_mod::user_report::MAX_REPORT_REASON_CHARACTERS;
 use iced::Element;
-use iced::widget::{Column, Container, Row, Space, column, container, image, row, text};
+use iced::widget::{
+    Column, Container, Row, Space, column, container, image, row, text, text_input,
+};
 use iced_core::alignment::{Horizontal, Vertical};
 use iced_core::image::Handle;
 use iced_core::{Length, Padding};
@@ -26,6 +36,11 @@ pub fn view_profile(app: &App, user: DisplayUserProfileData) -> Element<'_, Mess
     )
     .padding([0, 40]);
 
+    let hidden_stats_note = format!("Only friends can see the stats of {}", user.username);
+    let moderation_row = match &app.user_manager.most_recently_viewed_user {
+        UserType::Other(_) => Some(moderation_row(app, user.username.clone())),
+        UserType::Own => None,
+    };
     let mut username = Row::new().push(
         text(user.username.clone())
             .font(FIRA_SANS_EXTRABOLD)
@@ -33,20 +48,35 @@ pub fn view_profile(app: &App, user: DisplayUserProfileData) -> Element<'_, Mess
             .size(40),
     );
 
-    if user.friends_with_active_user {
-        let delete_friend_button = create_text_button(
+    // The own profile is shown as NotFriends as well, so there is no button to send a request
+    let friendship_button = match user.friendship_status {
+        FriendshipStatus::Friends => Some((
+            "Remove Friend",
+            SocialMessage::RemoveUserAsFriend(user.username),
+        )),
+        FriendshipStatus::RequestSent => Some((
+            "Withdraw Request",
+            SocialMessage::RemoveUserAsFriend(user.username),
+        )),
+        FriendshipStatus::RequestReceived => Some((
+            "Accept Request",
+            SocialMessage::AnswerFriendRequest(user.username, true),
+        )),
+        FriendshipStatus::NotFriends => None,
+    };
+
+    if let Some((button_text, social_message)) = friendship_button {
+        let friendship_button = create_text_button(
             &user.favorite_mascot,
-            "Remove Friend".to_string(),
+            button_text.to_string(),
             ButtonStyle::InactiveSolid,
             None,
         )
-        .on_press(Message::Social(SocialMessage::RemoveUserAsFriend(
-            user.username,
-        )));
+        .on_press(Message::Social(social_message));
 
         username = username
             .push(Space::new().width(Length::Fill))
-            .push(delete_friend_button)
+            .push(friendship_button)
             .align_y(Vertical::Center);
     }
 
@@ -156,7 +186,33 @@ pub fn view_profile(app: &App, user: DisplayUserProfileData) -> Element<'_, Mess
         .push(favorite_mascot_component)
         .spacing(LARGE_INDENT);
 
-    let content = column![header, activity_widget, stat_mascot_row]
+    let mut content = column![header];
+    if let Some(moderation_row) = moderation_row {
+        content = content.push(moderation_row);
+    }
+    if user.stats_hidden {
+        content = content.push(format_description_text(text(hidden_stats_note)));
+    } else {
+        content = content.push(activity_widget).push(stat_mascot_row);
+    }
+
+    let profile_workouts = &app.user_manager.profile_workouts;
+    if !profile_workouts.is_empty() {
+        let mut workouts = Column::new()
+            .push(
+                text("Recent Workouts")
+                    .font(FIRA_SANS_EXTRABOLD)
+                    .color(TEXT_COLOR)
+                    .size(25),
+            )
+            .spacing(INDENT);
+        for summary in profile_workouts {
+            workouts = workouts.push(workout_with_reactions(app, summary));
+        }
+        content = content.push(workouts);
+    }
+
+    let content = content
         .align_x(Horizontal::Center)
         .spacing(LARGE_INDENT)
         .padding(Padding {
@@ -166,3 +222,42 @@ pub fn view_profile(app: &App, user: DisplayUserProfileData) -> Element<'_, Mess
 
     content.into()
 }
+
+/// Blocking and reporting the viewed profile of another user
+fn moderation_row(app: &App, username: String) -> Row<'_, Message> {
+    let active_mascot = &app.mascot_manager.selected_mascot;
+    let report_reason = &app.user_manager.report_reason_input;
+
+    let report_input = text_input("Why does the profile break the rules?", report_reason)
+        .on_input(|reason| {
+            Message::Social(SocialMessage::EditReportReason(
+                reason.chars().take(MAX_REPORT_REASON_CHARACTERS).collect(),
+            ))
+        })
+        .on_submit(Message::Social(SocialMessage::ReportUser(username.clone())))
+        .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+        .font(FIRA_SANS_EXTRABOLD);
+
+    Row::new()
+        .push(report_input)
+        .push(
+            create_text_button(
+                active_mascot,
+                "Report".to_string(),
+                ButtonStyle::InactiveSolid,
+                Some(DEFAULT_CONTAINER_RADIUS.into()),
+            )
+            .on_press(Message::Social(SocialMessage::ReportUser(username.clone()))),
+        )
+        .push(
+            create_text_button(
+                active_mascot,
+                "Block".to_string(),
+                ButtonStyle::Active,
+                Some(DEFAULT_CONTAINER_RADIUS.into()),
+            )
+            .on_press(Message::Social(SocialMessage::BlockUser(username))),
+        )
+        .spacing(INDENT)
+        .align_y(Vertical::Center)
+}
@@ -59,6 +59,7 @@ impl App {
             .push(view_recent_workout_row(
                 &self.mascot_manager.selected_mascot,
                 &self.exercise_manager.recent_workouts,
+                &self.user_manager.own_workout_reactions,
             ))
             .spacing(INDENT);
 
@@ -14,7 +14,10 @@ use crate::client::gui::bb_theme::text_format::{
     FIRA_SANS_EXTRABOLD, format_button_text, format_description_text,
 };
 use crate::client::gui::user_interface::Message;
-use crate::client::server_communication::exercise_communicator::save_workout;
+use crate::client::server_communication::exercise_communicator::{
+    delete_workout, save_workout, update_workout,
+};
+use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::exercise_mod::weight::Kg;
 use crate::common::workout_preset::WorkoutPreset;
 use chrono::Local;
@@ -42,6 +45,11 @@ pub enum WorkoutCreationMessage {
     FinishWorkoutCreation,
     NewWithPreset(WorkoutPreset),
     PresetReplace(WorkoutPreset),
+    ShiftWorkoutDate(i64),
+    EditWorkout(Id),
+    EditWorkoutReplace(Id),
+    DeleteWorkout,
+    ConfirmDeleteWorkout,
 }
 
 impl WorkoutCreationMessage {
@@ -170,21 +178,40 @@ impl WorkoutCreationMessage {
             WorkoutCreationMessage::FinishWorkoutCreation => {
                 app.exercise_manager.filter_workout_creation();
                 let mut workout_clone: Option<WorkoutCreate> = None;
-                let local_date = Local::now().date_naive();
-                let is_first_workout_today =
-                    !app.exercise_manager.is_set_tracked_on_date(&local_date);
 
                 if let Some(workout) = &app.exercise_manager.workout_in_creation {
                     workout_clone = Some(workout.clone());
                 }
 
+                let workout_in_edit_id = app.exercise_manager.workout_in_edit_id;
+                let workout_date = app.exercise_manager.workout_date;
+                let workout_start_time = app.exercise_manager.workout_start_time();
+                let preset_training = app
+                    .exercise_manager
+                    .preset_training(Local::now().naive_local());
+
                 app.screen = Tab::Loading;
                 app.exercise_manager.clear_workout();
 
                 if let Some(workout) = workout_clone {
-                    if let Some(jwt) = &app.jsonwebtoken {
+                    if let Some(workout_id) = workout_in_edit_id
+                        && app.api_client.is_logged_in()
+                    {
+                        return Task::perform(
+                            update_workout(app.api_client.clone(), workout_id, workout.clone()),
+                            move |result| {
+                                Message::UpdateWorkout(result, workout_id, workout.clone())
+                            },
+                        );
+                    } else if app.api_client.is_logged_in() {
                         return Task::perform(
-                            save_workout(jwt.clone(), workout.clone(), is_first_workout_today),
+                            save_workout(
+                                app.api_client.clone(),
+                                workout.clone(),
+                                workout_date,
+                                workout_start_time,
+                                preset_training,
+                            ),
                             move |result| Message::SaveWorkout(result, workout.clone()),
                         );
                     } else {
@@ -222,18 +249,92 @@ impl WorkoutCreationMessage {
                         }
                     }
                 } else {
-                    app.workout_preset_manager.move_preset_to_front(preset);
                     app.screen = Tab::CreateWorkout;
+                    if app.workout_preset_manager.move_preset_to_front(preset) {
+                        return app.save_preset_order_on_server();
+                    }
                 }
                 Task::none()
             }
             WorkoutCreationMessage::PresetReplace(preset) => {
                 app.exercise_manager.force_workout_with_preset(preset);
-                app.workout_preset_manager.move_preset_to_front(preset);
+                app.screen = Tab::CreateWorkout;
+                app.pop_up_manager.reset();
+                if app.workout_preset_manager.move_preset_to_front(preset) {
+                    return app.save_preset_order_on_server();
+                }
+                Task::none()
+            }
+            WorkoutCreationMessage::ShiftWorkoutDate(days) => {
+                app.exercise_manager.shift_workout_date(*days);
+                Task::none()
+            }
+            WorkoutCreationMessage::EditWorkout(workout_id) => {
+                if let Err(err) = app.exercise_manager.start_workout_edit(*workout_id) {
+                    let workout_id = *workout_id;
+                    match err {
+                        CreateWorkoutError::WorkoutAlreadyInCreation => {
+                            app.pop_up_manager.new_pop_up(
+                                PopUpType::Question(Rc::new(move |overwrite| {
+                                    if overwrite {
+                                        Message::WorkoutCreation(
+                                            WorkoutCreationMessage::EditWorkoutReplace(workout_id),
+                                        )
+                                    } else {
+                                        Message::ResetPopUp
+                                    }
+                                })),
+                                "You already have a workout!".to_string(),
+                                "Do you want to discard your current workout to edit this one?"
+                                    .to_string(),
+                            )
+                        }
+                    }
+                } else {
+                    app.screen = Tab::CreateWorkout;
+                }
+                Task::none()
+            }
+            WorkoutCreationMessage::EditWorkoutReplace(workout_id) => {
+                app.exercise_manager.force_workout_edit(*workout_id);
                 app.screen = Tab::CreateWorkout;
                 app.pop_up_manager.reset();
                 Task::none()
             }
+            WorkoutCreationMessage::DeleteWorkout => {
+                app.pop_up_manager.new_pop_up(
+                    PopUpType::Question(Rc::new(|delete| {
+                        if delete {
+                            Message::WorkoutCreation(WorkoutCreationMessage::ConfirmDeleteWorkout)
+                        } else {
+                            Message::ResetPopUp
+                        }
+                    })),
+                    "Delete workout?".to_string(),
+                    "The workout and all of its sets will be removed from your history".to_string(),
+                );
+                Task::none()
+            }
+            WorkoutCreationMessage::ConfirmDeleteWorkout => {
+                app.pop_up_manager.reset();
+                let Some(workout_id) = app.exercise_manager.workout_in_edit_id else {
+                    return Task::none();
+                };
+                if !app.api_client.is_logged_in() {
+                    app.pop_up_manager.new_pop_up(
+                        PopUpType::Minor,
+                        "Deleting Workout failed".to_string(),
+                        "Log in to delete workouts".to_string(),
+                    );
+                    return Task::none();
+                }
+                app.screen = Tab::Loading;
+                app.exercise_manager.clear_workout();
+                Task::perform(
+                    delete_workout(app.api_client.clone(), workout_id),
+                    move |result| Message::DeleteWorkout(result, workout_id),
+                )
+            }
         }
     }
 }
@@ -316,16 +417,14 @@ pub fn view_exercise_edit<'a>(
         .push(delete_button)
         .spacing(10);
 
-    let mut counter: SetNumber = 1;
     let mut sets_column = Column::new().spacing(10).width(Fill);
 
     let descriptions: Element<Message> = view_descriptions();
 
     sets_column = sets_column.push(descriptions);
 
-    for _set in &exercise.sets {
+    for (counter, _set) in (1 as SetNumber..).zip(&exercise.sets) {
         sets_column = sets_column.push(view_set_edit(counter, app));
-        counter += 1;
     }
 
     let new_set_text = format_button_text(text("+")).size(23).center();
@@ -388,16 +487,14 @@ pub fn view_exercise_no_edit<'a>(
         .push(delete_button)
         .spacing(30);
 
-    let mut counter: SetNumber = 1;
     let mut sets_column = Column::new().spacing(10).width(Fill);
 
     let descriptions: Element<Message> = view_descriptions();
 
     sets_column = sets_column.push(descriptions);
 
-    for set in &exercise.sets {
+    for (counter, set) in (1 as SetNumber..).zip(&exercise.sets) {
         sets_column = sets_column.push(view_set_no_edit(set, counter));
-        counter += 1;
     }
 
     let column = Column::new().push(top_row).push(sets_column).spacing(20);
@@ -578,10 +675,8 @@ impl App {
         let mut column = Column::new().spacing(20);
 
         if let Some(current_workout) = &self.exercise_manager.workout_in_creation {
-            let mut counter: ExerciseNumber = 1;
-            for exercise in current_workout {
+            for (counter, exercise) in (1 as ExerciseNumber..).zip(current_workout) {
                 column = column.push(view_exercise(exercise, counter, self));
-                counter += 1;
             }
         }
 
@@ -609,7 +704,14 @@ impl App {
         .line_height(LineHeight::Absolute(Pixels(30.0)))
         .into();
 
-        let finish_workout_text = format_button_text(text("Finish Workout")).size(25).center();
+        let finish_workout_label = if self.exercise_manager.workout_in_edit_id.is_some() {
+            "Save Changes"
+        } else {
+            "Finish Workout"
+        };
+        let finish_workout_text = format_button_text(text(finish_workout_label))
+            .size(25)
+            .center();
 
         let check_box_image = image(Handle::from_path("assets/images/check_box.png"));
 
@@ -628,9 +730,29 @@ impl App {
         ))
         .into();
 
-        let add_exercise_and_finish: Column<Message> = Column::new()
+        let mut finish_row = Row::new().push(finish_workout_button).spacing(10);
+        if self.exercise_manager.workout_in_edit_id.is_some() {
+            let delete_workout_button: Element<Message> = create_element_button(
+                &self.mascot_manager.selected_mascot,
+                image(Handle::from_path("assets/images/trash_red.png")).into(),
+                ButtonStyle::InactiveTransparent,
+                None,
+            )
+            .width(Shrink)
+            .on_press(Message::WorkoutCreation(
+                WorkoutCreationMessage::DeleteWorkout,
+            ))
+            .into();
+            finish_row = finish_row.push(delete_workout_button);
+        }
+
+        let mut add_exercise_and_finish: Column<Message> = Column::new();
+        if self.exercise_manager.workout_in_edit_id.is_none() {
+            add_exercise_and_finish = add_exercise_and_finish.push(self.view_workout_date_picker());
+        }
+        let add_exercise_and_finish = add_exercise_and_finish
             .push(add_exercise)
-            .push(finish_workout_button)
+            .push(finish_row)
             .spacing(10)
             .height(FillPortion(1))
             .width(Fill);
@@ -658,3 +780,53 @@ impl App {
             .into()
     }
 }
+
+impl App {
+    /// Lets the user log a workout for an earlier day
+    fn view_workout_date_picker(&self) -> Element<'_, Message> {
+        let today = Local::now().date_naive();
+        let workout_date = self.exercise_manager.workout_date;
+        let date_text = if workout_date == today {
+            "Today".to_string()
+        } else {
+            workout_date.format("%d.%m.%Y").to_string()
+        };
+
+        let previous_day_button = create_element_button(
+            &self.mascot_manager.selected_mascot,
+            format_button_text(text("<")).size(20).center().into(),
+            ButtonStyle::InactiveTab,
+            None,
+        )
+        .on_press(Message::WorkoutCreation(
+            WorkoutCreationMessage::ShiftWorkoutDate(-1),
+        ));
+
+        let mut next_day_button = create_element_button(
+            &self.mascot_manager.selected_mascot,
+            format_button_text(text(">")).size(20).center().into(),
+            ButtonStyle::InactiveTab,
+            None,
+        );
+        if workout_date < today {
+            next_day_button = next_day_button.on_press(Message::WorkoutCreation(
+                WorkoutCreationMessage::ShiftWorkoutDate(1),
+            ));
+        }
+
+        Row::new()
+            .push(format_description_text(text("WORKOUT DATE")))
+            .push(Space::new().width(Fill))
+            .push(previous_day_button)
+            .push(
+                format_button_text(text(date_text))
+                    .size(20)
+                    .width(120)
+                    .center(),
+            )
+            .push(next_day_button)
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+    }
+}
@@ -1,6 +1,7 @@
 pub mod activity_widget;
 pub mod animated_background_mod;
 pub mod bmi_calculator;
+pub mod body_map_widget;
 mod canvas_utils;
 pub mod chart_widget;
 pub mod circle_widget;
@@ -0,0 +1,330 @@
+use crate::client::gui::app::App;
+use crate::client::gui::bb_theme::color::{
+    CONTAINER_COLOR, DESCRIPTION_TEXT_COLOR, LIGHTER_CONTAINER_COLOR, TEXT_COLOR,
+};
+use crate::client::gui::bb_theme::container::DEFAULT_CONTAINER_RADIUS;
+use crate::client::gui::bb_widget::canvas_utils::draw_text;
+use crate::client::gui::user_interface::Message;
+use crate::common::exercise_mod::general_exercise::Muscle;
+use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::mascot_mod::mascot_trait::MascotTrait;
+use iced::mouse;
+use iced::widget::canvas::{Cache, Event, Frame, Geometry, Path};
+use iced::widget::{Action, canvas};
+use iced::{Element, Rectangle, Renderer, Size, Theme};
+use iced_core::{Color, Point};
+use std::collections::HashMap;
+
+/// Sets per muscle, see calculate_weekly_muscle_volume
+pub type MuscleVolume = HashMap<Muscle, f32>;
+
+const BODY_MAP_WIDGET_WIDTH: f32 = 250.0;
+const BODY_MAP_WIDGET_HEIGHT: f32 = 250.0;
+/// Weekly sets from which on a muscle is drawn in the full mascot color
+const WEEKLY_SETS_FOR_FULL_COLOR: f32 = 10.0;
+const FRONT_BODY_CENTER_X: f32 = 70.0;
+const BACK_BODY_CENTER_X: f32 = 180.0;
+const BODY_TOP_Y: f32 = 14.0;
+const HEAD_RADIUS: f32 = 11.0;
+const MUSCLE_REGION_RADIUS: f32 = 4.0;
+const VIEW_NAME_Y: f32 = 204.0;
+const DESCRIPTION_Y: f32 = 228.0;
+const FONT_SIZE_VIEW_NAME: f32 = 13.0;
+const FONT_SIZE_DESCRIPTION: f32 = 17.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BodySide {
+    Front,
+    Back,
+}
+
+/// Area of a body part on the right half of the body, it is mirrored for the left half. <br>
+/// `x` is the distance to the center of the body and `y` the distance to the top of the head
+struct BodyRegion {
+    /// None for body parts without a tracked muscle
+    muscle: Option<Muscle>,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+const fn region(muscle: Option<Muscle>, x: f32, y: f32, width: f32, height: f32) -> BodyRegion {
+    BodyRegion {
+        muscle,
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+const FRONT_BODY_REGIONS: [BodyRegion; 11] = [
+    region(Some(Muscle::Neck), 1.0, 22.0, 5.0, 7.0),
+    region(Some(Muscle::Shoulders), 17.0, 29.0, 11.0, 12.0),
+    region(Some(Muscle::Chest), 1.0, 30.0, 15.0, 17.0),
+    region(Some(Muscle::Biceps), 20.0, 43.0, 8.0, 20.0),
+    region(Some(Muscle::Forearms), 22.0, 65.0, 8.0, 24.0),
+    region(Some(Muscle::Abdominals), 1.0, 49.0, 9.0, 32.0),
+    region(None, 12.0, 49.0, 6.0, 32.0),
+    region(Some(Muscle::Adductors), 1.0, 83.0, 10.0, 16.0),
+    region(Some(Muscle::Abductors), 13.0, 83.0, 6.0, 16.0),
+    region(Some(Muscle::Quadriceps), 4.0, 101.0, 14.0, 38.0),
+    region(None, 5.0, 141.0, 10.0, 38.0),
+];
+
+const BACK_BODY_REGIONS: [BodyRegion; 11] = [
+    region(Some(Muscle::Neck), 1.0, 22.0, 5.0, 5.0),
+    region(Some(Muscle::Traps), 1.0, 28.0, 15.0, 10.0),
+    region(Some(Muscle::Shoulders), 17.0, 29.0, 11.0, 12.0),
+    region(Some(Muscle::Triceps), 20.0, 43.0, 8.0, 20.0),
+    region(Some(Muscle::Forearms), 22.0, 65.0, 8.0, 24.0),
+    region(Some(Muscle::MiddleBack), 1.0, 40.0, 8.0, 22.0),
+    region(Some(Muscle::Lats), 10.0, 40.0, 8.0, 28.0),
+    region(Some(Muscle::LowerBack), 1.0, 64.0, 8.0, 17.0),
+    region(Some(Muscle::Glutes), 1.0, 83.0, 17.0, 16.0),
+    region(Some(Muscle::Hamstrings), 4.0, 101.0, 14.0, 38.0),
+    region(Some(Muscle::Calves), 5.0, 141.0, 10.0, 38.0),
+];
+
+const BODY_SIDES: [BodySide; 2] = [BodySide::Front, BodySide::Back];
+
+impl BodySide {
+    fn center_x(&self) -> f32 {
+        match self {
+            BodySide::Front => FRONT_BODY_CENTER_X,
+            BodySide::Back => BACK_BODY_CENTER_X,
+        }
+    }
+    fn name(&self) -> &'static str {
+        match self {
+            BodySide::Front => "Front",
+            BodySide::Back => "Back",
+        }
+    }
+    fn regions(&self) -> &'static [BodyRegion] {
+        match self {
+            BodySide::Front => &FRONT_BODY_REGIONS,
+            BodySide::Back => &BACK_BODY_REGIONS,
+        }
+    }
+}
+
+impl BodyRegion {
+    /// Bounds of the right and left half of the region inside the widget
+    fn bounds(&self, side: BodySide) -> [Rectangle; 2] {
+        let center_x = side.center_x();
+        let size = Size {
+            width: self.width,
+            height: self.height,
+        };
+        let y = BODY_TOP_Y + self.y;
+
+        [
+            Rectangle::new(Point::new(center_x + self.x, y), size),
+            Rectangle::new(Point::new(center_x - self.x - self.width, y), size),
+        ]
+    }
+}
+
+/// Returns the muscle drawn at `position`, which is relative to the widget
+fn muscle_at(position: Point) -> Option<Muscle> {
+    BODY_SIDES.iter().find_map(|side| {
+        side.regions()
+            .iter()
+            .find(|region| {
+                region
+                    .bounds(*side)
+                    .iter()
+                    .any(|half| half.contains(position))
+            })
+            .and_then(|region| region.muscle)
+    })
+}
+
+/// Blends from the color of untrained muscles to `full_color` the more sets are done
+fn muscle_color(weekly_sets: f32, full_color: Color) -> Color {
+    let ratio = (weekly_sets / WEEKLY_SETS_FOR_FULL_COLOR).clamp(0.0, 1.0);
+    let blend = |untrained: f32, full: f32| untrained + (full - untrained) * ratio;
+
+    Color::from_rgb(
+        blend(LIGHTER_CONTAINER_COLOR.r, full_color.r),
+        blend(LIGHTER_CONTAINER_COLOR.g, full_color.g),
+        blend(LIGHTER_CONTAINER_COLOR.b, full_color.b),
+    )
+}
+
+pub struct BodyMapWidget<'a> {
+    active_mascot: Mascot,
+    body_map_widget_state: &'a BodyMapWidgetState,
+}
+
+impl<'a> BodyMapWidget<'a> {
+    pub(crate) fn new(app: &'a App) -> Self {
+        BodyMapWidget {
+            active_mascot: app.mascot_manager.selected_mascot,
+            body_map_widget_state: &app.widget_manager.body_map_widget_state,
+        }
+    }
+    pub(crate) fn view(self) -> Element<'a, Message> {
+        canvas(self)
+            .width(BODY_MAP_WIDGET_WIDTH)
+            .height(BODY_MAP_WIDGET_HEIGHT)
+            .into()
+    }
+    fn weekly_sets(&self, muscle: Muscle) -> f32 {
+        self.body_map_widget_state
+            .muscle_volume
+            .get(&muscle)
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+#[derive(Default)]
+pub struct BodyMapWidgetState {
+    body_map: Cache,
+    muscle_volume: MuscleVolume,
+}
+
+impl BodyMapWidgetState {
+    pub fn update_data(&mut self, muscle_volume: MuscleVolume) {
+        self.muscle_volume = muscle_volume;
+        self.body_map.clear();
+    }
+}
+
+impl canvas::Program<Message> for BodyMapWidget<'_> {
+    /// Currently hovered muscle
+    type State = Option<Muscle>;
+
+    fn update(
+        &self,
+        hovered_muscle: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<Action<Message>> {
+        let Event::Mouse(mouse::Event::CursorMoved { .. }) = event else {
+            return None;
+        };
+        let muscle_under_cursor = cursor.position_in(bounds).and_then(muscle_at);
+
+        if muscle_under_cursor == *hovered_muscle {
+            return None;
+        }
+        *hovered_muscle = muscle_under_cursor;
+        Some(Action::request_redraw())
+    }
+
+    fn draw(
+        &self,
+        hovered_muscle: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let body_map = self
+            .body_map_widget_state
+            .body_map
+            .draw(renderer, bounds.size(), |frame| {
+                draw_background(frame);
+                draw_body(frame, self);
+            });
+
+        let mut description_frame = Frame::new(renderer, bounds.size());
+        let description = match hovered_muscle {
+            Some(muscle) => format!("{muscle}: {} sets", self.weekly_sets(*muscle)),
+            None => "Sets this week".to_string(),
+        };
+        draw_text(
+            &mut description_frame,
+            description,
+            FONT_SIZE_DESCRIPTION,
+            Point::new(bounds.width / 2.0, DESCRIPTION_Y),
+            TEXT_COLOR,
+        );
+
+        vec![body_map, description_frame.into_geometry()]
+    }
+}
+
+fn draw_background(frame: &mut Frame) {
+    let background =
+        Path::rounded_rectangle(Point::ORIGIN, frame.size(), DEFAULT_CONTAINER_RADIUS.into());
+
+    frame.fill(&background, CONTAINER_COLOR);
+}
+
+fn draw_body(frame: &mut Frame, body_map_widget: &BodyMapWidget) {
+    let full_color = body_map_widget.active_mascot.get_primary_color();
+
+    for side in BODY_SIDES {
+        let head = Path::circle(
+            Point::new(side.center_x(), BODY_TOP_Y + HEAD_RADIUS),
+            HEAD_RADIUS,
+        );
+        frame.fill(&head, LIGHTER_CONTAINER_COLOR);
+
+        for region in side.regions() {
+            let region_color = match region.muscle {
+                Some(muscle) => muscle_color(body_map_widget.weekly_sets(muscle), full_color),
+                None => LIGHTER_CONTAINER_COLOR,
+            };
+            for half in region.bounds(side) {
+                let path = Path::rounded_rectangle(
+                    half.position(),
+                    half.size(),
+                    MUSCLE_REGION_RADIUS.into(),
+                );
+                frame.fill(&path, region_color);
+            }
+        }
+
+        draw_text(
+            frame,
+            side.name().to_string(),
+            FONT_SIZE_VIEW_NAME,
+            Point::new(side.center_x(), VIEW_NAME_Y),
+            DESCRIPTION_TEXT_COLOR,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_muscle_is_shown_on_the_body_map() {
+        for muscle in Muscle::iter() {
+            assert!(
+                BODY_SIDES
+                    .iter()
+                    .flat_map(|side| side.regions())
+                    .any(|region| region.muscle == Some(muscle)),
+                "{muscle} is missing"
+            );
+        }
+    }
+
+    #[test]
+    fn muscle_is_found_on_both_halves_of_the_body() {
+        let chest = &FRONT_BODY_REGIONS[2];
+        for half in chest.bounds(BodySide::Front) {
+            assert_eq!(muscle_at(half.center()), Some(Muscle::Chest));
+        }
+        assert_eq!(muscle_at(Point::ORIGIN), None);
+    }
+
+    #[test]
+    fn muscle_color_is_capped_at_full_color() {
+        assert_eq!(muscle_color(0.0, Color::WHITE), LIGHTER_CONTAINER_COLOR);
+        assert_eq!(
+            muscle_color(WEEKLY_SETS_FOR_FULL_COLOR * 2.0, Color::WHITE),
+            Color::WHITE
+        );
+    }
+}
@@ -1,27 +1,119 @@
+use crate::client::backend::custom_exercise_editor::CustomExerciseEditor;
 use crate::client::backend::exercise_manager::ExerciseManager;
+use crate::client::backend::pop_up_manager::PopUpType;
 use crate::client::backend::widget_state::widget_state_manager::WidgetMessage;
-use crate::client::gui::bb_theme::color::TEXT_COLOR;
+use crate::client::gui::app::App;
+use crate::client::gui::bb_theme::color::{BACKGROUND_COLOR, TEXT_COLOR};
+use crate::client::gui::bb_theme::combo_box::{create_menu_style, create_text_input_style};
 use crate::client::gui::bb_theme::container::{
-    ContainerStyle, DEFAULT_TEXT_CONTAINER_PADDING, create_container_style,
+    ContainerStyle, DEFAULT_CONTAINER_RADIUS, DEFAULT_TEXT_CONTAINER_PADDING,
+    create_container_style,
+};
+use crate::client::gui::bb_theme::custom_button::{
+    ButtonStyle, create_element_button, create_text_button,
 };
-use crate::client::gui::bb_theme::custom_button::{ButtonStyle, create_element_button};
 use crate::client::gui::bb_theme::text_format::{FIRA_SANS_EXTRABOLD, format_description_text};
 use crate::client::gui::bb_widget::widget_utils::{
-    INDENT, LARGE_INDENT, descriptor_space_fill_text_row,
+    INDENT, LARGE_INDENT, descriptor_space_fill_element_row, descriptor_space_fill_text_row,
 };
 use crate::client::gui::user_interface::Message;
+use crate::client::server_communication::exercise_communicator::create_custom_exercise_on_server;
+use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::exercise_mod::custom_exercise::CustomExercise;
 use crate::common::exercise_mod::exercise::Exercise;
-use crate::common::exercise_mod::general_exercise::GeneralExerciseInfo;
+use crate::common::exercise_mod::general_exercise::{
+    ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, GeneralExerciseInfo, Muscle,
+};
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
-use iced::widget::{Column, Container, Row, Space, container, text};
+use iced::widget::{
+    Column, ComboBox, Container, Row, Space, combo_box, container, text, text_input,
+};
+use iced::{Element, Task};
 use iced_core::alignment::Vertical;
 use iced_core::{Length, Padding};
+use std::fmt::Display;
 
 const TITLE_SIZE: f32 = 30.0;
+const CUSTOM_EXERCISE_INPUT_WIDTH: f32 = 250.0;
+
+#[derive(Debug, Clone)]
+pub enum CustomExerciseMessage {
+    Open,
+    Cancel,
+    EditName(String),
+    SelectMuscle(Muscle),
+    SelectEquipment(ExerciseEquipment),
+    SelectForce(ExerciseForce),
+    SelectLevel(ExerciseLevel),
+    SelectCategory(ExerciseCategory),
+    ToggleSharing,
+    Create,
+    Created(Result<CustomExercise, ServerRequestError>),
+}
+
+impl CustomExerciseMessage {
+    pub fn update(self, app: &mut App) -> Task<Message> {
+        let editor = &mut app.exercise_manager.custom_exercise_editor;
+        let new_custom_exercise = &mut editor.new_custom_exercise;
+        match self {
+            CustomExerciseMessage::Open => editor.open(),
+            CustomExerciseMessage::Cancel => editor.close(),
+            CustomExerciseMessage::EditName(name) => new_custom_exercise.name = name,
+            CustomExerciseMessage::SelectMuscle(muscle) => {
+                new_custom_exercise.primary_muscle = muscle
+            }
+            CustomExerciseMessage::SelectEquipment(equipment) => {
+                new_custom_exercise.equipment = equipment
+            }
+            CustomExerciseMessage::SelectForce(force) => new_custom_exercise.force = force,
+            CustomExerciseMessage::SelectLevel(level) => new_custom_exercise.level = level,
+            CustomExerciseMessage::SelectCategory(category) => {
+                new_custom_exercise.category = category
+            }
+            CustomExerciseMessage::ToggleSharing => {
+                new_custom_exercise.shared_with_friends = !new_custom_exercise.shared_with_friends
+            }
+            CustomExerciseMessage::Create => match editor.parse(&app.exercise_manager.exercises) {
+                Ok(new_custom_exercise) => {
+                    if !app.api_client.is_logged_in() {
+                        return Task::none();
+                    }
+                    return Task::perform(
+                        create_custom_exercise_on_server(
+                            app.api_client.clone(),
+                            new_custom_exercise,
+                        ),
+                        |result| {
+                            Message::Widget(WidgetMessage::CustomExercise(
+                                CustomExerciseMessage::Created(result),
+                            ))
+                        },
+                    );
+                }
+                Err(reason) => app.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Invalid exercise".to_string(),
+                    reason.to_string(),
+                ),
+            },
+            CustomExerciseMessage::Created(Ok(custom_exercise)) => {
+                editor.close();
+                app.exercise_manager
+                    .add_custom_exercise(custom_exercise.general_exercise_info);
+            }
+            CustomExerciseMessage::Created(Err(err)) => app.pop_up_manager.new_pop_up(
+                PopUpType::Minor,
+                "Could not create exercise".to_string(),
+                err.to_error_message(),
+            ),
+        }
+        Task::none()
+    }
+}
 
 pub fn general_exercise_browser<'a>(
-    active_mascot: &Mascot,
+    active_mascot: &'a Mascot,
     exercise_manager: &'a ExerciseManager,
 ) -> Container<'a, Message> {
     let general_exercise_info_elements =
@@ -31,24 +123,168 @@ pub fn general_exercise_browser<'a>(
         .font(FIRA_SANS_EXTRABOLD)
         .color(TEXT_COLOR);
 
+    let custom_exercise_editor = &exercise_manager.custom_exercise_editor;
+    let custom_exercise_button = create_text_button(
+        active_mascot,
+        "Custom exercise".to_string(),
+        ButtonStyle::InactiveTab,
+        Some(DEFAULT_CONTAINER_RADIUS.into()),
+    )
+    .on_press_maybe((!custom_exercise_editor.is_open).then_some(Message::Widget(
+        WidgetMessage::CustomExercise(CustomExerciseMessage::Open),
+    )));
+
     let title_bar = Row::new()
         .push(browse_exercises_title)
         .push(format_description_text(text(format!(
             " - {} exercises",
             exercise_manager.exercises.len()
         ))))
+        .push(Space::new().width(Length::Fill))
+        .push(custom_exercise_button)
         .align_y(Vertical::Center);
 
-    let exercise_browser = Column::new()
-        .push(title_bar)
-        .push(general_exercise_info_elements)
-        .spacing(INDENT);
+    let mut exercise_browser = Column::new().push(title_bar).spacing(INDENT);
+    if custom_exercise_editor.is_open {
+        exercise_browser =
+            exercise_browser.push(custom_exercise_form(active_mascot, custom_exercise_editor));
+    }
+    exercise_browser = exercise_browser.push(general_exercise_info_elements);
 
     container(exercise_browser)
         .style(create_container_style(ContainerStyle::Default, None, None))
         .padding(LARGE_INDENT)
 }
 
+fn custom_exercise_form<'a>(
+    active_mascot: &'a Mascot,
+    editor: &'a CustomExerciseEditor,
+) -> Container<'a, Message> {
+    let new_custom_exercise = &editor.new_custom_exercise;
+    let custom_exercise_message = |message| Message::Widget(WidgetMessage::CustomExercise(message));
+
+    let name_input = text_input("Name of the exercise", &new_custom_exercise.name)
+        .on_input(move |name| custom_exercise_message(CustomExerciseMessage::EditName(name)))
+        .on_submit(custom_exercise_message(CustomExerciseMessage::Create))
+        .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+        .font(FIRA_SANS_EXTRABOLD)
+        .width(CUSTOM_EXERCISE_INPUT_WIDTH);
+
+    let muscle_combo_box = combo_box(
+        &editor.muscle_state,
+        "Select muscle...",
+        Some(&new_custom_exercise.primary_muscle),
+        move |muscle| custom_exercise_message(CustomExerciseMessage::SelectMuscle(muscle)),
+    );
+    let equipment_combo_box = combo_box(
+        &editor.equipment_state,
+        "Select equipment...",
+        Some(&new_custom_exercise.equipment),
+        move |equipment| custom_exercise_message(CustomExerciseMessage::SelectEquipment(equipment)),
+    );
+    let force_combo_box = combo_box(
+        &editor.force_state,
+        "Select force...",
+        Some(&new_custom_exercise.force),
+        move |force| custom_exercise_message(CustomExerciseMessage::SelectForce(force)),
+    );
+    let level_combo_box = combo_box(
+        &editor.level_state,
+        "Select level...",
+        Some(&new_custom_exercise.level),
+        move |level| custom_exercise_message(CustomExerciseMessage::SelectLevel(level)),
+    );
+    let category_combo_box = combo_box(
+        &editor.category_state,
+        "Select category...",
+        Some(&new_custom_exercise.category),
+        move |category| custom_exercise_message(CustomExerciseMessage::SelectCategory(category)),
+    );
+
+    let (sharing_text, sharing_style) = if new_custom_exercise.shared_with_friends {
+        ("Shared with friends", ButtonStyle::Active)
+    } else {
+        ("Private", ButtonStyle::InactiveTab)
+    };
+    let sharing_button = create_text_button(
+        active_mascot,
+        sharing_text.to_string(),
+        sharing_style,
+        Some(DEFAULT_CONTAINER_RADIUS.into()),
+    )
+    .on_press(custom_exercise_message(
+        CustomExerciseMessage::ToggleSharing,
+    ));
+
+    let create_button = create_text_button(
+        active_mascot,
+        "Create".to_string(),
+        ButtonStyle::Active,
+        Some(DEFAULT_CONTAINER_RADIUS.into()),
+    )
+    .on_press(custom_exercise_message(CustomExerciseMessage::Create));
+    let cancel_button = create_text_button(
+        active_mascot,
+        "Cancel".to_string(),
+        ButtonStyle::InactiveTab,
+        Some(DEFAULT_CONTAINER_RADIUS.into()),
+    )
+    .on_press(custom_exercise_message(CustomExerciseMessage::Cancel));
+
+    let buttons = Row::new()
+        .push(sharing_button)
+        .push(Space::new().width(Length::Fill))
+        .push(cancel_button)
+        .push(create_button)
+        .spacing(INDENT)
+        .align_y(Vertical::Center);
+
+    let fields = [
+        ("Name:", name_input.into()),
+        (
+            "Primary muscle:",
+            styled_combo_box(active_mascot, muscle_combo_box),
+        ),
+        (
+            "Equipment:",
+            styled_combo_box(active_mascot, equipment_combo_box),
+        ),
+        ("Force:", styled_combo_box(active_mascot, force_combo_box)),
+        ("Level:", styled_combo_box(active_mascot, level_combo_box)),
+        (
+            "Category:",
+            styled_combo_box(active_mascot, category_combo_box),
+        ),
+    ];
+    let mut form = Column::new().spacing(5);
+    for (description, element) in fields {
+        form = form.push(descriptor_space_fill_element_row(
+            description.to_string(),
+            element,
+        ));
+    }
+    form = form.push(buttons);
+
+    container(form)
+        .style(create_container_style(ContainerStyle::Light, None, None))
+        .padding(DEFAULT_TEXT_CONTAINER_PADDING)
+}
+
+fn styled_combo_box<'a, T>(
+    active_mascot: &'a Mascot,
+    combo_box: ComboBox<'a, T, Message>,
+) -> Element<'a, Message>
+where
+    T: Display + Clone + 'static,
+{
+    combo_box
+        .font(FIRA_SANS_EXTRABOLD)
+        .width(CUSTOM_EXERCISE_INPUT_WIDTH)
+        .input_style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+        .menu_style(create_menu_style(active_mascot))
+        .into()
+}
+
 fn display_general_exercise_infos<'a>(
     active_mascot: &Mascot,
     exercise_manager: &'a ExerciseManager,
@@ -150,8 +386,14 @@ fn create_extended_infos(general_exercise_info: &GeneralExerciseInfo) -> Row<'_,
         })
 }
 
-fn instruction_container(instructions: &String) -> Container<'_, Message> {
+fn instruction_container(instructions: &str) -> Container<'_, Message> {
     let instruction_title = format_description_text(text("Instruction:"));
+    // Custom exercises are created without instructions
+    let instructions = if instructions.is_empty() {
+        "No instructions"
+    } else {
+        instructions
+    };
     let instruction_text = text(instructions)
         .font(FIRA_SANS_EXTRABOLD)
         .color(TEXT_COLOR)
@@ -184,6 +426,10 @@ fn detail_column(general_exercise_info: &GeneralExerciseInfo) -> Column<'_, Mess
             "Primary muscle:".to_string(),
             general_exercise_info.primary_muscle.to_string(),
         ),
+        descriptor_space_fill_text_row(
+            "Secondary muscles:".to_string(),
+            secondary_muscles_text(&general_exercise_info.secondary_muscles),
+        ),
         descriptor_space_fill_text_row(
             "Category:".to_string(),
             general_exercise_info.category.to_string(),
@@ -194,3 +440,13 @@ fn detail_column(general_exercise_info: &GeneralExerciseInfo) -> Column<'_, Mess
     }
     content
 }
+fn secondary_muscles_text(secondary_muscles: &[Muscle]) -> String {
+    if secondary_muscles.is_empty() {
+        return "None".to_string();
+    }
+    secondary_muscles
+        .iter()
+        .map(Muscle::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -11,20 +11,27 @@ use crate::client::gui::bb_widget::workout::{
 use crate::client::gui::user_interface::Message;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::workout_preset::WorkoutPreset;
+use crate::common::workout_reaction::WorkoutReactions;
 use iced::Element;
 use iced::widget::Row;
 
 pub fn view_recent_workout_row<'a>(
     mascot: &Mascot,
     recent_workouts: &'a Vec<RecentWorkoutVisualization>,
+    own_workout_reactions: &[WorkoutReactions],
 ) -> Element<'a, Message> {
     let mut recent_workout_row = Row::new()
         .height(DEFAULT_RECENT_WORKOUT_WIDGET_HEIGHT + SCROLLBAR_PADDING)
         .spacing(INDENT);
 
     for recent_workout in recent_workouts {
-        recent_workout_row =
-            recent_workout_row.push(WorkoutWidget::new_recent_workout_widget(recent_workout));
+        let reactions = own_workout_reactions
+            .iter()
+            .find(|reactions| reactions.workout_id == recent_workout.workout_id);
+        recent_workout_row = recent_workout_row.push(WorkoutWidget::new_recent_workout_widget(
+            recent_workout,
+            reactions,
+        ));
     }
 
     create_scrollable(recent_workout_row, *mascot, ScrollableStyle::Mascot)
@@ -4,6 +4,7 @@ use crate::client::gui::bb_theme::text_format;
 use crate::client::gui::bb_theme::text_format::format_button_text;
 use crate::client::gui::user_interface::Message;
 use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use iced::advanced::{
     layout::Layout,
     widget::Widget,
@@ -44,6 +45,13 @@ where
     buy_element: Element<'a, Message, Theme, Renderer>,
     font: Option<<Renderer as iced_core::text::Renderer>::Font>,
     active_mascot: Mascot,
+    /// Image and name of a freshly bought mascot, faded in over the gacha image
+    revealed_mascot: Option<(
+        image::Image<<Renderer as iced_core::image::Renderer>::Handle>,
+        String,
+    )>,
+    /// 0 shows only the gacha image, 1 only the revealed mascot
+    reveal_progress: f32,
 }
 
 impl<'a> ShopWidget<'a, Message, Renderer>
@@ -63,6 +71,15 @@ where
         self.image = image;
         self
     }
+    pub fn reveal_mascot(mut self, mascot: Mascot, reveal_progress: f32) -> Self {
+        self.revealed_mascot = Some((
+            Image::new(Handle::from_path(mascot.get_file_path())),
+            format!("You got {}!", mascot.get_name()),
+        ));
+        self.reveal_progress = reveal_progress.clamp(0.0, 1.0);
+        self
+    }
+
     pub(crate) fn new(name: String, price: usize, mascot: &Mascot, message: Message) -> Self {
         let buy_button: iced_anim::widget::Button<'_, Message, Theme, iced::Renderer> =
             create_element_button(
@@ -95,6 +112,8 @@ where
             buy_element: buy_button.on_press(message).into(),
             font: text_format::FIRA_SANS_EXTRABOLD.into(),
             active_mascot: *mascot,
+            revealed_mascot: None,
+            reveal_progress: 0.0,
         }
     }
 }
@@ -159,11 +178,35 @@ where
             height: IMAGE_HEIGHT,
         };
 
-        renderer.draw_image(self.image.clone(), image_rectangle, image_rectangle);
+        let mut title = &self.title;
+        match &self.revealed_mascot {
+            Some((mascot_image, revealed_title)) => {
+                let mut gacha_image = self.image.clone();
+                gacha_image.opacity = 1.0 - self.reveal_progress;
+                renderer.draw_image(gacha_image, image_rectangle, image_rectangle);
+
+                // The mascot grows from the center of the gacha image
+                let mascot_size = IMAGE_WIDTH * (0.5 + 0.5 * self.reveal_progress);
+                let mascot_rectangle = Rectangle {
+                    x: image_rectangle.center_x() - mascot_size / 2.0,
+                    y: image_rectangle.center_y() - mascot_size / 2.0,
+                    width: mascot_size,
+                    height: mascot_size,
+                };
+                let mut mascot_image = mascot_image.clone();
+                mascot_image.opacity = self.reveal_progress;
+                renderer.draw_image(mascot_image, mascot_rectangle, image_rectangle);
+
+                if self.reveal_progress > 0.5 {
+                    title = revealed_title;
+                }
+            }
+            None => renderer.draw_image(self.image.clone(), image_rectangle, image_rectangle),
+        }
 
         renderer.fill_text(
             iced_core::text::Text {
-                content: self.title.to_string(),
+                content: title.to_string(),
                 bounds: layout.bounds().size(),
                 size: iced_core::Pixels(TITLE_FONT_SIZE),
                 line_height: Default::default(),
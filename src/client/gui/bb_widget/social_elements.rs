@@ -1,17 +1,31 @@
 use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::social::SocialMessage;
-use crate::client::gui::bb_theme::container::DEFAULT_CONTAINER_RADIUS;
-use crate::client::gui::bb_theme::custom_button::{ButtonStyle, create_element_button};
-use crate::client::gui::bb_theme::text_format::{format_button_text, format_description_text};
+use crate::client::gui::bb_theme::color::BACKGROUND_COLOR;
+use crate::client::gui::bb_theme::combo_box::create_text_input_style;
+use crate::client::gui::bb_theme::container::{
+    ContainerStyle, DEFAULT_CONTAINER_RADIUS, create_container_style,
+};
+use crate::client::gui::bb_theme::custom_button::{
+    ButtonStyle, create_element_button, create_text_button,
+};
+use crate::client::gui::bb_theme::text_format::{
+    FIRA_SANS_EXTRABOLD, format_button_text, format_description_text,
+};
 use crate::client::gui::bb_widget::widget_utils::INDENT;
 use crate::client::gui::user_interface::Message;
+use crate::common::challenge::{Challenge, ChallengeStatus};
+use crate::common::leaderboard::{LeaderboardEntry, LeaderboardMetric};
 use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use crate::common::profile_picture::{
     MEDIUM_PROFILE_PICTURE_DIMENSION, SMALL_PROFILE_PICTURE_DIMENSION,
 };
+use crate::common::social_feed::FeedEvent;
+use crate::common::user_mod::friend_request::FriendshipStatus;
 use crate::common::user_mod::user::{ForeignUser, UserInformation, UserType};
+use crate::common::workout_reaction::WorkoutSummary;
 use iced::Element;
-use iced::widget::{Column, Row, Space, column, image, text};
+use iced::widget::{Column, Row, Space, column, container, image, text, text_input};
 use iced_core::Length;
 use iced_core::alignment::{Horizontal, Vertical};
 use iced_core::image::Handle;
@@ -89,22 +103,12 @@ pub fn user_profile_button<'a>(
 
     let text_column = Column::new().push(name).push(streak);
 
-    let add_friend_button = create_element_button(
-        active_mascot,
-        image("assets/images/user_plus.png").into(),
-        ButtonStyle::Active,
-        Some(DEFAULT_CONTAINER_RADIUS.into()),
-    )
-    .on_press(Message::Social(SocialMessage::AddUserAsFriend(
-        user.username.clone(),
-    )));
-
     let contents = Row::new()
         .push(profile_picture)
         .push(Space::new().width(50))
         .push(text_column)
         .push(Space::new().width(Length::Fill))
-        .push(add_friend_button)
+        .push(friend_request_buttons(active_mascot, user))
         .align_y(Vertical::Center);
 
     create_element_button(
@@ -120,6 +124,298 @@ pub fn user_profile_button<'a>(
     )))
 }
 
+/// Event of the friends feed, framed in the primary color of the friend's favorite mascot
+pub fn feed_event_element<'a>(event: &'a FeedEvent) -> Element<'a, Message> {
+    let profile_picture: Element<Message> = image(Handle::from_path(&event.profile_picture_path))
+        .width(SMALL_PROFILE_PICTURE_DIMENSION)
+        .height(SMALL_PROFILE_PICTURE_DIMENSION)
+        .into();
+
+    let description = Column::new()
+        .push(format_button_text(text(&event.username)))
+        .push(
+            format_description_text(text(event.kind.description())).wrapping(Wrapping::WordOrGlyph),
+        )
+        .width(Length::Fill);
+
+    let created_at =
+        format_description_text(text(event.created_at.format("%d.%m.%y %H:%M").to_string()));
+
+    let contents = Row::new()
+        .push(profile_picture)
+        .push(description)
+        .push(created_at)
+        .spacing(INDENT)
+        .align_y(Vertical::Center);
+
+    container(contents)
+        .style(create_container_style(
+            ContainerStyle::Light,
+            None,
+            Some(event.favorite_mascot.get_primary_color()),
+        ))
+        .width(USER_BUTTON_WIDTH)
+        .padding(INDENT)
+        .into()
+}
+
+/// Row of the leaderboard, the logged-in user is framed in the color of their active mascot
+pub fn leaderboard_entry_element<'a>(
+    app: &App,
+    entry: &'a LeaderboardEntry,
+    metric: LeaderboardMetric,
+) -> Element<'a, Message> {
+    let profile_picture: Element<Message> = image(Handle::from_path(&entry.profile_picture_path))
+        .width(SMALL_PROFILE_PICTURE_DIMENSION)
+        .height(SMALL_PROFILE_PICTURE_DIMENSION)
+        .into();
+
+    let value = format!("{} {}", entry.value, metric.get_unit());
+    let contents = Row::new()
+        .push(format_button_text(text(format!("#{}", entry.rank))).width(40))
+        .push(profile_picture)
+        .push(format_button_text(text(&entry.username)))
+        .push(Space::new().width(Length::Fill))
+        .push(format_description_text(text(value)))
+        .spacing(INDENT)
+        .align_y(Vertical::Center);
+
+    let border_color = entry
+        .username
+        .eq_ignore_ascii_case(&app.user_manager.user_info.username)
+        .then(|| app.mascot_manager.selected_mascot.get_primary_color());
+
+    container(contents)
+        .style(create_container_style(
+            ContainerStyle::Light,
+            None,
+            border_color,
+        ))
+        .width(USER_BUTTON_WIDTH)
+        .padding(INDENT)
+        .into()
+}
+
+/// Challenge with its stake and state, pending challenges the user received can be answered
+pub fn challenge_element<'a>(app: &App, challenge: &'a Challenge) -> Element<'a, Message> {
+    let active_mascot = &app.mascot_manager.selected_mascot;
+    let username = &app.user_manager.user_info.username;
+
+    let details = format!(
+        "vs {} | {} coins | {} - {}",
+        challenge.other_user(username),
+        challenge.stake,
+        challenge.start_date.format("%d.%m."),
+        challenge.end_date.format("%d.%m.%Y"),
+    );
+    let mut contents = Row::new()
+        .push(
+            column![
+                format_button_text(text(challenge.goal.description())),
+                format_description_text(text(details)),
+            ]
+            .width(Length::Fill),
+        )
+        .push(format_description_text(text(
+            challenge.state_description(username),
+        )))
+        .spacing(INDENT)
+        .align_y(Vertical::Center);
+
+    if challenge.status == ChallengeStatus::Pending
+        && challenge.opponent.eq_ignore_ascii_case(username)
+    {
+        contents = contents
+            .push(
+                create_text_button(
+                    active_mascot,
+                    "Decline".to_string(),
+                    ButtonStyle::InactiveSolid,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::AnswerChallenge(
+                    challenge.id,
+                    false,
+                ))),
+            )
+            .push(
+                create_text_button(
+                    active_mascot,
+                    "Accept".to_string(),
+                    ButtonStyle::Active,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::AnswerChallenge(
+                    challenge.id,
+                    true,
+                ))),
+            );
+    }
+
+    container(contents)
+        .style(create_container_style(ContainerStyle::Light, None, None))
+        .width(USER_BUTTON_WIDTH)
+        .padding(INDENT)
+        .into()
+}
+
+/// A workout on a profile with buttons to give kudos and to add or delete comments
+pub fn workout_with_reactions<'a>(
+    app: &'a App,
+    summary: &'a WorkoutSummary,
+) -> Element<'a, Message> {
+    let active_mascot = &app.mascot_manager.selected_mascot;
+    let user_manager = &app.user_manager;
+    let workout_id = summary.workout_id;
+    let reactions = &summary.reactions;
+
+    let kudos_style = if reactions.has_kudos_from(&user_manager.user_info.username) {
+        ButtonStyle::Active
+    } else {
+        ButtonStyle::InactiveSolid
+    };
+    let kudos_button = create_text_button(
+        active_mascot,
+        format!("Kudos {}", reactions.kudos.len()),
+        kudos_style,
+        Some(DEFAULT_CONTAINER_RADIUS.into()),
+    )
+    .on_press(Message::Social(SocialMessage::ToggleKudos(workout_id)));
+
+    let header = Row::new()
+        .push(format_button_text(text(
+            summary.date.format("%d.%m.%Y").to_string(),
+        )))
+        .push(
+            format_description_text(text(summary.exercise_names.join(", ")))
+                .wrapping(Wrapping::WordOrGlyph)
+                .width(Length::Fill),
+        )
+        .push(kudos_button)
+        .spacing(INDENT)
+        .align_y(Vertical::Center);
+
+    let mut comments = Column::new().spacing(INDENT / 2.0);
+    for comment in &reactions.comments {
+        let mut comment_row = Row::new()
+            .push(format_button_text(text(format!("{}:", comment.author))))
+            .push(
+                format_description_text(text(&comment.text))
+                    .wrapping(Wrapping::WordOrGlyph)
+                    .width(Length::Fill),
+            )
+            .spacing(INDENT)
+            .align_y(Vertical::Center);
+        if user_manager.can_delete_comment(&summary.owner, comment) {
+            comment_row = comment_row.push(
+                create_text_button(
+                    active_mascot,
+                    "Delete".to_string(),
+                    ButtonStyle::InactiveSolid,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::DeleteComment(
+                    workout_id, comment.id,
+                ))),
+            );
+        }
+        comments = comments.push(comment_row);
+    }
+
+    let comment_input = text_input(
+        "Write a comment...",
+        user_manager
+            .comment_inputs
+            .get(&workout_id)
+            .map(String::as_str)
+            .unwrap_or_default(),
+    )
+    .on_input(move |text| Message::Social(SocialMessage::EditComment(workout_id, text)))
+    .on_submit(Message::Social(SocialMessage::SendComment(workout_id)))
+    .style(create_text_input_style(active_mascot, BACKGROUND_COLOR))
+    .font(FIRA_SANS_EXTRABOLD);
+    let send_button = create_text_button(
+        active_mascot,
+        "Send".to_string(),
+        ButtonStyle::Active,
+        Some(DEFAULT_CONTAINER_RADIUS.into()),
+    )
+    .on_press(Message::Social(SocialMessage::SendComment(workout_id)));
+
+    let contents = Column::new()
+        .push(header)
+        .push(comments)
+        .push(
+            Row::new()
+                .push(comment_input)
+                .push(send_button)
+                .spacing(INDENT)
+                .align_y(Vertical::Center),
+        )
+        .spacing(INDENT);
+
+    container(contents)
+        .style(create_container_style(ContainerStyle::Default, None, None))
+        .width(Length::Fill)
+        .padding(INDENT)
+        .into()
+}
+
+/// Buttons to send or answer a friend request, depending on the relationship to `user`
+fn friend_request_buttons<'a>(active_mascot: &Mascot, user: &ForeignUser) -> Row<'a, Message> {
+    let username = user.username.clone();
+    let mut buttons = Row::new().spacing(INDENT).align_y(Vertical::Center);
+
+    match user.friendship_status {
+        FriendshipStatus::NotFriends => {
+            buttons = buttons.push(
+                create_element_button(
+                    active_mascot,
+                    image("assets/images/user_plus.png").into(),
+                    ButtonStyle::Active,
+                    Some(DEFAULT_CONTAINER_RADIUS.into()),
+                )
+                .on_press(Message::Social(SocialMessage::SendFriendRequest(username))),
+            )
+        }
+        FriendshipStatus::RequestSent => {
+            buttons = buttons.push(create_text_button(
+                active_mascot,
+                "Request sent".to_string(),
+                ButtonStyle::InactiveSolid,
+                Some(DEFAULT_CONTAINER_RADIUS.into()),
+            ))
+        }
+        FriendshipStatus::RequestReceived => {
+            buttons = buttons
+                .push(
+                    create_text_button(
+                        active_mascot,
+                        "Decline".to_string(),
+                        ButtonStyle::InactiveSolid,
+                        Some(DEFAULT_CONTAINER_RADIUS.into()),
+                    )
+                    .on_press(Message::Social(
+                        SocialMessage::AnswerFriendRequest(username.clone(), false),
+                    )),
+                )
+                .push(
+                    create_text_button(
+                        active_mascot,
+                        "Accept".to_string(),
+                        ButtonStyle::Active,
+                        Some(DEFAULT_CONTAINER_RADIUS.into()),
+                    )
+                    .on_press(Message::Social(
+                        SocialMessage::AnswerFriendRequest(username, true),
+                    )),
+                )
+        }
+        FriendshipStatus::Friends => {}
+    }
+    buttons
+}
+
 pub fn profile_tab_button<'a>(
     user_info: &'a UserInformation,
     mascot: &Mascot,
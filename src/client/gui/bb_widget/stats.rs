@@ -1,8 +1,11 @@
 use crate::client::gui::app::App;
+use crate::client::gui::bb_tab::workout_creation::WorkoutCreationMessage;
 use crate::client::gui::bb_theme::container::{
     ContainerStyle, DEFAULT_CONTAINER_RADIUS, create_container_style,
 };
-use crate::client::gui::bb_theme::custom_button::DEFAULT_BUTTON_RADIUS;
+use crate::client::gui::bb_theme::custom_button::{
+    ButtonStyle, DEFAULT_BUTTON_RADIUS, create_element_button,
+};
 use crate::client::gui::bb_theme::text_format::{
     format_button_text, format_description_text, kg_to_string,
 };
@@ -85,6 +88,21 @@ pub fn exercise_stat_column(app: &App) -> Column<'_, Message> {
                 )),
         );
     }
+    if let Some(workout_id) = app
+        .exercise_manager
+        .latest_workout_id_of_selected_exercise()
+    {
+        let edit_button = create_element_button(
+            &app.mascot_manager.selected_mascot,
+            format_button_text(text("Edit last workout")).into(),
+            ButtonStyle::Active,
+            None,
+        )
+        .on_press(Message::WorkoutCreation(
+            WorkoutCreationMessage::EditWorkout(workout_id),
+        ));
+        lines = lines.push(Space::new().height(INDENT)).push(edit_button);
+    }
     lines
 }
 
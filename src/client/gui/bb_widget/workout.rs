@@ -7,7 +7,8 @@ use crate::client::gui::{bb_theme, bb_widget};
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use crate::common::mascot_mod::rare_mascot::RareMascot;
-use crate::common::workout_preset::WorkoutPreset;
+use crate::common::workout_preset::{PresetStats, WorkoutPreset};
+use crate::common::workout_reaction::WorkoutReactions;
 use iced::Element;
 use iced::widget::Row;
 use iced_core::alignment::Vertical;
@@ -30,6 +31,7 @@ const IMAGE_HEIGHT: f32 = 125.0 * SCALE;
 //FONT
 const DEFAULT_TITLE_FONT_SIZE: f32 = 17.0 * SCALE;
 const DEFAULT_DESCRIPTION_FONT_SIZE: f32 = 15.0 * SCALE;
+const FOOTER_FONT_SIZE: f32 = 13.0 * SCALE;
 const MAX_EXERCISE_NAME_LENGTH: usize = 20;
 
 pub struct WorkoutWidget<Renderer>
@@ -44,6 +46,8 @@ where
     width: f32,
     height: f32,
     font: <Renderer>::Font,
+    /// Small line at the bottom, e.g. the stats of a preset
+    footer: Option<String>,
     on_press: Option<Message>,
 }
 impl<Renderer> WorkoutWidget<Renderer>
@@ -69,6 +73,7 @@ where
             ],
             description_font_size: DEFAULT_DESCRIPTION_FONT_SIZE,
             font: bb_theme::text_format::FIRA_SANS_EXTRABOLD,
+            footer: None,
             on_press: None,
         }
     }
@@ -84,6 +89,7 @@ where
             exercises: preset.exercises.clone(),
             description_font_size: DEFAULT_DESCRIPTION_FONT_SIZE,
             font: bb_theme::text_format::FIRA_SANS_EXTRABOLD,
+            footer: preset.id.map(|_| format_preset_stats(&preset.stats)),
             on_press: Some(Message::WorkoutCreation(
                 WorkoutCreationMessage::NewWithPreset(preset.clone()),
             )),
@@ -109,10 +115,15 @@ where
             ],
             description_font_size: DEFAULT_DESCRIPTION_FONT_SIZE,
             font: bb_theme::text_format::FIRA_SANS_EXTRABOLD,
+            footer: None,
             on_press: None,
         }
     }
-    pub fn new_recent_workout_widget(recent_workout: &RecentWorkoutVisualization) -> Self {
+    /// The footer shows the number of kudos and comments if the workout has any
+    pub fn new_recent_workout_widget(
+        recent_workout: &RecentWorkoutVisualization,
+        reactions: Option<&WorkoutReactions>,
+    ) -> Self {
         WorkoutWidget {
             width: DEFAULT_WORKOUT_WIDGET_WIDTH,
             height: DEFAULT_RECENT_WORKOUT_WIDGET_HEIGHT,
@@ -122,7 +133,10 @@ where
             exercises: recent_workout.exercise_names.clone(),
             description_font_size: DEFAULT_DESCRIPTION_FONT_SIZE,
             font: bb_theme::text_format::FIRA_SANS_EXTRABOLD,
-            on_press: None,
+            footer: reactions.map(WorkoutReactions::summary),
+            on_press: Some(Message::WorkoutCreation(
+                WorkoutCreationMessage::EditWorkout(recent_workout.workout_id),
+            )),
         }
     }
     pub fn set_image(
@@ -157,6 +171,21 @@ where
         workout_presets
     }
 }
+/// E.g. "3x | last 12.03.25 | avg 45 min"
+fn format_preset_stats(stats: &PresetStats) -> String {
+    if stats.times_trained == 0 {
+        return "Not trained yet".to_string();
+    }
+    let mut parts = vec![format!("{}x", stats.times_trained)];
+    if let Some(last_trained) = stats.last_trained {
+        parts.push(format!("last {}", last_trained.format("%d.%m.%y")));
+    }
+    if let Some(average_duration) = stats.average_duration_minutes {
+        parts.push(format!("avg {average_duration} min"));
+    }
+    parts.join(" | ")
+}
+
 impl<Renderer> Widget<Message, Theme, Renderer> for WorkoutWidget<Renderer>
 where
     Renderer: image::Renderer + text::Renderer,
@@ -229,7 +258,11 @@ where
             *viewport,
         );
 
-        let printable_index: usize = if self.image.is_none() { 5 } else { 3 };
+        let mut printable_index: usize = if self.image.is_none() { 5 } else { 3 };
+        if self.footer.is_some() {
+            // the last exercise line makes room for the footer
+            printable_index -= 1;
+        }
 
         let mut printable_exercises = Vec::<String>::new();
         for (i, exercise) in self.exercises.iter().enumerate() {
@@ -277,6 +310,28 @@ where
                 *viewport,
             );
         }
+
+        if let Some(footer) = &self.footer {
+            renderer.fill_text(
+                text::Text {
+                    content: footer.clone(),
+                    bounds: layout.bounds().size(),
+                    size: iced_core::Pixels(FOOTER_FONT_SIZE),
+                    line_height: Default::default(),
+                    font: self.font,
+                    align_x: Alignment::Left,
+                    shaping: Default::default(),
+                    wrapping: Default::default(),
+                    align_y: Vertical::Top,
+                },
+                Point {
+                    x: layout.bounds().x + INDENT,
+                    y: layout.bounds().y + self.height - INDENT - FOOTER_FONT_SIZE,
+                },
+                bb_theme::color::TEXT_COLOR,
+                *viewport,
+            );
+        }
     }
     fn update(
         &mut self,
@@ -2,6 +2,7 @@ use crate::client::backend::exercise_create::WorkoutCreate;
 use crate::client::backend::login_state::LoginStates;
 use crate::client::backend::pop_up_manager::PopUpType;
 use crate::client::backend::widget_state::widget_state_manager::WidgetMessage;
+use crate::client::client_config::{ClientArgs, ClientConfig};
 pub use crate::client::gui::app::App;
 use crate::client::gui::bb_tab::health::HealthMessage;
 use crate::client::gui::bb_tab::login::{LoginMessage, view_login};
@@ -17,6 +18,8 @@ use crate::client::gui::size;
 use crate::client::server_communication::request_data::LoginServerRequestData;
 use crate::client::server_communication::server_communicator::ServerRequestError;
 use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::workout::SavedWorkout;
+use clap::Parser;
 use iced::widget::{Row, Stack, container};
 use iced::{Element, Task};
 use iced_core::window::{Position, Settings};
@@ -37,7 +40,9 @@ pub enum Message {
 
     // WorkoutMessage (Combine)
     WorkoutCreation(WorkoutCreationMessage),
-    SaveWorkout(Result<Id, ServerRequestError>, WorkoutCreate),
+    SaveWorkout(Result<SavedWorkout, ServerRequestError>, WorkoutCreate),
+    UpdateWorkout(Result<(), ServerRequestError>, Id, WorkoutCreate),
+    DeleteWorkout(Result<(), ServerRequestError>, Id),
 
     // PresetMessage (Can stay)
     PresetCreation(PresetCreationMessage),
@@ -53,14 +58,7 @@ impl App {
         match message {
             Message::Select(Tab::Exit) => iced::exit(),
             Message::Select(tab) => {
-                self.widget_manager.activity_widget.update_data(
-                    self.mascot_manager.selected_mascot,
-                    self.user_manager
-                        .user_info
-                        .profile_stat_manager
-                        .activity_data
-                        .clone(),
-                );
+                self.refresh_activity_widget();
 
                 if let Tab::CreateWorkout = tab {
                     self.exercise_manager.start_workout();
@@ -82,7 +80,7 @@ impl App {
                 self.pop_up_manager.reset();
                 Task::none()
             }
-            Message::Settings(settings_msg) => settings_msg.update(self, self.jsonwebtoken.clone()),
+            Message::Settings(settings_msg) => settings_msg.update(self),
 
             Message::UpdateInfoOnServerResult(res, info_type) => {
                 match res {
@@ -104,15 +102,48 @@ impl App {
                 );
                 Task::none()
             }
-            Message::SaveWorkout(Ok(id), workout_create) => {
+            Message::SaveWorkout(Ok(saved_workout), workout_create) => {
                 self.exercise_manager.save_workout(
                     &workout_create,
-                    id,
+                    saved_workout.workout_id,
+                    saved_workout.date,
                     &mut self.user_manager.user_info,
                 );
+                self.refresh_activity_widget();
+                self.user_manager.user_info.coin_balance = saved_workout.coin_balance;
+                if let Some((preset_id, stats)) = saved_workout.trained_preset {
+                    self.workout_preset_manager
+                        .update_preset_stats(preset_id, stats);
+                }
+                self.screen = Tab::Workout;
+                Task::none()
+            }
+            Message::UpdateWorkout(Ok(()), workout_id, workout_create) => {
+                self.exercise_manager.replace_workout(
+                    workout_id,
+                    &workout_create,
+                    &mut self.user_manager.user_info,
+                );
+                self.refresh_activity_widget();
+                self.screen = Tab::Workout;
+                Task::none()
+            }
+            Message::DeleteWorkout(Ok(()), workout_id) => {
+                self.exercise_manager
+                    .delete_workout(workout_id, &mut self.user_manager.user_info);
+                self.refresh_activity_widget();
                 self.screen = Tab::Workout;
                 Task::none()
             }
+            Message::UpdateWorkout(Err(_), _, _) | Message::DeleteWorkout(Err(_), _) => {
+                self.screen = Tab::Workout;
+                self.pop_up_manager.new_pop_up(
+                    PopUpType::Minor,
+                    "Error while changing workout on server!".to_string(),
+                    "Server offline or had internal error \nTry again later".to_string(),
+                );
+                Task::none()
+            }
 
             Message::PresetCreation(preset_creation_msg) => preset_creation_msg.update(self),
             Message::Login(login_msg) => login_msg.update(self),
@@ -127,7 +158,7 @@ impl App {
                 Task::none()
             }
             Message::RequestLoginData(Err(_err)) => {
-                *self = App::default();
+                self.reset_to_login();
                 //TODO FIX MAJOR POPUP BACKGROUND
                 self.pop_up_manager.new_pop_up(
                     PopUpType::Major,
@@ -187,6 +218,14 @@ impl App {
 }
 
 pub fn client_main() -> iced::Result {
+    let client_config = match ClientConfig::load(ClientArgs::parse()) {
+        Ok(client_config) => client_config,
+        Err(err) => {
+            eprintln!("{}", err.to_error_message());
+            std::process::exit(1);
+        }
+    };
+
     let default_size = Size::new(size::FRAME_WIDTH, size::FRAME_HEIGHT);
     let settings: Settings = Settings {
         size: default_size,
@@ -208,11 +247,15 @@ pub fn client_main() -> iced::Result {
         platform_specific: Default::default(),
         exit_on_close_request: true,
     };
-    iced::application(App::default, App::update, App::view)
-        .window(settings)
-        .title("BuffBuddies")
-        .font(include_bytes!(
-            "../../../assets/Fira_Sans/FiraSans-ExtraBold.ttf"
-        ))
-        .run()
+    iced::application(
+        move || App::new(client_config.clone()),
+        App::update,
+        App::view,
+    )
+    .window(settings)
+    .title("BuffBuddies")
+    .font(include_bytes!(
+        "../../../assets/Fira_Sans/FiraSans-ExtraBold.ttf"
+    ))
+    .run()
 }
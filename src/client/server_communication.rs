@@ -1,6 +1,9 @@
+pub mod api_client;
+pub mod challenge_communicator;
 pub mod exercise_communicator;
 pub mod mascot_communicator;
 pub mod preset_communicator;
 pub mod request_data;
 pub mod server_communicator;
 pub mod user_communicator;
+pub mod workout_reaction_communicator;
@@ -0,0 +1,212 @@
+use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::login::{AuthTokens, RefreshTokenRequest};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::sync::{Arc, Mutex};
+
+pub const DEFAULT_SERVER_URL: &str = "http://127.0.0.1:3000";
+
+/// Connection to the BuffBuddies server which every communicator goes through.
+/// Cloning is cheap, as all clones share the same connection pool and login session.
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    /// Base url of the server without a trailing slash, e.g. http://127.0.0.1:3000
+    base_url: String,
+    /// Tokens of the logged-in user. Every connection to the server after the login
+    /// has to contain the access token in its header to qualify as a valid request
+    auth_tokens: Arc<Mutex<Option<AuthTokens>>>,
+    /// Makes sure only one request at a time refreshes the tokens,
+    /// as every refresh token can only be used once
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+    http_client: Client,
+}
+
+impl Default for ApiClient {
+    fn default() -> Self {
+        ApiClient::new(DEFAULT_SERVER_URL.to_string())
+    }
+}
+
+impl ApiClient {
+    pub fn new(base_url: String) -> Self {
+        ApiClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth_tokens: Arc::new(Mutex::new(None)),
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+            http_client: Client::new(),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Only the address changes, the connection pool is kept
+    pub fn set_base_url(&mut self, base_url: String) {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+    }
+
+    pub fn auth_tokens(&self) -> Option<AuthTokens> {
+        self.auth_tokens
+            .lock()
+            .expect("auth token lock poisoned")
+            .clone()
+    }
+
+    /// Changes the session of this client and all of its clones
+    pub fn set_auth_tokens(&self, auth_tokens: Option<AuthTokens>) {
+        *self.auth_tokens.lock().expect("auth token lock poisoned") = auth_tokens;
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.auth_tokens().is_some()
+    }
+
+    /// `path` has to start with a slash, e.g. "/user/info/get"
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Request without authorization header, only needed before the login
+    pub fn post_unauthenticated(&self, path: &str) -> RequestBuilder {
+        self.http_client.post(self.url(path))
+    }
+
+    /// Has to be sent with ApiClient::send to be authorized
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.http_client.get(self.url(path))
+    }
+
+    /// Has to be sent with ApiClient::send to be authorized
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.http_client.post(self.url(path))
+    }
+
+    /// Has to be sent with ApiClient::send to be authorized
+    pub fn put(&self, path: &str) -> RequestBuilder {
+        self.http_client.put(self.url(path))
+    }
+
+    /// Has to be sent with ApiClient::send to be authorized
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.http_client.delete(self.url(path))
+    }
+
+    /// Sends the request with the access token of the logged-in user. <br>
+    /// If the server rejects the access token, e.g. because it expired,
+    /// the tokens are refreshed once and the request is repeated
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, ServerRequestError> {
+        let access_token = self
+            .auth_tokens()
+            .ok_or(ServerRequestError::NoJWTValidation)?
+            .access_token;
+        let retry_request = request.try_clone();
+
+        let response = send_with_access_token(request, &access_token).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let Some(retry_request) = retry_request else {
+            return Ok(response);
+        };
+
+        let new_access_token = self.refresh_auth_tokens(&access_token).await?;
+        send_with_access_token(retry_request, &new_access_token).await
+    }
+
+    /// Returns the new access token
+    async fn refresh_auth_tokens(
+        &self,
+        rejected_access_token: &str,
+    ) -> Result<String, ServerRequestError> {
+        let _refresh_guard = self.refresh_lock.lock().await;
+
+        let auth_tokens = self
+            .auth_tokens()
+            .ok_or(ServerRequestError::NoJWTValidation)?;
+        // Another request already refreshed the tokens while this one was waiting
+        if auth_tokens.access_token != rejected_access_token {
+            return Ok(auth_tokens.access_token);
+        }
+
+        let response = self
+            .post_unauthenticated("/user/token/refresh")
+            .json(&RefreshTokenRequest {
+                refresh_token: auth_tokens.refresh_token,
+            })
+            .send()
+            .await
+            .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+        let response = response
+            .error_for_status()
+            .map_err(|_| ServerRequestError::NoJWTValidation)?;
+        let new_auth_tokens = response
+            .json::<AuthTokens>()
+            .await
+            .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+
+        let new_access_token = new_auth_tokens.access_token.clone();
+        self.set_auth_tokens(Some(new_auth_tokens));
+        Ok(new_access_token)
+    }
+}
+
+async fn send_with_access_token(
+    request: RequestBuilder,
+    access_token: &str,
+) -> Result<Response, ServerRequestError> {
+    request
+        .header("Authorization", format!("Token {access_token}"))
+        .send()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_auth_tokens() -> AuthTokens {
+        AuthTokens {
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+        }
+    }
+
+    #[test]
+    fn trailing_slash_is_removed() {
+        let api_client = ApiClient::new("http://10.0.0.2:3000/".to_string());
+        assert_eq!(api_client.base_url(), "http://10.0.0.2:3000");
+        assert_eq!(
+            api_client.url("/user/info/get"),
+            "http://10.0.0.2:3000/user/info/get"
+        );
+    }
+    #[test]
+    fn change_base_url() {
+        let mut api_client = ApiClient::default();
+        api_client.set_base_url("https://buffbuddies.example/".to_string());
+        assert_eq!(
+            api_client.url("/server"),
+            "https://buffbuddies.example/server"
+        );
+    }
+    #[tokio::test]
+    async fn authorized_request_without_login_fails() {
+        let api_client = ApiClient::default();
+        assert!(!api_client.is_logged_in());
+        assert!(matches!(
+            api_client.send(api_client.get("/user/info/get")).await,
+            Err(ServerRequestError::NoJWTValidation)
+        ));
+    }
+    #[test]
+    fn clones_share_the_session() {
+        let api_client = ApiClient::default();
+        let api_client_clone = api_client.clone();
+        api_client.set_auth_tokens(Some(test_auth_tokens()));
+        assert_eq!(api_client_clone.auth_tokens(), Some(test_auth_tokens()));
+
+        api_client_clone.set_auth_tokens(None);
+        assert!(!api_client.is_logged_in());
+    }
+}
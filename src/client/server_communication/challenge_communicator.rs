@@ -0,0 +1,57 @@
+use crate::client::server_communication::api_client::ApiClient;
+use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::challenge::{ChallengeAnswer, ChallengeOverview, NewChallenge};
+
+/// Challenges of the logged-in user, the server resolves the ones which ended first
+pub async fn get_challenges_from_server(
+    api_client: ApiClient,
+) -> Result<ChallengeOverview, ServerRequestError> {
+    let response = api_client
+        .send(api_client.get("/social/challenges"))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<ChallengeOverview>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+pub async fn send_challenge_to_server(
+    api_client: ApiClient,
+    new_challenge: NewChallenge,
+) -> Result<ChallengeOverview, ServerRequestError> {
+    let response = api_client
+        .send(api_client.post("/social/challenges").json(&new_challenge))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<ChallengeOverview>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+pub async fn answer_challenge_on_server(
+    api_client: ApiClient,
+    answer: ChallengeAnswer,
+) -> Result<ChallengeOverview, ServerRequestError> {
+    let response = api_client
+        .send(api_client.post("/social/challenges/answer").json(&answer))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<ChallengeOverview>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
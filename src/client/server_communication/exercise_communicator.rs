@@ -1,41 +1,145 @@
+use crate::client::backend::exercise_catalog_cache::CachedExerciseCatalog;
 use crate::client::backend::exercise_create::{ExerciseCreate, StrengthSetCreate, WorkoutCreate};
+use crate::client::server_communication::api_client::ApiClient;
 use crate::client::server_communication::server_communicator::ServerRequestError;
-use crate::common::exercise_mod::exercise::Exercise;
-use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::exercise_mod::custom_exercise::{CustomExercise, NewCustomExercise};
+use crate::common::exercise_mod::exercise::ExerciseSets;
+use crate::common::exercise_mod::general_exercise::{GeneralExerciseInfo, Id};
 use crate::common::exercise_mod::set::Reps;
 use crate::common::exercise_mod::weight::Kg;
+use crate::common::workout::{PresetTraining, SavedWorkout};
+use chrono::{Local, NaiveDate, NaiveTime};
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
-pub async fn get_exercise_data_from_server(
-    jwt: String,
-) -> Result<Vec<Exercise>, ServerRequestError> {
-    let response = reqwest::Client::new()
-        .get("http://127.0.0.1:3000/user/exercises")
-        .header("Authorization", format!("Token {jwt}"))
-        .send()
+/// Sends the ETag of the cached catalog, so the server only sends the catalog if it changed.
+/// A newly downloaded catalog replaces the cache
+pub async fn get_exercise_catalog_from_server(
+    api_client: ApiClient,
+    cache_path: PathBuf,
+) -> Result<Vec<GeneralExerciseInfo>, ServerRequestError> {
+    let cached_catalog = CachedExerciseCatalog::load(&cache_path).await;
+
+    let mut request = api_client.get("/exercises/catalog");
+    if let Some(cached_catalog) = &cached_catalog {
+        request = request.header(IF_NONE_MATCH, &cached_catalog.etag);
+    }
+    let response = api_client.send(request).await?;
+
+    if response.status() == StatusCode::NOT_MODIFIED
+        && let Some(cached_catalog) = cached_catalog
+    {
+        return Ok(cached_catalog.exercises);
+    }
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+
+    let exercises = response
+        .json::<Vec<GeneralExerciseInfo>>()
         .await
         .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
 
+    let Some(etag) = etag else {
+        return Ok(exercises);
+    };
+    let new_cached_catalog = CachedExerciseCatalog { etag, exercises };
+    // A failed write only means the catalog is downloaded again on the next login
+    if let Err(err) = new_cached_catalog.save(&cache_path).await {
+        eprintln!(
+            "Could not cache the exercise catalog in {}: {err}",
+            cache_path.display()
+        );
+    }
+    Ok(new_cached_catalog.exercises)
+}
+
+pub async fn get_exercise_sets_from_server(
+    api_client: ApiClient,
+) -> Result<Vec<ExerciseSets>, ServerRequestError> {
+    let response = api_client
+        .send(api_client.get("/user/exercises/sets"))
+        .await?;
+
     let response = response
         .error_for_status()
         .map_err(|_| ServerRequestError::HTTPError)?;
 
     let data = response
-        .json::<Vec<Exercise>>()
+        .json::<Vec<ExerciseSets>>()
         .await
         .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
 
     Ok(data)
 }
 
+/// Custom exercises of the user and the ones friends share with them
+pub async fn get_custom_exercises_from_server(
+    api_client: ApiClient,
+) -> Result<Vec<CustomExercise>, ServerRequestError> {
+    let response = api_client
+        .send(api_client.get("/user/exercises/custom"))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<Vec<CustomExercise>>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+/// Returns the created exercise with the id the server gave it
+pub async fn create_custom_exercise_on_server(
+    api_client: ApiClient,
+    new_custom_exercise: NewCustomExercise,
+) -> Result<CustomExercise, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/exercises/custom")
+                .json(&new_custom_exercise),
+        )
+        .await
+        .map_err(|_| ServerRequestError::CouldNotSendData)?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<CustomExercise>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct WorkoutJson {
     workout: Vec<ExerciseJson>,
-    first_workout: bool,
+    date: Option<NaiveDate>,
+    start_time: Option<NaiveTime>,
+    preset_training: Option<PresetTraining>,
+    /// Lets the server check the date and start time against the clock of the client
+    utc_offset_minutes: i32,
 }
 
 impl WorkoutJson {
-    pub fn new(workout: Vec<ExerciseCreate>, first_workout: bool) -> Self {
+    pub fn new(
+        workout: Vec<ExerciseCreate>,
+        date: Option<NaiveDate>,
+        start_time: Option<NaiveTime>,
+        preset_training: Option<PresetTraining>,
+    ) -> Self {
         WorkoutJson {
             workout: {
                 let mut workout_json = Vec::new();
@@ -44,7 +148,10 @@ impl WorkoutJson {
                 }
                 workout_json
             },
-            first_workout,
+            date,
+            start_time,
+            preset_training,
+            utc_offset_minutes: Local::now().offset().local_minus_utc() / 60,
         }
     }
 }
@@ -86,27 +193,63 @@ impl From<StrengthSetCreate> for SetJson {
 }
 
 pub async fn save_workout(
-    jwt: String,
+    api_client: ApiClient,
     workout: WorkoutCreate,
-    first_workout: bool,
-) -> Result<Id, ServerRequestError> {
-    let workout_json: WorkoutJson = WorkoutJson::new(workout, first_workout);
-    let response = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/workout/save")
-        .json(&workout_json)
-        .header("Authorization", format!("Token {jwt}"))
-        .send()
-        .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    date: NaiveDate,
+    start_time: Option<NaiveTime>,
+    preset_training: Option<PresetTraining>,
+) -> Result<SavedWorkout, ServerRequestError> {
+    let workout_json: WorkoutJson =
+        WorkoutJson::new(workout, Some(date), start_time, preset_training);
+    let response = api_client
+        .send(api_client.post("/workout/save").json(&workout_json))
+        .await?;
 
     let response = response
         .error_for_status()
         .map_err(|_| ServerRequestError::HTTPError)?;
 
     let data = response
-        .json::<Id>()
+        .json::<SavedWorkout>()
         .await
         .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
 
     Ok(data)
 }
+
+/// Replaces the sets of an already saved workout
+pub async fn update_workout(
+    api_client: ApiClient,
+    workout_id: Id,
+    workout: WorkoutCreate,
+) -> Result<(), ServerRequestError> {
+    let workout_json: WorkoutJson = WorkoutJson::new(workout, None, None, None);
+    let response = api_client
+        .send(
+            api_client
+                .put(&format!("/workout/{workout_id}"))
+                .json(&workout_json),
+        )
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn delete_workout(
+    api_client: ApiClient,
+    workout_id: Id,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(api_client.delete(&format!("/workout/{workout_id}")))
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
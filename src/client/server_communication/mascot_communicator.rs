@@ -1,16 +1,14 @@
+use crate::client::server_communication::api_client::ApiClient;
 use crate::client::server_communication::server_communicator::ServerRequestError;
-use crate::common::mascot_mod::mascot::Mascot;
-use crate::common::mascot_mod::mascot_data_transfer::MascotDataServerClientTransfer;
+use crate::common::mascot_mod::mascot::{Mascot, MascotRarity};
+use crate::common::mascot_mod::mascot_data_transfer::{
+    MascotDataServerClientTransfer, MascotRollAnswer,
+};
 
 pub async fn get_mascot_data_from_server(
-    jwt: String,
+    api_client: ApiClient,
 ) -> Result<MascotDataServerClientTransfer, ServerRequestError> {
-    let response = reqwest::Client::new()
-        .get("http://127.0.0.1:3000/mascot/get")
-        .header("Authorization", format!("Token {jwt}"))
-        .send()
-        .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    let response = api_client.send(api_client.get("/mascot/get")).await?;
 
     let response = response
         .error_for_status()
@@ -24,16 +22,12 @@ pub async fn get_mascot_data_from_server(
     Ok(data)
 }
 pub async fn update_selected_mascot_on_server(
-    jwt: String,
+    api_client: ApiClient,
     new_mascot: Mascot,
 ) -> Result<(), ServerRequestError> {
-    let response = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/mascot/select")
-        .header("Authorization", format!("Token {jwt}"))
-        .json(&new_mascot)
-        .send()
-        .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    let response = api_client
+        .send(api_client.post("/mascot/select").json(&new_mascot))
+        .await?;
 
     //println!("{}", response.status());
 
@@ -44,15 +38,23 @@ pub async fn update_selected_mascot_on_server(
     Ok(())
 }
 
-pub async fn buy_mascot(jwt: String, mascot: Mascot) -> Result<Mascot, ServerRequestError> {
-    let res = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/mascot/buy")
-        .header("Authorization", format!("Token {jwt}"))
-        .json(&mascot)
-        .send()
-        .await;
-    match res {
-        Ok(_) => Ok(mascot),
-        Err(_server_error) => Err(ServerRequestError::CouldNotSendData),
-    }
+/// The server picks the mascot, see MascotRollAnswer
+pub async fn roll_mascot(
+    api_client: ApiClient,
+    rarity: MascotRarity,
+) -> Result<MascotRollAnswer, ServerRequestError> {
+    let response = api_client
+        .send(api_client.post("/mascot/roll").json(&rarity))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    let data = response
+        .json::<MascotRollAnswer>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+
+    Ok(data)
 }
@@ -1,15 +1,12 @@
+use crate::client::server_communication::api_client::ApiClient;
 use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::workout_preset::WorkoutPreset;
 
 pub async fn get_preset_data_from_server(
-    jwt: String,
+    api_client: ApiClient,
 ) -> Result<Vec<WorkoutPreset>, ServerRequestError> {
-    let response = reqwest::Client::new()
-        .get("http://127.0.0.1:3000/user/presets")
-        .header("Authorization", format!("Token {jwt}"))
-        .send()
-        .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    let response = api_client.send(api_client.get("/user/presets")).await?;
 
     let response = response
         .error_for_status()
@@ -24,15 +21,70 @@ pub async fn get_preset_data_from_server(
     Ok(data)
 }
 
-pub async fn save_preset(jwt: String, preset: WorkoutPreset) -> Result<(), ServerRequestError> {
-    let res = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/preset/save")
-        .json(&preset)
-        .header("Authorization", format!("Token {jwt}"))
-        .send()
-        .await;
-    match res {
-        Ok(_) => Ok(()),
-        Err(_server_error) => Err(ServerRequestError::CouldNotSendData),
-    }
+/// Returns the id the server gave the new preset
+pub async fn save_preset(
+    api_client: ApiClient,
+    preset: WorkoutPreset,
+) -> Result<Id, ServerRequestError> {
+    let response = api_client
+        .send(api_client.post("/preset/save").json(&preset))
+        .await
+        .map_err(|_| ServerRequestError::CouldNotSendData)?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<Id>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+pub async fn update_preset(
+    api_client: ApiClient,
+    preset_id: Id,
+    preset: WorkoutPreset,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .put(&format!("/preset/{preset_id}"))
+                .json(&preset),
+        )
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn delete_preset(api_client: ApiClient, preset_id: Id) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(api_client.delete(&format!("/preset/{preset_id}")))
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+/// Saves the order in which the presets are shown
+pub async fn save_preset_order(
+    api_client: ApiClient,
+    preset_ids: Vec<Id>,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(api_client.put("/user/presets/order").json(&preset_ids))
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
 }
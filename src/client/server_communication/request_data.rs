@@ -1,11 +1,18 @@
+use crate::client::server_communication::api_client::ApiClient;
 use crate::client::server_communication::server_communicator::ServerRequestError;
 use crate::client::server_communication::{
-    exercise_communicator, mascot_communicator, preset_communicator, user_communicator,
+    challenge_communicator, exercise_communicator, mascot_communicator, preset_communicator,
+    user_communicator, workout_reaction_communicator,
 };
-use crate::common::exercise_mod::exercise::Exercise;
+use crate::common::challenge::Challenge;
+use crate::common::exercise_mod::exercise::{Exercise, combine_catalog_with_sets};
+use crate::common::leaderboard::{LeaderboardEntry, LeaderboardQuery};
 use crate::common::mascot_mod::mascot_data_transfer::MascotDataServerClientTransfer;
+use crate::common::social_feed::FeedPage;
 use crate::common::user_mod::user::{ForeignUser, UserInformation};
 use crate::common::workout_preset::WorkoutPreset;
+use crate::common::workout_reaction::WorkoutReactions;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 #[derive(Debug)]
@@ -15,28 +22,69 @@ pub struct LoginServerRequestData {
     pub presets: Vec<WorkoutPreset>,
     pub mascot_data: MascotDataServerClientTransfer,
     pub foreign_users: Vec<ForeignUser>,
+    pub feed: FeedPage,
+    pub own_workout_reactions: Vec<WorkoutReactions>,
+    /// Ranked by the default query
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub challenges: Vec<Challenge>,
+    pub blocked_users: Vec<String>,
 }
+/// Requires a logged-in api_client, else NoJWTValidation is returned
+/// The exercises are the catalog and the custom exercises of the user.
+/// The exercise catalog is only downloaded if the copy at `exercise_catalog_cache_path` is outdated
 pub async fn request_login_data(
-    jwt: Option<String>,
+    api_client: ApiClient,
+    exercise_catalog_cache_path: PathBuf,
 ) -> Result<Arc<LoginServerRequestData>, ServerRequestError> {
-    if let Some(jwt_string) = jwt {
-        let exercises =
-            exercise_communicator::get_exercise_data_from_server(jwt_string.clone()).await?;
-        let presets = preset_communicator::get_preset_data_from_server(jwt_string.clone()).await?;
-        let user_information =
-            user_communicator::get_user_information_from_server(jwt_string.clone()).await?;
-        let mascot_data =
-            mascot_communicator::get_mascot_data_from_server(jwt_string.clone()).await?;
-        let foreign_users = user_communicator::get_foreign_users_from_server(jwt_string).await?;
-
-        Ok(Arc::new(LoginServerRequestData {
-            exercises,
-            presets,
-            user_information,
-            mascot_data,
-            foreign_users,
-        }))
-    } else {
-        Err(ServerRequestError::NoJWTValidation)
+    if !api_client.is_logged_in() {
+        return Err(ServerRequestError::NoJWTValidation);
     }
+    let mut general_exercise_infos = exercise_communicator::get_exercise_catalog_from_server(
+        api_client.clone(),
+        exercise_catalog_cache_path,
+    )
+    .await?;
+    let custom_exercises =
+        exercise_communicator::get_custom_exercises_from_server(api_client.clone()).await?;
+    general_exercise_infos.extend(
+        custom_exercises
+            .into_iter()
+            .map(|custom_exercise| custom_exercise.general_exercise_info),
+    );
+    let exercise_sets =
+        exercise_communicator::get_exercise_sets_from_server(api_client.clone()).await?;
+    let exercises = combine_catalog_with_sets(general_exercise_infos, exercise_sets);
+    let presets = preset_communicator::get_preset_data_from_server(api_client.clone()).await?;
+    // Loaded before the user information, as resolving ended challenges can pay out coins
+    let challenges = challenge_communicator::get_challenges_from_server(api_client.clone())
+        .await?
+        .challenges;
+    let user_information =
+        user_communicator::get_user_information_from_server(api_client.clone()).await?;
+    let mascot_data = mascot_communicator::get_mascot_data_from_server(api_client.clone()).await?;
+    let foreign_users =
+        user_communicator::get_foreign_users_from_server(api_client.clone()).await?;
+    let feed = user_communicator::get_friends_feed_from_server(api_client.clone(), None).await?;
+    let own_workout_reactions =
+        workout_reaction_communicator::get_own_workout_reactions_from_server(api_client.clone())
+            .await?;
+    let leaderboard = user_communicator::get_leaderboard_from_server(
+        api_client.clone(),
+        LeaderboardQuery::default(),
+    )
+    .await?;
+    let blocked_users = user_communicator::get_blocked_users_from_server(api_client).await?;
+
+    Ok(Arc::new(LoginServerRequestData {
+        exercises,
+        presets,
+        user_information,
+        mascot_data,
+        foreign_users,
+        feed,
+        own_workout_reactions,
+        leaderboard,
+        challenges,
+        blocked_users,
+    }))
 }
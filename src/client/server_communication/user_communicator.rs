@@ -1,10 +1,22 @@
+use crate::client::server_communication::api_client::ApiClient;
 use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::leaderboard::{LeaderboardEntry, LeaderboardQuery};
 use crate::common::login::{
-    RequestValidRegisterAnswer, RequestValidRegisterError, RequestValidUserAnswer,
-    RequestValidUserError,
+    AuthTokens, RefreshTokenRequest, RequestValidRegisterAnswer, RequestValidRegisterError,
+    RequestValidUserAnswer, RequestValidUserError,
+};
+use crate::common::social_feed::FeedPage;
+use crate::common::user_mod::account::{
+    AccountChangeAnswer, AccountDeletion, AccountDeletionAnswer, PasswordChange, UsernameChange,
+};
+use crate::common::user_mod::friend_request::{
+    FriendRequest, FriendRequestAnswer, FriendshipStatus,
 };
-use crate::common::user_mod::friend_request::FriendRequest;
 use crate::common::user_mod::user::{ForeignUser, UserInformation};
+use crate::common::user_mod::user_goals::GoalType;
+use crate::common::user_mod::user_log::UserLogEntry;
+use crate::common::user_mod::user_report::UserReport;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
@@ -19,10 +31,13 @@ impl From<(String, String)> for LoginRequest {
     }
 }
 /// Checks if the login data exists on serverside
-/// Returns jwt if login was successful else RequestValidUserError
-pub async fn valid_login(login_request: LoginRequest) -> Result<String, RequestValidUserError> {
-    let res = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/user/login")
+/// Returns the session tokens if login was successful else RequestValidUserError
+pub async fn valid_login(
+    api_client: ApiClient,
+    login_request: LoginRequest,
+) -> Result<AuthTokens, RequestValidUserError> {
+    let res = api_client
+        .post_unauthenticated("/user/login")
         .json(&login_request)
         .send()
         .await
@@ -35,17 +50,18 @@ pub async fn valid_login(login_request: LoginRequest) -> Result<String, RequestV
         Ok(answer) => match answer {
             RequestValidUserAnswer::UserNotFound => Err(RequestValidUserError::UserNotFound),
             RequestValidUserAnswer::WrongPassword => Err(RequestValidUserError::WrongPassword),
-            RequestValidUserAnswer::Valid(jwt) => Ok(jwt),
+            RequestValidUserAnswer::Valid(auth_tokens) => Ok(auth_tokens),
         },
         Err(_e) => Err(RequestValidUserError::ServerError),
     }
 }
 
 pub async fn valid_register(
+    api_client: ApiClient,
     login_request: LoginRequest,
-) -> Result<String, RequestValidRegisterError> {
-    let res = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/user/register")
+) -> Result<AuthTokens, RequestValidRegisterError> {
+    let res = api_client
+        .post_unauthenticated("/user/register")
         .json(&login_request)
         .send()
         .await
@@ -59,21 +75,114 @@ pub async fn valid_register(
             RequestValidRegisterAnswer::UserAlreadyExists => {
                 Err(RequestValidRegisterError::UserAlreadyExists)
             }
-            RequestValidRegisterAnswer::Valid(jwt) => Ok(jwt),
+            RequestValidRegisterAnswer::Valid(auth_tokens) => Ok(auth_tokens),
         },
         Err(_e) => Err(RequestValidRegisterError::ServerError),
     }
 }
 
-pub async fn get_user_information_from_server(
-    jwt: String,
-) -> Result<UserInformation, ServerRequestError> {
-    let response = reqwest::Client::new()
-        .get("http://127.0.0.1:3000/user/info/get")
-        .header("Authorization", format!("Token {jwt}"))
+/// Revokes the session on the server and forgets the tokens of the client
+pub async fn logout_on_server(api_client: ApiClient) -> Result<(), ServerRequestError> {
+    let auth_tokens = api_client
+        .auth_tokens()
+        .ok_or(ServerRequestError::NoJWTValidation)?;
+    api_client.set_auth_tokens(None);
+
+    let response = api_client
+        .post_unauthenticated("/user/logout")
+        .json(&RefreshTokenRequest {
+            refresh_token: auth_tokens.refresh_token,
+        })
         .send()
         .await
+        .map_err(|_| ServerRequestError::CouldNotSendData)?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+/// The server logs out every session, the client continues with the new one it got
+pub async fn change_password_on_server(
+    api_client: ApiClient,
+    password_change: PasswordChange,
+) -> Result<AccountChangeAnswer, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/account/password")
+                .json(&password_change),
+        )
+        .await?;
+    receive_account_change_answer(api_client, response).await
+}
+
+/// The server logs out every session, the client continues with the new one it got
+pub async fn change_username_on_server(
+    api_client: ApiClient,
+    username_change: UsernameChange,
+) -> Result<AccountChangeAnswer, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/account/username")
+                .json(&username_change),
+        )
+        .await?;
+    receive_account_change_answer(api_client, response).await
+}
+
+async fn receive_account_change_answer(
+    api_client: ApiClient,
+    response: reqwest::Response,
+) -> Result<AccountChangeAnswer, ServerRequestError> {
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    let answer = response
+        .json::<AccountChangeAnswer>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    if let AccountChangeAnswer::Valid(auth_tokens) = &answer {
+        api_client.set_auth_tokens(Some(auth_tokens.clone()));
+    }
+    Ok(answer)
+}
+
+/// Forgets the tokens of the client once the account is deleted
+pub async fn delete_account_on_server(
+    api_client: ApiClient,
+    account_deletion: AccountDeletion,
+) -> Result<AccountDeletionAnswer, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/account/delete")
+                .json(&account_deletion),
+        )
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    let answer = response
+        .json::<AccountDeletionAnswer>()
+        .await
         .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    if answer == AccountDeletionAnswer::Deleted {
+        api_client.set_auth_tokens(None);
+    }
+    Ok(answer)
+}
+
+pub async fn get_user_information_from_server(
+    api_client: ApiClient,
+) -> Result<UserInformation, ServerRequestError> {
+    let response = api_client.send(api_client.get("/user/info/get")).await?;
 
     let response = response
         .error_for_status()
@@ -90,16 +199,55 @@ pub async fn get_user_information_from_server(
 }
 
 pub async fn update_user_info_on_server(
-    jwt: String,
+    api_client: ApiClient,
     new_user_info: UserInformation,
 ) -> Result<(), ServerRequestError> {
-    let response = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/user/info/update")
-        .header("Authorization", format!("Token {jwt}"))
-        .json(&new_user_info)
-        .send()
-        .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?; //TODO create variant could not send data
+    let response = api_client
+        .send(api_client.post("/user/info/update").json(&new_user_info))
+        .await?; //TODO create variant could not send data
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+/// Saves the value of a weight, water, steps or sleep log, overwriting the entry of that date
+pub async fn save_user_log_on_server(
+    api_client: ApiClient,
+    goal_type: GoalType,
+    entry: UserLogEntry,
+) -> Result<(), ServerRequestError> {
+    let log_path = goal_type
+        .log_path()
+        .ok_or(ServerRequestError::CouldNotSendData)?;
+    let response = api_client
+        .send(
+            api_client
+                .put(&format!("/user/logs/{log_path}"))
+                .json(&entry),
+        )
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn delete_user_log_on_server(
+    api_client: ApiClient,
+    goal_type: GoalType,
+    date: NaiveDate,
+) -> Result<(), ServerRequestError> {
+    let log_path = goal_type
+        .log_path()
+        .ok_or(ServerRequestError::CouldNotSendData)?;
+    let response = api_client
+        .send(api_client.delete(&format!("/user/logs/{log_path}/{date}")))
+        .await?;
 
     response
         .error_for_status()
@@ -109,14 +257,9 @@ pub async fn update_user_info_on_server(
 }
 
 pub async fn get_foreign_users_from_server(
-    jwt: String,
+    api_client: ApiClient,
 ) -> Result<Vec<ForeignUser>, ServerRequestError> {
-    let response = reqwest::Client::new()
-        .get("http://127.0.0.1:3000/user/foreign/get")
-        .header("Authorization", format!("Token {jwt}"))
-        .send()
-        .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+    let response = api_client.send(api_client.get("/user/foreign/get")).await?;
 
     let response = response
         .error_for_status()
@@ -131,17 +274,40 @@ pub async fn get_foreign_users_from_server(
 
     Ok(data)
 }
-pub async fn add_foreign_user_as_friend_on_server(
-    jwt: String,
+/// Returns the relationship the server stored, Friends if the other user already sent a request
+pub async fn send_friend_request_on_server(
+    api_client: ApiClient,
     friend_request: FriendRequest,
-) -> Result<(), ServerRequestError> {
-    let response = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/user/foreign/add_friend")
-        .header("Authorization", format!("Token {jwt}"))
-        .json(&friend_request)
-        .send()
+) -> Result<FriendshipStatus, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/foreign/send_friend_request")
+                .json(&friend_request),
+        )
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<FriendshipStatus>()
         .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+pub async fn answer_friend_request_on_server(
+    api_client: ApiClient,
+    friend_request_answer: FriendRequestAnswer,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/foreign/answer_friend_request")
+                .json(&friend_request_answer),
+        )
+        .await?;
 
     response
         .error_for_status()
@@ -151,16 +317,82 @@ pub async fn add_foreign_user_as_friend_on_server(
 }
 
 pub async fn remove_foreign_user_as_friend_on_server(
-    jwt: String,
+    api_client: ApiClient,
     friend_request: FriendRequest,
 ) -> Result<(), ServerRequestError> {
-    let response = reqwest::Client::new()
-        .post("http://127.0.0.1:3000/user/foreign/remove_friend")
-        .header("Authorization", format!("Token {jwt}"))
-        .json(&friend_request)
-        .send()
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/foreign/remove_friend")
+                .json(&friend_request),
+        )
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn block_user_on_server(
+    api_client: ApiClient,
+    friend_request: FriendRequest,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(api_client.post("/user/foreign/block").json(&friend_request))
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn unblock_user_on_server(
+    api_client: ApiClient,
+    friend_request: FriendRequest,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post("/user/foreign/unblock")
+                .json(&friend_request),
+        )
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn get_blocked_users_from_server(
+    api_client: ApiClient,
+) -> Result<Vec<String>, ServerRequestError> {
+    let response = api_client
+        .send(api_client.get("/user/foreign/blocked"))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<Vec<String>>()
         .await
-        .map_err(|_| ServerRequestError::CouldNotRetrieveData)?;
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+pub async fn report_user_on_server(
+    api_client: ApiClient,
+    report: UserReport,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(api_client.post("/user/foreign/report").json(&report))
+        .await?;
 
     response
         .error_for_status()
@@ -168,3 +400,47 @@ pub async fn remove_foreign_user_as_friend_on_server(
 
     Ok(())
 }
+
+/// Loads the newest page of the friends feed, or the page of events older than `before`
+pub async fn get_friends_feed_from_server(
+    api_client: ApiClient,
+    before: Option<i64>,
+) -> Result<FeedPage, ServerRequestError> {
+    let mut request = api_client.get("/social/feed");
+    if let Some(before) = before {
+        request = request.query(&[("before", before)]);
+    }
+    let response = api_client.send(request).await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<FeedPage>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+/// The logged-in user and their friends ranked as the query describes
+pub async fn get_leaderboard_from_server(
+    api_client: ApiClient,
+    leaderboard_query: LeaderboardQuery,
+) -> Result<Vec<LeaderboardEntry>, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .get("/social/leaderboard")
+                .query(&leaderboard_query),
+        )
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<Vec<LeaderboardEntry>>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
@@ -0,0 +1,103 @@
+use crate::client::server_communication::api_client::ApiClient;
+use crate::client::server_communication::server_communicator::ServerRequestError;
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::workout_reaction::{
+    NewWorkoutComment, WorkoutComment, WorkoutReactions, WorkoutSummary,
+};
+
+/// Reactions on the workouts of the logged-in user, workouts without any are left out
+pub async fn get_own_workout_reactions_from_server(
+    api_client: ApiClient,
+) -> Result<Vec<WorkoutReactions>, ServerRequestError> {
+    let response = api_client
+        .send(api_client.get("/user/workouts/reactions"))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<Vec<WorkoutReactions>>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+/// Latest workouts of a friend or the logged-in user with their reactions
+pub async fn get_profile_workouts_from_server(
+    api_client: ApiClient,
+    username: String,
+) -> Result<Vec<WorkoutSummary>, ServerRequestError> {
+    let response = api_client
+        .send(api_client.get(&format!("/social/workouts/{username}")))
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<Vec<WorkoutSummary>>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+/// Gives kudos to the workout or takes them back
+pub async fn set_kudos_on_server(
+    api_client: ApiClient,
+    workout_id: Id,
+    give_kudos: bool,
+) -> Result<(), ServerRequestError> {
+    let path = format!("/workout/{workout_id}/kudos");
+    let request = if give_kudos {
+        api_client.post(&path)
+    } else {
+        api_client.delete(&path)
+    };
+    let response = api_client.send(request).await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
+
+pub async fn add_comment_on_server(
+    api_client: ApiClient,
+    workout_id: Id,
+    new_comment: NewWorkoutComment,
+) -> Result<WorkoutComment, ServerRequestError> {
+    let response = api_client
+        .send(
+            api_client
+                .post(&format!("/workout/{workout_id}/comments"))
+                .json(&new_comment),
+        )
+        .await?;
+
+    let response = response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    response
+        .json::<WorkoutComment>()
+        .await
+        .map_err(|_| ServerRequestError::CouldNotRetrieveData)
+}
+
+pub async fn delete_comment_on_server(
+    api_client: ApiClient,
+    workout_id: Id,
+    comment_id: i64,
+) -> Result<(), ServerRequestError> {
+    let response = api_client
+        .send(api_client.delete(&format!("/workout/{workout_id}/comments/{comment_id}")))
+        .await?;
+
+    response
+        .error_for_status()
+        .map_err(|_| ServerRequestError::HTTPError)?;
+
+    Ok(())
+}
@@ -1,6 +1,11 @@
+pub mod challenge;
 pub mod exercise_mod;
+pub mod leaderboard;
 pub mod login;
 pub mod mascot_mod;
 pub mod profile_picture;
+pub mod social_feed;
 pub mod user_mod;
+pub mod workout;
 pub mod workout_preset;
+pub mod workout_reaction;
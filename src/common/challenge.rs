@@ -0,0 +1,232 @@
+use crate::common::exercise_mod::weight::Kg;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// Longest time span a challenge can run
+pub const MAX_CHALLENGE_DAYS: i64 = 31;
+
+/// What decides who wins a challenge, computed from the workouts both users log in its time span
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChallengeGoal {
+    MostWorkouts,
+    /// Sum of weight times reps of every set
+    MostVolume,
+    /// The first one to lift at least `weight` in a set of the exercise wins
+    FirstToLift {
+        exercise_name: String,
+        weight: Kg,
+    },
+}
+
+impl ChallengeGoal {
+    /// E.g. "First to lift 100 kg in Bench Press"
+    pub fn description(&self) -> String {
+        match self {
+            ChallengeGoal::MostWorkouts => "Most workouts".to_string(),
+            ChallengeGoal::MostVolume => "Most volume".to_string(),
+            ChallengeGoal::FirstToLift {
+                exercise_name,
+                weight,
+            } => format!("First to lift {weight} kg in {exercise_name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, Serialize, Deserialize)]
+pub enum ChallengeStatus {
+    /// Waiting for the opponent to accept
+    Pending,
+    Active,
+    Declined,
+    /// Resolved after the end date, the winner got both stakes
+    Completed,
+    /// Never accepted before the end date
+    Expired,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Challenge {
+    pub id: i64,
+    pub challenger: String,
+    pub opponent: String,
+    pub goal: ChallengeGoal,
+    pub start_date: NaiveDate,
+    /// Last day which counts towards the challenge
+    pub end_date: NaiveDate,
+    /// Coins each user puts in, held back by the server until the challenge is resolved
+    pub stake: u32,
+    pub status: ChallengeStatus,
+    /// None while the challenge runs and after a draw
+    pub winner: Option<String>,
+}
+
+impl Challenge {
+    pub fn other_user(&self, username: &str) -> &str {
+        if self.challenger.eq_ignore_ascii_case(username) {
+            &self.opponent
+        } else {
+            &self.challenger
+        }
+    }
+
+    /// E.g. "Won", "Waiting for anna" or "Ends 2026-03-08"
+    pub fn state_description(&self, username: &str) -> String {
+        match self.status {
+            ChallengeStatus::Pending if self.opponent.eq_ignore_ascii_case(username) => {
+                "Waiting for your answer".to_string()
+            }
+            ChallengeStatus::Pending => format!("Waiting for {}", self.opponent),
+            ChallengeStatus::Active => format!("Ends {}", self.end_date),
+            ChallengeStatus::Declined => "Declined".to_string(),
+            ChallengeStatus::Expired => "Expired".to_string(),
+            ChallengeStatus::Completed => match &self.winner {
+                Some(winner) if winner.eq_ignore_ascii_case(username) => "Won".to_string(),
+                Some(_) => "Lost".to_string(),
+                None => "Draw".to_string(),
+            },
+        }
+    }
+}
+
+/// Challenge the logged-in user sends to a friend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewChallenge {
+    pub opponent: String,
+    pub goal: ChallengeGoal,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub stake: u32,
+}
+
+impl NewChallenge {
+    /// Reason why the challenge can't be sent, None if it is valid
+    pub fn validation_error(&self, today: NaiveDate) -> Option<&'static str> {
+        if self.opponent.trim().is_empty() {
+            Some("Choose a friend to challenge")
+        } else if self.start_date < today {
+            // The result of a past time span is already known
+            Some("A challenge can't start in the past")
+        } else if self.end_date < self.start_date {
+            Some("A challenge can't end before it starts")
+        } else if self.end_date - self.start_date >= Duration::days(MAX_CHALLENGE_DAYS) {
+            Some("A challenge can last at most 31 days")
+        } else if let ChallengeGoal::FirstToLift {
+            exercise_name,
+            weight,
+        } = &self.goal
+        {
+            if exercise_name.trim().is_empty() {
+                Some("Choose the exercise to lift")
+            } else if !weight.is_finite() || *weight <= 0.0 {
+                Some("The weight to lift has to be positive")
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Answer of the opponent to a pending challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeAnswer {
+    pub challenge_id: i64,
+    pub accept: bool,
+}
+
+/// Challenges of the logged-in user with their coin balance, which changes whenever stakes move
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChallengeOverview {
+    /// Newest first
+    pub challenges: Vec<Challenge>,
+    pub coin_balance: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn new_challenge(goal: ChallengeGoal) -> NewChallenge {
+        NewChallenge {
+            opponent: "anna".to_string(),
+            goal,
+            start_date: date(2),
+            end_date: date(8),
+            stake: 10,
+        }
+    }
+
+    #[test]
+    fn challenge_needs_a_valid_time_span_and_goal() {
+        let today = date(2);
+        let mut challenge = new_challenge(ChallengeGoal::MostWorkouts);
+        assert_eq!(challenge.validation_error(today), None);
+
+        challenge.end_date = date(1);
+        assert!(challenge.validation_error(today).is_some());
+
+        challenge.end_date = NaiveDate::from_ymd_opt(2026, 4, 2).unwrap();
+        assert!(challenge.validation_error(today).is_some());
+
+        challenge.end_date = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        assert_eq!(challenge.validation_error(today), None);
+
+        let mut lift = new_challenge(ChallengeGoal::FirstToLift {
+            exercise_name: "Bench Press".to_string(),
+            weight: 0.0,
+        });
+        assert!(lift.validation_error(today).is_some());
+        lift.goal = ChallengeGoal::FirstToLift {
+            exercise_name: "Bench Press".to_string(),
+            weight: 100.0,
+        };
+        assert_eq!(lift.validation_error(today), None);
+    }
+
+    #[test]
+    fn challenge_can_not_start_in_the_past() {
+        let challenge = new_challenge(ChallengeGoal::FirstToLift {
+            exercise_name: "Bench Press".to_string(),
+            weight: 100.0,
+        });
+
+        assert_eq!(challenge.validation_error(date(1)), None);
+        assert_eq!(challenge.validation_error(date(2)), None);
+        assert_eq!(
+            challenge.validation_error(date(3)),
+            Some("A challenge can't start in the past")
+        );
+    }
+
+    #[test]
+    fn state_is_described_from_the_view_of_the_user() {
+        let mut challenge = Challenge {
+            id: 1,
+            challenger: "ben".to_string(),
+            opponent: "anna".to_string(),
+            goal: ChallengeGoal::MostVolume,
+            start_date: date(2),
+            end_date: date(8),
+            stake: 0,
+            status: ChallengeStatus::Pending,
+            winner: None,
+        };
+        assert_eq!(challenge.state_description("ben"), "Waiting for anna");
+        assert_eq!(
+            challenge.state_description("anna"),
+            "Waiting for your answer"
+        );
+        assert_eq!(challenge.other_user("anna"), "ben");
+
+        challenge.status = ChallengeStatus::Completed;
+        challenge.winner = Some("anna".to_string());
+        assert_eq!(challenge.state_description("anna"), "Won");
+        assert_eq!(challenge.state_description("ben"), "Lost");
+    }
+}
@@ -1,3 +1,4 @@
+pub mod custom_exercise;
 pub mod exercise;
 pub mod general_exercise;
 pub mod set;
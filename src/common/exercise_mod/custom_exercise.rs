@@ -0,0 +1,90 @@
+use crate::common::exercise_mod::general_exercise::{
+    ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, GeneralExerciseInfo, Muscle,
+};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_CUSTOM_EXERCISE_NAME_CHARACTERS: usize = 60;
+
+/// Exercise a user created because it is missing in the catalog, e.g. a machine of their gym
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomExercise {
+    pub general_exercise_info: GeneralExerciseInfo,
+    pub created_by: String,
+    /// Friends of the creator can track the exercise as well
+    pub shared_with_friends: bool,
+}
+
+/// Everything the user enters to create a custom exercise
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewCustomExercise {
+    pub name: String,
+    pub primary_muscle: Muscle,
+    pub equipment: ExerciseEquipment,
+    pub force: ExerciseForce,
+    pub level: ExerciseLevel,
+    pub category: ExerciseCategory,
+    pub shared_with_friends: bool,
+}
+
+impl NewCustomExercise {
+    /// Reason why the exercise can't be created, None if it is valid. <br>
+    /// `existing_names` are the names of every exercise the user can already track
+    pub fn validation_error<'a>(
+        &self,
+        mut existing_names: impl Iterator<Item = &'a str>,
+    ) -> Option<&'static str> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            Some("Custom exercises need a name")
+        } else if name.chars().count() > MAX_CUSTOM_EXERCISE_NAME_CHARACTERS {
+            Some("Exercise names can have at most 60 characters")
+        } else if existing_names.any(|existing_name| existing_name.eq_ignore_ascii_case(name)) {
+            Some("An exercise with this name already exists")
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_custom_exercise(name: &str) -> NewCustomExercise {
+        NewCustomExercise {
+            name: name.to_string(),
+            primary_muscle: Muscle::Quadriceps,
+            equipment: ExerciseEquipment::Machine,
+            force: ExerciseForce::Push,
+            level: ExerciseLevel::Beginner,
+            category: ExerciseCategory::Strength,
+            shared_with_friends: false,
+        }
+    }
+
+    #[test]
+    fn custom_exercise_needs_a_new_name() {
+        let existing_names = ["Squat", "Leg Press"];
+
+        assert!(
+            new_custom_exercise("Pendulum squat")
+                .validation_error(existing_names.into_iter())
+                .is_none()
+        );
+        assert!(
+            new_custom_exercise("  ")
+                .validation_error(existing_names.into_iter())
+                .is_some()
+        );
+        assert!(
+            new_custom_exercise(" leg press ")
+                .validation_error(existing_names.into_iter())
+                .is_some()
+        );
+        assert!(
+            new_custom_exercise(&"a".repeat(MAX_CUSTOM_EXERCISE_NAME_CHARACTERS + 1))
+                .validation_error(existing_names.into_iter())
+                .is_some()
+        );
+    }
+}
@@ -4,7 +4,7 @@ use crate::common::exercise_mod::weight::{ExerciseWeight, Kg};
 use chrono::{Duration, Local, NaiveDate};
 use rand::RngExt;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter};
 
 pub type DateWeightPoints = Vec<(NaiveDate, Kg)>;
@@ -113,8 +113,8 @@ impl Exercise {
     /// Returns whether a set of this exercise is tracked with the given `workout_id` or not
     pub fn contains_set_with_workout_id(&self, workout_id: Id) -> bool {
         self.sets
-            .iter()
-            .flat_map(|(_, sets)| sets.iter())
+            .values()
+            .flat_map(|sets| sets.iter())
             .any(|set| set.workout_id == workout_id)
     }
 
@@ -130,8 +130,8 @@ impl Exercise {
         let mut result = Vec::new();
         let ids = self
             .sets
-            .iter()
-            .flat_map(|(_, sets)| sets.iter())
+            .values()
+            .flat_map(|sets| sets.iter())
             .map(|set| set.workout_id);
 
         for id in ids {
@@ -145,6 +145,34 @@ impl Exercise {
         result
     }
 }
+
+/// All sets a user has logged for one exercise of the catalog
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExerciseSets {
+    pub exercise_id: Id,
+    pub sets: BTreeMap<NaiveDate, Vec<StrengthSet>>,
+}
+
+/// Turns every exercise of the catalog into an Exercise with the matching sets.
+/// Sets of exercises missing in the catalog are dropped
+pub fn combine_catalog_with_sets(
+    catalog: Vec<GeneralExerciseInfo>,
+    exercise_sets: Vec<ExerciseSets>,
+) -> Vec<Exercise> {
+    let mut exercises: Vec<Exercise> = catalog.into_iter().map(Exercise::new).collect();
+    let index_by_id: HashMap<Id, usize> = exercises
+        .iter()
+        .enumerate()
+        .map(|(index, exercise)| (exercise.general_exercise_info.id, index))
+        .collect();
+
+    for exercise_sets in exercise_sets {
+        if let Some(index) = index_by_id.get(&exercise_sets.exercise_id) {
+            exercises[*index].sets = exercise_sets.sets;
+        }
+    }
+    exercises
+}
 pub fn get_weight_milestones(start_number: u32, end_number: u32, steps: u32) -> Vec<u32> {
     let mut milestones = vec![];
 
@@ -540,4 +568,33 @@ pub(crate) mod tests {
         assert_eq!(*simple_ten_step.first().unwrap(), 0);
         assert_eq!(*simple_ten_step.last().unwrap(), 12393);
     }
+    #[test]
+    fn sets_are_matched_to_catalog_by_id() {
+        let catalog: Vec<GeneralExerciseInfo> = [3, 1]
+            .into_iter()
+            .map(|id| GeneralExerciseInfo {
+                id,
+                ..GeneralExerciseInfo::test_obj()
+            })
+            .collect();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let sets = BTreeMap::from([(date, vec![StrengthSet::new(1, ExerciseWeight::Kg(50.0), 8)])]);
+        let exercise_sets = vec![
+            ExerciseSets {
+                exercise_id: 1,
+                sets: sets.clone(),
+            },
+            ExerciseSets {
+                exercise_id: 99,
+                sets: sets.clone(),
+            },
+        ];
+
+        let exercises = combine_catalog_with_sets(catalog, exercise_sets);
+
+        assert_eq!(exercises.len(), 2);
+        assert!(!exercises[0].is_tracked());
+        assert_eq!(exercises[1].general_exercise_info.id, 1);
+        assert_eq!(exercises[1].sets, sets);
+    }
 }
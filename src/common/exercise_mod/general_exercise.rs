@@ -1,9 +1,9 @@
 use serde::{Deserialize, Serialize};
-use strum_macros::{Display, EnumString};
+use strum_macros::{Display, EnumIter, EnumString};
 
 pub type Id = u32;
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GeneralExerciseInfo {
     pub id: Id,
     pub name: String,
@@ -13,6 +13,8 @@ pub struct GeneralExerciseInfo {
     pub equipment: ExerciseEquipment,
     /// Primarily targeted muscle
     pub primary_muscle: Muscle,
+    /// Muscles that are trained as well, never containing the primary muscle
+    pub secondary_muscles: Vec<Muscle>,
     pub instructions: String,
     pub category: ExerciseCategory,
 }
@@ -25,12 +27,26 @@ impl GeneralExerciseInfo {
             level: ExerciseLevel::Beginner,
             equipment: ExerciseEquipment::Body,
             primary_muscle: Muscle::Abdominals,
+            secondary_muscles: vec![Muscle::Chest],
             instructions: "This is a test exercise!".to_string(),
             category: ExerciseCategory::Strength,
         }
     }
 }
-#[derive(Display, EnumString, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[derive(
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    EnumIter,
+    Eq,
+    PartialEq,
+    Hash,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum Muscle {
     Abdominals,
@@ -55,7 +71,9 @@ pub enum Muscle {
     Neck,
 }
 
-#[derive(Debug, Display, EnumString, Default, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, Display, EnumString, EnumIter, PartialEq, Default, Serialize, Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum ExerciseForce {
     #[default]
@@ -64,7 +82,19 @@ pub enum ExerciseForce {
     Static,
 }
 
-#[derive(Display, EnumString, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[derive(
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    EnumIter,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum ExerciseLevel {
     #[default]
@@ -73,7 +103,9 @@ pub enum ExerciseLevel {
     Expert,
 }
 
-#[derive(Debug, Display, EnumString, Default, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, Display, EnumString, EnumIter, PartialEq, Default, Serialize, Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum ExerciseEquipment {
     #[default]
@@ -91,11 +123,24 @@ pub enum ExerciseEquipment {
     ExerciseBall,
     #[strum(serialize = "e-z curl bar")]
     EzCurlBar,
+    #[strum(serialize = "foam roll")]
     FoamRoll,
     Other,
 }
 
-#[derive(Display, EnumString, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[derive(
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    EnumIter,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum ExerciseCategory {
     #[default]
@@ -105,6 +150,7 @@ pub enum ExerciseCategory {
     Strongman,
     Powerlifting,
     Cardio,
+    #[strum(serialize = "olympic weightlifting")]
     OlympicWeightlifting,
     Crossfit,
     WeightedBodyweight,
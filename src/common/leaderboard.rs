@@ -0,0 +1,169 @@
+use crate::common::mascot_mod::mascot::Mascot;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+/// What the user and their friends are ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// Sum of weight times reps of every set
+    #[default]
+    Volume,
+    /// Current weekly workout streak, the same for every period
+    Streak,
+    Workouts,
+    /// Heaviest set of the chosen exercise
+    #[strum(to_string = "Best set")]
+    BestSet,
+}
+
+impl LeaderboardMetric {
+    pub fn get_unit(&self) -> &'static str {
+        match self {
+            LeaderboardMetric::Volume | LeaderboardMetric::BestSet => "kg",
+            LeaderboardMetric::Streak => "weeks",
+            LeaderboardMetric::Workouts => "workouts",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardPeriod {
+    #[default]
+    #[strum(to_string = "This week")]
+    Week,
+    #[strum(to_string = "This month")]
+    Month,
+}
+
+impl LeaderboardPeriod {
+    /// First and last day of the week (Monday to Sunday) or month `today` falls in
+    pub fn date_range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            LeaderboardPeriod::Week => {
+                let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                (monday, monday + Duration::days(6))
+            }
+            LeaderboardPeriod::Month => {
+                let first_day = today.with_day(1).unwrap_or(today);
+                let next_month = first_day
+                    .checked_add_months(chrono::Months::new(1))
+                    .unwrap_or(first_day);
+                (first_day, next_month.pred_opt().unwrap_or(first_day))
+            }
+        }
+    }
+}
+
+/// Parameters of `/social/leaderboard`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LeaderboardQuery {
+    pub metric: LeaderboardMetric,
+    pub period: LeaderboardPeriod,
+    /// Name of the exercise, only needed for the best set
+    pub exercise: Option<String>,
+}
+
+impl LeaderboardQuery {
+    /// Reason why the leaderboard can't be ranked, None if the query is complete
+    pub fn validation_error(&self) -> Option<&'static str> {
+        if self.metric == LeaderboardMetric::BestSet && self.exercise.is_none() {
+            Some("The best set leaderboard needs an exercise")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// Users with the same value share a rank
+    pub rank: u32,
+    pub username: String,
+    pub profile_picture_path: String,
+    pub favorite_mascot: Mascot,
+    pub value: f32,
+}
+
+/// Sorts the entries from the highest to the lowest value and ranks them.
+/// Ties share the rank and the next rank is skipped, e.g. 1, 1, 3
+pub fn rank_entries(entries: &mut [LeaderboardEntry]) {
+    entries.sort_by(|a, b| {
+        b.value
+            .total_cmp(&a.value)
+            .then_with(|| a.username.cmp(&b.username))
+    });
+    for index in 0..entries.len() {
+        entries[index].rank = if index > 0 && entries[index].value == entries[index - 1].value {
+            entries[index - 1].rank
+        } else {
+            index as u32 + 1
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(username: &str, value: f32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            rank: 0,
+            username: username.to_string(),
+            profile_picture_path: String::new(),
+            favorite_mascot: Mascot::default(),
+            value,
+        }
+    }
+
+    #[test]
+    fn ties_share_their_rank() {
+        let mut entries = vec![
+            entry("ben", 10.0),
+            entry("carl", 30.0),
+            entry("anna", 10.0),
+            entry("dora", 0.0),
+        ];
+        rank_entries(&mut entries);
+
+        let ranking: Vec<(&str, u32)> = entries
+            .iter()
+            .map(|entry| (entry.username.as_str(), entry.rank))
+            .collect();
+        assert_eq!(
+            ranking,
+            vec![("carl", 1), ("anna", 2), ("ben", 2), ("dora", 4)]
+        );
+    }
+
+    #[test]
+    fn best_set_needs_an_exercise() {
+        let mut query = LeaderboardQuery {
+            metric: LeaderboardMetric::BestSet,
+            ..Default::default()
+        };
+        assert!(query.validation_error().is_some());
+
+        query.exercise = Some("Squat".to_string());
+        assert_eq!(query.validation_error(), None);
+        assert_eq!(LeaderboardQuery::default().validation_error(), None);
+    }
+
+    #[test]
+    fn periods_cover_the_current_week_and_month() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        // Thursday
+        let today = date(2, 29);
+
+        assert_eq!(
+            LeaderboardPeriod::Week.date_range(today),
+            (date(2, 26), date(3, 3))
+        );
+        assert_eq!(
+            LeaderboardPeriod::Month.date_range(today),
+            (date(2, 1), date(2, 29))
+        );
+    }
+}
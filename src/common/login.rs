@@ -13,17 +13,31 @@ pub enum RequestValidRegisterError {
     UserAlreadyExists,
 }
 
+/// Tokens of a login session. The short-lived access token is sent with every request,
+/// the refresh token is only used to get a new pair once the access token expired
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+/// Body of /user/token/refresh and /user/logout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "answer", content = "token")]
 pub enum RequestValidUserAnswer {
     UserNotFound,
     WrongPassword,
-    Valid(String),
+    Valid(AuthTokens),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "answer", content = "token")]
 pub enum RequestValidRegisterAnswer {
     UserAlreadyExists,
-    Valid(String),
+    Valid(AuthTokens),
 }
@@ -1,10 +1,6 @@
-use crate::client::backend::mascot_manager::MascotManager;
-use crate::common::mascot_mod::mascot::MascotError;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use iced::{Color, color};
-use rand::RngExt;
 use serde::{Deserialize, Serialize};
-use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{EnumCount, EnumIter, EnumString};
 
 #[derive(
@@ -71,21 +67,3 @@ impl MascotTrait for EpicMascot {
         100
     }
 }
-
-impl EpicMascot {
-    pub fn random_new_epic(mascot_manager: &MascotManager) -> Result<EpicMascot, MascotError> {
-        let mut number = rand::rng().random_range(0..=EpicMascot::COUNT - 1);
-        let mut mascot = EpicMascot::iter().get(number).unwrap();
-        let mut counter = 0;
-        while counter < EpicMascot::COUNT && mascot_manager.owns_mascot(mascot) {
-            number = (number + 1) % EpicMascot::COUNT;
-            mascot = EpicMascot::iter().get(number).unwrap();
-            counter += 1;
-        }
-        if counter == EpicMascot::COUNT {
-            Err(MascotError::AllBought)
-        } else {
-            Ok(mascot)
-        }
-    }
-}
@@ -8,16 +8,45 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
 
+#[derive(Debug, PartialEq)]
 pub enum MascotError {
     AllBought,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MascotRarity {
     Epic,
     Rare,
 }
 
+impl MascotRarity {
+    /// Coins a random mascot of this rarity costs
+    pub fn get_prize(&self) -> u32 {
+        match self {
+            MascotRarity::Rare => RareMascot::default().get_prize(),
+            MascotRarity::Epic => EpicMascot::default().get_prize(),
+        }
+    }
+
+    pub fn mascots(&self) -> Vec<Mascot> {
+        match self {
+            MascotRarity::Rare => RareMascot::iter().map(Mascot::Rare).collect(),
+            MascotRarity::Epic => EpicMascot::iter().map(Mascot::Epic).collect(),
+        }
+    }
+
+    /// Picks a random mascot of this rarity which isn't in `owned_mascots`
+    pub fn random_unowned_mascot(&self, owned_mascots: &[Mascot]) -> Result<Mascot, MascotError> {
+        let mut rng = rand::rng();
+
+        self.mascots()
+            .into_iter()
+            .filter(|mascot| !owned_mascots.contains(mascot))
+            .choose(&mut rng)
+            .ok_or(MascotError::AllBought)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Mascot {
     Rare(RareMascot),
@@ -120,3 +149,36 @@ impl Mascot {
             .unwrap_or_default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_unowned_mascot_skips_owned_mascots() {
+        let owned_mascots = vec![
+            Mascot::Epic(EpicMascot::Capybara),
+            Mascot::Epic(EpicMascot::Shark),
+        ];
+        assert_eq!(
+            MascotRarity::Epic.random_unowned_mascot(&owned_mascots),
+            Ok(Mascot::Epic(EpicMascot::Reindeer))
+        );
+    }
+    #[test]
+    fn random_unowned_mascot_all_bought() {
+        let owned_mascots = MascotRarity::Rare.mascots();
+        assert_eq!(
+            MascotRarity::Rare.random_unowned_mascot(&owned_mascots),
+            Err(MascotError::AllBought)
+        );
+    }
+    #[test]
+    fn rarity_prize_matches_mascot_prize() {
+        for rarity in [MascotRarity::Rare, MascotRarity::Epic] {
+            for mascot in rarity.mascots() {
+                assert_eq!(mascot.get_prize(), rarity.get_prize());
+            }
+        }
+    }
+}
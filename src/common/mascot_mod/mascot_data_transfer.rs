@@ -7,3 +7,11 @@ pub struct MascotDataServerClientTransfer {
     pub selected_mascot: Mascot,
     pub owned_mascots: Vec<Mascot>,
 }
+
+/// Answer of the server after the user tried to buy a random mascot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MascotRollAnswer {
+    Rolled { mascot: Mascot, coin_balance: u32 },
+    NotEnoughCoins,
+    AllMascotsOwned,
+}
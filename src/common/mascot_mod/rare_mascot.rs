@@ -1,10 +1,6 @@
-use crate::client::backend::mascot_manager::MascotManager;
-use crate::common::mascot_mod::mascot::MascotError;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use iced::{Color, color};
-use rand::RngExt;
 use serde::{Deserialize, Serialize};
-use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{EnumCount, EnumIter, EnumString};
 
 #[derive(
@@ -78,21 +74,3 @@ impl MascotTrait for RareMascot {
         50
     }
 }
-
-impl RareMascot {
-    pub fn random_new_rare(mascot_manager: &MascotManager) -> Result<RareMascot, MascotError> {
-        let mut number = rand::rng().random_range(0..=RareMascot::COUNT - 1);
-        let mut mascot = RareMascot::iter().get(number).unwrap();
-        let mut counter = 0;
-        while counter < RareMascot::COUNT && mascot_manager.owns_mascot(mascot) {
-            number = (number + 1) % RareMascot::COUNT;
-            mascot = RareMascot::iter().get(number).unwrap();
-            counter += 1;
-        }
-        if counter == RareMascot::COUNT {
-            Err(MascotError::AllBought)
-        } else {
-            Ok(mascot)
-        }
-    }
-}
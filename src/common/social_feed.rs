@@ -0,0 +1,115 @@
+use crate::common::exercise_mod::weight::Kg;
+use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::user_mod::user_goals::GoalType;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Events per page of the friends feed if the client doesn't ask for a different number
+pub const DEFAULT_FEED_PAGE_SIZE: u32 = 20;
+pub const MAX_FEED_PAGE_SIZE: u32 = 50;
+
+/// Something a friend did, shown in the feed of the Social tab
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FeedEventKind {
+    WorkoutSaved {
+        /// Date the workout was tracked on, which differs from the event for backdated workouts
+        date: NaiveDate,
+        exercise_names: Vec<String>,
+        /// Sum of weight times reps of every set
+        volume: Kg,
+        /// Only known if the workout was logged on the day it was started
+        duration_minutes: Option<u32>,
+    },
+    /// Heavier set of an exercise than every set tracked before
+    PersonalRecord {
+        exercise_name: String,
+        weight: Kg,
+    },
+    MascotBought(Mascot),
+    GoalReached {
+        goal_type: GoalType,
+        value: f32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedEvent {
+    pub id: i64,
+    pub username: String,
+    pub profile_picture_path: String,
+    pub favorite_mascot: Mascot,
+    pub created_at: NaiveDateTime,
+    pub kind: FeedEventKind,
+}
+
+/// Events of the friends feed ordered from newest to oldest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedPage {
+    pub events: Vec<FeedEvent>,
+    /// Pass as `before` to load the next older page, None if there are no older events
+    pub next_before: Option<i64>,
+}
+
+impl FeedEventKind {
+    /// What happened, following the username of the friend
+    pub fn description(&self) -> String {
+        match self {
+            FeedEventKind::WorkoutSaved {
+                exercise_names,
+                volume,
+                duration_minutes,
+                ..
+            } => {
+                let mut description = format!(
+                    "finished a workout: {} - {volume} kg",
+                    exercise_names.join(", ")
+                );
+                if let Some(duration_minutes) = duration_minutes {
+                    description.push_str(&format!(" in {duration_minutes} min"));
+                }
+                description
+            }
+            FeedEventKind::PersonalRecord {
+                exercise_name,
+                weight,
+            } => format!("set a new PR: {weight} kg {exercise_name}"),
+            FeedEventKind::MascotBought(mascot) => format!("got the mascot {mascot}"),
+            FeedEventKind::GoalReached { goal_type, value } => {
+                let unit = goal_type.get_unit();
+                format!("reached the {goal_type} goal with {value} {unit}")
+                    .trim_end()
+                    .to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workout_description_lists_exercises_and_known_duration() {
+        let mut workout = FeedEventKind::WorkoutSaved {
+            date: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            exercise_names: vec!["Squat".to_string(), "Leg Press".to_string()],
+            volume: 2400.0,
+            duration_minutes: Some(45),
+        };
+        assert_eq!(
+            workout.description(),
+            "finished a workout: Squat, Leg Press - 2400 kg in 45 min"
+        );
+
+        if let FeedEventKind::WorkoutSaved {
+            duration_minutes, ..
+        } = &mut workout
+        {
+            *duration_minutes = None;
+        }
+        assert_eq!(
+            workout.description(),
+            "finished a workout: Squat, Leg Press - 2400 kg"
+        );
+    }
+}
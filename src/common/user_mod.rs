@@ -1,4 +1,6 @@
+pub mod account;
 pub mod friend_request;
 pub mod user;
 pub mod user_goals;
 pub mod user_log;
+pub mod user_report;
@@ -0,0 +1,99 @@
+use crate::common::login::AuthTokens;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_USERNAME_LENGTH: usize = 15;
+pub const MAX_PASSWORD_LENGTH: usize = 100;
+
+/// Body of /user/account/password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordChange {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Body of /user/account/username
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsernameChange {
+    pub password: String,
+    pub new_username: String,
+}
+
+/// Body of /user/account/delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountDeletion {
+    pub password: String,
+}
+
+/// Every session is logged out after a change, `Valid` contains the tokens of a new one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "answer", content = "token")]
+pub enum AccountChangeAnswer {
+    WrongPassword,
+    UsernameAlreadyExists,
+    Valid(AuthTokens),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AccountDeletionAnswer {
+    WrongPassword,
+    Deleted,
+}
+
+impl PasswordChange {
+    /// Reason why the password can't be changed, None if it is valid
+    pub fn validation_error(&self) -> Option<&'static str> {
+        if self.new_password.is_empty() {
+            Some("Password can't be empty!")
+        } else if self.new_password.len() > MAX_PASSWORD_LENGTH {
+            Some("Passwords can have at most 100 characters")
+        } else if self.new_password == self.current_password {
+            Some("The new password is the same as the current one")
+        } else {
+            None
+        }
+    }
+}
+
+impl UsernameChange {
+    /// Reason why the username can't be changed, None if it is valid
+    pub fn validation_error(&self) -> Option<&'static str> {
+        if self.new_username.is_empty() {
+            Some("Username can't be empty!")
+        } else if self.new_username.len() > MAX_USERNAME_LENGTH {
+            Some("Usernames can have at most 15 characters")
+        } else if self.new_username.trim() != self.new_username {
+            Some("Usernames can't start or end with spaces")
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_account_changes() {
+        let password_change = |current_password: &str, new_password: &str| PasswordChange {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        };
+        assert!(password_change("old", "new").validation_error().is_none());
+        assert!(password_change("old", "").validation_error().is_some());
+        assert!(password_change("old", "old").validation_error().is_some());
+
+        let username_change = |new_username: &str| UsernameChange {
+            password: "123".to_string(),
+            new_username: new_username.to_string(),
+        };
+        assert!(username_change("anna").validation_error().is_none());
+        assert!(username_change("").validation_error().is_some());
+        assert!(username_change(" anna").validation_error().is_some());
+        assert!(
+            username_change("a_very_long_username")
+                .validation_error()
+                .is_some()
+        );
+    }
+}
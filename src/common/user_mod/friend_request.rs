@@ -3,6 +3,28 @@ use serde::{Deserialize, Serialize};
 /// Primarily used to send new Friendship data from client to server
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FriendRequest {
-    /// The Person you want to add/delete as a friend
+    /// The Person you want to send a friend request to, remove as a friend, block or unblock
     pub username: String,
 }
+
+/// Answer of the receiver to a pending friend request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FriendRequestAnswer {
+    /// The Person who sent the friend request
+    pub username: String,
+    pub accept: bool,
+}
+
+/// Relationship of a foreign user to the logged-in user. <br>
+/// A friend request is pending until the receiver accepts or declines it,
+/// accepting makes both users friends of each other
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum FriendshipStatus {
+    #[default]
+    NotFriends,
+    /// The logged-in user sent a friend request which is still pending
+    RequestSent,
+    /// The foreign user sent a friend request which is still pending
+    RequestReceived,
+    Friends,
+}
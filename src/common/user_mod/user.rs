@@ -3,6 +3,7 @@ use crate::common::exercise_mod::exercise::Exercise;
 use crate::common::exercise_mod::weight::Kg;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::profile_picture::ProfilePictureTypes;
+use crate::common::user_mod::friend_request::FriendshipStatus;
 use crate::common::user_mod::user_goals::{GoalType, UserGoals};
 use crate::common::user_mod::user_log::UserLog;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,36 @@ pub enum Gender {
     Female,
 }
 
+/// Who can find the user and see their stats. Friends always see everything
+#[derive(
+    Display,
+    Clone,
+    Copy,
+    EnumString,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    EnumIter,
+)]
+pub enum ProfileVisibility {
+    #[default]
+    Public,
+    /// Found in discovery, but only friends see the stats
+    #[strum(serialize = "FriendsOnly", to_string = "Friends only")]
+    FriendsOnly,
+    /// Not found in discovery and only friends see the stats
+    Private,
+}
+
+impl ProfileVisibility {
+    pub fn shows_stats_to(&self, friendship_status: FriendshipStatus) -> bool {
+        *self == ProfileVisibility::Public || friendship_status == FriendshipStatus::Friends
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum UserType {
     /// The currently logged-in user
@@ -36,6 +67,7 @@ pub struct UserInformation {
     pub gender: Gender,
     pub coin_balance: u32,
     pub favorite_mascot: Mascot,
+    pub profile_visibility: ProfileVisibility,
     pub user_goals: UserGoals,
     pub user_logs: UserLog,
     //Doesn't include "new" data only for performance, doesn't need to be in db
@@ -53,6 +85,7 @@ impl UserInformation {
             gender: Gender::Male,
             coin_balance: 0,
             favorite_mascot: Mascot::default(),
+            profile_visibility: ProfileVisibility::default(),
             profile_stat_manager: ProfileStatManager::new(
                 exercise_data,
                 default_user_goals.weekly_workouts as u32,
@@ -71,7 +104,9 @@ pub struct ForeignUser {
     pub profile_stat_manager: ProfileStatManager,
     pub favorite_mascot: Mascot,
     pub owned_mascots: Vec<Mascot>,
-    pub friends_with_active_user: bool,
+    pub friendship_status: FriendshipStatus,
+    /// The user only shows their stats to friends, so they are left empty
+    pub stats_hidden: bool,
 }
 impl Default for ForeignUser {
     fn default() -> Self {
@@ -85,7 +120,8 @@ impl Default for ForeignUser {
             ),
             favorite_mascot: Mascot::default(),
             owned_mascots: vec![Mascot::default()],
-            friends_with_active_user: false,
+            friendship_status: FriendshipStatus::NotFriends,
+            stats_hidden: false,
         }
     }
 }
@@ -97,7 +133,8 @@ pub struct DisplayUserProfileData {
     pub profile_stat_manager: ProfileStatManager,
     pub favorite_mascot: Mascot,
     pub owned_mascots: Vec<Mascot>,
-    pub friends_with_active_user: bool,
+    pub friendship_status: FriendshipStatus,
+    pub stats_hidden: bool,
 }
 impl Default for DisplayUserProfileData {
     fn default() -> Self {
@@ -113,7 +150,8 @@ impl From<ForeignUser> for DisplayUserProfileData {
             profile_stat_manager: value.profile_stat_manager,
             favorite_mascot: value.favorite_mascot,
             owned_mascots: value.owned_mascots,
-            friends_with_active_user: value.friends_with_active_user,
+            friendship_status: value.friendship_status,
+            stats_hidden: value.stats_hidden,
         }
     }
 }
@@ -126,7 +164,8 @@ impl DisplayUserProfileData {
             profile_stat_manager: value.profile_stat_manager,
             favorite_mascot: value.favorite_mascot,
             owned_mascots,
-            friends_with_active_user: false,
+            friendship_status: FriendshipStatus::NotFriends,
+            stats_hidden: false,
         }
     }
 }
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter};
 
 /// GoalsTypes of a user also see UserGoals
-#[derive(Default, Debug, Clone, Copy, Display, EnumIter, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, Display, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GoalType {
     #[strum(to_string = "Weekly workouts")]
     WeeklyWorkouts,
@@ -20,6 +20,32 @@ pub enum GoalType {
     Sleep,
 }
 impl GoalType {
+    /// GoalTypes with a daily progress bar in the health tab
+    pub const DAILY_LOG_TYPES: [GoalType; 3] = [GoalType::Water, GoalType::Steps, GoalType::Sleep];
+    /// GoalTypes whose values are logged per date through /user/logs/{goal_type}
+    pub const LOGGED_TYPES: [GoalType; 4] = [
+        GoalType::Weight,
+        GoalType::Water,
+        GoalType::Steps,
+        GoalType::Sleep,
+    ];
+
+    /// Path segment of the log routes, None if the goal type has no log
+    pub fn log_path(&self) -> Option<&'static str> {
+        match self {
+            GoalType::Weight => Some("weight"),
+            GoalType::Water => Some("water"),
+            GoalType::Steps => Some("steps"),
+            GoalType::Sleep => Some("sleep"),
+            GoalType::WeeklyWorkouts => None,
+        }
+    }
+    pub fn from_log_path(path: &str) -> Option<GoalType> {
+        GoalType::LOGGED_TYPES
+            .into_iter()
+            .find(|goal_type| goal_type.log_path() == Some(path))
+    }
+
     /// Formats the UserGoal data according to the fields unit
     pub fn get_formatted_user_goal_strings(&self, user_goals: &UserGoals) -> String {
         match self {
@@ -111,6 +137,15 @@ impl Default for UserGoals {
 }
 
 impl UserGoals {
+    pub fn get_goal_by_type(&self, goal_type: &GoalType) -> f32 {
+        match goal_type {
+            GoalType::WeeklyWorkouts => self.weekly_workouts,
+            GoalType::Weight => self.weight,
+            GoalType::Water => self.water,
+            GoalType::Steps => self.steps,
+            GoalType::Sleep => self.sleep,
+        }
+    }
     pub fn get_goal_by_type_mut(&mut self, goal_type: &GoalType) -> &mut f32 {
         match goal_type {
             GoalType::WeeklyWorkouts => &mut self.weekly_workouts,
@@ -4,6 +4,28 @@ use serde::{Deserialize, Serialize};
 
 pub type Log = Vec<(NaiveDate, f32)>;
 
+/// Value of a daily log like water, steps or sleep on a single day
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UserLogEntry {
+    pub date: NaiveDate,
+    pub value: f32,
+}
+
+impl UserLogEntry {
+    /// Reason why the entry can't be logged, None if it is valid
+    pub fn validation_error(&self, goal_type: &GoalType, today: NaiveDate) -> Option<&'static str> {
+        if !self.value.is_finite() || self.value < 0.0 {
+            Some("Log values have to be positive numbers")
+        } else if *goal_type == GoalType::Weight && self.value == 0.0 {
+            Some("A weight of 0 kg can not be logged")
+        } else if self.date > today {
+            Some("Logs can not be saved in the future")
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserLog {
     pub weight_log: Log,
@@ -32,18 +54,140 @@ impl UserLog {
         })
     }
     pub fn update_log(&mut self, goal_type: &GoalType, new_entry: f32) -> Result<(), &str> {
-        let today = Local::now().date_naive();
+        self.update_log_on_date(goal_type, Local::now().date_naive(), new_entry)
+    }
 
+    /// Sets the value of the given day, the log stays sorted by date
+    pub fn update_log_on_date(
+        &mut self,
+        goal_type: &GoalType,
+        date: NaiveDate,
+        new_entry: f32,
+    ) -> Result<(), &str> {
         let log = self
             .get_log_by_goal_type_mut(goal_type)
             .ok_or("Goal type doesn't have a log")?;
 
-        if let Some((_, value)) = log.iter_mut().find(|(date, _)| *date == today) {
-            *value = new_entry;
-        } else {
-            log.push((today, new_entry)); // push to the end as today should be the most recent day
+        match log.binary_search_by_key(&date, |(log_date, _)| *log_date) {
+            Ok(index) => log[index].1 = new_entry,
+            Err(index) => log.insert(index, (date, new_entry)),
         }
 
         Ok(())
     }
+
+    /// Removes the entry of the given day, returns false if there was none
+    pub fn remove_log_on_date(
+        &mut self,
+        goal_type: &GoalType,
+        date: NaiveDate,
+    ) -> Result<bool, &str> {
+        let log = self
+            .get_log_by_goal_type_mut(goal_type)
+            .ok_or("Goal type doesn't have a log")?;
+
+        let length_before = log.len();
+        log.retain(|(log_date, _)| *log_date != date);
+        Ok(log.len() != length_before)
+    }
+
+    /// Value logged today, 0 if nothing was logged yet
+    pub fn todays_value(&self, goal_type: &GoalType) -> f32 {
+        let today = Local::now().date_naive();
+        self.get_log_by_goal_type(goal_type)
+            .and_then(|log| log.iter().find(|(date, _)| *date == today))
+            .map(|(_, value)| *value)
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_log_on_date_keeps_log_sorted() {
+        let mut user_log = UserLog::default();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+
+        user_log
+            .update_log_on_date(&GoalType::Water, day(5), 2.0)
+            .unwrap();
+        user_log
+            .update_log_on_date(&GoalType::Water, day(1), 1.0)
+            .unwrap();
+        user_log
+            .update_log_on_date(&GoalType::Water, day(3), 1.5)
+            .unwrap();
+        user_log
+            .update_log_on_date(&GoalType::Water, day(3), 0.5)
+            .unwrap();
+
+        assert_eq!(
+            user_log.water_log,
+            vec![(day(1), 1.0), (day(3), 0.5), (day(5), 2.0)]
+        );
+        assert!(
+            user_log
+                .update_log_on_date(&GoalType::WeeklyWorkouts, day(1), 1.0)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn remove_log_on_date_only_removes_that_day() {
+        let mut user_log = UserLog::default();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        user_log.sleep_log = vec![(day(1), 7.0), (day(2), 8.0)];
+
+        assert!(
+            user_log
+                .remove_log_on_date(&GoalType::Sleep, day(1))
+                .unwrap()
+        );
+        assert!(
+            !user_log
+                .remove_log_on_date(&GoalType::Sleep, day(1))
+                .unwrap()
+        );
+        assert_eq!(user_log.sleep_log, vec![(day(2), 8.0)]);
+    }
+
+    #[test]
+    fn invalid_entries_have_a_validation_error() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let entry = |date: NaiveDate, value: f32| UserLogEntry { date, value };
+        let yesterday = today.pred_opt().unwrap();
+
+        assert!(
+            entry(today, 2.5)
+                .validation_error(&GoalType::Water, today)
+                .is_none()
+        );
+        assert!(
+            entry(yesterday, 0.0)
+                .validation_error(&GoalType::Sleep, today)
+                .is_none()
+        );
+        assert!(
+            entry(yesterday, 0.0)
+                .validation_error(&GoalType::Weight, today)
+                .is_some()
+        );
+        assert!(
+            entry(today.succ_opt().unwrap(), 1.0)
+                .validation_error(&GoalType::Water, today)
+                .is_some()
+        );
+        assert!(
+            entry(today, -1.0)
+                .validation_error(&GoalType::Steps, today)
+                .is_some()
+        );
+        assert!(
+            entry(today, f32::NAN)
+                .validation_error(&GoalType::Water, today)
+                .is_some()
+        );
+    }
 }
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_REPORT_REASON_CHARACTERS: usize = 280;
+
+/// Report of a profile whose description violates the rules, read by the moderators
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserReport {
+    /// The reported user
+    pub username: String,
+    pub reason: String,
+}
+
+impl UserReport {
+    /// Reason why the report can't be sent, None if it is valid
+    pub fn validation_error(&self) -> Option<&'static str> {
+        let reason = self.reason.trim();
+        if reason.is_empty() {
+            Some("Tell us why the profile breaks the rules")
+        } else if reason.chars().count() > MAX_REPORT_REASON_CHARACTERS {
+            Some("Reasons can have at most 280 characters")
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::workout_preset::PresetStats;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Answer of the server after a workout was saved
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SavedWorkout {
+    pub workout_id: Id,
+    /// Date the workout was saved with, today if no date was given
+    pub date: NaiveDate,
+    /// Coin balance of the user after the daily reward was paid out
+    pub coin_balance: u32,
+    /// Updated stats of the preset the workout was started from
+    #[serde(default)]
+    pub trained_preset: Option<(Id, PresetStats)>,
+}
+
+/// Longer workouts are assumed to be ones that were left open and saved later
+pub const MAX_WORKOUT_DURATION_MINUTES: u32 = 12 * 60;
+
+/// Preset a new workout was started from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PresetTraining {
+    pub preset_id: Id,
+    /// Only known if the workout was logged on the day it was started
+    pub duration_minutes: Option<u32>,
+}
+
+impl PresetTraining {
+    /// Reason why the training can't be recorded, None if it is valid
+    pub fn validation_error(&self) -> Option<&'static str> {
+        match self.duration_minutes {
+            Some(duration_minutes) if !is_plausible_workout_duration(duration_minutes) => {
+                Some("The workout duration has to be between 1 minute and 12 hours")
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn is_plausible_workout_duration(duration_minutes: u32) -> bool {
+    (1..=MAX_WORKOUT_DURATION_MINUTES).contains(&duration_minutes)
+}
@@ -1,21 +1,39 @@
+use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::mascot_mod::mascot::Mascot;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WorkoutPreset {
+    /// None until the preset was saved on the server
+    #[serde(default)]
+    pub id: Option<Id>,
     pub name: String,
     pub image: PresetImage,
     pub exercises: Vec<String>,
+    /// Filled by the server, ignored when a preset is saved
+    #[serde(default)]
+    pub stats: PresetStats,
+}
+
+/// How often and how long a preset was trained by its user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PresetStats {
+    pub times_trained: u32,
+    pub last_trained: Option<NaiveDate>,
+    pub average_duration_minutes: Option<u32>,
 }
 
 impl Default for WorkoutPreset {
     fn default() -> Self {
         WorkoutPreset {
+            id: None,
             name: "Preset Title".to_string(),
             image: PresetImage::Default,
             exercises: Vec::default(),
+            stats: PresetStats::default(),
         }
     }
 }
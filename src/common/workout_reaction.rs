@@ -0,0 +1,118 @@
+use crate::common::exercise_mod::general_exercise::Id;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_COMMENT_CHARACTERS: usize = 280;
+
+/// Short comment a friend or the owner left on a workout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkoutComment {
+    pub id: i64,
+    pub workout_id: Id,
+    pub author: String,
+    pub text: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Text the user enters to comment on a workout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewWorkoutComment {
+    pub text: String,
+}
+
+impl NewWorkoutComment {
+    /// Reason why the comment can't be sent, None if it is valid
+    pub fn validation_error(&self) -> Option<&'static str> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            Some("Comments can not be empty")
+        } else if text.chars().count() > MAX_COMMENT_CHARACTERS {
+            Some("Comments can have at most 280 characters")
+        } else {
+            None
+        }
+    }
+}
+
+/// Kudos and comments of a workout, the comments are ordered from oldest to newest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkoutReactions {
+    pub workout_id: Id,
+    /// Users who gave kudos
+    pub kudos: Vec<String>,
+    pub comments: Vec<WorkoutComment>,
+}
+
+impl WorkoutReactions {
+    pub fn new(workout_id: Id) -> Self {
+        WorkoutReactions {
+            workout_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn has_kudos_from(&self, username: &str) -> bool {
+        self.kudos
+            .iter()
+            .any(|kudos_giver| kudos_giver.eq_ignore_ascii_case(username))
+    }
+
+    /// Gives the kudos of `username` if they haven't yet, else takes them back. <br>
+    /// Returns whether the user gives kudos now
+    pub fn toggle_kudos(&mut self, username: &str) -> bool {
+        if self.has_kudos_from(username) {
+            self.kudos
+                .retain(|kudos_giver| !kudos_giver.eq_ignore_ascii_case(username));
+            false
+        } else {
+            self.kudos.push(username.to_string());
+            true
+        }
+    }
+
+    /// E.g. "3 kudos | 1 comment"
+    pub fn summary(&self) -> String {
+        let comments = match self.comments.len() {
+            1 => "1 comment".to_string(),
+            count => format!("{count} comments"),
+        };
+        format!("{} kudos | {comments}", self.kudos.len())
+    }
+}
+
+/// A recent workout of a user with its reactions, shown on their profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkoutSummary {
+    pub workout_id: Id,
+    pub owner: String,
+    pub date: NaiveDate,
+    pub exercise_names: Vec<String>,
+    pub reactions: WorkoutReactions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_need_text_within_the_limit() {
+        let comment = |text: &str| NewWorkoutComment {
+            text: text.to_string(),
+        };
+        assert!(comment("  ").validation_error().is_some());
+        assert!(comment(&"a".repeat(281)).validation_error().is_some());
+        assert_eq!(comment(" Strong! ").validation_error(), None);
+    }
+
+    #[test]
+    fn kudos_toggle_once_per_user() {
+        let mut reactions = WorkoutReactions::new(4);
+
+        assert!(reactions.toggle_kudos("anna"));
+        assert!(reactions.has_kudos_from("Anna"));
+        assert_eq!(reactions.summary(), "1 kudos | 0 comments");
+
+        assert!(!reactions.toggle_kudos("anna"));
+        assert!(reactions.kudos.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod database_mod;
 mod jwt;
 mod routes;
+pub mod server_config;
 pub mod server_main;
@@ -1,11 +1,21 @@
 pub mod database;
+pub mod database_account;
+pub mod database_challenge;
+pub mod database_custom_exercise;
 pub mod database_exercise;
+pub mod database_feed;
+pub mod database_leaderboard;
 pub mod database_mascot;
+pub mod database_moderation;
 pub mod database_preset;
+pub mod database_refresh_token;
 pub mod database_social;
 pub mod database_user;
 pub mod database_user_goals;
-mod database_user_logs;
+pub mod database_user_logs;
 mod database_utils;
+pub mod database_workout_reaction;
+pub mod exercise_catalog;
 pub mod import_exercises;
 pub mod init_demo_account;
+pub mod migrations;
@@ -1,5 +1,7 @@
 use crate::client::server_communication::exercise_communicator::SetJson;
-use crate::common::exercise_mod::general_exercise::{ExerciseCategory, ExerciseLevel, Muscle};
+use crate::common::exercise_mod::general_exercise::{ExerciseCategory, ExerciseLevel, Id, Muscle};
+use crate::common::exercise_mod::weight::Kg;
+use crate::common::user_mod::friend_request::FriendshipStatus;
 pub(crate) use crate::server::database_mod::database_exercise::{
     add_exercise_log, add_workout_to_exercise_log, get_exercises_stats, get_general_exercise_info,
 };
@@ -7,182 +9,72 @@ use crate::server::database_mod::database_social::{
     add_friend, get_all_friends, get_discovery_users, get_single_foreign_user,
 };
 use crate::server::database_mod::database_user::{add_user, get_user_information};
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use crate::server::database_mod::migrations::run_migrations;
 use crate::server::routes::workout::ExerciseJson;
 use chrono::NaiveDate;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
-use std::str::FromStr;
+use std::path::Path;
+
+/// Opens the SQLite database at `database_path`, creating the file and its folder if missing
+pub async fn init_pool(database_path: &Path) -> Result<SqlitePool, sqlx::Error> {
+    if let Some(database_folder) = database_path.parent()
+        && !database_folder.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(database_folder).await?;
+    }
 
-pub async fn init_pool() -> Result<SqlitePool, sqlx::Error> {
-    let options =
-        SqliteConnectOptions::from_str("sqlite:database/database.db")?.create_if_missing(true);
+    // Renaming and deleting users cascades through the foreign keys
+    let options = SqliteConnectOptions::new()
+        .filename(database_path)
+        .create_if_missing(true)
+        .foreign_keys(true);
 
     let pool = SqlitePoolOptions::new().connect_with(options).await?;
     Ok(pool)
 }
 
+/// Brings the schema up to date, see migrations::MIGRATIONS
 pub async fn init_db(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS users (
-    username TEXT PRIMARY KEY,
-    user_password TEXT NOT NULL,
-    coin_balance INTEGER NOT NULL,
-    weight FLOAT NOT NULL,
-    height FLOAT NOT NULL,
-    gender TEXT NOT NULL,
-    favorite_mascot TEXT NOT NULL,
-    selected_mascot TEXT NOT NULL,
-    profile_picture TEXT,
-    description TEXT
-    );",
-    )
-    .execute(pool)
-    .await?; //TODO change height to INTEGER
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS mascot(
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    mascot_name TEXT NOT NULL UNIQUE,
-    description TEXT NULL
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS user_mascot(
-    username TEXT NOT NULL,
-    mascot_name TEXT NOT NULL,
-    level INTEGER NOT NULL,
-    PRIMARY KEY (username, mascot_name),
-
-    FOREIGN KEY (username) REFERENCES users (username),
-    FOREIGN KEY (mascot_name) REFERENCES mascot (mascot_name)
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS exercise (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    name TEXT NOT NULL UNIQUE,
-    exercise_force_name TEXT,
-    exercise_level_name TEXT NOT NULL,
-    exercise_equipment_name TEXT,
-    muscle_name TEXT NOT NULL,
-    instructions TEXT NOT NULL,
-    exercise_category_name TEXT NOT NULL
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        " CREATE TABLE IF NOT EXISTS exerciseLog (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    date TEXT NOT NULL,
-    username TEXT NOT NULL,
-    reps INTEGER NOT NULL,
-    exercise_id INTEGER NOT NULL,
-    weight_in_kg FLOAT NOT NULL,
-    workout_id INTEGER NOT NULL,
-    FOREIGN KEY (exercise_id) REFERENCES exercise(id),
-    FOREIGN KEY (username) REFERENCES users(username)
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS preset (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    preset_name TEXT NOT NULL,
-    preset_image TEXT NOT NULL,
-    number_of_exercises INTEGER NOT NULL,
-    estimated_duration INTEGER NOT NULL,
-    description TEXT
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS preset_exercise(
-    preset_id INTEGER NOT NULL,
-    exercise_name TEXT NOT NULL,
-
-    FOREIGN KEY (preset_id) REFERENCES preset (id)
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS user_preset(
-    username TEXT NOT NULL,
-    preset_id INTEGER NOT NULL,
-    times_preset_trained INTEGER NOT NULL,
-    PRIMARY KEY (username, preset_id),
-
-    FOREIGN KEY (username) REFERENCES users (username),
-    FOREIGN KEY (preset_id) REFERENCES preset (id)
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS friendship (
-        username TEXT NOT NULL,
-        friendname TEXT NOT NULL,
-        PRIMARY KEY (username, friendname),
-
-        FOREIGN KEY (username) REFERENCES users(username),
-        FOREIGN KEY (friendname) REFERENCES users(username)
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS logs (
-        username TEXT NOT NULL,
-        date TEXT NOT NULL,
-        value FLOAT NOT NULL,
-        log_type TEXT NOT NULL,
-
-        PRIMARY KEY(username, date, log_type),
-        FOREIGN KEY (username) REFERENCES users(username)
-    );",
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS user_goals (
-    username TEXT NOT NULL,
-    weekly_workouts FLOAT NOT NULL,
-    weight FLOAT NOT NULL,
-    water FLOAT NOT NULL,
-    steps FLOAT NOT NULL,
-    sleep FLOAT NOT NULL,
-
-    PRIMARY KEY (username),
-
-    FOREIGN KEY (username) REFERENCES users(username)
-);",
-    )
-    .execute(pool)
-    .await?;
-
+    run_migrations(pool).await?;
     Ok(())
 }
 
 #[allow(dead_code)]
 pub async fn reset_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query("DROP TABLE IF EXISTS schema_migrations")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS refresh_tokens")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS user_report")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS user_block")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS challenge")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS workout_comment")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS workout_kudos")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS feed_event")
+        .execute(pool)
+        .await?;
+    sqlx::query("DROP TABLE IF EXISTS friend_request")
+        .execute(pool)
+        .await?;
     sqlx::query("DROP TABLE IF EXISTS friendship")
         .execute(pool)
         .await?;
+    sqlx::query("DROP TABLE IF EXISTS preset_history")
+        .execute(pool)
+        .await?;
     sqlx::query("DROP TABLE IF EXISTS user_preset")
         .execute(pool)
         .await?;
@@ -220,6 +112,7 @@ pub async fn reset_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
 
 #[allow(dead_code)]
 pub async fn test_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let exercise_catalog = ExerciseCatalog::default();
     reset_database(pool).await.expect("database reset failed");
 
     println!("reseting database was sucecss");
@@ -277,9 +170,11 @@ pub async fn test_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     )
     .await?;
 
-    let felix_foreign_user = get_single_foreign_user(pool, "robert", "felix").await?;
+    let felix_foreign_user =
+        get_single_foreign_user(pool, &exercise_catalog, "robert", "felix").await?;
 
-    let discovered_foreign_users = get_discovery_users(pool, "robert", 5).await?;
+    let discovered_foreign_users =
+        get_discovery_users(pool, &exercise_catalog, "robert", 5).await?;
 
     let mut discovered_user_list = String::new();
 
@@ -292,21 +187,24 @@ pub async fn test_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     println!("These are the discovered users: {}", discovered_user_list);
 
     assert_eq!(felix_foreign_user.username, "felix");
-    assert!(felix_foreign_user.friends_with_active_user);
+    assert_eq!(
+        felix_foreign_user.friendship_status,
+        FriendshipStatus::Friends
+    );
 
     println!("get single_foreign_user was success");
 
-    let friends_robert = get_all_friends(pool, "robert").await?;
+    let friends_robert = get_all_friends(pool, &exercise_catalog, "robert").await?;
 
     assert_eq!(friends_robert.len(), 3);
     assert_eq!(friends_robert[0].username, "felix");
 
     println!("get_all_friends was success");
 
-    let felix_exercises = get_exercises_stats(pool, "felix").await?;
+    let felix_exercises = get_exercises_stats(pool, &exercise_catalog, "felix").await?;
 
     let exercise_info_from_incline_bench_pull = get_general_exercise_info(pool, 3).await?;
-    let user_information_robert = get_user_information(pool, "robert").await?;
+    let user_information_robert = get_user_information(pool, &exercise_catalog, "robert").await?;
 
     assert_eq!(
         felix_exercises[2].general_exercise_info.name,
@@ -439,9 +337,27 @@ pub async fn test_values_for_db(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
+/// Saves a workout of one set of 10 reps and returns its id
+pub async fn save_test_workout(
+    pool: &SqlitePool,
+    username: &str,
+    exercise_name: &str,
+    weight: Kg,
+    date: NaiveDate,
+) -> Id {
+    let workout = vec![ExerciseJson {
+        name: exercise_name.to_string(),
+        sets: vec![SetJson { weight, reps: 10 }],
+    }];
+    add_workout_to_exercise_log(pool, username, workout, date)
+        .await
+        .expect("saving workout failed")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::exercise_mod::general_exercise::Id;
     use crate::common::user_mod::user_goals::UserGoals;
     use crate::common::workout_preset::{PresetImage, WorkoutPreset};
     use crate::server::database_mod::database_preset::{
@@ -449,6 +365,7 @@ mod tests {
     };
     use crate::server::database_mod::database_social::add_friend;
     use crate::server::database_mod::database_user_goals::{get_user_goals, update_user_goals};
+    use sqlx::Row;
 
     #[tokio::test]
     async fn test_add_and_get_preset() {
@@ -458,9 +375,11 @@ mod tests {
             .expect("inserting test values failed");
 
         let workout = WorkoutPreset {
+            id: None,
             name: "test_workout".to_string(),
             image: PresetImage::Bench,
             exercises: vec!["Bankdrücken".to_string(), "Squat".to_string()],
+            stats: Default::default(),
         };
 
         let preset_id = add_preset(&pool, &workout, 69)
@@ -477,6 +396,7 @@ mod tests {
         let preset = &preset_from_user[0];
 
         assert_eq!(preset_from_user.len(), 1);
+        assert_eq!(preset.id, Some(preset_id as Id));
         assert_eq!(preset.name, "test_workout");
         assert_eq!(preset.image, PresetImage::Bench);
         assert_eq!(
@@ -529,7 +449,7 @@ mod tests {
             .await
             .expect("adding friend failed");
 
-        let testuser_friends = get_all_friends(&pool, "testuser")
+        let testuser_friends = get_all_friends(&pool, &ExerciseCatalog::default(), "testuser")
             .await
             .expect("getting friends failed");
 
@@ -537,9 +457,10 @@ mod tests {
         assert_eq!(testuser_friends[0].username, "testuser2");
         assert_eq!(testuser_friends[1].owned_mascots.len(), 1);
 
-        let test_discovered_users = get_discovery_users(&pool, "testuser", 3)
-            .await
-            .expect("get discovered users failed");
+        let test_discovered_users =
+            get_discovery_users(&pool, &ExerciseCatalog::default(), "testuser", 3)
+                .await
+                .expect("get discovered users failed");
         let mut discovered_list: Vec<String> = Vec::new();
         for discovered_user in test_discovered_users {
             discovered_list.push(discovered_user.username);
@@ -608,7 +529,7 @@ mod tests {
             .await
             .expect("Adding workout was failure");
 
-        let testuser_stats = get_exercises_stats(&pool, "testuser")
+        let testuser_stats = get_exercises_stats(&pool, &ExerciseCatalog::default(), "testuser")
             .await
             .expect("Getting stats failed");
 
@@ -618,4 +539,34 @@ mod tests {
         assert_eq!(testuser_stats[0].all_time_sets(), 2);
         assert_eq!(test_workout_id, 2);
     }
+    #[tokio::test]
+    async fn init_db_adds_start_time_to_old_exercise_log() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query(
+            "CREATE TABLE exerciseLog (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL,
+            username TEXT NOT NULL,
+            reps INTEGER NOT NULL,
+            exercise_id INTEGER NOT NULL,
+            weight_in_kg FLOAT NOT NULL,
+            workout_id INTEGER NOT NULL
+            );",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        init_db(&pool).await.unwrap();
+        init_db(&pool).await.unwrap();
+
+        let column_count: i64 = sqlx::query(
+            "SELECT COUNT(*) AS column_count FROM pragma_table_info('exerciseLog') WHERE name = 'start_time'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get("column_count");
+        assert_eq!(column_count, 1);
+    }
 }
@@ -0,0 +1,352 @@
+use crate::server::database_mod::database_challenge::refund_open_challenges;
+use crate::server::database_mod::database_refresh_token::revoke_user_sessions;
+use sqlx::SqlitePool;
+
+/// Stores the new password hash and logs out every session of the user
+pub async fn update_password(
+    pool: &SqlitePool,
+    username: &str,
+    password_hash: &str,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let result = sqlx::query("UPDATE users SET user_password = ? WHERE username = ?")
+        .bind(password_hash)
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+    revoke_user_sessions(&mut transaction, username).await?;
+
+    transaction.commit().await
+}
+
+/// Renames the user in every table through the foreign keys and in their reports.
+/// Every session is logged out, as its access tokens carry the old username. <br>
+/// Returns false without changes if `new_username` is taken
+pub async fn rename_user(
+    pool: &SqlitePool,
+    username: &str,
+    new_username: &str,
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let taken = sqlx::query("SELECT 1 FROM users WHERE username = ?")
+        .bind(new_username)
+        .fetch_optional(&mut *transaction)
+        .await?
+        .is_some();
+    if taken {
+        return Ok(false);
+    }
+
+    let result = sqlx::query("UPDATE users SET username = ? WHERE username = ?")
+        .bind(new_username)
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+    // Reports have no foreign keys, so they outlive deleted accounts
+    for report_column in ["reporter", "reported"] {
+        sqlx::query(&format!(
+            "UPDATE user_report SET {report_column} = ? WHERE {report_column} = ?"
+        ))
+        .bind(new_username)
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+    }
+    revoke_user_sessions(&mut transaction, new_username).await?;
+
+    transaction.commit().await?;
+    Ok(true)
+}
+
+/// Deletes the user and through the foreign keys everything referencing them, except for reports.
+/// Stakes other users hold in open challenges are refunded. <br>
+/// Custom exercises other users tracked become catalog exercises, so their sets are kept.
+/// If the catalog already has an exercise with the name, the sets are moved to it instead
+pub async fn delete_user(pool: &SqlitePool, username: &str) -> Result<(), sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    refund_open_challenges(&mut transaction, username).await?;
+
+    // Kudos and comments only know the id of the workout, not its owner
+    for reaction_table in ["workout_kudos", "workout_comment"] {
+        sqlx::query(&format!(
+            "DELETE FROM {reaction_table}
+            WHERE workout_id IN (SELECT workout_id FROM exerciseLog WHERE username = ?)"
+        ))
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+    }
+    sqlx::query(
+        "UPDATE exerciseLog SET exercise_id = (SELECT catalog.id FROM exercise AS catalog
+            JOIN exercise AS custom ON custom.name = catalog.name
+            WHERE catalog.created_by IS NULL AND custom.id = exerciseLog.exercise_id)
+        WHERE username != ? AND exercise_id IN (SELECT custom.id FROM exercise AS custom
+            JOIN exercise AS catalog ON catalog.name = custom.name AND catalog.created_by IS NULL
+            WHERE custom.created_by = ?)",
+    )
+    .bind(username)
+    .bind(username)
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query(
+        "UPDATE exercise SET created_by = NULL, shared_with_friends = 0 WHERE created_by = ?
+        AND id IN (SELECT exercise_id FROM exerciseLog WHERE username != ?)",
+    )
+    .bind(username)
+    .bind(username)
+    .execute(&mut *transaction)
+    .await?;
+
+    let result = sqlx::query("DELETE FROM users WHERE username = ?")
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    transaction.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::challenge::{ChallengeGoal, NewChallenge};
+    use crate::common::exercise_mod::custom_exercise::NewCustomExercise;
+    use crate::common::exercise_mod::general_exercise::{
+        ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, Muscle,
+    };
+    use crate::common::user_mod::friend_request::FriendshipStatus;
+    use crate::common::user_mod::user_report::UserReport;
+    use crate::common::workout_reaction::NewWorkoutComment;
+    use crate::server::database_mod::database::{
+        save_test_workout, setup_test_db, test_values_for_db,
+    };
+    use crate::server::database_mod::database_challenge::{answer_challenge, create_challenge};
+    use crate::server::database_mod::database_custom_exercise::add_custom_exercise;
+    use crate::server::database_mod::database_moderation::{get_open_reports, report_user};
+    use crate::server::database_mod::database_refresh_token::{
+        add_refresh_token, is_session_active,
+    };
+    use crate::server::database_mod::database_social::{add_friend, get_friendship_status};
+    use crate::server::database_mod::database_user::{
+        get_user_coin_balance, update_user_coin_balance,
+    };
+    use crate::server::database_mod::database_workout_reaction::{add_comment, add_kudos};
+    use chrono::{Local, NaiveDate};
+    use sqlx::Row;
+
+    /// Tables and columns referencing users(username) through foreign keys, see migration 16
+    const USER_REFERENCES: &[(&str, &str)] = &[
+        ("user_mascot", "username"),
+        ("exerciseLog", "username"),
+        ("user_preset", "username"),
+        ("friendship", "username"),
+        ("friendship", "friendname"),
+        ("logs", "username"),
+        ("user_goals", "username"),
+        ("refresh_tokens", "username"),
+        ("preset_history", "username"),
+        ("exercise", "created_by"),
+        ("friend_request", "sender"),
+        ("friend_request", "receiver"),
+        ("feed_event", "username"),
+        ("workout_kudos", "username"),
+        ("workout_comment", "username"),
+        ("challenge", "challenger"),
+        ("challenge", "opponent"),
+        ("user_block", "blocker"),
+        ("user_block", "blocked"),
+        ("daily_reward", "username"),
+        ("workout", "username"),
+    ];
+    /// Reports keep the usernames after an account is deleted
+    const REPORT_REFERENCES: &[(&str, &str)] =
+        &[("user_report", "reporter"), ("user_report", "reported")];
+
+    async fn references_to(pool: &SqlitePool, username: &str) -> i64 {
+        let mut references = 0;
+        for (table, column) in USER_REFERENCES.iter().chain(REPORT_REFERENCES) {
+            let count: i64 =
+                sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table} WHERE {column} = ?"))
+                    .bind(username)
+                    .fetch_one(pool)
+                    .await
+                    .unwrap();
+            references += count;
+        }
+        references
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renamed_user_keeps_their_data_and_loses_their_sessions() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        save_test_workout(&pool, "testuser", "Squat", 100.0, date(2)).await;
+        let expires_at = Local::now().timestamp() + 60;
+        add_refresh_token(&pool, "hash", "testuser", "session", expires_at)
+            .await
+            .unwrap();
+        let report = UserReport {
+            username: "testuser".to_string(),
+            reason: "Insults".to_string(),
+        };
+        report_user(&pool, "testuser2", &report).await.unwrap();
+        let references_before = references_to(&pool, "testuser").await;
+
+        assert!(!rename_user(&pool, "testuser", "testuser2").await.unwrap());
+        assert!(rename_user(&pool, "testuser", "anna").await.unwrap());
+
+        assert_eq!(references_to(&pool, "testuser").await, 0);
+        assert_eq!(references_to(&pool, "anna").await, references_before);
+        assert_eq!(
+            get_friendship_status(&pool, "testuser2", "anna")
+                .await
+                .unwrap(),
+            FriendshipStatus::Friends
+        );
+        assert!(
+            !is_session_active(&pool, "session", Local::now().timestamp())
+                .await
+                .unwrap()
+        );
+        assert!(matches!(
+            rename_user(&pool, "testuser", "ben").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn deleting_user_removes_everything_but_reports_and_refunds_stakes() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        for username in ["testuser", "testuser2"] {
+            update_user_coin_balance(&pool, username, 50).await.unwrap();
+        }
+        let today = Local::now().date_naive();
+        let new_challenge = NewChallenge {
+            opponent: "testuser2".to_string(),
+            goal: ChallengeGoal::MostWorkouts,
+            start_date: today,
+            end_date: today,
+            stake: 20,
+        };
+        assert!(
+            create_challenge(&pool, "testuser", &new_challenge)
+                .await
+                .unwrap()
+        );
+        let challenge_id: i64 = sqlx::query_scalar("SELECT id FROM challenge")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            answer_challenge(&pool, "testuser2", challenge_id, true, today)
+                .await
+                .unwrap(),
+            None
+        );
+        let workout_id = save_test_workout(&pool, "testuser", "Squat", 100.0, date(2)).await;
+        add_kudos(&pool, "testuser2", workout_id).await.unwrap();
+        let comment = NewWorkoutComment {
+            text: "Strong!".to_string(),
+        };
+        add_comment(&pool, "testuser2", workout_id, &comment)
+            .await
+            .unwrap();
+
+        let report = UserReport {
+            username: "testuser".to_string(),
+            reason: "Insults".to_string(),
+        };
+        report_user(&pool, "testuser2", &report).await.unwrap();
+
+        delete_user(&pool, "testuser").await.unwrap();
+
+        assert_eq!(references_to(&pool, "testuser").await, 1);
+        let reports = get_open_reports(&pool).await.unwrap();
+        assert_eq!(reports[0].reported, "testuser");
+        let reactions: i64 = sqlx::query_scalar(
+            "SELECT (SELECT COUNT(*) FROM workout_kudos) + (SELECT COUNT(*) FROM workout_comment)",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(reactions, 0);
+        assert_eq!(get_user_coin_balance(&pool, "testuser2").await.unwrap(), 50);
+        assert!(matches!(
+            delete_user(&pool, "testuser").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn sets_of_others_on_custom_exercises_of_deleted_user_are_kept() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        for name in ["Pendulum squat", "Hack squat"] {
+            let custom_exercise = NewCustomExercise {
+                name: name.to_string(),
+                primary_muscle: Muscle::Quadriceps,
+                equipment: ExerciseEquipment::Machine,
+                force: ExerciseForce::Push,
+                level: ExerciseLevel::Intermediate,
+                category: ExerciseCategory::Strength,
+                shared_with_friends: true,
+            };
+            add_custom_exercise(&pool, "testuser", &custom_exercise)
+                .await
+                .unwrap();
+            save_test_workout(&pool, "testuser2", name, 100.0, date(2)).await;
+        }
+        // Imported into the catalog after the custom exercise was created
+        sqlx::query(
+            "INSERT INTO exercise (name, exercise_level_name, muscle_name, instructions, exercise_category_name)
+            VALUES ('Hack squat', 'Beginner', 'Quadriceps', 'push', 'Strength')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        delete_user(&pool, "testuser").await.unwrap();
+
+        let kept_sets = sqlx::query(
+            "SELECT exercise.name, exercise.created_by FROM exerciseLog
+            JOIN exercise ON exercise.id = exerciseLog.exercise_id
+            WHERE exerciseLog.username = 'testuser2' ORDER BY exercise.name",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let kept_sets: Vec<(String, Option<String>)> = kept_sets
+            .iter()
+            .map(|row| (row.get("name"), row.get("created_by")))
+            .collect();
+        assert_eq!(
+            kept_sets,
+            vec![
+                ("Hack squat".to_string(), None),
+                ("Pendulum squat".to_string(), None)
+            ]
+        );
+        let exercises_named_hack_squat: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM exercise WHERE name = 'Hack squat'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(exercises_named_hack_squat, 1);
+    }
+}
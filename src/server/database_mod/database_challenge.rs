@@ -0,0 +1,517 @@
+use crate::common::challenge::{Challenge, ChallengeGoal, ChallengeStatus, NewChallenge};
+use crate::server::database_mod::database_utils;
+use chrono::NaiveDate;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::str::FromStr;
+
+/// Saves the challenge and holds back the stake of the challenger. <br>
+/// Returns false without saving if the challenger doesn't have enough coins
+pub async fn create_challenge(
+    pool: &SqlitePool,
+    challenger: &str,
+    new_challenge: &NewChallenge,
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    if !debit_coins(&mut transaction, challenger, new_challenge.stake).await? {
+        return Ok(false);
+    }
+
+    let (goal, exercise_name, target_weight) = match &new_challenge.goal {
+        ChallengeGoal::MostWorkouts => ("MostWorkouts", None, None),
+        ChallengeGoal::MostVolume => ("MostVolume", None, None),
+        ChallengeGoal::FirstToLift {
+            exercise_name,
+            weight,
+        } => ("FirstToLift", Some(exercise_name.trim()), Some(*weight)),
+    };
+    sqlx::query(
+        "INSERT INTO challenge (challenger, opponent, goal, exercise_name, target_weight,
+        start_date, end_date, stake, status, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'Pending', datetime('now'))",
+    )
+    .bind(challenger)
+    .bind(&new_challenge.opponent)
+    .bind(goal)
+    .bind(exercise_name)
+    .bind(target_weight)
+    .bind(database_utils::format_naive_date_for_database(
+        &new_challenge.start_date,
+    ))
+    .bind(database_utils::format_naive_date_for_database(
+        &new_challenge.end_date,
+    ))
+    .bind(new_challenge.stake)
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(true)
+}
+
+/// Accepting holds back the stake of the opponent, declining refunds the challenger. <br>
+/// Returns the reason why the challenge can't be accepted, None if it was answered.
+/// A challenge which already started can't be accepted anymore, as its result may already be
+/// known, so it expires and the challenger is refunded.
+/// RowNotFound if `opponent` has no pending challenge with this id
+pub async fn answer_challenge(
+    pool: &SqlitePool,
+    opponent: &str,
+    challenge_id: i64,
+    accept: bool,
+    today: NaiveDate,
+) -> Result<Option<&'static str>, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let challenge =
+        sqlx::query("SELECT * FROM challenge WHERE id = ? AND opponent = ? AND status = 'Pending'")
+            .bind(challenge_id)
+            .bind(opponent)
+            .fetch_one(&mut *transaction)
+            .await?;
+    let challenge = challenge_from_row(&challenge);
+
+    let (status, rejection) = if accept && challenge.start_date < today {
+        (
+            ChallengeStatus::Expired,
+            Some("The challenge already started, so it expired"),
+        )
+    } else if accept {
+        if !debit_coins(&mut transaction, opponent, challenge.stake).await? {
+            return Ok(Some("Not enough coins for the stake"));
+        }
+        (ChallengeStatus::Active, None)
+    } else {
+        (ChallengeStatus::Declined, None)
+    };
+    if status != ChallengeStatus::Active {
+        credit_coins(&mut transaction, &challenge.challenger, challenge.stake).await?;
+    }
+
+    sqlx::query("UPDATE challenge SET status = ? WHERE id = ?")
+        .bind(status.to_string())
+        .bind(challenge_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(rejection)
+}
+
+/// Every challenge the user sent or received, newest first
+pub async fn get_challenges(
+    pool: &SqlitePool,
+    username: &str,
+) -> Result<Vec<Challenge>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT * FROM challenge WHERE challenger = ? OR opponent = ? ORDER BY id DESC",
+    )
+    .bind(username)
+    .bind(username)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(challenge_from_row).collect())
+}
+
+/// Resolves the active challenges which ended before `today`. <br>
+/// The winner of an active challenge gets both stakes, after a draw both get their stake back.
+/// Pending challenges which started before `today` expire and the challenger is refunded.
+/// Returns how many challenges were resolved
+pub async fn resolve_expired_challenges(
+    pool: &SqlitePool,
+    today: NaiveDate,
+) -> Result<u32, sqlx::Error> {
+    let today = database_utils::format_naive_date_for_database(&today);
+    let rows = sqlx::query(
+        "SELECT * FROM challenge WHERE (status = 'Pending' AND start_date < ?)
+        OR (status = 'Active' AND end_date < ?)",
+    )
+    .bind(&today)
+    .bind(&today)
+    .fetch_all(pool)
+    .await?;
+
+    let mut resolved = 0;
+    for row in &rows {
+        let challenge = challenge_from_row(row);
+        let mut transaction = pool.begin().await?;
+        let (status, winner) = match challenge.status {
+            ChallengeStatus::Pending => (ChallengeStatus::Expired, None),
+            _ => (
+                ChallengeStatus::Completed,
+                find_winner(&mut transaction, &challenge).await?,
+            ),
+        };
+
+        // Only the first of two concurrent resolutions moves the coins
+        let update =
+            sqlx::query("UPDATE challenge SET status = ?, winner = ? WHERE id = ? AND status = ?")
+                .bind(status.to_string())
+                .bind(&winner)
+                .bind(challenge.id)
+                .bind(challenge.status.to_string())
+                .execute(&mut *transaction)
+                .await?;
+        if update.rows_affected() != 1 {
+            continue;
+        }
+
+        match (challenge.status, &winner) {
+            (ChallengeStatus::Pending, _) => {
+                credit_coins(&mut transaction, &challenge.challenger, challenge.stake).await?;
+            }
+            (_, Some(winner)) => {
+                credit_coins(&mut transaction, winner, challenge.stake * 2).await?;
+            }
+            (_, None) => {
+                credit_coins(&mut transaction, &challenge.challenger, challenge.stake).await?;
+                credit_coins(&mut transaction, &challenge.opponent, challenge.stake).await?;
+            }
+        }
+        transaction.commit().await?;
+        resolved += 1;
+    }
+    Ok(resolved)
+}
+
+/// Refunds the stakes other users hold in the pending and active challenges of `username`,
+/// before the account and with it the challenges are deleted
+pub async fn refund_open_challenges(
+    connection: &mut SqliteConnection,
+    username: &str,
+) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT * FROM challenge WHERE status IN ('Pending', 'Active') AND (challenger = ? OR opponent = ?)",
+    )
+    .bind(username)
+    .bind(username)
+    .fetch_all(&mut *connection)
+    .await?;
+
+    for row in &rows {
+        let challenge = challenge_from_row(row);
+        // The opponent of a pending challenge didn't pay the stake yet
+        if challenge.status == ChallengeStatus::Active || challenge.challenger != username {
+            credit_coins(
+                &mut *connection,
+                challenge.other_user(username),
+                challenge.stake,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// None if both users did equally well
+async fn find_winner(
+    connection: &mut SqliteConnection,
+    challenge: &Challenge,
+) -> Result<Option<String>, sqlx::Error> {
+    let start_date = database_utils::format_naive_date_for_database(&challenge.start_date);
+    let end_date = database_utils::format_naive_date_for_database(&challenge.end_date);
+
+    if let ChallengeGoal::FirstToLift {
+        exercise_name,
+        weight,
+    } = &challenge.goal
+    {
+        let mut first_lifts = Vec::new();
+        for username in [&challenge.challenger, &challenge.opponent] {
+            // Sets are saved in the order they were lifted, so the lower id on the same day was first
+            let first_lift: Option<(String, i64)> = sqlx::query_as(
+                "SELECT exerciseLog.date, exerciseLog.id
+                FROM exerciseLog JOIN exercise ON exercise.id = exerciseLog.exercise_id
+                WHERE exerciseLog.username = ? AND exerciseLog.date BETWEEN ? AND ?
+                AND exercise.name = ? COLLATE NOCASE AND exerciseLog.weight_in_kg >= ?
+                ORDER BY exerciseLog.date, exerciseLog.id LIMIT 1",
+            )
+            .bind(username)
+            .bind(&start_date)
+            .bind(&end_date)
+            .bind(exercise_name)
+            .bind(weight)
+            .fetch_optional(&mut *connection)
+            .await?;
+            first_lifts.push(first_lift);
+        }
+        return Ok(match (&first_lifts[0], &first_lifts[1]) {
+            (Some(challenger_lift), Some(opponent_lift)) if opponent_lift < challenger_lift => {
+                Some(challenge.opponent.clone())
+            }
+            (Some(_), _) => Some(challenge.challenger.clone()),
+            (None, Some(_)) => Some(challenge.opponent.clone()),
+            (None, None) => None,
+        });
+    }
+
+    let score_query = match challenge.goal {
+        ChallengeGoal::MostWorkouts => {
+            "SELECT CAST(COUNT(DISTINCT workout_id) AS FLOAT) FROM exerciseLog
+            WHERE username = ? AND date BETWEEN ? AND ?"
+        }
+        _ => {
+            "SELECT COALESCE(SUM(weight_in_kg * reps), 0) FROM exerciseLog
+            WHERE username = ? AND date BETWEEN ? AND ?"
+        }
+    };
+    let mut scores = Vec::new();
+    for username in [&challenge.challenger, &challenge.opponent] {
+        let score: f64 = sqlx::query_scalar(score_query)
+            .bind(username)
+            .bind(&start_date)
+            .bind(&end_date)
+            .fetch_one(&mut *connection)
+            .await?;
+        scores.push(score);
+    }
+    Ok(if scores[0] > scores[1] {
+        Some(challenge.challenger.clone())
+    } else if scores[1] > scores[0] {
+        Some(challenge.opponent.clone())
+    } else {
+        None
+    })
+}
+
+/// Returns false without changes if the user has less than `amount` coins
+async fn debit_coins(
+    connection: &mut SqliteConnection,
+    username: &str,
+    amount: u32,
+) -> Result<bool, sqlx::Error> {
+    let debit = sqlx::query(
+        "UPDATE users SET coin_balance = coin_balance - ? WHERE username = ? AND coin_balance >= ?",
+    )
+    .bind(amount)
+    .bind(username)
+    .bind(amount)
+    .execute(connection)
+    .await?;
+    Ok(debit.rows_affected() == 1)
+}
+
+async fn credit_coins(
+    connection: &mut SqliteConnection,
+    username: &str,
+    amount: u32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE users SET coin_balance = coin_balance + ? WHERE username = ?")
+        .bind(amount)
+        .bind(username)
+        .execute(connection)
+        .await?;
+    Ok(())
+}
+
+fn challenge_from_row(row: &SqliteRow) -> Challenge {
+    let goal = match row.get::<&str, _>("goal") {
+        "MostWorkouts" => ChallengeGoal::MostWorkouts,
+        "MostVolume" => ChallengeGoal::MostVolume,
+        _ => ChallengeGoal::FirstToLift {
+            exercise_name: row
+                .get::<Option<String>, _>("exercise_name")
+                .unwrap_or_default(),
+            weight: row
+                .get::<Option<f64>, _>("target_weight")
+                .unwrap_or_default() as f32,
+        },
+    };
+    let date = |column: &str| {
+        database_utils::database_date_string_to_naive_date(row.get(column)).unwrap_or_default()
+    };
+    Challenge {
+        id: row.get("id"),
+        challenger: row.get("challenger"),
+        opponent: row.get("opponent"),
+        goal,
+        start_date: date("start_date"),
+        end_date: date("end_date"),
+        stake: row.get::<i64, _>("stake") as u32,
+        status: ChallengeStatus::from_str(row.get("status")).unwrap_or(ChallengeStatus::Expired),
+        winner: row.get("winner"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::{
+        save_test_workout, setup_test_db, test_values_for_db,
+    };
+    use crate::server::database_mod::database_user::{
+        get_user_coin_balance, update_user_coin_balance,
+    };
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    async fn setup_users_with_coins(coins: u32) -> SqlitePool {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        for username in ["testuser", "testuser2"] {
+            update_user_coin_balance(&pool, username, coins)
+                .await
+                .unwrap();
+        }
+        pool
+    }
+
+    fn new_challenge(goal: ChallengeGoal, stake: u32) -> NewChallenge {
+        NewChallenge {
+            opponent: "testuser2".to_string(),
+            goal,
+            start_date: date(2),
+            end_date: date(8),
+            stake,
+        }
+    }
+
+    async fn balance(pool: &SqlitePool, username: &str) -> u32 {
+        get_user_coin_balance(pool, username).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn winner_gets_both_stakes() {
+        let pool = setup_users_with_coins(100).await;
+        let challenge = new_challenge(
+            ChallengeGoal::FirstToLift {
+                exercise_name: "squat".to_string(),
+                weight: 100.0,
+            },
+            30,
+        );
+
+        assert!(
+            !create_challenge(
+                &pool,
+                "testuser",
+                &new_challenge(ChallengeGoal::MostWorkouts, 101)
+            )
+            .await
+            .unwrap()
+        );
+        assert!(
+            create_challenge(&pool, "testuser", &challenge)
+                .await
+                .unwrap()
+        );
+        assert_eq!(balance(&pool, "testuser").await, 70);
+
+        let challenge_id = get_challenges(&pool, "testuser2").await.unwrap()[0].id;
+        assert!(
+            answer_challenge(&pool, "testuser", challenge_id, true, date(2))
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            answer_challenge(&pool, "testuser2", challenge_id, true, date(2))
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(balance(&pool, "testuser2").await, 70);
+
+        save_test_workout(&pool, "testuser", "Squat", 100.0, date(6)).await;
+        save_test_workout(&pool, "testuser2", "Squat", 90.0, date(3)).await;
+        save_test_workout(&pool, "testuser2", "Squat", 105.0, date(5)).await;
+        // After the end date
+        save_test_workout(&pool, "testuser", "Squat", 120.0, date(9)).await;
+
+        assert_eq!(resolve_expired_challenges(&pool, date(8)).await.unwrap(), 0);
+        assert_eq!(resolve_expired_challenges(&pool, date(9)).await.unwrap(), 1);
+        assert_eq!(resolve_expired_challenges(&pool, date(9)).await.unwrap(), 0);
+
+        let challenge = &get_challenges(&pool, "testuser").await.unwrap()[0];
+        assert_eq!(challenge.status, ChallengeStatus::Completed);
+        assert_eq!(challenge.winner.as_deref(), Some("testuser2"));
+        assert_eq!(balance(&pool, "testuser").await, 70);
+        assert_eq!(balance(&pool, "testuser2").await, 130);
+    }
+
+    #[tokio::test]
+    async fn stakes_are_refunded_without_a_winner() {
+        let pool = setup_users_with_coins(50).await;
+        for _ in 0..3 {
+            create_challenge(
+                &pool,
+                "testuser",
+                &new_challenge(ChallengeGoal::MostWorkouts, 10),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(balance(&pool, "testuser").await, 20);
+        let challenges = get_challenges(&pool, "testuser2").await.unwrap();
+        answer_challenge(&pool, "testuser2", challenges[0].id, true, date(2))
+            .await
+            .unwrap();
+        answer_challenge(&pool, "testuser2", challenges[1].id, false, date(2))
+            .await
+            .unwrap();
+        assert_eq!(balance(&pool, "testuser").await, 30);
+        assert_eq!(balance(&pool, "testuser2").await, 40);
+
+        save_test_workout(&pool, "testuser", "Squat", 60.0, date(4)).await;
+        save_test_workout(&pool, "testuser2", "Squat", 40.0, date(5)).await;
+        resolve_expired_challenges(&pool, date(9)).await.unwrap();
+
+        let statuses: Vec<(ChallengeStatus, Option<String>)> = get_challenges(&pool, "testuser")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|challenge| (challenge.status, challenge.winner))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (ChallengeStatus::Completed, None),
+                (ChallengeStatus::Declined, None),
+                (ChallengeStatus::Expired, None),
+            ]
+        );
+        assert_eq!(balance(&pool, "testuser").await, 50);
+        assert_eq!(balance(&pool, "testuser2").await, 50);
+    }
+
+    #[tokio::test]
+    async fn started_challenge_can_not_be_accepted() {
+        let pool = setup_users_with_coins(50).await;
+        create_challenge(
+            &pool,
+            "testuser",
+            &new_challenge(ChallengeGoal::MostWorkouts, 10),
+        )
+        .await
+        .unwrap();
+        let challenge_id = get_challenges(&pool, "testuser2").await.unwrap()[0].id;
+
+        assert!(
+            answer_challenge(&pool, "testuser2", challenge_id, true, date(3))
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        let challenge = &get_challenges(&pool, "testuser2").await.unwrap()[0];
+        assert_eq!(challenge.status, ChallengeStatus::Expired);
+        assert_eq!(balance(&pool, "testuser").await, 50);
+        assert_eq!(balance(&pool, "testuser2").await, 50);
+    }
+
+    #[tokio::test]
+    async fn pending_challenge_expires_once_it_started() {
+        let pool = setup_users_with_coins(50).await;
+        create_challenge(
+            &pool,
+            "testuser",
+            &new_challenge(ChallengeGoal::MostWorkouts, 10),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(resolve_expired_challenges(&pool, date(2)).await.unwrap(), 0);
+        assert_eq!(resolve_expired_challenges(&pool, date(3)).await.unwrap(), 1);
+        assert_eq!(balance(&pool, "testuser").await, 50);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::common::exercise_mod::custom_exercise::{CustomExercise, NewCustomExercise};
+use crate::server::database_mod::database_exercise::general_exercise_info_from_row;
+use sqlx::{Row, SqlitePool};
+
+/// Custom exercises `username` can track, ordered by id. These are their own ones, the ones
+/// friends share with them and the ones they tracked before, e.g. of a friend who removed them
+pub async fn get_custom_exercises(
+    pool: &SqlitePool,
+    username: &str,
+) -> Result<Vec<CustomExercise>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT * FROM exercise WHERE created_by IS NOT NULL AND (created_by = ?
+        OR (shared_with_friends AND created_by IN (SELECT username FROM friendship WHERE friendname = ?))
+        OR id IN (SELECT exercise_id FROM exerciseLog WHERE username = ?))
+        ORDER BY id",
+    )
+    .bind(username)
+    .bind(username)
+    .bind(username)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| CustomExercise {
+            general_exercise_info: general_exercise_info_from_row(row),
+            created_by: row.get("created_by"),
+            shared_with_friends: row.get("shared_with_friends"),
+        })
+        .collect())
+}
+
+/// Stores a custom exercise of `username` with a trimmed name.
+/// Check the name with NewCustomExercise::validation_error first
+pub async fn add_custom_exercise(
+    pool: &SqlitePool,
+    username: &str,
+    new_custom_exercise: &NewCustomExercise,
+) -> Result<CustomExercise, sqlx::Error> {
+    let exercise_id: i64 = sqlx::query(
+        "INSERT INTO exercise (name, exercise_force_name, exercise_level_name, exercise_equipment_name,
+        muscle_name, instructions, exercise_category_name, created_by, shared_with_friends)
+        VALUES (?, ?, ?, ?, ?, '', ?, ?, ?)",
+    )
+    .bind(new_custom_exercise.name.trim())
+    .bind(new_custom_exercise.force.to_string())
+    .bind(new_custom_exercise.level.to_string())
+    .bind(new_custom_exercise.equipment.to_string())
+    .bind(new_custom_exercise.primary_muscle.to_string())
+    .bind(new_custom_exercise.category.to_string())
+    .bind(username)
+    .bind(new_custom_exercise.shared_with_friends)
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
+
+    let row = sqlx::query("SELECT * FROM exercise WHERE id = ?")
+        .bind(exercise_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(CustomExercise {
+        general_exercise_info: general_exercise_info_from_row(&row),
+        created_by: username.to_string(),
+        shared_with_friends: new_custom_exercise.shared_with_friends,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::exercise_mod::general_exercise::{
+        ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, Muscle,
+    };
+    use crate::server::database_mod::database::{
+        save_test_workout, setup_test_db, test_values_for_db,
+    };
+    use crate::server::database_mod::database_exercise::add_workout_to_exercise_log;
+    use crate::server::database_mod::database_social::{add_friend, remove_friend};
+    use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+    use crate::server::routes::workout::ExerciseJson;
+    use chrono::NaiveDate;
+
+    fn pendulum_squat(shared_with_friends: bool) -> NewCustomExercise {
+        NewCustomExercise {
+            name: " Pendulum squat ".to_string(),
+            primary_muscle: Muscle::Quadriceps,
+            equipment: ExerciseEquipment::Machine,
+            force: ExerciseForce::Push,
+            level: ExerciseLevel::Intermediate,
+            category: ExerciseCategory::Strength,
+            shared_with_friends,
+        }
+    }
+
+    fn names(custom_exercises: &[CustomExercise]) -> Vec<&str> {
+        custom_exercises
+            .iter()
+            .map(|custom_exercise| custom_exercise.general_exercise_info.name.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn custom_exercise_is_private_and_left_out_of_the_catalog() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+
+        let custom_exercise = add_custom_exercise(&pool, "testuser", &pendulum_squat(false))
+            .await
+            .unwrap();
+        assert_eq!(custom_exercise.general_exercise_info.name, "Pendulum squat");
+        assert_eq!(
+            custom_exercise.general_exercise_info.primary_muscle,
+            Muscle::Quadriceps
+        );
+
+        let own_exercises = get_custom_exercises(&pool, "testuser").await.unwrap();
+        assert_eq!(names(&own_exercises), vec!["Pendulum squat"]);
+        assert!(
+            get_custom_exercises(&pool, "testuser2")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let catalog = ExerciseCatalog::default();
+        assert_eq!(catalog.current(&pool).await.unwrap().exercises.len(), 2);
+
+        // Another user can use the same name for their own exercise
+        add_custom_exercise(&pool, "testuser2", &pendulum_squat(false))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shared_custom_exercise_can_be_tracked_by_friends() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_custom_exercise(&pool, "testuser", &pendulum_squat(true))
+            .await
+            .unwrap();
+        let workout = || {
+            vec![ExerciseJson {
+                name: "Pendulum squat".to_string(),
+                sets: vec![],
+            }]
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        assert!(
+            add_workout_to_exercise_log(&pool, "testuser2", workout(), date)
+                .await
+                .is_err()
+        );
+
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        assert_eq!(
+            names(&get_custom_exercises(&pool, "testuser2").await.unwrap()),
+            vec!["Pendulum squat"]
+        );
+        add_workout_to_exercise_log(&pool, "testuser2", workout(), date)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn tracked_custom_exercise_stays_after_unfriending() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_custom_exercise(&pool, "testuser", &pendulum_squat(true))
+            .await
+            .unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        save_test_workout(
+            &pool,
+            "testuser2",
+            "Pendulum squat",
+            80.0,
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+        )
+        .await;
+
+        remove_friend(&pool, "testuser", "testuser2").await.unwrap();
+        assert_eq!(
+            names(&get_custom_exercises(&pool, "testuser2").await.unwrap()),
+            vec!["Pendulum squat"]
+        );
+    }
+}
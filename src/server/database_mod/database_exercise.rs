@@ -1,15 +1,25 @@
-use crate::common::exercise_mod::exercise::Exercise;
+use crate::client::server_communication::exercise_communicator::SetJson;
+use crate::common::exercise_mod::exercise::{Exercise, ExerciseSets, combine_catalog_with_sets};
 use crate::common::exercise_mod::general_exercise::{
     ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, GeneralExerciseInfo, Id,
     Muscle,
 };
 use crate::common::exercise_mod::set::StrengthSet;
 use crate::common::exercise_mod::weight::Kg;
+use crate::common::workout::{PresetTraining, SavedWorkout};
+use crate::server::database_mod::database_custom_exercise::get_custom_exercises;
+use crate::server::database_mod::database_feed::delete_workout_events;
+use crate::server::database_mod::database_preset::{
+    forget_preset_training, get_preset_stats, record_preset_training,
+};
 use crate::server::database_mod::database_utils;
-use crate::server::routes::workout::ExerciseJson;
-use chrono::NaiveDate;
-use sqlx::{Row, SqlitePool};
-use std::collections::BTreeMap;
+use crate::server::database_mod::database_workout_reaction::delete_workout_reactions;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use crate::server::routes::workout::{ExerciseJson, LoggedWorkoutJson};
+use chrono::{NaiveDate, NaiveTime};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 
 pub async fn get_general_exercise_info(
@@ -21,7 +31,59 @@ pub async fn get_general_exercise_info(
         .fetch_one(pool)
         .await?;
 
-    Ok(GeneralExerciseInfo {
+    let secondary_muscle_rows = sqlx::query(
+        "SELECT muscle_name FROM exercise_secondary_muscle WHERE exercise_id = ? ORDER BY position",
+    )
+    .bind(exercise_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut general_exercise_info = general_exercise_info_from_row(&row);
+    general_exercise_info.secondary_muscles = secondary_muscle_rows
+        .iter()
+        .filter_map(|row| Muscle::from_str(row.get("muscle_name")).ok())
+        .collect();
+    Ok(general_exercise_info)
+}
+
+/// All exercises of the catalog ordered by id, see ExerciseCatalog.
+/// Custom exercises of users are left out
+pub async fn get_all_general_exercise_infos(
+    pool: &SqlitePool,
+) -> Result<Vec<GeneralExerciseInfo>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM exercise WHERE created_by IS NULL ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    let secondary_muscle_rows = sqlx::query(
+        "SELECT exercise_id, muscle_name FROM exercise_secondary_muscle ORDER BY exercise_id, position",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut secondary_muscles_by_exercise: HashMap<Id, Vec<Muscle>> = HashMap::new();
+    for row in secondary_muscle_rows {
+        if let Ok(muscle) = Muscle::from_str(row.get("muscle_name")) {
+            secondary_muscles_by_exercise
+                .entry(row.get("exercise_id"))
+                .or_default()
+                .push(muscle);
+        }
+    }
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let mut general_exercise_info = general_exercise_info_from_row(row);
+            general_exercise_info.secondary_muscles = secondary_muscles_by_exercise
+                .remove(&general_exercise_info.id)
+                .unwrap_or_default();
+            general_exercise_info
+        })
+        .collect())
+}
+
+pub fn general_exercise_info_from_row(row: &SqliteRow) -> GeneralExerciseInfo {
+    GeneralExerciseInfo {
         id: row.get("id"),
         name: row.get("name"),
         force: ExerciseForce::from_str(row.get("exercise_force_name"))
@@ -31,10 +93,11 @@ pub async fn get_general_exercise_info(
         equipment: ExerciseEquipment::from_str(row.get("exercise_equipment_name"))
             .unwrap_or(ExerciseEquipment::Body),
         primary_muscle: Muscle::from_str(row.get("muscle_name")).unwrap_or(Muscle::Chest),
+        secondary_muscles: Vec::new(),
         instructions: row.get("instructions"),
         category: ExerciseCategory::from_str(row.get("exercise_category_name"))
             .unwrap_or(ExerciseCategory::Strength),
-    })
+    }
 }
 #[allow(dead_code)]
 pub async fn get_exercise_name(pool: &SqlitePool, exercise_id: i64) -> Result<String, sqlx::Error> {
@@ -140,58 +203,70 @@ pub async fn get_exercise_muscle_name(
     Ok(exercise_muscle)
 }
 
+/// Every exercise of the catalog and every custom exercise of `username` with all sets
+/// they have logged for it. The catalog exercises come from the in-memory catalog
 pub async fn get_exercises_stats(
     pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
     username: &str,
 ) -> Result<Vec<Exercise>, sqlx::Error> {
-    let exercise_row_for_user = sqlx::query(
-        "SELECT date,reps,weight_in_kg,exercise_id,workout_id FROM exerciseLog WHERE username = ? ",
-    )
-    .bind(username)
-    .fetch_all(pool)
-    .await?;
+    let mut exercises = exercise_catalog.current(pool).await?.exercises.clone();
+    exercises.extend(
+        get_custom_exercises(pool, username)
+            .await?
+            .into_iter()
+            .map(|custom_exercise| custom_exercise.general_exercise_info),
+    );
+    let exercise_sets = get_exercise_sets(pool, username).await?;
+    Ok(combine_catalog_with_sets(exercises, exercise_sets))
+}
+
+/// Runs once for every loaded profile, so it has to stay on the
+/// `exerciseLog_username_exercise_id` index
+pub(crate) const EXERCISE_SETS_QUERY: &str = "SELECT exerciseLog.date, exerciseLog.reps,
+    exerciseLog.weight_in_kg, exerciseLog.exercise_id, exerciseLog.workout_id
+    FROM exerciseLog JOIN exercise ON exercise.id = exerciseLog.exercise_id
+    WHERE exerciseLog.username = ?
+    ORDER BY exerciseLog.exercise_id, exerciseLog.id";
 
-    let all_exercises = sqlx::query("SELECT id, name FROM exercise")
+/// The sets `username` has logged, grouped by exercise. Untracked exercises are left out
+pub async fn get_exercise_sets(
+    pool: &SqlitePool,
+    username: &str,
+) -> Result<Vec<ExerciseSets>, sqlx::Error> {
+    let log_rows = sqlx::query(EXERCISE_SETS_QUERY)
+        .bind(username)
         .fetch_all(pool)
         .await?;
 
-    let mut exercises = Vec::new();
-    let mut exercise_ids: Vec<i64> = Vec::new();
-
-    for all_exercise_counter in all_exercises {
-        let id = all_exercise_counter.get("id");
-        let exercise_info = get_general_exercise_info(pool, id).await?;
-        exercises.push(Exercise {
-            general_exercise_info: exercise_info,
-            sets: BTreeMap::new(),
-        });
-        exercise_ids.push(id);
-    }
-
-    for exercise_log_counter in exercise_row_for_user {
-        let exercise_id: i64 = exercise_log_counter.get("exercise_id");
-        let workout_id: Id = exercise_log_counter.get("workout_id");
-        let reps: u32 = exercise_log_counter.get("reps");
-        let weight: Kg = exercise_log_counter.get("weight_in_kg");
-        let date: &str = exercise_log_counter.get("date");
-
-        let real_date = database_utils::database_date_string_to_naive_date(date).unwrap();
-
-        for i in 0..exercise_ids.len() {
-            if exercise_ids[i] == exercise_id {
-                let exercise = &mut exercises[i];
-                exercise.sets.entry(real_date).or_insert_with(Vec::new);
-                let set = StrengthSet {
-                    workout_id,
-                    weight,
-                    reps,
-                };
-                //exercise.name = real_name;
-                exercise.sets.get_mut(&real_date).unwrap().push(set);
-            }
+    let mut exercise_sets: Vec<ExerciseSets> = Vec::new();
+    for log_row in log_rows {
+        let exercise_id: Id = log_row.get("exercise_id");
+        let date: &str = log_row.get("date");
+        let Ok(real_date) = database_utils::database_date_string_to_naive_date(date) else {
+            continue;
+        };
+
+        // Rows are ordered by exercise, so all sets of an exercise follow each other
+        if exercise_sets.last().map(|sets| sets.exercise_id) != Some(exercise_id) {
+            exercise_sets.push(ExerciseSets {
+                exercise_id,
+                sets: BTreeMap::new(),
+            });
+        }
+        if let Some(current_exercise_sets) = exercise_sets.last_mut() {
+            current_exercise_sets
+                .sets
+                .entry(real_date)
+                .or_default()
+                .push(StrengthSet {
+                    workout_id: log_row.get("workout_id"),
+                    weight: log_row.get::<Kg, _>("weight_in_kg"),
+                    reps: log_row.get("reps"),
+                });
         }
     }
-    Ok(exercises)
+    Ok(exercise_sets)
 }
 
 pub async fn add_workout_to_exercise_log(
@@ -201,46 +276,283 @@ pub async fn add_workout_to_exercise_log(
     date: NaiveDate,
 ) -> Result<Id, sqlx::Error> {
     let mut transaction = pool.begin().await?;
+    let workout_id = insert_workout(&mut transaction, username, workout, date, None).await?;
+    transaction.commit().await?;
 
-    let max_id_row = sqlx::query("SELECT MAX(workout_id) as max_id FROM exerciseLog")
-        .fetch_optional(&mut *transaction)
-        .await?;
+    Ok(workout_id)
+}
 
-    let string_date = database_utils::format_naive_date_for_database(&date);
+/// Saves the workout and pays `daily_reward` coins if it is the first workout of the user on `date`.
+/// Workouts without any sets are stored without a reward.
+/// Rewarded days are remembered, so deleting and saving the workout again doesn't pay twice.
+/// As the check uses the date of the workout, backdated workouts are rewarded like any other.
+/// A preset the workout was started from is counted in the same transaction
+pub async fn add_workout_with_daily_reward(
+    pool: &SqlitePool,
+    username: &str,
+    workout: Vec<ExerciseJson>,
+    date: NaiveDate,
+    start_time: Option<NaiveTime>,
+    daily_reward: u32,
+    preset_training: Option<PresetTraining>,
+) -> Result<SavedWorkout, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+
+    let has_sets = workout.iter().any(|exercise| !exercise.sets.is_empty());
+    let workout_id = insert_workout(&mut transaction, username, workout, date, start_time).await?;
+
+    if has_sets {
+        let first_workout_of_day =
+            sqlx::query("INSERT OR IGNORE INTO daily_reward (username, date) VALUES (?, ?)")
+                .bind(username)
+                .bind(database_utils::format_naive_date_for_database(&date))
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected()
+                == 1;
+        if first_workout_of_day {
+            sqlx::query("UPDATE users SET coin_balance = coin_balance + ? WHERE username = ?")
+                .bind(daily_reward)
+                .bind(username)
+                .execute(&mut *transaction)
+                .await?;
+        }
+    }
+    let coin_balance = sqlx::query("SELECT coin_balance FROM users WHERE username = ?")
+        .bind(username)
+        .fetch_one(&mut *transaction)
+        .await?
+        .get("coin_balance");
+
+    let mut trained_preset = None;
+    if let Some(preset_training) = preset_training
+        && record_preset_training(
+            &mut transaction,
+            username,
+            workout_id,
+            date,
+            preset_training,
+        )
+        .await?
+    {
+        let stats =
+            get_preset_stats(&mut transaction, username, preset_training.preset_id as i64).await?;
+        trained_preset = Some((preset_training.preset_id, stats));
+    }
+
+    transaction.commit().await?;
+
+    Ok(SavedWorkout {
+        workout_id,
+        date,
+        coin_balance,
+        trained_preset,
+    })
+}
 
-    let mut next_id: i64 = match max_id_row {
-        Some(r) => r.get("max_id"),
-        None => 1,
+/// Returns the date and the exercises of a workout, None if the user has no workout with this id
+pub async fn get_workout(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+) -> Result<Option<LoggedWorkoutJson>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT exerciseLog.date, exerciseLog.start_time, exercise.name, exerciseLog.weight_in_kg,
+        exerciseLog.reps FROM exerciseLog JOIN exercise ON exercise.id = exerciseLog.exercise_id
+        WHERE exerciseLog.username = ? AND exerciseLog.workout_id = ?
+        ORDER BY exerciseLog.id",
+    )
+    .bind(username)
+    .bind(workout_id)
+    .fetch_all(pool)
+    .await?;
+
+    let Some(first_row) = rows.first() else {
+        return Ok(None);
     };
+    let date = database_utils::database_date_string_to_naive_date(first_row.get("date"))
+        .map_err(|err| sqlx::Error::Decode(Box::new(err)))?;
+    let start_time = parse_start_time(first_row.get("start_time"))?;
+
+    let mut workout: Vec<ExerciseJson> = Vec::new();
+    for row in rows {
+        let name: String = row.get("name");
+        let set = SetJson {
+            weight: row.get("weight_in_kg"),
+            reps: row.get("reps"),
+        };
+        match workout.iter_mut().find(|exercise| exercise.name == name) {
+            Some(exercise) => exercise.sets.push(set),
+            None => workout.push(ExerciseJson {
+                name,
+                sets: vec![set],
+            }),
+        }
+    }
+
+    Ok(Some(LoggedWorkoutJson {
+        date,
+        start_time,
+        workout,
+    }))
+}
 
-    next_id += 1;
+/// Replaces every set of the workout while keeping its id, date and start time. <br>
+/// Returns false without changing anything if the user has no workout with this id
+pub async fn update_workout(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+    workout: Vec<ExerciseJson>,
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+
+    let date_row = sqlx::query(
+        "SELECT date, start_time FROM exerciseLog WHERE username = ? AND workout_id = ? LIMIT 1",
+    )
+    .bind(username)
+    .bind(workout_id)
+    .fetch_optional(&mut *transaction)
+    .await?;
+    let Some(date_row) = date_row else {
+        transaction.rollback().await?;
+        return Ok(false);
+    };
+    let date = database_utils::database_date_string_to_naive_date(date_row.get("date"))
+        .map_err(|err| sqlx::Error::Decode(Box::new(err)))?;
+    let start_time = parse_start_time(date_row.get("start_time"))?;
+
+    sqlx::query("DELETE FROM exerciseLog WHERE username = ? AND workout_id = ?")
+        .bind(username)
+        .bind(workout_id)
+        .execute(&mut *transaction)
+        .await?;
+    insert_workout_sets(
+        &mut transaction,
+        username,
+        workout_id,
+        workout,
+        date,
+        start_time,
+    )
+    .await?;
+
+    transaction.commit().await?;
+    Ok(true)
+}
+
+/// Returns false if the user has no workout with this id
+pub async fn delete_workout(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let result = sqlx::query("DELETE FROM exerciseLog WHERE username = ? AND workout_id = ?")
+        .bind(username)
+        .bind(workout_id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query("DELETE FROM workout WHERE id = ? AND username = ?")
+        .bind(workout_id)
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+    forget_preset_training(&mut transaction, username, workout_id).await?;
+    delete_workout_events(&mut transaction, username, workout_id).await?;
+    // Reactions are only stored by workout id, which must belong to the user
+    if result.rows_affected() > 0 {
+        delete_workout_reactions(&mut transaction, workout_id).await?;
+    }
+    transaction.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+const START_TIME_FORMAT: &str = "%H:%M";
+
+fn parse_start_time(start_time: Option<String>) -> Result<Option<NaiveTime>, sqlx::Error> {
+    start_time
+        .map(|start_time| NaiveTime::parse_from_str(&start_time, START_TIME_FORMAT))
+        .transpose()
+        .map_err(|err| sqlx::Error::Decode(Box::new(err)))
+}
+
+async fn insert_workout(
+    connection: &mut SqliteConnection,
+    username: &str,
+    workout: Vec<ExerciseJson>,
+    date: NaiveDate,
+    start_time: Option<NaiveTime>,
+) -> Result<Id, sqlx::Error> {
+    // AUTOINCREMENT never hands out the id of a deleted workout again
+    let workout_id = sqlx::query("INSERT INTO workout (username) VALUES (?)")
+        .bind(username)
+        .execute(&mut *connection)
+        .await?
+        .last_insert_rowid() as Id;
+
+    insert_workout_sets(connection, username, workout_id, workout, date, start_time).await?;
+
+    Ok(workout_id)
+}
+
+async fn insert_workout_sets(
+    connection: &mut SqliteConnection,
+    username: &str,
+    workout_id: Id,
+    workout: Vec<ExerciseJson>,
+    date: NaiveDate,
+    start_time: Option<NaiveTime>,
+) -> Result<(), sqlx::Error> {
+    let string_date = database_utils::format_naive_date_for_database(&date);
+    let string_start_time = start_time.map(|time| time.format(START_TIME_FORMAT).to_string());
 
     for exercises in workout {
-        let exercise_id_row = sqlx::query("SELECT id FROM exercise WHERE name = ?")
-            .bind(&exercises.name)
-            .fetch_one(pool)
-            .await?;
-        let exercise_id: i64 = exercise_id_row.get("id");
+        let exercise_id = get_trackable_exercise_id(connection, username, &exercises.name).await?;
 
         for set in exercises.sets {
             sqlx::query(
-                "INSERT INTO exerciseLog (date, username, reps, exercise_id, weight_in_kg, workout_id)
-             VALUES (?, ?, ?, ?, ?, ?)"
+                "INSERT INTO exerciseLog (date, username, reps, exercise_id, weight_in_kg, workout_id, start_time)
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
             )
                 .bind(&string_date)
                 .bind(username.to_string())
                 .bind(set.reps)
                 .bind(exercise_id)
                 .bind(set.weight)
-                .bind(next_id)
-                .execute(&mut *transaction)
+                .bind(workout_id)
+                .bind(&string_start_time)
+                .execute(&mut *connection)
                 .await?;
         }
     }
 
-    transaction.commit().await?;
+    Ok(())
+}
+
+/// Id of the exercise called `name` that `username` can track, a catalog exercise, one of their
+/// custom exercises or one a friend shares. Their own custom exercises win on equal names.
+/// Returns RowNotFound if there is none
+async fn get_trackable_exercise_id(
+    connection: &mut SqliteConnection,
+    username: &str,
+    name: &str,
+) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT id FROM exercise WHERE name = ? AND (created_by IS NULL OR created_by = ?
+        OR (shared_with_friends AND created_by IN (SELECT username FROM friendship WHERE friendname = ?)))
+        ORDER BY CASE WHEN created_by = ? THEN 0 WHEN created_by IS NULL THEN 1 ELSE 2 END
+        LIMIT 1",
+    )
+    .bind(name)
+    .bind(username)
+    .bind(username)
+    .bind(username)
+    .fetch_one(connection)
+    .await?;
 
-    Ok(next_id as Id)
+    Ok(row.get("id"))
 }
 
 pub async fn add_exercise_log(
@@ -252,12 +564,13 @@ pub async fn add_exercise_log(
     date: NaiveDate,
     workout_id: u32,
 ) -> Result<(), sqlx::Error> {
-    let row = sqlx::query("SELECT id from exercise WHERE name = ?")
-        .bind(exercise_name)
-        .fetch_one(pool)
+    let mut connection = pool.acquire().await?;
+    let id = get_trackable_exercise_id(&mut connection, username, exercise_name).await?;
+    sqlx::query("INSERT OR IGNORE INTO workout (id, username) VALUES (?, ?)")
+        .bind(workout_id)
+        .bind(username)
+        .execute(&mut *connection)
         .await?;
-
-    let id: i64 = row.get("id");
     let date_str = database_utils::format_naive_date_for_database(&date);
     sqlx::query(
         "INSERT INTO exerciseLog (date, username, reps, exercise_id, weight_in_kg, workout_id)
@@ -269,8 +582,272 @@ pub async fn add_exercise_log(
     .bind(id)
     .bind(weight)
     .bind(workout_id)
-    .execute(pool)
+    .execute(&mut *connection)
     .await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::workout_preset::WorkoutPreset;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+    use crate::server::database_mod::database_preset::{add_preset, add_preset_to_user};
+    use crate::server::database_mod::database_user::get_user_coin_balance;
+
+    fn squat_workout(number_of_sets: usize) -> Vec<ExerciseJson> {
+        vec![ExerciseJson {
+            name: "Squat".to_string(),
+            sets: (0..number_of_sets)
+                .map(|_| SetJson {
+                    weight: 60.0,
+                    reps: 5,
+                })
+                .collect(),
+        }]
+    }
+
+    #[tokio::test]
+    async fn only_first_workout_of_day_is_rewarded() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let start_balance = get_user_coin_balance(&pool, "testuser").await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let first =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+        assert_eq!(first.coin_balance, start_balance + 5);
+
+        let second =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(1), date, None, 5, None)
+                .await
+                .unwrap();
+        assert_eq!(second.coin_balance, start_balance + 5);
+        assert_ne!(first.workout_id, second.workout_id);
+
+        let next_day = date.succ_opt().unwrap();
+        let third = add_workout_with_daily_reward(
+            &pool,
+            "testuser",
+            squat_workout(1),
+            next_day,
+            None,
+            5,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(third.coin_balance, start_balance + 10);
+        assert_eq!(
+            get_user_coin_balance(&pool, "testuser").await.unwrap(),
+            start_balance + 10
+        );
+    }
+    #[tokio::test]
+    async fn deleted_workout_is_not_rewarded_again() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let start_balance = get_user_coin_balance(&pool, "testuser").await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let first =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+        assert!(
+            delete_workout(&pool, "testuser", first.workout_id)
+                .await
+                .unwrap()
+        );
+        let saved_again =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+
+        assert_eq!(saved_again.coin_balance, start_balance + 5);
+        assert_eq!(
+            get_user_coin_balance(&pool, "testuser").await.unwrap(),
+            start_balance + 5
+        );
+    }
+    #[tokio::test]
+    async fn workout_without_sets_is_not_rewarded() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let start_balance = get_user_coin_balance(&pool, "testuser").await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let saved =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(0), date, None, 5, None)
+                .await
+                .unwrap();
+        assert_eq!(saved.coin_balance, start_balance);
+    }
+    #[tokio::test]
+    async fn get_update_and_delete_workout() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let saved =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+
+        let workout = get_workout(&pool, "testuser", saved.workout_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(workout.date, date);
+        assert_eq!(workout.workout[0].name, "Squat");
+        assert_eq!(workout.workout[0].sets.len(), 2);
+
+        let mut edited_workout = squat_workout(1);
+        edited_workout[0].sets[0].weight = 62.5;
+        assert!(
+            update_workout(&pool, "testuser", saved.workout_id, edited_workout)
+                .await
+                .unwrap()
+        );
+        let workout = get_workout(&pool, "testuser", saved.workout_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(workout.date, date);
+        assert_eq!(workout.workout[0].sets.len(), 1);
+        assert_eq!(workout.workout[0].sets[0].weight, 62.5);
+
+        assert!(
+            delete_workout(&pool, "testuser", saved.workout_id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            get_workout(&pool, "testuser", saved.workout_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+    #[tokio::test]
+    async fn ids_of_deleted_workouts_are_not_reused() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let deleted =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+        assert!(
+            delete_workout(&pool, "testuser", deleted.workout_id)
+                .await
+                .unwrap()
+        );
+
+        let saved =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+        assert!(saved.workout_id > deleted.workout_id);
+    }
+    #[tokio::test]
+    async fn preset_training_is_recorded_with_the_workout() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let preset = WorkoutPreset {
+            exercises: vec!["Squat".to_string()],
+            ..Default::default()
+        };
+        let preset_id = add_preset(&pool, &preset, 10).await.unwrap();
+        add_preset_to_user(&pool, "testuser", preset_id)
+            .await
+            .unwrap();
+        let preset_training = PresetTraining {
+            preset_id: preset_id as Id,
+            duration_minutes: Some(40),
+        };
+
+        let saved = add_workout_with_daily_reward(
+            &pool,
+            "testuser",
+            squat_workout(2),
+            date,
+            None,
+            5,
+            Some(preset_training),
+        )
+        .await
+        .unwrap();
+
+        let (trained_preset_id, stats) = saved.trained_preset.unwrap();
+        assert_eq!(trained_preset_id, preset_id as Id);
+        assert_eq!(stats.times_trained, 1);
+        assert_eq!(stats.last_trained, Some(date));
+        assert_eq!(stats.average_duration_minutes, Some(40));
+    }
+
+    #[tokio::test]
+    async fn workouts_of_other_users_are_not_accessible() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let saved =
+            add_workout_with_daily_reward(&pool, "testuser", squat_workout(2), date, None, 5, None)
+                .await
+                .unwrap();
+
+        assert!(
+            get_workout(&pool, "testuser2", saved.workout_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            !update_workout(&pool, "testuser2", saved.workout_id, squat_workout(1))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !delete_workout(&pool, "testuser2", saved.workout_id)
+                .await
+                .unwrap()
+        );
+        let workout = get_workout(&pool, "testuser", saved.workout_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(workout.workout[0].sets.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn secondary_muscles_are_loaded_in_order() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        for (position, muscle_name) in ["quadriceps", "not a muscle", "lower back"]
+            .iter()
+            .enumerate()
+        {
+            sqlx::query(
+                "INSERT INTO exercise_secondary_muscle (exercise_id, position, muscle_name) VALUES (2, ?, ?)",
+            )
+            .bind(position as i64)
+            .bind(muscle_name)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let squat = get_general_exercise_info(&pool, 2).await.unwrap();
+        assert_eq!(
+            squat.secondary_muscles,
+            vec![Muscle::Quadriceps, Muscle::LowerBack]
+        );
+
+        let all_exercises = get_all_general_exercise_infos(&pool).await.unwrap();
+        assert!(all_exercises[0].secondary_muscles.is_empty());
+        assert_eq!(all_exercises[1].secondary_muscles, squat.secondary_muscles);
+    }
+}
@@ -0,0 +1,517 @@
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::exercise_mod::weight::Kg;
+use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::social_feed::{FeedEvent, FeedEventKind, FeedPage};
+use crate::common::user_mod::user_goals::GoalType;
+use crate::server::database_mod::database_mascot::mascot_from_string;
+use crate::server::database_mod::database_user_goals::get_user_goals;
+use crate::server::database_mod::database_utils;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+const WORKOUT_SAVED: &str = "WorkoutSaved";
+const PERSONAL_RECORD: &str = "PersonalRecord";
+const MASCOT_BOUGHT: &str = "MascotBought";
+const GOAL_REACHED: &str = "GoalReached";
+
+/// Feed events are a side effect, so a failure is only logged and never fails the action
+/// that caused the event
+pub fn log_failed_feed_event(username: &str, result: Result<(), sqlx::Error>) {
+    if let Err(err) = result {
+        eprintln!("{}: Saving a feed event failed: {err}", username);
+    }
+}
+
+/// Records the saved workout, the personal records set in it and whether it completed the
+/// weekly workouts goal. Workouts without sets are not recorded
+pub async fn add_workout_events(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+    duration_minutes: Option<u32>,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+
+    let exercise_rows = sqlx::query(
+        "SELECT exercise.name, MAX(workout_set.weight_in_kg) AS weight, workout_set.date,
+        (SELECT MAX(earlier_set.weight_in_kg) FROM exerciseLog AS earlier_set
+            WHERE earlier_set.username = workout_set.username
+            AND earlier_set.exercise_id = workout_set.exercise_id
+            AND earlier_set.workout_id != workout_set.workout_id) AS previous_weight
+        FROM exerciseLog AS workout_set JOIN exercise ON exercise.id = workout_set.exercise_id
+        WHERE workout_set.username = ? AND workout_set.workout_id = ?
+        GROUP BY workout_set.exercise_id
+        ORDER BY MIN(workout_set.id)",
+    )
+    .bind(username)
+    .bind(workout_id)
+    .fetch_all(&mut *transaction)
+    .await?;
+    let Some(first_row) = exercise_rows.first() else {
+        return Ok(());
+    };
+    let date = database_utils::database_date_string_to_naive_date(first_row.get("date"))
+        .map_err(|err| sqlx::Error::Decode(Box::new(err)))?;
+
+    insert_event(
+        &mut transaction,
+        username,
+        WORKOUT_SAVED,
+        EventColumns {
+            workout_id: Some(workout_id),
+            duration_minutes,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    for row in &exercise_rows {
+        let weight: f64 = row.get("weight");
+        let previous_weight: Option<f64> = row.get("previous_weight");
+        // The first sets of an exercise are no record worth sharing
+        if previous_weight.is_some_and(|previous_weight| weight > previous_weight) {
+            insert_event(
+                &mut transaction,
+                username,
+                PERSONAL_RECORD,
+                EventColumns {
+                    workout_id: Some(workout_id),
+                    exercise_name: Some(row.get("name")),
+                    value: Some(weight),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+    }
+
+    if let Some(workout_days) =
+        completed_weekly_workouts_goal(&mut transaction, username, workout_id, date).await?
+    {
+        insert_event(
+            &mut transaction,
+            username,
+            GOAL_REACHED,
+            EventColumns {
+                workout_id: Some(workout_id),
+                goal_type: Some(GoalType::WeeklyWorkouts),
+                value: Some(workout_days as f64),
+                ..Default::default()
+            },
+        )
+        .await?;
+    }
+
+    transaction.commit().await
+}
+
+/// Number of workout days in the week of `date` if the workout was the first on its day and
+/// made them reach the weekly workouts goal exactly
+async fn completed_weekly_workouts_goal(
+    connection: &mut SqliteConnection,
+    username: &str,
+    workout_id: Id,
+    date: NaiveDate,
+) -> Result<Option<i64>, sqlx::Error> {
+    let week_start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    let week_end = week_start + Duration::days(6);
+
+    let row = sqlx::query(
+        "SELECT COUNT(DISTINCT date) AS workout_days,
+        EXISTS(SELECT 1 FROM exerciseLog WHERE username = ? AND date = ? AND workout_id != ?)
+            AS worked_out_before
+        FROM exerciseLog WHERE username = ? AND date BETWEEN ? AND ?",
+    )
+    .bind(username)
+    .bind(database_utils::format_naive_date_for_database(&date))
+    .bind(workout_id)
+    .bind(username)
+    .bind(database_utils::format_naive_date_for_database(&week_start))
+    .bind(database_utils::format_naive_date_for_database(&week_end))
+    .fetch_one(&mut *connection)
+    .await?;
+    let workout_days: i64 = row.get("workout_days");
+    let worked_out_before: bool = row.get("worked_out_before");
+
+    let weekly_workouts_goal: f64 =
+        sqlx::query_scalar("SELECT weekly_workouts FROM user_goals WHERE username = ?")
+            .bind(username)
+            .fetch_optional(&mut *connection)
+            .await?
+            .unwrap_or(GoalType::WeeklyWorkouts.get_default_goal_value() as f64);
+
+    Ok((!worked_out_before && workout_days as f64 == weekly_workouts_goal).then_some(workout_days))
+}
+
+pub async fn add_mascot_bought_event(
+    pool: &SqlitePool,
+    username: &str,
+    mascot: Mascot,
+) -> Result<(), sqlx::Error> {
+    let mut connection = pool.acquire().await?;
+    insert_event(
+        &mut connection,
+        username,
+        MASCOT_BOUGHT,
+        EventColumns {
+            mascot_name: Some(mascot.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Records that a daily goal was reached if `new_value` reaches it and `previous_value`,
+/// the value logged on the same date before, didn't
+pub async fn add_goal_reached_event_if_new(
+    pool: &SqlitePool,
+    username: &str,
+    goal_type: GoalType,
+    previous_value: Option<f32>,
+    new_value: f32,
+) -> Result<(), sqlx::Error> {
+    if !GoalType::DAILY_LOG_TYPES.contains(&goal_type) {
+        return Ok(());
+    }
+    let goal = get_user_goals(pool, username)
+        .await?
+        .get_goal_by_type(&goal_type);
+    let reached_before = previous_value.is_some_and(|previous_value| previous_value >= goal);
+    if reached_before || new_value < goal {
+        return Ok(());
+    }
+
+    let mut connection = pool.acquire().await?;
+    insert_event(
+        &mut connection,
+        username,
+        GOAL_REACHED,
+        EventColumns {
+            goal_type: Some(goal_type),
+            value: Some(new_value as f64),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Removes the events of a deleted workout
+pub async fn delete_workout_events(
+    connection: &mut SqliteConnection,
+    username: &str,
+    workout_id: Id,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM feed_event WHERE username = ? AND workout_id = ?")
+        .bind(username)
+        .bind(workout_id)
+        .execute(&mut *connection)
+        .await?;
+    Ok(())
+}
+
+/// Up to `limit` events of the friends of `username`, newest first.
+/// Only events older than the event with the id `before` are returned if it is set
+pub async fn get_friends_feed(
+    pool: &SqlitePool,
+    username: &str,
+    before: Option<i64>,
+    limit: u32,
+) -> Result<FeedPage, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT feed_event.*, users.profile_picture, users.favorite_mascot
+        FROM feed_event JOIN users ON users.username = feed_event.username
+        WHERE feed_event.username IN (SELECT friendname FROM friendship WHERE username = ?)
+        AND feed_event.id < ?
+        ORDER BY feed_event.id DESC
+        LIMIT ?",
+    )
+    .bind(username)
+    .bind(before.unwrap_or(i64::MAX))
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let next_before = match rows.last() {
+        Some(last_row) if rows.len() == limit as usize => Some(last_row.get("id")),
+        _ => None,
+    };
+
+    let mut events = Vec::new();
+    for row in &rows {
+        // Events of workouts without sets left are skipped
+        if let Some(kind) = feed_event_kind_from_row(pool, row).await? {
+            events.push(FeedEvent {
+                id: row.get("id"),
+                username: row.get("username"),
+                profile_picture_path: row.get("profile_picture"),
+                favorite_mascot: mascot_from_string(row.get("favorite_mascot")),
+                created_at: row.get("created_at"),
+                kind,
+            });
+        }
+    }
+
+    Ok(FeedPage {
+        events,
+        next_before,
+    })
+}
+
+async fn feed_event_kind_from_row(
+    pool: &SqlitePool,
+    row: &SqliteRow,
+) -> Result<Option<FeedEventKind>, sqlx::Error> {
+    let value: Option<f64> = row.get("value");
+    let value = value.unwrap_or_default() as f32;
+
+    let kind = match row.get::<&str, _>("event_type") {
+        WORKOUT_SAVED => {
+            let workout_id: Id = row.get("workout_id");
+            let duration_minutes: Option<u32> = row.get("duration_minutes");
+            return get_workout_saved_event(
+                pool,
+                row.get("username"),
+                workout_id,
+                duration_minutes,
+            )
+            .await;
+        }
+        PERSONAL_RECORD => FeedEventKind::PersonalRecord {
+            exercise_name: row.get("exercise_name"),
+            weight: value as Kg,
+        },
+        MASCOT_BOUGHT => FeedEventKind::MascotBought(mascot_from_string(row.get("mascot_name"))),
+        GOAL_REACHED => {
+            let goal_type_name: String = row.get("goal_type");
+            let Some(goal_type) = goal_type_from_column(&goal_type_name) else {
+                return Ok(None);
+            };
+            FeedEventKind::GoalReached { goal_type, value }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(kind))
+}
+
+async fn get_workout_saved_event(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+    duration_minutes: Option<u32>,
+) -> Result<Option<FeedEventKind>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT exercise.name, exerciseLog.date, SUM(exerciseLog.weight_in_kg * exerciseLog.reps) AS volume
+        FROM exerciseLog JOIN exercise ON exercise.id = exerciseLog.exercise_id
+        WHERE exerciseLog.username = ? AND exerciseLog.workout_id = ?
+        GROUP BY exerciseLog.exercise_id
+        ORDER BY MIN(exerciseLog.id)",
+    )
+    .bind(username)
+    .bind(workout_id)
+    .fetch_all(pool)
+    .await?;
+    let Some(first_row) = rows.first() else {
+        return Ok(None);
+    };
+    let date = database_utils::database_date_string_to_naive_date(first_row.get("date"))
+        .map_err(|err| sqlx::Error::Decode(Box::new(err)))?;
+
+    Ok(Some(FeedEventKind::WorkoutSaved {
+        date,
+        exercise_names: rows.iter().map(|row| row.get("name")).collect(),
+        volume: rows
+            .iter()
+            .map(|row| row.get::<f64, _>("volume") as Kg)
+            .sum(),
+        duration_minutes,
+    }))
+}
+
+fn goal_type_from_column(goal_type_name: &str) -> Option<GoalType> {
+    match goal_type_name {
+        "weekly_workouts" => Some(GoalType::WeeklyWorkouts),
+        log_path => GoalType::from_log_path(log_path),
+    }
+}
+
+fn goal_type_column(goal_type: GoalType) -> &'static str {
+    goal_type.log_path().unwrap_or("weekly_workouts")
+}
+
+/// Optional columns of feed_event, which ones are set depends on the event type
+#[derive(Default)]
+struct EventColumns {
+    workout_id: Option<Id>,
+    duration_minutes: Option<u32>,
+    exercise_name: Option<String>,
+    mascot_name: Option<String>,
+    goal_type: Option<GoalType>,
+    value: Option<f64>,
+}
+
+async fn insert_event(
+    connection: &mut SqliteConnection,
+    username: &str,
+    event_type: &str,
+    columns: EventColumns,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO feed_event (username, created_at, event_type, workout_id, duration_minutes,
+        exercise_name, mascot_name, goal_type, value)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(username)
+    .bind(Local::now().naive_local())
+    .bind(event_type)
+    .bind(columns.workout_id)
+    .bind(columns.duration_minutes)
+    .bind(columns.exercise_name)
+    .bind(columns.mascot_name)
+    .bind(columns.goal_type.map(goal_type_column))
+    .bind(columns.value)
+    .execute(&mut *connection)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::mascot_mod::rare_mascot::RareMascot;
+    use crate::server::database_mod::database::{
+        save_test_workout, setup_test_db, test_values_for_db,
+    };
+    use crate::server::database_mod::database_exercise::delete_workout;
+    use crate::server::database_mod::database_social::add_friend;
+
+    async fn save_workout(pool: &SqlitePool, username: &str, weight: Kg, day: u32) -> Id {
+        let date = NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        let workout_id = save_test_workout(pool, username, "Squat", weight, date).await;
+        add_workout_events(pool, username, workout_id, Some(40))
+            .await
+            .unwrap();
+        workout_id
+    }
+
+    async fn feed_of_testuser(pool: &SqlitePool) -> Vec<FeedEventKind> {
+        get_friends_feed(pool, "testuser", None, 20)
+            .await
+            .unwrap()
+            .events
+            .into_iter()
+            .map(|event| event.kind)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn feed_only_contains_events_of_friends() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_mascot_bought_event(&pool, "testuser2", Mascot::Rare(RareMascot::Whale))
+            .await
+            .unwrap();
+        assert!(feed_of_testuser(&pool).await.is_empty());
+
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        assert_eq!(
+            feed_of_testuser(&pool).await,
+            vec![FeedEventKind::MascotBought(Mascot::Rare(RareMascot::Whale))]
+        );
+    }
+
+    #[tokio::test]
+    async fn heavier_set_than_before_is_a_personal_record() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+
+        save_workout(&pool, "testuser2", 100.0, 2).await;
+        let workout_id = save_workout(&pool, "testuser2", 110.0, 3).await;
+
+        let feed = feed_of_testuser(&pool).await;
+        assert_eq!(feed.len(), 3);
+        assert_eq!(
+            feed[0],
+            FeedEventKind::PersonalRecord {
+                exercise_name: "Squat".to_string(),
+                weight: 110.0
+            }
+        );
+        assert_eq!(
+            feed[1],
+            FeedEventKind::WorkoutSaved {
+                date: NaiveDate::from_ymd_opt(2026, 3, 3).unwrap(),
+                exercise_names: vec!["Squat".to_string()],
+                volume: 1100.0,
+                duration_minutes: Some(40),
+            }
+        );
+
+        delete_workout(&pool, "testuser2", workout_id)
+            .await
+            .unwrap();
+        assert_eq!(feed_of_testuser(&pool).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn feed_is_loaded_page_by_page() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        for _ in 0..3 {
+            add_mascot_bought_event(&pool, "testuser2", Mascot::Rare(RareMascot::Whale))
+                .await
+                .unwrap();
+        }
+
+        let first_page = get_friends_feed(&pool, "testuser", None, 2).await.unwrap();
+        assert_eq!(first_page.events.len(), 2);
+        assert!(first_page.events[0].id > first_page.events[1].id);
+
+        let second_page = get_friends_feed(&pool, "testuser", first_page.next_before, 2)
+            .await
+            .unwrap();
+        assert_eq!(second_page.events.len(), 1);
+        assert!(second_page.events[0].id < first_page.events[1].id);
+        assert_eq!(second_page.next_before, None);
+    }
+
+    #[tokio::test]
+    async fn daily_goal_is_only_reached_once_per_day() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        let water_goal = get_user_goals(&pool, "testuser2").await.unwrap().water;
+
+        add_goal_reached_event_if_new(&pool, "testuser2", GoalType::Water, None, water_goal - 1.0)
+            .await
+            .unwrap();
+        add_goal_reached_event_if_new(
+            &pool,
+            "testuser2",
+            GoalType::Water,
+            Some(water_goal - 1.0),
+            water_goal,
+        )
+        .await
+        .unwrap();
+        add_goal_reached_event_if_new(
+            &pool,
+            "testuser2",
+            GoalType::Water,
+            Some(water_goal),
+            water_goal + 1.0,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            feed_of_testuser(&pool).await,
+            vec![FeedEventKind::GoalReached {
+                goal_type: GoalType::Water,
+                value: water_goal
+            }]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::client::backend::profile_stat_manager::calculate_weekly_workout_streak;
+use crate::common::leaderboard::{
+    LeaderboardEntry, LeaderboardMetric, LeaderboardQuery, rank_entries,
+};
+use crate::server::database_mod::database_exercise::get_exercises_stats;
+use crate::server::database_mod::database_mascot::mascot_from_string;
+use crate::server::database_mod::database_user_goals::get_user_goals;
+use crate::server::database_mod::database_utils;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use chrono::NaiveDate;
+use sqlx::{Row, SqlitePool};
+
+/// Ranks `username` and their friends by the metric of the query in the period `today` falls in.
+/// The best set needs the name of an exercise, else every value is 0
+pub async fn get_leaderboard(
+    pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
+    username: &str,
+    query: &LeaderboardQuery,
+    today: NaiveDate,
+) -> Result<Vec<LeaderboardEntry>, sqlx::Error> {
+    let participant_rows = sqlx::query(
+        "SELECT username, profile_picture, favorite_mascot FROM users
+        WHERE username = ? OR username IN (SELECT friendname FROM friendship WHERE username = ?)",
+    )
+    .bind(username)
+    .bind(username)
+    .fetch_all(pool)
+    .await?;
+
+    let (first_day, last_day) = query.period.date_range(today);
+    let first_day = database_utils::format_naive_date_for_database(&first_day);
+    let last_day = database_utils::format_naive_date_for_database(&last_day);
+
+    let mut entries = Vec::new();
+    for row in &participant_rows {
+        let participant: String = row.get("username");
+        let value = match query.metric {
+            LeaderboardMetric::Volume => {
+                sqlx::query_scalar::<_, f64>(
+                    "SELECT COALESCE(SUM(weight_in_kg * reps), 0) FROM exerciseLog
+                    WHERE username = ? AND date BETWEEN ? AND ?",
+                )
+                .bind(&participant)
+                .bind(&first_day)
+                .bind(&last_day)
+                .fetch_one(pool)
+                .await? as f32
+            }
+            LeaderboardMetric::Workouts => {
+                sqlx::query_scalar::<_, i64>(
+                    "SELECT COUNT(DISTINCT workout_id) FROM exerciseLog
+                    WHERE username = ? AND date BETWEEN ? AND ?",
+                )
+                .bind(&participant)
+                .bind(&first_day)
+                .bind(&last_day)
+                .fetch_one(pool)
+                .await? as f32
+            }
+            LeaderboardMetric::BestSet => {
+                // Matched by name, so friends' custom exercises of the same machine compete
+                sqlx::query_scalar::<_, f64>(
+                    "SELECT COALESCE(MAX(exerciseLog.weight_in_kg), 0)
+                    FROM exerciseLog JOIN exercise ON exercise.id = exerciseLog.exercise_id
+                    WHERE exerciseLog.username = ? AND exerciseLog.date BETWEEN ? AND ?
+                    AND exercise.name = ? COLLATE NOCASE",
+                )
+                .bind(&participant)
+                .bind(&first_day)
+                .bind(&last_day)
+                .bind(query.exercise.as_deref().unwrap_or_default())
+                .fetch_one(pool)
+                .await? as f32
+            }
+            LeaderboardMetric::Streak => {
+                let exercises = get_exercises_stats(pool, exercise_catalog, &participant).await?;
+                let weekly_workouts_goal =
+                    get_user_goals(pool, &participant).await?.weekly_workouts;
+                calculate_weekly_workout_streak(&exercises, weekly_workouts_goal as u32) as f32
+            }
+        };
+
+        entries.push(LeaderboardEntry {
+            rank: 0,
+            username: participant,
+            profile_picture_path: row.get("profile_picture"),
+            favorite_mascot: mascot_from_string(row.get("favorite_mascot")),
+            value,
+        });
+    }
+
+    rank_entries(&mut entries);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::leaderboard::LeaderboardPeriod;
+    use crate::server::database_mod::database::{
+        save_test_workout, setup_test_db, test_values_for_db,
+    };
+    use crate::server::database_mod::database_social::add_friend;
+
+    fn ranking(entries: &[LeaderboardEntry]) -> Vec<(&str, f32)> {
+        entries
+            .iter()
+            .map(|entry| (entry.username.as_str(), entry.value))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn only_friends_are_ranked_within_the_period() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let catalog = ExerciseCatalog::default();
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let last_week = NaiveDate::from_ymd_opt(2026, 2, 27).unwrap();
+        save_test_workout(&pool, "testuser", "Squat", 100.0, today).await;
+        save_test_workout(&pool, "testuser2", "Squat", 60.0, today).await;
+        save_test_workout(&pool, "testuser2", "Squat", 200.0, last_week).await;
+        let volume_query = LeaderboardQuery::default();
+
+        let entries = get_leaderboard(&pool, &catalog, "testuser", &volume_query, today)
+            .await
+            .unwrap();
+        assert_eq!(ranking(&entries), vec![("testuser", 1000.0)]);
+
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        let entries = get_leaderboard(&pool, &catalog, "testuser", &volume_query, today)
+            .await
+            .unwrap();
+        assert_eq!(
+            ranking(&entries),
+            vec![("testuser", 1000.0), ("testuser2", 600.0)]
+        );
+
+        let best_set_query = LeaderboardQuery {
+            metric: LeaderboardMetric::BestSet,
+            period: LeaderboardPeriod::Month,
+            exercise: Some("squat".to_string()),
+        };
+        let entries = get_leaderboard(&pool, &catalog, "testuser", &best_set_query, today)
+            .await
+            .unwrap();
+        assert_eq!(
+            ranking(&entries),
+            vec![("testuser", 100.0), ("testuser2", 60.0)]
+        );
+        assert_eq!(entries[1].rank, 2);
+    }
+}
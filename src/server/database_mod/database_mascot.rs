@@ -1,4 +1,5 @@
-use crate::common::mascot_mod::mascot::Mascot;
+use crate::common::mascot_mod::mascot::{Mascot, MascotRarity};
+use crate::common::mascot_mod::mascot_data_transfer::MascotRollAnswer;
 use crate::common::mascot_mod::mascot_trait::MascotTrait;
 use sqlx::{Row, SqlitePool};
 use std::str::FromStr;
@@ -62,3 +63,156 @@ pub async fn add_mascot_to_user(
 
     Ok(())
 }
+
+/// Buys a random mascot of `rarity` the user doesn't own yet. <br>
+/// Checking the balance, picking the mascot and debiting the coins happen in one transaction
+pub async fn roll_mascot(
+    pool: &SqlitePool,
+    username: &str,
+    rarity: MascotRarity,
+) -> Result<MascotRollAnswer, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let prize = rarity.get_prize();
+
+    let coin_balance: i64 = sqlx::query("SELECT coin_balance FROM users WHERE username = ?")
+        .bind(username)
+        .fetch_one(&mut *transaction)
+        .await?
+        .get("coin_balance");
+    if coin_balance < prize as i64 {
+        return Ok(MascotRollAnswer::NotEnoughCoins);
+    }
+
+    let owned_mascots: Vec<Mascot> =
+        sqlx::query("SELECT mascot_name FROM user_mascot WHERE username = ?")
+            .bind(username)
+            .fetch_all(&mut *transaction)
+            .await?
+            .iter()
+            .filter_map(|row| Mascot::from_str(row.get("mascot_name")).ok())
+            .collect();
+    let Ok(mascot) = rarity.random_unowned_mascot(&owned_mascots) else {
+        return Ok(MascotRollAnswer::AllMascotsOwned);
+    };
+
+    sqlx::query("INSERT INTO user_mascot (username, mascot_name, level) VALUES (?, ?, ?)")
+        .bind(username)
+        .bind(mascot.to_string())
+        .bind(1)
+        .execute(&mut *transaction)
+        .await?;
+
+    let debit = sqlx::query(
+        "UPDATE users SET coin_balance = coin_balance - ? WHERE username = ? AND coin_balance >= ?",
+    )
+    .bind(prize)
+    .bind(username)
+    .bind(prize)
+    .execute(&mut *transaction)
+    .await?;
+    if debit.rows_affected() != 1 {
+        return Ok(MascotRollAnswer::NotEnoughCoins);
+    }
+
+    transaction.commit().await?;
+
+    Ok(MascotRollAnswer::Rolled {
+        mascot,
+        coin_balance: coin_balance as u32 - prize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::mascot_mod::epic_mascot::EpicMascot;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+    use crate::server::database_mod::database_user::{
+        get_user_coin_balance, update_user_coin_balance,
+    };
+
+    async fn setup_mascot_db() -> SqlitePool {
+        let pool = setup_test_db().await;
+        for mascot in Mascot::iter().filter(|mascot| *mascot != Mascot::default()) {
+            sqlx::query("INSERT INTO mascot (mascot_name, description) VALUES (?, 'test')")
+                .bind(mascot.to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        test_values_for_db(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn roll_debits_coins_and_adds_unowned_mascot() {
+        let pool = setup_mascot_db().await;
+        update_user_coin_balance(&pool, "testuser", 120)
+            .await
+            .unwrap();
+        add_mascot_to_user(&pool, "testuser", "Capybara")
+            .await
+            .unwrap();
+        add_mascot_to_user(&pool, "testuser", "Shark")
+            .await
+            .unwrap();
+
+        let answer = roll_mascot(&pool, "testuser", MascotRarity::Epic)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            answer,
+            MascotRollAnswer::Rolled {
+                mascot: Mascot::Epic(EpicMascot::Reindeer),
+                coin_balance: 20
+            }
+        ));
+        assert_eq!(get_user_coin_balance(&pool, "testuser").await.unwrap(), 20);
+        assert!(
+            get_mascots_from_user(&pool, "testuser")
+                .await
+                .unwrap()
+                .contains(&Mascot::Epic(EpicMascot::Reindeer))
+        );
+    }
+    #[tokio::test]
+    async fn roll_without_enough_coins_changes_nothing() {
+        let pool = setup_mascot_db().await;
+        update_user_coin_balance(&pool, "testuser", 99)
+            .await
+            .unwrap();
+
+        let answer = roll_mascot(&pool, "testuser", MascotRarity::Epic)
+            .await
+            .unwrap();
+
+        assert!(matches!(answer, MascotRollAnswer::NotEnoughCoins));
+        assert_eq!(get_user_coin_balance(&pool, "testuser").await.unwrap(), 99);
+        assert_eq!(
+            get_mascots_from_user(&pool, "testuser").await.unwrap(),
+            vec![Mascot::default()]
+        );
+    }
+    #[tokio::test]
+    async fn roll_with_all_mascots_owned_changes_nothing() {
+        let pool = setup_mascot_db().await;
+        update_user_coin_balance(&pool, "testuser", 500)
+            .await
+            .unwrap();
+        for mascot in MascotRarity::Rare.mascots() {
+            if mascot != Mascot::default() {
+                add_mascot_to_user(&pool, "testuser", &mascot.to_string())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let answer = roll_mascot(&pool, "testuser", MascotRarity::Rare)
+            .await
+            .unwrap();
+
+        assert!(matches!(answer, MascotRollAnswer::AllMascotsOwned));
+        assert_eq!(get_user_coin_balance(&pool, "testuser").await.unwrap(), 500);
+    }
+}
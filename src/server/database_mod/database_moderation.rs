@@ -0,0 +1,243 @@
+use crate::common::user_mod::user_report::UserReport;
+use sqlx::{Row, SqlitePool};
+
+/// Open report of a profile as the moderators see it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportedProfile {
+    pub id: i64,
+    pub reporter: String,
+    pub reported: String,
+    pub reason: String,
+    /// Description of the profile when it was reported
+    pub reported_description: String,
+    pub created_at: String,
+}
+
+/// Blocks `blocked` for `blocker` and removes their friendship and friend requests.
+/// Returns RowNotFound if `blocked` doesn't exist
+pub async fn block_user(
+    pool: &SqlitePool,
+    blocker: &str,
+    blocked: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT 1 FROM users WHERE username = ?")
+        .bind(blocked)
+        .fetch_one(pool)
+        .await?;
+
+    let mut transaction = pool.begin().await?;
+    sqlx::query(
+        "INSERT OR IGNORE INTO user_block (blocker, blocked, blocked_at) VALUES (?, ?, datetime('now'))",
+    )
+    .bind(blocker)
+    .bind(blocked)
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query(
+        "DELETE FROM friendship WHERE (username = ? AND friendname = ?)
+        OR (username = ? AND friendname = ?)",
+    )
+    .bind(blocker)
+    .bind(blocked)
+    .bind(blocked)
+    .bind(blocker)
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query(
+        "DELETE FROM friend_request WHERE (sender = ? AND receiver = ?)
+        OR (sender = ? AND receiver = ?)",
+    )
+    .bind(blocker)
+    .bind(blocked)
+    .bind(blocked)
+    .bind(blocker)
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Returns false if `blocker` didn't block `blocked`
+pub async fn unblock_user(
+    pool: &SqlitePool,
+    blocker: &str,
+    blocked: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM user_block WHERE blocker = ? AND blocked = ?")
+        .bind(blocker)
+        .bind(blocked)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Users `blocker` blocked, most recently blocked first
+pub async fn get_blocked_users(
+    pool: &SqlitePool,
+    blocker: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT blocked FROM user_block WHERE blocker = ? ORDER BY blocked_at DESC")
+        .bind(blocker)
+        .fetch_all(pool)
+        .await
+}
+
+/// Whether one of the users blocked the other one
+pub async fn is_blocked_either_way(
+    pool: &SqlitePool,
+    username: &str,
+    other_username: &str,
+) -> Result<bool, sqlx::Error> {
+    let block = sqlx::query(
+        "SELECT 1 FROM user_block WHERE (blocker = ? AND blocked = ?) OR (blocker = ? AND blocked = ?)",
+    )
+    .bind(username)
+    .bind(other_username)
+    .bind(other_username)
+    .bind(username)
+    .fetch_optional(pool)
+    .await?;
+    Ok(block.is_some())
+}
+
+/// Saves the report together with the current description of the reported profile.
+/// Returns RowNotFound if the reported user doesn't exist
+pub async fn report_user(
+    pool: &SqlitePool,
+    reporter: &str,
+    report: &UserReport,
+) -> Result<(), sqlx::Error> {
+    let description: String = sqlx::query("SELECT description FROM users WHERE username = ?")
+        .bind(&report.username)
+        .fetch_one(pool)
+        .await?
+        .get("description");
+
+    sqlx::query(
+        "INSERT INTO user_report (reporter, reported, reason, reported_description, status, created_at)
+        VALUES (?, ?, ?, ?, 'Open', datetime('now'))",
+    )
+    .bind(reporter)
+    .bind(&report.username)
+    .bind(report.reason.trim())
+    .bind(description)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Reports no moderator handled yet, oldest first
+pub async fn get_open_reports(pool: &SqlitePool) -> Result<Vec<ReportedProfile>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM user_report WHERE status = 'Open' ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ReportedProfile {
+            id: row.get("id"),
+            reporter: row.get("reporter"),
+            reported: row.get("reported"),
+            reason: row.get("reason"),
+            reported_description: row.get("reported_description"),
+            created_at: row.get("created_at"),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::user_mod::friend_request::FriendshipStatus;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+    use crate::server::database_mod::database_social::{
+        add_friend, get_discovery_users, get_friendship_status, send_friend_request,
+    };
+    use crate::server::database_mod::database_user::update_user_description;
+    use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+
+    async fn discovered_usernames(pool: &SqlitePool, active_user: &str) -> Vec<String> {
+        get_discovery_users(pool, &ExerciseCatalog::default(), active_user, 200)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.username)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn blocking_hides_users_from_each_other() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+
+        block_user(&pool, "testuser", "testuser2").await.unwrap();
+        assert_eq!(
+            get_friendship_status(&pool, "testuser2", "testuser")
+                .await
+                .unwrap(),
+            FriendshipStatus::NotFriends
+        );
+        assert!(
+            !discovered_usernames(&pool, "testuser")
+                .await
+                .contains(&"testuser2".to_string())
+        );
+        assert!(
+            !discovered_usernames(&pool, "testuser2")
+                .await
+                .contains(&"testuser".to_string())
+        );
+        assert!(matches!(
+            send_friend_request(&pool, "testuser2", "testuser").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+        assert_eq!(
+            get_blocked_users(&pool, "testuser").await.unwrap(),
+            vec!["testuser2".to_string()]
+        );
+
+        assert!(unblock_user(&pool, "testuser", "testuser2").await.unwrap());
+        assert!(!unblock_user(&pool, "testuser", "testuser2").await.unwrap());
+        assert!(
+            discovered_usernames(&pool, "testuser2")
+                .await
+                .contains(&"testuser".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn report_keeps_the_reported_description() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        update_user_description(&pool, "testuser2", "rude words")
+            .await
+            .unwrap();
+        let report = UserReport {
+            username: "testuser2".to_string(),
+            reason: " Insults ".to_string(),
+        };
+
+        report_user(&pool, "testuser", &report).await.unwrap();
+        update_user_description(&pool, "testuser2", "nice words")
+            .await
+            .unwrap();
+
+        let reports = get_open_reports(&pool).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].reported, "testuser2");
+        assert_eq!(reports[0].reason, "Insults");
+        assert_eq!(reports[0].reported_description, "rude words");
+
+        let unknown_user = UserReport {
+            username: "nobody".to_string(),
+            ..report
+        };
+        assert!(matches!(
+            report_user(&pool, "testuser", &unknown_user).await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+}
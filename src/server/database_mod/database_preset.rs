@@ -1,5 +1,9 @@
-use crate::common::workout_preset::WorkoutPreset;
-use sqlx::{Row, SqlitePool};
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::workout::PresetTraining;
+use crate::common::workout_preset::{PresetStats, WorkoutPreset};
+use crate::server::database_mod::database_utils;
+use chrono::NaiveDate;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 
 #[allow(dead_code)]
 pub async fn add_preset(
@@ -20,32 +24,58 @@ pub async fn add_preset(
     .await?;
 
     let preset_id = preset_insert.last_insert_rowid();
-    for exercises in workout_preset.exercises.iter() {
+    insert_preset_exercises(&mut transaction, preset_id, &workout_preset.exercises).await?;
+
+    transaction.commit().await?;
+
+    Ok(preset_id)
+}
+
+async fn insert_preset_exercises(
+    connection: &mut SqliteConnection,
+    preset_id: i64,
+    exercises: &[String],
+) -> Result<(), sqlx::Error> {
+    for exercise in exercises {
         sqlx::query(
             "INSERT INTO preset_exercise (preset_id, exercise_name)
                         VALUES (?, ?)",
         )
         .bind(preset_id)
-        .bind(exercises)
-        .execute(&mut *transaction)
+        .bind(exercise)
+        .execute(&mut *connection)
         .await?;
     }
+    Ok(())
+}
 
-    transaction.commit().await?;
-
-    Ok(preset_id)
+async fn user_has_preset(
+    connection: &mut SqliteConnection,
+    username: &str,
+    preset_id: i64,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM user_preset WHERE username = ? AND preset_id = ?)",
+    )
+    .bind(username)
+    .bind(preset_id)
+    .fetch_one(&mut *connection)
+    .await
 }
 #[allow(dead_code)]
 pub async fn get_presets_for_user(
     pool: &SqlitePool,
     username: &str,
 ) -> Result<Vec<WorkoutPreset>, sqlx::Error> {
-    let preset_id_rows = sqlx::query("SELECT preset_id FROM user_preset WHERE username = ? ")
-        .bind(username)
-        .fetch_all(pool)
-        .await?;
+    let preset_id_rows = sqlx::query(
+        "SELECT preset_id FROM user_preset WHERE username = ? ORDER BY position, preset_id DESC",
+    )
+    .bind(username)
+    .fetch_all(pool)
+    .await?;
 
     let mut workout_presets = Vec::new();
+    let mut connection = pool.acquire().await?;
 
     for preset_id_row in preset_id_rows {
         let temp_id: i64 = preset_id_row.get("preset_id");
@@ -70,10 +100,14 @@ pub async fn get_presets_for_user(
             exercises.push(temp_exercise_name);
         }
 
+        let stats = get_preset_stats(&mut connection, username, temp_id).await?;
+
         let temp_workout_preset = WorkoutPreset {
+            id: Some(temp_id as Id),
             name: temp_preset_name,
             image: temp_image.into(),
             exercises,
+            stats,
         };
 
         workout_presets.push(temp_workout_preset);
@@ -81,9 +115,55 @@ pub async fn get_presets_for_user(
 
     Ok(workout_presets)
 }
-#[allow(dead_code)]
-pub async fn delete_preset(pool: &SqlitePool, preset_id: i64) -> Result<(), sqlx::Error> {
+/// Overwrites name, image and exercises of a preset.
+/// Returns false if the preset doesn't belong to the user
+pub async fn update_preset(
+    pool: &SqlitePool,
+    username: &str,
+    preset_id: i64,
+    workout_preset: &WorkoutPreset,
+) -> Result<bool, sqlx::Error> {
     let mut transaction = pool.begin().await?;
+    if !user_has_preset(&mut transaction, username, preset_id).await? {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        "UPDATE preset SET preset_name = ?, preset_image = ?, number_of_exercises = ? WHERE id = ?",
+    )
+    .bind(workout_preset.name.clone())
+    .bind(workout_preset.image.to_string())
+    .bind(workout_preset.exercises.len() as i64)
+    .bind(preset_id)
+    .execute(&mut *transaction)
+    .await?;
+
+    sqlx::query("DELETE FROM preset_exercise WHERE preset_id = ?")
+        .bind(preset_id)
+        .execute(&mut *transaction)
+        .await?;
+    insert_preset_exercises(&mut transaction, preset_id, &workout_preset.exercises).await?;
+
+    transaction.commit().await?;
+
+    Ok(true)
+}
+
+/// Returns false if the preset doesn't belong to the user
+pub async fn delete_preset(
+    pool: &SqlitePool,
+    username: &str,
+    preset_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    if !user_has_preset(&mut transaction, username, preset_id).await? {
+        return Ok(false);
+    }
+
+    sqlx::query("DELETE FROM preset_history WHERE preset_id = ?")
+        .bind(preset_id)
+        .execute(&mut *transaction)
+        .await?;
 
     sqlx::query("DELETE FROM user_preset WHERE preset_id = ?")
         .bind(preset_id)
@@ -102,7 +182,42 @@ pub async fn delete_preset(pool: &SqlitePool, preset_id: i64) -> Result<(), sqlx
 
     transaction.commit().await?;
 
-    Ok(())
+    Ok(true)
+}
+
+/// Stores the order in which the presets are shown, the first id is shown first.
+/// Returns false if the ids aren't exactly the presets of the user
+pub async fn reorder_presets(
+    pool: &SqlitePool,
+    username: &str,
+    preset_ids: &[i64],
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+
+    let mut owned_preset_ids: Vec<i64> =
+        sqlx::query_scalar("SELECT preset_id FROM user_preset WHERE username = ?")
+            .bind(username)
+            .fetch_all(&mut *transaction)
+            .await?;
+    let mut requested_preset_ids = preset_ids.to_vec();
+    owned_preset_ids.sort_unstable();
+    requested_preset_ids.sort_unstable();
+    if owned_preset_ids != requested_preset_ids {
+        return Ok(false);
+    }
+
+    for (position, preset_id) in preset_ids.iter().enumerate() {
+        sqlx::query("UPDATE user_preset SET position = ? WHERE username = ? AND preset_id = ?")
+            .bind(position as i64)
+            .bind(username)
+            .bind(preset_id)
+            .execute(&mut *transaction)
+            .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(true)
 }
 
 #[allow(dead_code)]
@@ -111,20 +226,28 @@ pub async fn add_preset_to_user(
     username: &str,
     preset_id: i64,
 ) -> Result<(), sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    // new presets are shown first
+    sqlx::query("UPDATE user_preset SET position = position + 1 WHERE username = ?")
+        .bind(username)
+        .execute(&mut *transaction)
+        .await?;
+
     sqlx::query(
-        "INSERT INTO user_preset (username, preset_id, times_preset_trained)
-                VALUES (?, ?,?)",
+        "INSERT INTO user_preset (username, preset_id, times_preset_trained, position)
+                VALUES (?, ?, ?, 0)",
     )
     .bind(username)
     .bind(preset_id)
     .bind(0)
-    .execute(pool)
+    .execute(&mut *transaction)
     .await?;
+
+    transaction.commit().await?;
     Ok(())
 }
-#[allow(dead_code)]
-pub async fn increment_preset_trained_from_user(
-    pool: &SqlitePool,
+async fn increment_preset_trained_from_user(
+    connection: &mut SqliteConnection,
     username: &str,
     preset_id: i64,
 ) -> Result<(), sqlx::Error> {
@@ -135,11 +258,114 @@ pub async fn increment_preset_trained_from_user(
     )
     .bind(username)
     .bind(preset_id)
-    .execute(pool)
+    .execute(&mut *connection)
     .await?;
 
     Ok(())
 }
+
+/// Counts a workout that was started from the preset.
+/// Returns false if the preset doesn't belong to the user
+pub async fn record_preset_training(
+    connection: &mut SqliteConnection,
+    username: &str,
+    workout_id: Id,
+    date: NaiveDate,
+    preset_training: PresetTraining,
+) -> Result<bool, sqlx::Error> {
+    let preset_id = preset_training.preset_id as i64;
+    if !user_has_preset(&mut *connection, username, preset_id).await? {
+        return Ok(false);
+    }
+
+    increment_preset_trained_from_user(&mut *connection, username, preset_id).await?;
+    sqlx::query(
+        "INSERT INTO preset_history (workout_id, username, preset_id, date, duration_minutes)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(workout_id)
+    .bind(username)
+    .bind(preset_id)
+    .bind(database_utils::format_naive_date_for_database(&date))
+    .bind(preset_training.duration_minutes)
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(true)
+}
+
+/// Undoes record_preset_training when the workout gets deleted
+pub async fn forget_preset_training(
+    connection: &mut SqliteConnection,
+    username: &str,
+    workout_id: Id,
+) -> Result<(), sqlx::Error> {
+    let preset_id: Option<i64> = sqlx::query_scalar(
+        "SELECT preset_id FROM preset_history WHERE username = ? AND workout_id = ?",
+    )
+    .bind(username)
+    .bind(workout_id)
+    .fetch_optional(&mut *connection)
+    .await?;
+    let Some(preset_id) = preset_id else {
+        return Ok(());
+    };
+
+    sqlx::query(
+        "UPDATE user_preset
+         SET times_preset_trained = MAX(times_preset_trained - 1, 0)
+         WHERE username = ? AND preset_id = ?",
+    )
+    .bind(username)
+    .bind(preset_id)
+    .execute(&mut *connection)
+    .await?;
+    sqlx::query("DELETE FROM preset_history WHERE username = ? AND workout_id = ?")
+        .bind(username)
+        .bind(workout_id)
+        .execute(&mut *connection)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_preset_stats(
+    connection: &mut SqliteConnection,
+    username: &str,
+    preset_id: i64,
+) -> Result<PresetStats, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT user_preset.times_preset_trained AS times_trained,
+                MAX(preset_history.date) AS last_trained,
+                AVG(preset_history.duration_minutes) AS average_duration
+         FROM user_preset
+         LEFT JOIN preset_history
+            ON preset_history.username = user_preset.username
+            AND preset_history.preset_id = user_preset.preset_id
+         WHERE user_preset.username = ? AND user_preset.preset_id = ?
+         GROUP BY user_preset.preset_id",
+    )
+    .bind(username)
+    .bind(preset_id)
+    .fetch_optional(connection)
+    .await?;
+    let Some(row) = row else {
+        return Ok(PresetStats::default());
+    };
+
+    let times_trained: i64 = row.get("times_trained");
+    let last_trained: Option<String> = row.get("last_trained");
+    let average_duration: Option<f64> = row.get("average_duration");
+
+    Ok(PresetStats {
+        times_trained: times_trained as u32,
+        last_trained: last_trained
+            .map(|date| database_utils::database_date_string_to_naive_date(&date))
+            .transpose()
+            .map_err(|err| sqlx::Error::Decode(Box::new(err)))?,
+        average_duration_minutes: average_duration.map(|minutes| minutes.round() as u32),
+    })
+}
 #[allow(dead_code)]
 pub async fn get_preset_trained_from_user(
     pool: &SqlitePool,
@@ -159,3 +385,204 @@ pub async fn get_preset_trained_from_user(
         None => 0,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::workout_preset::PresetImage;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+
+    fn preset(name: &str, exercises: &[&str]) -> WorkoutPreset {
+        WorkoutPreset {
+            id: None,
+            name: name.to_string(),
+            image: PresetImage::Default,
+            exercises: exercises
+                .iter()
+                .map(|exercise| exercise.to_string())
+                .collect(),
+            stats: PresetStats::default(),
+        }
+    }
+
+    async fn add_preset_for(pool: &SqlitePool, username: &str, preset: &WorkoutPreset) -> i64 {
+        let preset_id = add_preset(pool, preset, 10).await.unwrap();
+        add_preset_to_user(pool, username, preset_id).await.unwrap();
+        preset_id
+    }
+
+    fn names(presets: &[WorkoutPreset]) -> Vec<&str> {
+        presets.iter().map(|preset| preset.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn newest_preset_is_first_and_order_can_be_changed() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let push_id = add_preset_for(&pool, "testuser", &preset("Push", &["Bench Press"])).await;
+        let pull_id = add_preset_for(&pool, "testuser", &preset("Pull", &["Pull Up"])).await;
+        let legs_id = add_preset_for(&pool, "testuser", &preset("Legs", &["Squat"])).await;
+
+        let presets = get_presets_for_user(&pool, "testuser").await.unwrap();
+        assert_eq!(names(&presets), vec!["Legs", "Pull", "Push"]);
+
+        assert!(
+            reorder_presets(&pool, "testuser", &[push_id, legs_id, pull_id])
+                .await
+                .unwrap()
+        );
+        let presets = get_presets_for_user(&pool, "testuser").await.unwrap();
+        assert_eq!(names(&presets), vec!["Push", "Legs", "Pull"]);
+
+        // every preset of the user has to be part of the new order
+        assert!(
+            !reorder_presets(&pool, "testuser", &[push_id, legs_id])
+                .await
+                .unwrap()
+        );
+        assert!(
+            !reorder_presets(&pool, "testuser", &[push_id, legs_id, pull_id, 999])
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn update_preset_replaces_name_and_exercises() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let preset_id = add_preset_for(&pool, "testuser", &preset("Push", &["Bench Press"])).await;
+
+        let mut changed = preset("Chest", &["Dips", "Bench Press"]);
+        changed.image = PresetImage::Bench;
+        assert!(
+            update_preset(&pool, "testuser", preset_id, &changed)
+                .await
+                .unwrap()
+        );
+
+        let presets = get_presets_for_user(&pool, "testuser").await.unwrap();
+        assert_eq!(
+            presets,
+            vec![WorkoutPreset {
+                id: Some(preset_id as Id),
+                ..changed
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn presets_of_other_users_can_not_be_changed() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let preset_id = add_preset_for(&pool, "testuser", &preset("Push", &["Bench Press"])).await;
+
+        assert!(
+            !update_preset(
+                &pool,
+                "someone_else",
+                preset_id,
+                &preset("Mine", &["Squat"])
+            )
+            .await
+            .unwrap()
+        );
+        assert!(
+            !delete_preset(&pool, "someone_else", preset_id)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            get_presets_for_user(&pool, "testuser").await.unwrap().len(),
+            1
+        );
+
+        assert!(delete_preset(&pool, "testuser", preset_id).await.unwrap());
+        assert!(
+            get_presets_for_user(&pool, "testuser")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn preset_trainings_are_counted_and_summarized() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let preset_id = add_preset_for(&pool, "testuser", &preset("Push", &["Bench Press"])).await;
+        let training = |duration_minutes| PresetTraining {
+            preset_id: preset_id as Id,
+            duration_minutes,
+        };
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+
+        assert_eq!(
+            get_preset_stats(&mut pool.acquire().await.unwrap(), "testuser", preset_id)
+                .await
+                .unwrap(),
+            PresetStats::default()
+        );
+
+        for (workout_id, date, duration) in [
+            (1000, day(1), Some(40)),
+            (1001, day(5), None),
+            (1002, day(3), Some(50)),
+        ] {
+            assert!(
+                record_preset_training(
+                    &mut pool.acquire().await.unwrap(),
+                    "testuser",
+                    workout_id,
+                    date,
+                    training(duration)
+                )
+                .await
+                .unwrap()
+            );
+        }
+        assert!(
+            !record_preset_training(
+                &mut pool.acquire().await.unwrap(),
+                "someone_else",
+                1003,
+                day(6),
+                training(None)
+            )
+            .await
+            .unwrap()
+        );
+
+        assert_eq!(
+            get_preset_trained_from_user(&pool, "testuser", preset_id)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            get_preset_stats(&mut pool.acquire().await.unwrap(), "testuser", preset_id)
+                .await
+                .unwrap(),
+            PresetStats {
+                times_trained: 3,
+                last_trained: Some(day(5)),
+                average_duration_minutes: Some(45),
+            }
+        );
+
+        let mut connection = pool.acquire().await.unwrap();
+        forget_preset_training(&mut connection, "testuser", 1001)
+            .await
+            .unwrap();
+        drop(connection);
+        let presets = get_presets_for_user(&pool, "testuser").await.unwrap();
+        assert_eq!(
+            presets[0].stats,
+            PresetStats {
+                times_trained: 2,
+                last_trained: Some(day(3)),
+                average_duration_minutes: Some(45),
+            }
+        );
+    }
+}
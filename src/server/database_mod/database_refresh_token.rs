@@ -0,0 +1,209 @@
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+/// Stored refresh token, see table refresh_tokens
+#[derive(Debug, PartialEq)]
+pub struct RefreshTokenEntry {
+    pub username: String,
+    pub session_id: String,
+    pub expires_at: i64,
+    pub revoked: bool,
+}
+
+pub async fn add_refresh_token(
+    pool: &SqlitePool,
+    token_hash: &str,
+    username: &str,
+    session_id: &str,
+    expires_at: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO refresh_tokens (token_hash, username, session_id, expires_at, revoked)
+        VALUES (?, ?, ?, ?, 0)",
+    )
+    .bind(token_hash)
+    .bind(username)
+    .bind(session_id)
+    .bind(expires_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_refresh_token(
+    pool: &SqlitePool,
+    token_hash: &str,
+) -> Result<Option<RefreshTokenEntry>, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT username, session_id, expires_at, revoked FROM refresh_tokens WHERE token_hash = ?",
+    )
+    .bind(token_hash)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| RefreshTokenEntry {
+        username: row.get("username"),
+        session_id: row.get("session_id"),
+        expires_at: row.get("expires_at"),
+        revoked: row.get("revoked"),
+    }))
+}
+
+/// Revokes the old refresh token and stores its successor in the same session. <br>
+/// Returns false without storing anything if the old token was already revoked
+pub async fn rotate_refresh_token(
+    pool: &SqlitePool,
+    old_token_hash: &str,
+    new_token_hash: &str,
+    new_expires_at: i64,
+) -> Result<bool, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+
+    let revoked_token = sqlx::query(
+        "UPDATE refresh_tokens SET revoked = 1 WHERE token_hash = ? AND revoked = 0
+        RETURNING username, session_id",
+    )
+    .bind(old_token_hash)
+    .fetch_optional(&mut *transaction)
+    .await?;
+
+    let Some(revoked_token) = revoked_token else {
+        transaction.rollback().await?;
+        return Ok(false);
+    };
+
+    sqlx::query(
+        "INSERT INTO refresh_tokens (token_hash, username, session_id, expires_at, revoked)
+        VALUES (?, ?, ?, ?, 0)",
+    )
+    .bind(new_token_hash)
+    .bind(revoked_token.get::<String, _>("username"))
+    .bind(revoked_token.get::<String, _>("session_id"))
+    .bind(new_expires_at)
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(true)
+}
+
+/// Revokes every refresh token of the session, which also invalidates its access tokens
+pub async fn revoke_session(pool: &SqlitePool, session_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE refresh_tokens SET revoked = 1 WHERE session_id = ?")
+        .bind(session_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Revokes every session of the user, e.g. after the password changed
+pub async fn revoke_user_sessions(
+    connection: &mut SqliteConnection,
+    username: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE refresh_tokens SET revoked = 1 WHERE username = ?")
+        .bind(username)
+        .execute(connection)
+        .await?;
+    Ok(())
+}
+
+/// A session is active as long as it has a refresh token which is neither revoked nor expired
+pub async fn is_session_active(
+    pool: &SqlitePool,
+    session_id: &str,
+    now: i64,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT EXISTS(
+            SELECT 1 FROM refresh_tokens WHERE session_id = ? AND revoked = 0 AND expires_at > ?
+        ) AS active",
+    )
+    .bind(session_id)
+    .bind(now)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.get("active"))
+}
+
+pub async fn delete_expired_refresh_tokens(pool: &SqlitePool, now: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM refresh_tokens WHERE expires_at <= ?")
+        .bind(now)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+
+    #[tokio::test]
+    async fn rotate_keeps_session_active() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_refresh_token(&pool, "old", "testuser", "session", 100)
+            .await
+            .unwrap();
+
+        assert!(
+            rotate_refresh_token(&pool, "old", "new", 200)
+                .await
+                .unwrap()
+        );
+
+        let old_token = get_refresh_token(&pool, "old").await.unwrap().unwrap();
+        assert!(old_token.revoked);
+        let new_token = get_refresh_token(&pool, "new").await.unwrap().unwrap();
+        assert_eq!(
+            new_token,
+            RefreshTokenEntry {
+                username: "testuser".to_string(),
+                session_id: "session".to_string(),
+                expires_at: 200,
+                revoked: false,
+            }
+        );
+        assert!(is_session_active(&pool, "session", 150).await.unwrap());
+        assert!(!is_session_active(&pool, "session", 200).await.unwrap());
+    }
+    #[tokio::test]
+    async fn revoked_token_can_not_be_rotated() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_refresh_token(&pool, "old", "testuser", "session", 100)
+            .await
+            .unwrap();
+
+        assert!(
+            rotate_refresh_token(&pool, "old", "new", 200)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !rotate_refresh_token(&pool, "old", "other", 200)
+                .await
+                .unwrap()
+        );
+        assert_eq!(get_refresh_token(&pool, "other").await.unwrap(), None);
+    }
+    #[tokio::test]
+    async fn revoke_session_and_cleanup() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        add_refresh_token(&pool, "first", "testuser", "session", 100)
+            .await
+            .unwrap();
+        add_refresh_token(&pool, "second", "testuser", "other_session", 50)
+            .await
+            .unwrap();
+
+        revoke_session(&pool, "session").await.unwrap();
+        assert!(!is_session_active(&pool, "session", 0).await.unwrap());
+        assert!(is_session_active(&pool, "other_session", 0).await.unwrap());
+
+        delete_expired_refresh_tokens(&pool, 60).await.unwrap();
+        assert_eq!(get_refresh_token(&pool, "second").await.unwrap(), None);
+        assert!(get_refresh_token(&pool, "first").await.unwrap().is_some());
+    }
+}
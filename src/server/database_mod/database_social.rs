@@ -1,70 +1,206 @@
 use crate::client::backend::profile_stat_manager::ProfileStatManager;
-use crate::common::user_mod::user::ForeignUser;
+use crate::common::user_mod::friend_request::FriendshipStatus;
+use crate::common::user_mod::user::{ForeignUser, ProfileVisibility};
+use crate::common::user_mod::user_goals::UserGoals;
 use crate::server::database_mod::database::get_exercises_stats;
 use crate::server::database_mod::database_mascot::mascot_from_string;
+use crate::server::database_mod::database_moderation::is_blocked_either_way;
 use crate::server::database_mod::database_user_goals::get_user_goals;
-use sqlx::{Row, SqlitePool};
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::str::FromStr;
 
-#[allow(dead_code)]
+/// Makes both users friends of each other, see send_friend_request for the usual way
 pub async fn add_friend(
     pool: &SqlitePool,
     username: &str,
     friendname: &str,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    insert_friendship(&mut transaction, username, friendname).await?;
+    transaction.commit().await
+}
+
+async fn insert_friendship(
+    connection: &mut SqliteConnection,
+    username: &str,
+    friendname: &str,
 ) -> Result<(), sqlx::Error> {
     if username != friendname {
-        sqlx::query("INSERT OR IGNORE INTO friendship (username, friendname) VALUES (?,?)")
-            .bind(username)
-            .bind(friendname)
-            .execute(pool)
-            .await?;
+        for (username, friendname) in [(username, friendname), (friendname, username)] {
+            sqlx::query("INSERT OR IGNORE INTO friendship (username, friendname) VALUES (?,?)")
+                .bind(username)
+                .bind(friendname)
+                .execute(&mut *connection)
+                .await?;
+        }
     }
     Ok(())
 }
-#[allow(dead_code)]
+
+/// Ends the friendship of both users and withdraws friend requests between them
 pub async fn remove_friend(
     pool: &SqlitePool,
     username: &str,
     friendname: &str,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query("DELETE FROM friendship WHERE username = ? AND friendname = ?")
-        .bind(username)
-        .bind(friendname)
-        .execute(pool)
-        .await?;
-    Ok(())
+    let mut transaction = pool.begin().await?;
+    sqlx::query(
+        "DELETE FROM friendship WHERE (username = ? AND friendname = ?)
+        OR (username = ? AND friendname = ?)",
+    )
+    .bind(username)
+    .bind(friendname)
+    .bind(friendname)
+    .bind(username)
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query(
+        "DELETE FROM friend_request WHERE (sender = ? AND receiver = ?)
+        OR (sender = ? AND receiver = ?)",
+    )
+    .bind(username)
+    .bind(friendname)
+    .bind(friendname)
+    .bind(username)
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await
 }
 
-pub async fn get_single_foreign_user(
+/// Relationship of `target_username` to `active_user`
+pub async fn get_friendship_status(
     pool: &SqlitePool,
     active_user: &str,
     target_username: &str,
-) -> Result<ForeignUser, sqlx::Error> {
-    let row = sqlx::query("SELECT * FROM users WHERE username = ?")
-        .bind(target_username)
-        .fetch_one(pool)
-        .await?;
-
-    let exercise_stats = get_exercises_stats(pool, target_username).await?;
-
+) -> Result<FriendshipStatus, sqlx::Error> {
     let friend = sqlx::query("SELECT 1 FROM friendship WHERE username = ? AND friendname = ?")
         .bind(active_user)
         .bind(target_username)
         .fetch_optional(pool)
         .await?;
+    if friend.is_some() {
+        return Ok(FriendshipStatus::Friends);
+    }
 
-    let is_friend = friend.is_some();
+    let pending_request = sqlx::query(
+        "SELECT sender FROM friend_request WHERE status = 'Pending'
+        AND ((sender = ? AND receiver = ?) OR (sender = ? AND receiver = ?))",
+    )
+    .bind(active_user)
+    .bind(target_username)
+    .bind(target_username)
+    .bind(active_user)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(match pending_request {
+        Some(row) if row.get::<String, _>("sender") == active_user => FriendshipStatus::RequestSent,
+        Some(_) => FriendshipStatus::RequestReceived,
+        None => FriendshipStatus::NotFriends,
+    })
+}
 
-    let owned_mascot_rows = sqlx::query("SELECT mascot_name FROM user_mascot WHERE username = ?")
+/// Sends a friend request from `sender` to `receiver` and returns the new relationship.
+/// A pending request of the receiver is accepted instead, and a declined request can be sent again.
+/// Returns RowNotFound if the receiver doesn't exist or one of the users blocked the other one
+pub async fn send_friend_request(
+    pool: &SqlitePool,
+    sender: &str,
+    receiver: &str,
+) -> Result<FriendshipStatus, sqlx::Error> {
+    sqlx::query("SELECT 1 FROM users WHERE username = ?")
+        .bind(receiver)
+        .fetch_one(pool)
+        .await?;
+    if is_blocked_either_way(pool, sender, receiver).await? {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    match get_friendship_status(pool, sender, receiver).await? {
+        FriendshipStatus::RequestReceived => {
+            answer_friend_request(pool, sender, receiver, true).await?;
+            Ok(FriendshipStatus::Friends)
+        }
+        FriendshipStatus::NotFriends => {
+            sqlx::query(
+                "INSERT INTO friend_request (sender, receiver, status, sent_at)
+                VALUES (?, ?, 'Pending', datetime('now'))
+                ON CONFLICT (sender, receiver) DO UPDATE SET status = 'Pending', sent_at = datetime('now')",
+            )
+            .bind(sender)
+            .bind(receiver)
+            .execute(pool)
+            .await?;
+            Ok(FriendshipStatus::RequestSent)
+        }
+        status => Ok(status),
+    }
+}
+
+/// Accepts or declines the pending friend request `sender` sent to `receiver`.
+/// Returns RowNotFound if there is no such request
+pub async fn answer_friend_request(
+    pool: &SqlitePool,
+    receiver: &str,
+    sender: &str,
+    accept: bool,
+) -> Result<(), sqlx::Error> {
+    let status = if accept { "Accepted" } else { "Declined" };
+    let mut transaction = pool.begin().await?;
+    let result = sqlx::query(
+        "UPDATE friend_request SET status = ?
+        WHERE sender = ? AND receiver = ? AND status = 'Pending'",
+    )
+    .bind(status)
+    .bind(sender)
+    .bind(receiver)
+    .execute(&mut *transaction)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+    if accept {
+        insert_friendship(&mut transaction, receiver, sender).await?;
+    }
+    transaction.commit().await
+}
+
+/// Stats, goals and owned mascots are left empty unless the target user shows them to `active_user`
+pub async fn get_single_foreign_user(
+    pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
+    active_user: &str,
+    target_username: &str,
+) -> Result<ForeignUser, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM users WHERE username = ?")
         .bind(target_username)
-        .fetch_all(pool)
+        .fetch_one(pool)
         .await?;
 
+    let friendship_status = get_friendship_status(pool, active_user, target_username).await?;
+    let profile_visibility =
+        ProfileVisibility::from_str(row.get("profile_visibility")).unwrap_or_default();
+    let stats_hidden = !profile_visibility.shows_stats_to(friendship_status);
+
+    let mut exercise_stats = Vec::new();
     let mut owned_mascots = Vec::new();
-    for mascot_row in owned_mascot_rows {
-        let mascot_name: String = mascot_row.get("mascot_name");
-        owned_mascots.push(mascot_from_string(&mascot_name));
+    let mut user_goals = UserGoals::default();
+    if !stats_hidden {
+        exercise_stats = get_exercises_stats(pool, exercise_catalog, target_username).await?;
+
+        let owned_mascot_rows =
+            sqlx::query("SELECT mascot_name FROM user_mascot WHERE username = ?")
+                .bind(target_username)
+                .fetch_all(pool)
+                .await?;
+        for mascot_row in owned_mascot_rows {
+            let mascot_name: String = mascot_row.get("mascot_name");
+            owned_mascots.push(mascot_from_string(&mascot_name));
+        }
+        user_goals = get_user_goals(pool, target_username).await?;
     }
-    let user_goals = get_user_goals(pool, target_username).await?;
 
     Ok(ForeignUser {
         username: row.get("username"),
@@ -76,12 +212,14 @@ pub async fn get_single_foreign_user(
             user_goals.weekly_workouts as u32,
         ),
         owned_mascots,
-        friends_with_active_user: is_friend,
+        friendship_status,
+        stats_hidden,
     })
 }
 
 pub async fn get_all_friends(
     pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
     active_user: &str,
 ) -> Result<Vec<ForeignUser>, sqlx::Error> {
     let mut friends = Vec::new();
@@ -94,27 +232,61 @@ pub async fn get_all_friends(
     for friend_row in all_friend_rows {
         let name: String = friend_row.get("friendname");
 
-        if let Ok(user) = get_single_foreign_user(pool, active_user, &name).await {
+        if let Ok(user) = get_single_foreign_user(pool, exercise_catalog, active_user, &name).await
+        {
             friends.push(user);
         }
     }
     Ok(friends)
 }
 
+/// Users who sent `active_user` a pending friend request, oldest request first
+pub async fn get_friend_request_senders(
+    pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
+    active_user: &str,
+) -> Result<Vec<ForeignUser>, sqlx::Error> {
+    let sender_rows = sqlx::query(
+        "SELECT sender FROM friend_request WHERE receiver = ? AND status = 'Pending' ORDER BY sent_at",
+    )
+    .bind(active_user)
+    .fetch_all(pool)
+    .await?;
+
+    let mut senders = Vec::new();
+    for sender_row in sender_rows {
+        let name: String = sender_row.get("sender");
+        if let Ok(user) = get_single_foreign_user(pool, exercise_catalog, active_user, &name).await
+        {
+            senders.push(user);
+        }
+    }
+    Ok(senders)
+}
+
+/// Random users who are neither friends of `active_user` nor sent them a pending friend request.
+/// Private users and users who blocked or were blocked by `active_user` are never discovered
 pub async fn get_discovery_users(
     pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
     active_user: &str,
     limit: i64,
 ) -> Result<Vec<ForeignUser>, sqlx::Error> {
     let discovered_users_rows = sqlx::query(
         "SELECT username FROM users
-         WHERE username != ?
+         WHERE username != ? AND profile_visibility != 'Private'
          AND username NOT IN (SELECT friendname FROM friendship WHERE username = ?)
+         AND username NOT IN (SELECT sender FROM friend_request WHERE receiver = ? AND status = 'Pending')
+         AND username NOT IN (SELECT blocked FROM user_block WHERE blocker = ?)
+         AND username NOT IN (SELECT blocker FROM user_block WHERE blocked = ?)
          ORDER BY RANDOM()
          LIMIT ?",
     )
     .bind(active_user)
     .bind(active_user)
+    .bind(active_user)
+    .bind(active_user)
+    .bind(active_user)
     .bind(limit)
     .fetch_all(pool)
     .await?;
@@ -123,10 +295,273 @@ pub async fn get_discovery_users(
 
     for row in discovered_users_rows {
         let discovered_username: String = row.get("username");
-        if let Ok(user) = get_single_foreign_user(pool, active_user, &discovered_username).await {
+        if let Ok(user) =
+            get_single_foreign_user(pool, exercise_catalog, active_user, &discovered_username).await
+        {
             discovery_users.push(user);
         }
     }
 
     Ok(discovery_users)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+    use crate::server::database_mod::database_exercise::EXERCISE_SETS_QUERY;
+    use crate::server::database_mod::database_user::{
+        add_user, get_user_information, update_user_info_settings,
+    };
+    use std::time::{Duration, Instant};
+
+    const SEEDED_EXERCISES: usize = 600;
+    const SEEDED_USERS: usize = 40;
+    const LOGS_PER_USER: usize = 500;
+
+    /// Roughly the size of a real database: the full exercise table and users with a long history
+    async fn setup_seeded_db() -> SqlitePool {
+        let pool = setup_test_db().await;
+        sqlx::query("INSERT INTO mascot (mascot_name, description) VALUES ('Duck', 'test')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut transaction = pool.begin().await.unwrap();
+        for exercise in 0..SEEDED_EXERCISES {
+            sqlx::query(
+                "INSERT INTO exercise (name, exercise_level_name, muscle_name, instructions, exercise_category_name)
+                 VALUES (?, 'Beginner', 'Chest', 'test', 'Strength')",
+            )
+            .bind(format!("Exercise {exercise}"))
+            .execute(&mut *transaction)
+            .await
+            .unwrap();
+        }
+        transaction.commit().await.unwrap();
+
+        for user in 0..SEEDED_USERS {
+            let username = format!("user{user}");
+            add_user(&pool, &username, "123").await.unwrap();
+            add_friend(&pool, "user0", &username).await.unwrap();
+
+            let mut transaction = pool.begin().await.unwrap();
+            for log in 0..LOGS_PER_USER {
+                sqlx::query(
+                    "INSERT INTO exerciseLog (date, username, reps, exercise_id, weight_in_kg, workout_id)
+                     VALUES (?, ?, 10, ?, 50.0, ?)",
+                )
+                .bind(format!("2025-{:02}-{:02}", log % 12 + 1, log % 28 + 1))
+                .bind(&username)
+                .bind((log * 7 % SEEDED_EXERCISES + 1) as i64)
+                .bind((log / 5) as i64)
+                .execute(&mut *transaction)
+                .await
+                .unwrap();
+            }
+            transaction.commit().await.unwrap();
+        }
+        pool
+    }
+
+    #[tokio::test]
+    async fn foreign_users_of_seeded_db_are_loaded_with_all_sets() {
+        let pool = setup_seeded_db().await;
+
+        let friends = get_all_friends(&pool, &ExerciseCatalog::default(), "user0")
+            .await
+            .unwrap();
+
+        assert_eq!(friends.len(), SEEDED_USERS - 1);
+        assert!(
+            friends
+                .iter()
+                .all(|friend| friend.profile_stat_manager.total_sets == LOGS_PER_USER as u64)
+        );
+    }
+
+    #[tokio::test]
+    async fn sets_of_foreign_users_are_searched_by_index() {
+        let pool = setup_seeded_db().await;
+
+        let details: Vec<String> =
+            sqlx::query(&format!("EXPLAIN QUERY PLAN {EXERCISE_SETS_QUERY}"))
+                .bind("user1")
+                .fetch_all(&pool)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| row.get("detail"))
+                .collect();
+        // Searching the username index keeps loading a profile independent of other users' logs
+        assert!(
+            details[0].contains("USING INDEX exerciseLog_username_exercise_id"),
+            "{details:?}"
+        );
+        assert!(
+            details
+                .iter()
+                .all(|detail| detail.starts_with("SEARCH") && !detail.contains("TEMP B-TREE")),
+            "{details:?}"
+        );
+    }
+
+    /// Wall-clock times depend on the machine and build, run it with
+    /// `cargo test --release -- --ignored foreign_users_of_seeded_db_load_fast`
+    #[tokio::test]
+    #[ignore = "benchmark"]
+    async fn foreign_users_of_seeded_db_load_fast() {
+        let pool = setup_seeded_db().await;
+        let exercise_catalog = ExerciseCatalog::default();
+
+        let start = Instant::now();
+        let friends = get_all_friends(&pool, &exercise_catalog, "user0")
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < Duration::from_secs(2),
+            "loading {} friends took {elapsed:?}",
+            friends.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn accepted_friend_request_makes_both_users_friends() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let exercise_catalog = ExerciseCatalog::default();
+
+        let status = send_friend_request(&pool, "testuser", "testuser2")
+            .await
+            .unwrap();
+        assert_eq!(status, FriendshipStatus::RequestSent);
+        assert_eq!(
+            get_friendship_status(&pool, "testuser2", "testuser")
+                .await
+                .unwrap(),
+            FriendshipStatus::RequestReceived
+        );
+        let senders = get_friend_request_senders(&pool, &exercise_catalog, "testuser2")
+            .await
+            .unwrap();
+        assert_eq!(senders.len(), 1);
+        assert_eq!(senders[0].username, "testuser");
+
+        answer_friend_request(&pool, "testuser2", "testuser", true)
+            .await
+            .unwrap();
+        for (username, friendname) in [("testuser", "testuser2"), ("testuser2", "testuser")] {
+            let friends = get_all_friends(&pool, &exercise_catalog, username)
+                .await
+                .unwrap();
+            assert_eq!(friends.len(), 1);
+            assert_eq!(friends[0].username, friendname);
+            assert_eq!(friends[0].friendship_status, FriendshipStatus::Friends);
+        }
+        assert!(
+            get_friend_request_senders(&pool, &exercise_catalog, "testuser2")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn declined_friend_request_can_be_sent_again() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        send_friend_request(&pool, "testuser", "testuser2")
+            .await
+            .unwrap();
+
+        answer_friend_request(&pool, "testuser2", "testuser", false)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_friendship_status(&pool, "testuser", "testuser2")
+                .await
+                .unwrap(),
+            FriendshipStatus::NotFriends
+        );
+        assert!(matches!(
+            answer_friend_request(&pool, "testuser2", "testuser", true).await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+
+        let status = send_friend_request(&pool, "testuser", "testuser2")
+            .await
+            .unwrap();
+        assert_eq!(status, FriendshipStatus::RequestSent);
+    }
+
+    #[tokio::test]
+    async fn crossing_friend_requests_make_both_users_friends() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        send_friend_request(&pool, "testuser", "testuser2")
+            .await
+            .unwrap();
+
+        let status = send_friend_request(&pool, "testuser2", "testuser")
+            .await
+            .unwrap();
+        assert_eq!(status, FriendshipStatus::Friends);
+
+        remove_friend(&pool, "testuser2", "testuser").await.unwrap();
+        assert_eq!(
+            get_friendship_status(&pool, "testuser", "testuser2")
+                .await
+                .unwrap(),
+            FriendshipStatus::NotFriends
+        );
+        assert!(matches!(
+            send_friend_request(&pool, "testuser", "nobody").await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+
+    async fn set_visibility(pool: &SqlitePool, username: &str, visibility: ProfileVisibility) {
+        let mut user_info = get_user_information(pool, &ExerciseCatalog::default(), username)
+            .await
+            .unwrap();
+        user_info.profile_visibility = visibility;
+        update_user_info_settings(pool, username, user_info)
+            .await
+            .unwrap();
+    }
+
+    async fn is_discovered_by(pool: &SqlitePool, active_user: &str, username: &str) -> bool {
+        get_discovery_users(pool, &ExerciseCatalog::default(), active_user, 200)
+            .await
+            .unwrap()
+            .iter()
+            .any(|user| user.username == username)
+    }
+
+    #[tokio::test]
+    async fn profile_visibility_hides_stats_and_private_users() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let exercise_catalog = ExerciseCatalog::default();
+
+        set_visibility(&pool, "testuser2", ProfileVisibility::FriendsOnly).await;
+        assert!(is_discovered_by(&pool, "testuser", "testuser2").await);
+        let stranger = get_single_foreign_user(&pool, &exercise_catalog, "testuser", "testuser2")
+            .await
+            .unwrap();
+        assert!(stranger.stats_hidden);
+        assert!(stranger.owned_mascots.is_empty());
+
+        set_visibility(&pool, "testuser2", ProfileVisibility::Private).await;
+        assert!(!is_discovered_by(&pool, "testuser", "testuser2").await);
+
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        let friend = get_single_foreign_user(&pool, &exercise_catalog, "testuser", "testuser2")
+            .await
+            .unwrap();
+        assert!(!friend.stats_hidden);
+        assert!(!friend.owned_mascots.is_empty());
+    }
+}
@@ -1,13 +1,14 @@
 use crate::client::backend::profile_stat_manager::ProfileStatManager;
 use crate::common::exercise_mod::weight::Kg;
 use crate::common::mascot_mod::mascot::Mascot;
-use crate::common::user_mod::user::{Gender, UserInformation};
+use crate::common::user_mod::user::{Gender, ProfileVisibility, UserInformation};
 use crate::common::user_mod::user_goals::{GoalType, UserGoals};
 use crate::common::user_mod::user_log::UserLog;
 use crate::server::database_mod::database::get_exercises_stats;
 use crate::server::database_mod::database_mascot::{add_mascot_to_user, mascot_from_string};
 use crate::server::database_mod::database_user_goals::get_user_goals;
 use crate::server::database_mod::database_user_logs::{add_user_log, get_user_log};
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
 use chrono::Local;
 use sqlx::Row;
 use sqlx::SqlitePool;
@@ -92,7 +93,7 @@ pub async fn get_user_weight(pool: &SqlitePool, username: &str) -> Result<f32, s
     Ok(row.get("weight"))
 }
 #[allow(dead_code)]
-pub async fn get_user_height(pool: &SqlitePool, username: &str) -> Result<f32, sqlx::Error> {
+pub async fn get_user_height(pool: &SqlitePool, username: &str) -> Result<u32, sqlx::Error> {
     let row = sqlx::query("SELECT height FROM users WHERE username = ?")
         .bind(username)
         .fetch_one(pool)
@@ -158,7 +159,8 @@ pub async fn update_user_info_settings(
     weight = ?,
     height = ?,
     description = ?,
-    profile_picture = ?
+    profile_picture = ?,
+    profile_visibility = ?
     WHERE
         username = ?
     ",
@@ -169,6 +171,7 @@ pub async fn update_user_info_settings(
     .bind(new_user_info.height)
     .bind(new_user_info.description)
     .bind(new_user_info.profile_picture_path)
+    .bind(new_user_info.profile_visibility.to_string())
     .bind(username)
     .execute(pool)
     .await?;
@@ -311,6 +314,7 @@ pub async fn update_user_selected_mascot(
 
 pub async fn get_user_information(
     pool: &SqlitePool,
+    exercise_catalog: &ExerciseCatalog,
     username: &str,
 ) -> Result<UserInformation, sqlx::Error> {
     let row = sqlx::query("SELECT * FROM users WHERE username = ?")
@@ -318,7 +322,7 @@ pub async fn get_user_information(
         .fetch_one(pool)
         .await?;
 
-    let exercise_stats = get_exercises_stats(pool, username).await?;
+    let exercise_stats = get_exercises_stats(pool, exercise_catalog, username).await?;
 
     let user_goals = get_user_goals(pool, username).await?;
 
@@ -334,13 +338,15 @@ pub async fn get_user_information(
         description: row.get("description"),
         profile_picture_path: row.get("profile_picture"),
         weight: row.get::<Kg, _>("weight"),
-        height: row.get::<f64, _>("height") as u32,
+        height: row.get("height"),
         gender: match row.get("gender") {
             "Female" => Gender::Female,
             _ => Gender::Male,
         },
         coin_balance: row.get::<i64, _>("coin_balance") as u32,
         favorite_mascot: mascot_from_string(row.get("favorite_mascot")),
+        profile_visibility: ProfileVisibility::from_str(row.get("profile_visibility"))
+            .unwrap_or_default(),
         profile_stat_manager: ProfileStatManager::new(
             &exercise_stats,
             user_goals.weekly_workouts as u32,
@@ -22,7 +22,7 @@ pub async fn add_user_log(
     sqlx::query(
         "INSERT INTO logs (date, username, value, log_type)
          VALUES (?, ?, ?, ?)
-         ON CONFLICT(username, date)
+         ON CONFLICT(username, date, log_type)
          DO UPDATE SET value = excluded.value;",
     )
     .bind(formatted_date)
@@ -62,3 +62,124 @@ pub async fn get_user_log(
 
     Ok(rows)
 }
+
+/// Value logged on the date, None if there is no entry of that type
+pub async fn get_user_log_value(
+    pool: &SqlitePool,
+    username: &str,
+    date: NaiveDate,
+    log_type: GoalType,
+) -> Result<Option<f32>, sqlx::Error> {
+    let formatted_date = database_utils::format_naive_date_for_database(&date);
+
+    sqlx::query_scalar("SELECT value FROM logs WHERE username = ? AND date = ? AND log_type = ?")
+        .bind(username)
+        .bind(formatted_date)
+        .bind(log_type.to_string())
+        .fetch_optional(pool)
+        .await
+}
+
+/// Returns false if there was no entry of that type on the date
+pub async fn delete_user_log(
+    pool: &SqlitePool,
+    username: &str,
+    date: NaiveDate,
+    log_type: GoalType,
+) -> Result<bool, sqlx::Error> {
+    let formatted_date = database_utils::format_naive_date_for_database(&date);
+
+    let result = sqlx::query("DELETE FROM logs WHERE username = ? AND date = ? AND log_type = ?")
+        .bind(username)
+        .bind(formatted_date)
+        .bind(log_type.to_string())
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+
+    #[tokio::test]
+    async fn add_user_log_overwrites_value_of_same_day() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        add_user_log(&pool, "testuser", 1.5, date, GoalType::Water)
+            .await
+            .unwrap();
+        add_user_log(&pool, "testuser", 2.5, date, GoalType::Water)
+            .await
+            .unwrap();
+
+        let log = get_user_log(&pool, "testuser", GoalType::Water)
+            .await
+            .unwrap();
+        let entries_on_date: Vec<_> = log.iter().filter(|(d, _)| *d == date).collect();
+        assert_eq!(entries_on_date, vec![&(date, 2.5)]);
+    }
+
+    #[tokio::test]
+    async fn different_log_types_on_same_day_are_kept_apart() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        add_user_log(&pool, "testuser", 2.0, date, GoalType::Water)
+            .await
+            .unwrap();
+        add_user_log(&pool, "testuser", 8000.0, date, GoalType::Steps)
+            .await
+            .unwrap();
+        add_user_log(&pool, "testuser", 7.5, date, GoalType::Sleep)
+            .await
+            .unwrap();
+
+        for (goal_type, value) in [
+            (GoalType::Water, 2.0),
+            (GoalType::Steps, 8000.0),
+            (GoalType::Sleep, 7.5),
+        ] {
+            let log = get_user_log(&pool, "testuser", goal_type).await.unwrap();
+            assert!(log.contains(&(date, value)), "{goal_type} log: {log:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_user_log_removes_only_matching_entry() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        add_user_log(&pool, "testuser", 2.0, date, GoalType::Water)
+            .await
+            .unwrap();
+        add_user_log(&pool, "testuser", 7.0, date, GoalType::Sleep)
+            .await
+            .unwrap();
+
+        assert!(
+            delete_user_log(&pool, "testuser", date, GoalType::Water)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !delete_user_log(&pool, "testuser", date, GoalType::Water)
+                .await
+                .unwrap()
+        );
+
+        let water_log = get_user_log(&pool, "testuser", GoalType::Water)
+            .await
+            .unwrap();
+        assert!(!water_log.iter().any(|(d, _)| *d == date));
+        let sleep_log = get_user_log(&pool, "testuser", GoalType::Sleep)
+            .await
+            .unwrap();
+        assert!(sleep_log.contains(&(date, 7.0)));
+    }
+}
@@ -0,0 +1,392 @@
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::workout_reaction::{
+    NewWorkoutComment, WorkoutComment, WorkoutReactions, WorkoutSummary,
+};
+use crate::server::database_mod::database_utils;
+use chrono::Local;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
+
+/// Whether `username` may see and react to the workout, which its owner and their friends can
+pub async fn is_workout_visible(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT 1 FROM exerciseLog WHERE workout_id = ? AND (username = ?
+        OR username IN (SELECT friendname FROM friendship WHERE username = ?))
+        LIMIT 1",
+    )
+    .bind(workout_id)
+    .bind(username)
+    .bind(username)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.is_some())
+}
+
+/// Giving kudos twice keeps the first ones
+pub async fn add_kudos(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO workout_kudos (workout_id, username, given_at) VALUES (?, ?, ?)",
+    )
+    .bind(workout_id)
+    .bind(username)
+    .bind(Local::now().naive_local())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Returns whether the user had given kudos
+pub async fn remove_kudos(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM workout_kudos WHERE workout_id = ? AND username = ?")
+        .bind(workout_id)
+        .bind(username)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Stores the comment with a trimmed text. Check it with NewWorkoutComment::validation_error first
+pub async fn add_comment(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+    new_comment: &NewWorkoutComment,
+) -> Result<WorkoutComment, sqlx::Error> {
+    let created_at = Local::now().naive_local();
+    let text = new_comment.text.trim();
+    let id = sqlx::query(
+        "INSERT INTO workout_comment (workout_id, username, text, created_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(workout_id)
+    .bind(username)
+    .bind(text)
+    .bind(created_at)
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
+
+    Ok(WorkoutComment {
+        id,
+        workout_id,
+        author: username.to_string(),
+        text: text.to_string(),
+        created_at,
+    })
+}
+
+/// Only the author of the comment and the owner of the workout can delete it,
+/// returns false for everyone else. RowNotFound if the workout has no such comment
+pub async fn delete_comment(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+    comment_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT username AS author,
+        EXISTS(SELECT 1 FROM exerciseLog WHERE workout_id = ? AND username = ?) AS owns_workout
+        FROM workout_comment WHERE id = ? AND workout_id = ?",
+    )
+    .bind(workout_id)
+    .bind(username)
+    .bind(comment_id)
+    .bind(workout_id)
+    .fetch_one(pool)
+    .await?;
+    let author: String = row.get("author");
+    let owns_workout: bool = row.get("owns_workout");
+    if author != username && !owns_workout {
+        return Ok(false);
+    }
+
+    sqlx::query("DELETE FROM workout_comment WHERE id = ?")
+        .bind(comment_id)
+        .execute(pool)
+        .await?;
+    Ok(true)
+}
+
+/// Removes the kudos and comments of a deleted workout,
+/// as its id can be given to the next saved workout
+pub async fn delete_workout_reactions(
+    connection: &mut SqliteConnection,
+    workout_id: Id,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM workout_kudos WHERE workout_id = ?")
+        .bind(workout_id)
+        .execute(&mut *connection)
+        .await?;
+    sqlx::query("DELETE FROM workout_comment WHERE workout_id = ?")
+        .bind(workout_id)
+        .execute(&mut *connection)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_workout_reactions(
+    pool: &SqlitePool,
+    workout_id: Id,
+) -> Result<WorkoutReactions, sqlx::Error> {
+    let kudos_rows = sqlx::query(
+        "SELECT workout_id, username FROM workout_kudos WHERE workout_id = ? ORDER BY given_at",
+    )
+    .bind(workout_id)
+    .fetch_all(pool)
+    .await?;
+    let comment_rows =
+        sqlx::query("SELECT * FROM workout_comment WHERE workout_id = ? ORDER BY id")
+            .bind(workout_id)
+            .fetch_all(pool)
+            .await?;
+
+    Ok(reactions_from_rows(&kudos_rows, &comment_rows)
+        .into_values()
+        .next()
+        .unwrap_or_else(|| WorkoutReactions::new(workout_id)))
+}
+
+/// Reactions on the workouts of `username`, workouts without any are left out
+pub async fn get_reactions_on_own_workouts(
+    pool: &SqlitePool,
+    username: &str,
+) -> Result<Vec<WorkoutReactions>, sqlx::Error> {
+    let kudos_rows = sqlx::query(
+        "SELECT workout_id, username FROM workout_kudos
+        WHERE workout_id IN (SELECT workout_id FROM exerciseLog WHERE username = ?)
+        ORDER BY given_at",
+    )
+    .bind(username)
+    .fetch_all(pool)
+    .await?;
+    let comment_rows = sqlx::query(
+        "SELECT * FROM workout_comment
+        WHERE workout_id IN (SELECT workout_id FROM exerciseLog WHERE username = ?)
+        ORDER BY id",
+    )
+    .bind(username)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reactions_from_rows(&kudos_rows, &comment_rows)
+        .into_values()
+        .collect())
+}
+
+/// Up to `limit` of the latest workouts of `owner` together with their reactions, newest first
+pub async fn get_recent_workout_summaries(
+    pool: &SqlitePool,
+    owner: &str,
+    limit: u32,
+) -> Result<Vec<WorkoutSummary>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT exerciseLog.workout_id, exerciseLog.date, exercise.name
+        FROM exerciseLog JOIN exercise ON exercise.id = exerciseLog.exercise_id
+        WHERE exerciseLog.username = ? AND exerciseLog.workout_id IN
+            (SELECT DISTINCT workout_id FROM exerciseLog WHERE username = ?
+            ORDER BY workout_id DESC LIMIT ?)
+        GROUP BY exerciseLog.workout_id, exerciseLog.exercise_id
+        ORDER BY exerciseLog.workout_id DESC, MIN(exerciseLog.id)",
+    )
+    .bind(owner)
+    .bind(owner)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let mut summaries: Vec<WorkoutSummary> = Vec::new();
+    for row in &rows {
+        let workout_id: Id = row.get("workout_id");
+        let exercise_name: String = row.get("name");
+        match summaries.last_mut() {
+            Some(summary) if summary.workout_id == workout_id => {
+                summary.exercise_names.push(exercise_name)
+            }
+            _ => summaries.push(WorkoutSummary {
+                workout_id,
+                owner: owner.to_string(),
+                date: database_utils::database_date_string_to_naive_date(row.get("date"))
+                    .map_err(|err| sqlx::Error::Decode(Box::new(err)))?,
+                exercise_names: vec![exercise_name],
+                reactions: WorkoutReactions::new(workout_id),
+            }),
+        }
+    }
+
+    for summary in &mut summaries {
+        summary.reactions = get_workout_reactions(pool, summary.workout_id).await?;
+    }
+    Ok(summaries)
+}
+
+fn reactions_from_rows(
+    kudos_rows: &[SqliteRow],
+    comment_rows: &[SqliteRow],
+) -> BTreeMap<Id, WorkoutReactions> {
+    let mut reactions: BTreeMap<Id, WorkoutReactions> = BTreeMap::new();
+    for row in kudos_rows {
+        let workout_id: Id = row.get("workout_id");
+        reactions
+            .entry(workout_id)
+            .or_insert_with(|| WorkoutReactions::new(workout_id))
+            .kudos
+            .push(row.get("username"));
+    }
+    for row in comment_rows {
+        let comment = comment_from_row(row);
+        reactions
+            .entry(comment.workout_id)
+            .or_insert_with(|| WorkoutReactions::new(comment.workout_id))
+            .comments
+            .push(comment);
+    }
+    reactions
+}
+
+fn comment_from_row(row: &SqliteRow) -> WorkoutComment {
+    WorkoutComment {
+        id: row.get("id"),
+        workout_id: row.get("workout_id"),
+        author: row.get("username"),
+        text: row.get("text"),
+        created_at: row.get("created_at"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::{
+        save_test_workout, setup_test_db, test_values_for_db,
+    };
+    use crate::server::database_mod::database_exercise::delete_workout;
+    use crate::server::database_mod::database_social::add_friend;
+    use chrono::NaiveDate;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn comment(text: &str) -> NewWorkoutComment {
+        NewWorkoutComment {
+            text: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn only_owner_and_friends_can_see_workouts() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let workout_id = save_test_workout(&pool, "testuser", "Squat", 100.0, date(2)).await;
+
+        assert!(
+            is_workout_visible(&pool, "testuser", workout_id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !is_workout_visible(&pool, "testuser2", workout_id)
+                .await
+                .unwrap()
+        );
+        add_friend(&pool, "testuser", "testuser2").await.unwrap();
+        assert!(
+            is_workout_visible(&pool, "testuser2", workout_id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !is_workout_visible(&pool, "testuser2", workout_id + 1)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn comments_can_be_deleted_by_author_and_workout_owner() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let workout_id = save_test_workout(&pool, "testuser", "Squat", 100.0, date(2)).await;
+
+        let own_comment = add_comment(&pool, "testuser", workout_id, &comment("Leg day"))
+            .await
+            .unwrap();
+        let friend_comment = add_comment(&pool, "testuser2", workout_id, &comment(" Strong! "))
+            .await
+            .unwrap();
+        assert_eq!(friend_comment.text, "Strong!");
+
+        assert!(
+            !delete_comment(&pool, "testuser2", workout_id, own_comment.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            delete_comment(&pool, "testuser", workout_id, friend_comment.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            delete_comment(&pool, "testuser", workout_id, friend_comment.id)
+                .await
+                .is_err()
+        );
+
+        let reactions = get_workout_reactions(&pool, workout_id).await.unwrap();
+        assert_eq!(reactions.comments, vec![own_comment]);
+    }
+
+    #[tokio::test]
+    async fn reactions_are_counted_and_removed_with_the_workout() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let older_workout_id = save_test_workout(&pool, "testuser", "Squat", 100.0, date(2)).await;
+        let workout_id = save_test_workout(&pool, "testuser", "Squat", 100.0, date(3)).await;
+
+        add_kudos(&pool, "testuser2", workout_id).await.unwrap();
+        add_kudos(&pool, "testuser2", workout_id).await.unwrap();
+        add_comment(&pool, "testuser2", workout_id, &comment("Nice"))
+            .await
+            .unwrap();
+
+        let own_reactions = get_reactions_on_own_workouts(&pool, "testuser")
+            .await
+            .unwrap();
+        assert_eq!(own_reactions.len(), 1);
+        assert_eq!(own_reactions[0].kudos, vec!["testuser2".to_string()]);
+        assert_eq!(own_reactions[0].summary(), "1 kudos | 1 comment");
+
+        let summaries = get_recent_workout_summaries(&pool, "testuser", 3)
+            .await
+            .unwrap();
+        let workout_ids: Vec<Id> = summaries.iter().map(|summary| summary.workout_id).collect();
+        assert_eq!(workout_ids, vec![workout_id, older_workout_id]);
+        assert_eq!(summaries[0].exercise_names, vec!["Squat".to_string()]);
+        assert_eq!(summaries[0].reactions.kudos.len(), 1);
+
+        // Deleting someone else's workout must not touch its reactions
+        assert!(
+            !delete_workout(&pool, "testuser2", workout_id)
+                .await
+                .unwrap()
+        );
+        assert!(remove_kudos(&pool, "testuser2", workout_id).await.unwrap());
+        delete_workout(&pool, "testuser", workout_id).await.unwrap();
+        assert_eq!(
+            get_workout_reactions(&pool, workout_id).await.unwrap(),
+            WorkoutReactions::new(workout_id)
+        );
+    }
+}
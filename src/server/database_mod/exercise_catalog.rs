@@ -0,0 +1,140 @@
+use crate::common::exercise_mod::general_exercise::GeneralExerciseInfo;
+use crate::server::database_mod::database_exercise::get_all_general_exercise_infos;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::sync::{Arc, Mutex};
+
+/// In-memory copy of the exercise table, shared by all routes through the AppState.
+/// The table is only loaded again after its version changed, see migration 19
+#[derive(Clone, Default)]
+pub struct ExerciseCatalog {
+    loaded_catalog: Arc<Mutex<Option<Arc<LoadedCatalog>>>>,
+}
+
+pub struct LoadedCatalog {
+    /// All exercises ordered by id
+    pub exercises: Vec<GeneralExerciseInfo>,
+    /// Quoted hash of the serialized exercises, used as ETag of /exercises/catalog.
+    /// Only changes if the exercises change, so clients can keep their copy across restarts
+    pub etag: String,
+    version: i64,
+}
+
+impl ExerciseCatalog {
+    /// The catalog as it is stored in the database right now
+    pub async fn current(&self, pool: &SqlitePool) -> Result<Arc<LoadedCatalog>, sqlx::Error> {
+        let version: i64 = sqlx::query_scalar("SELECT version FROM exercise_catalog_version")
+            .fetch_one(pool)
+            .await?;
+        if let Some(loaded_catalog) = self.lock().as_ref()
+            && loaded_catalog.version == version
+        {
+            return Ok(loaded_catalog.clone());
+        }
+
+        let exercises = get_all_general_exercise_infos(pool).await?;
+        let serialized_exercises =
+            serde_json::to_vec(&exercises).map_err(|err| sqlx::Error::Decode(err.into()))?;
+        let etag = format!("\"{:x}\"", Sha256::digest(serialized_exercises));
+        let loaded_catalog = Arc::new(LoadedCatalog {
+            exercises,
+            etag,
+            version,
+        });
+        *self.lock() = Some(loaded_catalog.clone());
+        Ok(loaded_catalog)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Arc<LoadedCatalog>>> {
+        // The catalog is only replaced as a whole, so it is fine to use after a panic
+        self.loaded_catalog
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+
+    #[tokio::test]
+    async fn catalog_is_ordered_by_id_and_only_loaded_again_after_changes() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool)
+            .await
+            .expect("inserting test values failed");
+        let exercise_catalog = ExerciseCatalog::default();
+
+        let loaded_catalog = exercise_catalog.current(&pool).await.unwrap();
+        assert_eq!(loaded_catalog.exercises.len(), 2);
+        assert!(
+            loaded_catalog
+                .exercises
+                .windows(2)
+                .all(|pair| pair[0].id < pair[1].id)
+        );
+        let cloned_catalog = exercise_catalog.clone();
+        assert!(Arc::ptr_eq(
+            &cloned_catalog.current(&pool).await.unwrap(),
+            &loaded_catalog
+        ));
+
+        sqlx::query("DELETE FROM exercise WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            cloned_catalog.current(&pool).await.unwrap().exercises.len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_exercises_do_not_change_the_version() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool)
+            .await
+            .expect("inserting test values failed");
+        let exercise_catalog = ExerciseCatalog::default();
+        let loaded_catalog = exercise_catalog.current(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO exercise (name, exercise_level_name, muscle_name, instructions, exercise_category_name, created_by)
+            VALUES ('Pendulum squat', 'Beginner', 'Quadriceps', '', 'Strength', 'testuser')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(Arc::ptr_eq(
+            &exercise_catalog.current(&pool).await.unwrap(),
+            &loaded_catalog
+        ));
+    }
+
+    #[tokio::test]
+    async fn etag_only_changes_with_the_exercises() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool)
+            .await
+            .expect("inserting test values failed");
+        let exercise_catalog = ExerciseCatalog::default();
+
+        let etag = exercise_catalog.current(&pool).await.unwrap().etag.clone();
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(
+            ExerciseCatalog::default()
+                .current(&pool)
+                .await
+                .unwrap()
+                .etag,
+            etag
+        );
+
+        sqlx::query("UPDATE exercise SET instructions = 'changed' WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_ne!(exercise_catalog.current(&pool).await.unwrap().etag, etag);
+    }
+}
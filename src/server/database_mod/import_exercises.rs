@@ -1,87 +1,542 @@
+use crate::common::exercise_mod::general_exercise::{
+    ExerciseCategory, ExerciseEquipment, ExerciseForce, ExerciseLevel, Muscle,
+};
 use serde::Deserialize;
-use sqlx::{Sqlite, SqlitePool, Transaction};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+/// Exercise in the format of the free-exercise-db, e.g. `exercises/Air_Bike.json`
 #[derive(Deserialize)]
-pub struct ExerciseJson {
+struct ExerciseFile {
     name: String,
     force: Option<String>,
     level: String,
     equipment: Option<String>,
     #[serde(rename = "primaryMuscles")]
-    primary_muscle: Vec<String>,
+    primary_muscles: Vec<String>,
+    #[serde(rename = "secondaryMuscles", default)]
+    secondary_muscles: Vec<String>,
+    #[serde(default)]
     instructions: Vec<String>,
     category: String,
+    /// Relative to the imported folder, e.g. `Air_Bike/0.jpg`
+    #[serde(default)]
+    images: Vec<String>,
 }
 
-/// Only used for initial filling of the exercise table
-#[allow(dead_code)]
-pub async fn import_exercises(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    let mut transaction = pool.begin().await.expect("Transaction err");
+/// Everything stored about an exercise, used to find out if an import changes it
+#[derive(Debug, Clone, PartialEq)]
+struct CatalogEntry {
+    name: String,
+    force: Option<String>,
+    level: String,
+    equipment: Option<String>,
+    primary_muscle: String,
+    secondary_muscles: Vec<String>,
+    instruction_steps: Vec<String>,
+    category: String,
+    image_paths: Vec<String>,
+}
 
-    let mut exercise_folders = tokio::fs::read_dir("assets/exercises")
-        .await
-        .expect("exercise folder err");
+#[derive(Debug)]
+pub enum ImportExercisesError {
+    CouldNotReadFolder(PathBuf, std::io::Error),
+    DatabaseError(sqlx::Error),
+}
+impl ImportExercisesError {
+    pub fn to_error_message(&self) -> String {
+        match self {
+            ImportExercisesError::CouldNotReadFolder(path, err) => {
+                format!("Could not read the folder {}: {err}", path.display())
+            }
+            ImportExercisesError::DatabaseError(err) => {
+                format!("Nothing was imported, the database failed: {err}")
+            }
+        }
+    }
+}
+impl From<sqlx::Error> for ImportExercisesError {
+    fn from(err: sqlx::Error) -> Self {
+        ImportExercisesError::DatabaseError(err)
+    }
+}
 
-    let mut exercises: Vec<ExerciseJson> = Vec::new();
+/// Differences between the imported folder and the exercise table before the import
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+    /// Exercises of the database which aren't in the folder. They are kept, as logs reference them
+    pub not_in_folder: Vec<String>,
+    /// Files which were skipped, with the reason
+    pub invalid_files: Vec<(PathBuf, String)>,
+}
 
-    while let Some(exercise_folder) = exercise_folders
-        .next_entry()
-        .await
-        .expect("next exercise err")
-    {
-        let exercise_json_path = exercise_folder.path().join("exercise.json");
-        if !exercise_json_path.exists() {
+impl ImportReport {
+    pub fn to_summary(&self) -> String {
+        let mut summary = format!(
+            "{} added, {} updated, {} unchanged, {} only in the database, {} invalid",
+            self.added.len(),
+            self.updated.len(),
+            self.unchanged,
+            self.not_in_folder.len(),
+            self.invalid_files.len()
+        );
+        for name in &self.added {
+            summary.push_str(&format!("\n+ {name}"));
+        }
+        for name in &self.updated {
+            summary.push_str(&format!("\n~ {name}"));
+        }
+        for (path, reason) in &self.invalid_files {
+            summary.push_str(&format!("\n! {}: {reason}", path.display()));
+        }
+        summary
+    }
+}
+
+/// Imports every exercise of `folder`, either as `<folder>/<name>.json` or `<folder>/<name>/exercise.json`. <br>
+/// Exercises are matched by name, so running the import again only applies the changes.
+/// Either all valid files are imported or none, invalid files are skipped and reported
+pub async fn import_exercises(
+    pool: &SqlitePool,
+    folder: &Path,
+) -> Result<ImportReport, ImportExercisesError> {
+    let mut report = ImportReport::default();
+    let mut entries = read_catalog_entries(folder, &mut report.invalid_files).await?;
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut transaction = pool.begin().await?;
+    let mut existing_entries = get_catalog_entries(&mut transaction).await?;
+
+    for entry in entries {
+        match existing_entries.remove(&entry.name) {
+            None => {
+                insert_catalog_entry(&mut transaction, &entry).await?;
+                report.added.push(entry.name);
+            }
+            Some((_, existing_entry)) if existing_entry == entry => report.unchanged += 1,
+            Some((exercise_id, _)) => {
+                update_catalog_entry(&mut transaction, exercise_id, &entry).await?;
+                report.updated.push(entry.name);
+            }
+        }
+    }
+    transaction.commit().await?;
+
+    report.not_in_folder = existing_entries.into_keys().collect();
+    report.not_in_folder.sort();
+    Ok(report)
+}
+
+async fn read_catalog_entries(
+    folder: &Path,
+    invalid_files: &mut Vec<(PathBuf, String)>,
+) -> Result<Vec<CatalogEntry>, ImportExercisesError> {
+    let folder_error = |err| ImportExercisesError::CouldNotReadFolder(folder.to_path_buf(), err);
+    let mut folder_entries = tokio::fs::read_dir(folder).await.map_err(folder_error)?;
+
+    let mut entries: Vec<CatalogEntry> = Vec::new();
+    while let Some(folder_entry) = folder_entries.next_entry().await.map_err(folder_error)? {
+        let path = folder_entry.path();
+        let exercise_path = if path.is_dir() {
+            path.join("exercise.json")
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            path
+        } else {
+            continue;
+        };
+        // Folders without exercise.json only contain images
+        if !exercise_path.exists() {
             continue;
         }
-        let exercise_json = tokio::fs::read_to_string(&exercise_json_path)
-            .await
-            .expect("json to string err");
-        let exercise_info: ExerciseJson =
-            serde_json::from_str(&exercise_json).expect("deserialize err");
 
-        if exercise_info.category == "strength" {
-            exercises.push(exercise_info);
+        match read_catalog_entry(&exercise_path).await {
+            Ok(entry) if entries.iter().any(|other| other.name == entry.name) => {
+                invalid_files.push((exercise_path, format!("{} is a duplicate", entry.name)))
+            }
+            Ok(entry) => entries.push(entry),
+            Err(reason) => invalid_files.push((exercise_path, reason)),
         }
     }
+    invalid_files.sort();
+    Ok(entries)
+}
 
-    exercises.sort_by(|a, b| a.name.cmp(&b.name));
+async fn read_catalog_entry(path: &Path) -> Result<CatalogEntry, String> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|err| err.to_string())?;
+    let exercise_file: ExerciseFile =
+        serde_json::from_str(&content).map_err(|err| err.to_string())?;
+    exercise_file.into_catalog_entry()
+}
 
-    for exercise in exercises {
-        insert_exercise_in_db(&mut transaction, &exercise)
-            .await
-            .expect("insert in db err");
+impl ExerciseFile {
+    /// Checks every value against the enums of GeneralExerciseInfo, so the server can read it later
+    fn into_catalog_entry(self) -> Result<CatalogEntry, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("The name is empty".to_string());
+        }
+        if let Some(force) = &self.force {
+            ExerciseForce::from_str(force).map_err(|_| format!("Unknown force {force}"))?;
+        }
+        ExerciseLevel::from_str(&self.level)
+            .map_err(|_| format!("Unknown level {}", self.level))?;
+        if let Some(equipment) = &self.equipment {
+            ExerciseEquipment::from_str(equipment)
+                .map_err(|_| format!("Unknown equipment {equipment}"))?;
+        }
+        ExerciseCategory::from_str(&self.category)
+            .map_err(|_| format!("Unknown category {}", self.category))?;
+        for muscle in self.primary_muscles.iter().chain(&self.secondary_muscles) {
+            Muscle::from_str(muscle).map_err(|_| format!("Unknown muscle {muscle}"))?;
+        }
+        let Some(primary_muscle) = self.primary_muscles.first().cloned() else {
+            return Err("No primary muscle".to_string());
+        };
+
+        let mut secondary_muscles: Vec<String> = Vec::new();
+        for muscle in self.secondary_muscles {
+            if muscle != primary_muscle && !secondary_muscles.contains(&muscle) {
+                secondary_muscles.push(muscle);
+            }
+        }
+
+        Ok(CatalogEntry {
+            name,
+            force: self.force,
+            level: self.level,
+            equipment: self.equipment,
+            primary_muscle,
+            secondary_muscles,
+            instruction_steps: self.instructions,
+            category: self.category,
+            image_paths: self.images,
+        })
     }
+}
 
-    transaction.commit().await.expect("commit err");
-    Ok(())
+/// All exercises of the catalog by name with their id, custom exercises of users are left out
+async fn get_catalog_entries(
+    connection: &mut SqliteConnection,
+) -> Result<HashMap<String, (i64, CatalogEntry)>, sqlx::Error> {
+    let exercise_rows = sqlx::query("SELECT * FROM exercise WHERE created_by IS NULL")
+        .fetch_all(&mut *connection)
+        .await?;
+    let secondary_muscle_rows = sqlx::query(
+        "SELECT exercise_id, muscle_name FROM exercise_secondary_muscle ORDER BY exercise_id, position",
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+    let instruction_rows = sqlx::query(
+        "SELECT exercise_id, instruction FROM exercise_instruction ORDER BY exercise_id, step",
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+    let image_rows = sqlx::query(
+        "SELECT exercise_id, image_path FROM exercise_image ORDER BY exercise_id, position",
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+
+    let mut entries_by_id: HashMap<i64, CatalogEntry> = HashMap::new();
+    for row in exercise_rows {
+        entries_by_id.insert(
+            row.get("id"),
+            CatalogEntry {
+                name: row.get("name"),
+                force: row.get("exercise_force_name"),
+                level: row.get("exercise_level_name"),
+                equipment: row.get("exercise_equipment_name"),
+                primary_muscle: row.get("muscle_name"),
+                secondary_muscles: Vec::new(),
+                instruction_steps: Vec::new(),
+                category: row.get("exercise_category_name"),
+                image_paths: Vec::new(),
+            },
+        );
+    }
+    for row in secondary_muscle_rows {
+        if let Some(entry) = entries_by_id.get_mut(&row.get("exercise_id")) {
+            entry.secondary_muscles.push(row.get("muscle_name"));
+        }
+    }
+    for row in instruction_rows {
+        if let Some(entry) = entries_by_id.get_mut(&row.get("exercise_id")) {
+            entry.instruction_steps.push(row.get("instruction"));
+        }
+    }
+    for row in image_rows {
+        if let Some(entry) = entries_by_id.get_mut(&row.get("exercise_id")) {
+            entry.image_paths.push(row.get("image_path"));
+        }
+    }
+
+    Ok(entries_by_id
+        .into_iter()
+        .map(|(exercise_id, entry)| (entry.name.clone(), (exercise_id, entry)))
+        .collect())
 }
 
-/// Only used for initial filling of the exercise table
-#[allow(dead_code)]
-async fn insert_exercise_in_db(
-    transaction: &mut Transaction<'_, Sqlite>,
-    exercise_json: &ExerciseJson,
+async fn insert_catalog_entry(
+    connection: &mut SqliteConnection,
+    entry: &CatalogEntry,
 ) -> Result<(), sqlx::Error> {
-    let instructions = exercise_json.instructions.join(" ");
-    let muscle = exercise_json
-        .primary_muscle
-        .first()
-        .map(|mus| mus.as_str())
-        .unwrap_or("None"); //Shouldn't happen normally
+    let exercise_id = sqlx::query(
+        "INSERT INTO exercise (name, exercise_force_name, exercise_level_name, exercise_equipment_name,
+        muscle_name, instructions, exercise_category_name)
+        VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&entry.name)
+    .bind(&entry.force)
+    .bind(&entry.level)
+    .bind(&entry.equipment)
+    .bind(&entry.primary_muscle)
+    .bind(entry.instruction_steps.join(" "))
+    .bind(&entry.category)
+    .execute(&mut *connection)
+    .await?
+    .last_insert_rowid();
+
+    insert_catalog_entry_details(connection, exercise_id, entry).await
+}
 
+/// Keeps the id, so logged sets still belong to the exercise
+async fn update_catalog_entry(
+    connection: &mut SqliteConnection,
+    exercise_id: i64,
+    entry: &CatalogEntry,
+) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "INSERT INTO exercise (name,exercise_force_name,exercise_level_name,exercise_equipment_name, muscle_name, instructions, exercise_category_name)
-    VALUES (?,?,?,?,?,?,?) ",
+        "UPDATE exercise SET exercise_force_name = ?, exercise_level_name = ?, exercise_equipment_name = ?,
+        muscle_name = ?, instructions = ?, exercise_category_name = ?
+        WHERE id = ?",
     )
-        .bind(&exercise_json.name)
-        .bind(&exercise_json.force)
-        .bind(&exercise_json.level)
-        .bind(&exercise_json.equipment)
+    .bind(&entry.force)
+    .bind(&entry.level)
+    .bind(&entry.equipment)
+    .bind(&entry.primary_muscle)
+    .bind(entry.instruction_steps.join(" "))
+    .bind(&entry.category)
+    .bind(exercise_id)
+    .execute(&mut *connection)
+    .await?;
+
+    for table in [
+        "exercise_secondary_muscle",
+        "exercise_instruction",
+        "exercise_image",
+    ] {
+        sqlx::query(&format!("DELETE FROM {table} WHERE exercise_id = ?"))
+            .bind(exercise_id)
+            .execute(&mut *connection)
+            .await?;
+    }
+    insert_catalog_entry_details(connection, exercise_id, entry).await
+}
+
+async fn insert_catalog_entry_details(
+    connection: &mut SqliteConnection,
+    exercise_id: i64,
+    entry: &CatalogEntry,
+) -> Result<(), sqlx::Error> {
+    for (position, muscle) in entry.secondary_muscles.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO exercise_secondary_muscle (exercise_id, position, muscle_name) VALUES (?, ?, ?)",
+        )
+        .bind(exercise_id)
+        .bind(position as i64)
         .bind(muscle)
-        .bind(&instructions)
-        .bind(&exercise_json.category)
-        .execute(&mut **transaction)
+        .execute(&mut *connection)
         .await?;
-
+    }
+    for (step, instruction) in entry.instruction_steps.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO exercise_instruction (exercise_id, step, instruction) VALUES (?, ?, ?)",
+        )
+        .bind(exercise_id)
+        .bind(step as i64)
+        .bind(instruction)
+        .execute(&mut *connection)
+        .await?;
+    }
+    for (position, image_path) in entry.image_paths.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO exercise_image (exercise_id, position, image_path) VALUES (?, ?, ?)",
+        )
+        .bind(exercise_id)
+        .bind(position as i64)
+        .bind(image_path)
+        .execute(&mut *connection)
+        .await?;
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::setup_test_db;
+
+    const AIR_BIKE: &str = r#"{
+        "name": "Air Bike",
+        "force": "pull",
+        "level": "beginner",
+        "mechanic": "compound",
+        "equipment": "body only",
+        "primaryMuscles": ["abdominals"],
+        "secondaryMuscles": [],
+        "instructions": ["Lie flat on the floor.", "Bring your knees up."],
+        "category": "strength",
+        "images": ["Air_Bike/0.jpg", "Air_Bike/1.jpg"],
+        "id": "Air_Bike"
+    }"#;
+    const ROWING: &str = r#"{
+        "name": "Rowing, Stationary",
+        "force": null,
+        "level": "intermediate",
+        "equipment": "machine",
+        "primaryMuscles": ["quadriceps"],
+        "secondaryMuscles": ["biceps", "calves", "middle back"],
+        "instructions": ["Sit down.", "Row."],
+        "category": "cardio",
+        "images": []
+    }"#;
+
+    async fn test_folder(name: &str) -> PathBuf {
+        let folder = std::env::temp_dir()
+            .join(format!("buff_buddies_import_{}", std::process::id()))
+            .join(name);
+        let _ = tokio::fs::remove_dir_all(&folder).await;
+        tokio::fs::create_dir_all(folder.join("Rowing_Stationary"))
+            .await
+            .unwrap();
+        tokio::fs::write(folder.join("Air_Bike.json"), AIR_BIKE)
+            .await
+            .unwrap();
+        tokio::fs::write(folder.join("Rowing_Stationary/exercise.json"), ROWING)
+            .await
+            .unwrap();
+        folder
+    }
+
+    async fn stored_values(pool: &SqlitePool, query: &str, exercise_name: &str) -> Vec<String> {
+        sqlx::query(query)
+            .bind(exercise_name)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn import_stores_every_category_with_details() {
+        let pool = setup_test_db().await;
+        let folder = test_folder("details").await;
+        tokio::fs::write(folder.join("Broken.json"), "{ \"name\": \"Broken\" }")
+            .await
+            .unwrap();
+
+        let report = import_exercises(&pool, &folder).await.unwrap();
+
+        assert_eq!(report.added, vec!["Air Bike", "Rowing, Stationary"]);
+        assert_eq!(report.invalid_files.len(), 1);
+        assert_eq!(
+            stored_values(
+                &pool,
+                "SELECT exercise_secondary_muscle.muscle_name FROM exercise_secondary_muscle JOIN exercise ON exercise.id = exercise_id
+                WHERE name = ? ORDER BY position",
+                "Rowing, Stationary"
+            )
+            .await,
+            vec!["biceps", "calves", "middle back"]
+        );
+        assert_eq!(
+            stored_values(
+                &pool,
+                "SELECT instruction FROM exercise_instruction JOIN exercise ON exercise.id = exercise_id
+                WHERE name = ? ORDER BY step",
+                "Air Bike"
+            )
+            .await,
+            vec!["Lie flat on the floor.", "Bring your knees up."]
+        );
+        assert_eq!(
+            stored_values(
+                &pool,
+                "SELECT image_path FROM exercise_image JOIN exercise ON exercise.id = exercise_id
+                WHERE name = ? ORDER BY position",
+                "Air Bike"
+            )
+            .await,
+            vec!["Air_Bike/0.jpg", "Air_Bike/1.jpg"]
+        );
+        tokio::fs::remove_dir_all(&folder).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn repeated_import_only_applies_changes() {
+        let pool = setup_test_db().await;
+        let folder = test_folder("repeated").await;
+        import_exercises(&pool, &folder).await.unwrap();
+        let air_bike_id: i64 = sqlx::query("SELECT id FROM exercise WHERE name = 'Air Bike'")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+
+        let report = import_exercises(&pool, &folder).await.unwrap();
+        assert!(report.added.is_empty() && report.updated.is_empty());
+        assert_eq!(report.unchanged, 2);
+
+        tokio::fs::write(
+            folder.join("Air_Bike.json"),
+            AIR_BIKE.replace("\"beginner\"", "\"expert\""),
+        )
+        .await
+        .unwrap();
+        tokio::fs::remove_dir_all(folder.join("Rowing_Stationary"))
+            .await
+            .unwrap();
+        let report = import_exercises(&pool, &folder).await.unwrap();
+
+        assert_eq!(report.updated, vec!["Air Bike"]);
+        assert_eq!(report.not_in_folder, vec!["Rowing, Stationary"]);
+        let air_bike =
+            sqlx::query("SELECT id, exercise_level_name FROM exercise WHERE name = 'Air Bike'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(air_bike.get::<i64, _>("id"), air_bike_id);
+        assert_eq!(air_bike.get::<String, _>("exercise_level_name"), "expert");
+        let instruction_count: i64 =
+            sqlx::query("SELECT COUNT(*) FROM exercise_instruction WHERE exercise_id = ?")
+                .bind(air_bike_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+                .get(0);
+        assert_eq!(instruction_count, 2);
+        tokio::fs::remove_dir_all(&folder).await.unwrap();
+    }
+
+    #[test]
+    fn unknown_values_are_rejected() {
+        let exercise_file: ExerciseFile =
+            serde_json::from_str(&AIR_BIKE.replace("\"abdominals\"", "\"wings\"")).unwrap();
+        assert_eq!(
+            exercise_file.into_catalog_entry().unwrap_err(),
+            "Unknown muscle wings"
+        );
+    }
+}
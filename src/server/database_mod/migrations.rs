@@ -0,0 +1,1287 @@
+use sqlx::{Connection, Row, SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
+
+/// One forward-only change of the database schema. <br>
+/// Never edit a released migration, add a new one with the next version instead
+pub struct Migration {
+    pub version: i64,
+    pub description: &'static str,
+    steps: &'static [MigrationStep],
+}
+
+enum MigrationStep {
+    Sql(&'static str),
+    /// Databases created before the migrations existed may already contain the column
+    AddColumnIfMissing {
+        table: &'static str,
+        column: &'static str,
+        column_type: &'static str,
+    },
+    /// SQLite can't change the constraints of a column, so the rows are copied into a new table
+    /// with the given definition. Every column of the new table must exist in the old one.
+    /// Rows breaking a foreign key abort the migration and are reported, they have to be fixed
+    /// by hand. The indexes of the old table are dropped with it
+    RebuildTable {
+        table: &'static str,
+        definition: &'static str,
+    },
+}
+
+/// Every migration ordered by version. The first ones only use `IF NOT EXISTS`,
+/// so databases created before the migrations existed are taken over without changes
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Create the initial schema",
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS users (
+    username TEXT PRIMARY KEY,
+    user_password TEXT NOT NULL,
+    coin_balance INTEGER NOT NULL,
+    weight FLOAT NOT NULL,
+    height FLOAT NOT NULL,
+    gender TEXT NOT NULL,
+    favorite_mascot TEXT NOT NULL,
+    selected_mascot TEXT NOT NULL,
+    profile_picture TEXT,
+    description TEXT
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS mascot(
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    mascot_name TEXT NOT NULL UNIQUE,
+    description TEXT NULL
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS user_mascot(
+    username TEXT NOT NULL,
+    mascot_name TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (username, mascot_name),
+
+    FOREIGN KEY (username) REFERENCES users (username),
+    FOREIGN KEY (mascot_name) REFERENCES mascot (mascot_name)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS exercise (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    exercise_force_name TEXT,
+    exercise_level_name TEXT NOT NULL,
+    exercise_equipment_name TEXT,
+    muscle_name TEXT NOT NULL,
+    instructions TEXT NOT NULL,
+    exercise_category_name TEXT NOT NULL
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS exerciseLog (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL,
+    username TEXT NOT NULL,
+    reps INTEGER NOT NULL,
+    exercise_id INTEGER NOT NULL,
+    weight_in_kg FLOAT NOT NULL,
+    workout_id INTEGER NOT NULL,
+    FOREIGN KEY (exercise_id) REFERENCES exercise(id),
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS preset (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    preset_name TEXT NOT NULL,
+    preset_image TEXT NOT NULL,
+    number_of_exercises INTEGER NOT NULL,
+    estimated_duration INTEGER NOT NULL,
+    description TEXT
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS preset_exercise(
+    preset_id INTEGER NOT NULL,
+    exercise_name TEXT NOT NULL,
+
+    FOREIGN KEY (preset_id) REFERENCES preset (id)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS user_preset(
+    username TEXT NOT NULL,
+    preset_id INTEGER NOT NULL,
+    times_preset_trained INTEGER NOT NULL,
+    PRIMARY KEY (username, preset_id),
+
+    FOREIGN KEY (username) REFERENCES users (username),
+    FOREIGN KEY (preset_id) REFERENCES preset (id)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS friendship (
+    username TEXT NOT NULL,
+    friendname TEXT NOT NULL,
+    PRIMARY KEY (username, friendname),
+
+    FOREIGN KEY (username) REFERENCES users(username),
+    FOREIGN KEY (friendname) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS logs (
+    username TEXT NOT NULL,
+    date TEXT NOT NULL,
+    value FLOAT NOT NULL,
+    log_type TEXT NOT NULL,
+
+    PRIMARY KEY(username, date, log_type),
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS user_goals (
+    username TEXT NOT NULL,
+    weekly_workouts FLOAT NOT NULL,
+    weight FLOAT NOT NULL,
+    water FLOAT NOT NULL,
+    steps FLOAT NOT NULL,
+    sleep FLOAT NOT NULL,
+
+    PRIMARY KEY (username),
+
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+        ],
+    },
+    Migration {
+        version: 2,
+        description: "Store refresh tokens",
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS refresh_tokens (
+    token_hash TEXT PRIMARY KEY,
+    username TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    expires_at INTEGER NOT NULL,
+    revoked INTEGER NOT NULL DEFAULT 0,
+
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS refresh_tokens_session_id ON refresh_tokens(session_id);",
+            ),
+        ],
+    },
+    Migration {
+        version: 3,
+        description: "Add the start time of backdated workouts",
+        steps: &[MigrationStep::AddColumnIfMissing {
+            table: "exerciseLog",
+            column: "start_time",
+            column_type: "TEXT",
+        }],
+    },
+    Migration {
+        version: 4,
+        description: "Add the position of user presets",
+        steps: &[MigrationStep::AddColumnIfMissing {
+            table: "user_preset",
+            column: "position",
+            column_type: "INTEGER NOT NULL DEFAULT 0",
+        }],
+    },
+    Migration {
+        version: 5,
+        description: "Record preset trainings",
+        steps: &[MigrationStep::Sql(
+            "CREATE TABLE IF NOT EXISTS preset_history(
+    workout_id INTEGER PRIMARY KEY,
+    username TEXT NOT NULL,
+    preset_id INTEGER NOT NULL,
+    date TEXT NOT NULL,
+    duration_minutes INTEGER,
+
+    FOREIGN KEY (username) REFERENCES users (username),
+    FOREIGN KEY (preset_id) REFERENCES preset (id)
+    );",
+        )],
+    },
+    Migration {
+        version: 6,
+        description: "Index exercise logs by user and exercise",
+        steps: &[MigrationStep::Sql(
+            "CREATE INDEX IF NOT EXISTS exerciseLog_username_exercise_id ON exerciseLog(username, exercise_id);",
+        )],
+    },
+    Migration {
+        version: 7,
+        description: "Store the height of users as INTEGER",
+        // SQLite can't change the type of a column, so the table is rebuilt
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE users_new (
+    username TEXT PRIMARY KEY,
+    user_password TEXT NOT NULL,
+    coin_balance INTEGER NOT NULL,
+    weight FLOAT NOT NULL,
+    height INTEGER NOT NULL,
+    gender TEXT NOT NULL,
+    favorite_mascot TEXT NOT NULL,
+    selected_mascot TEXT NOT NULL,
+    profile_picture TEXT,
+    description TEXT
+    );",
+            ),
+            MigrationStep::Sql(
+                "INSERT INTO users_new (username, user_password, coin_balance, weight, height, gender,
+    favorite_mascot, selected_mascot, profile_picture, description)
+    SELECT username, user_password, coin_balance, weight, CAST(ROUND(height) AS INTEGER), gender,
+    favorite_mascot, selected_mascot, profile_picture, description FROM users;",
+            ),
+            MigrationStep::Sql("DROP TABLE users;"),
+            MigrationStep::Sql("ALTER TABLE users_new RENAME TO users;"),
+        ],
+    },
+    Migration {
+        version: 8,
+        description: "Store secondary muscles, instruction steps and images of exercises",
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS exercise_secondary_muscle (
+    exercise_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    muscle_name TEXT NOT NULL,
+    PRIMARY KEY (exercise_id, position),
+
+    FOREIGN KEY (exercise_id) REFERENCES exercise(id)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS exercise_instruction (
+    exercise_id INTEGER NOT NULL,
+    step INTEGER NOT NULL,
+    instruction TEXT NOT NULL,
+    PRIMARY KEY (exercise_id, step),
+
+    FOREIGN KEY (exercise_id) REFERENCES exercise(id)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS exercise_image (
+    exercise_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    image_path TEXT NOT NULL,
+    PRIMARY KEY (exercise_id, position),
+
+    FOREIGN KEY (exercise_id) REFERENCES exercise(id)
+    );",
+            ),
+        ],
+    },
+    Migration {
+        version: 9,
+        description: "Store custom exercises of users next to the catalog",
+        // Exercise names are only unique per creator now, which needs a rebuild of the table
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE exercise_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    exercise_force_name TEXT,
+    exercise_level_name TEXT NOT NULL,
+    exercise_equipment_name TEXT,
+    muscle_name TEXT NOT NULL,
+    instructions TEXT NOT NULL,
+    exercise_category_name TEXT NOT NULL,
+    created_by TEXT,
+    shared_with_friends INTEGER NOT NULL DEFAULT 0,
+
+    FOREIGN KEY (created_by) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "INSERT INTO exercise_new (id, name, exercise_force_name, exercise_level_name,
+    exercise_equipment_name, muscle_name, instructions, exercise_category_name)
+    SELECT id, name, exercise_force_name, exercise_level_name, exercise_equipment_name,
+    muscle_name, instructions, exercise_category_name FROM exercise;",
+            ),
+            MigrationStep::Sql("DROP TABLE exercise;"),
+            MigrationStep::Sql("ALTER TABLE exercise_new RENAME TO exercise;"),
+            MigrationStep::Sql(
+                "CREATE UNIQUE INDEX IF NOT EXISTS exercise_catalog_name ON exercise(name) WHERE created_by IS NULL;",
+            ),
+            MigrationStep::Sql(
+                "CREATE UNIQUE INDEX IF NOT EXISTS exercise_custom_name ON exercise(created_by, name) WHERE created_by IS NOT NULL;",
+            ),
+        ],
+    },
+    Migration {
+        version: 10,
+        description: "Replace one-sided friendships with friend requests",
+        // Friendships are stored in both directions from now on. A one-sided friendship was
+        // never agreed to by the other user, so it becomes a pending friend request
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS friend_request (
+    sender TEXT NOT NULL,
+    receiver TEXT NOT NULL,
+    status TEXT NOT NULL,
+    sent_at TEXT NOT NULL,
+    PRIMARY KEY (sender, receiver),
+
+    FOREIGN KEY (sender) REFERENCES users(username),
+    FOREIGN KEY (receiver) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "INSERT OR IGNORE INTO friend_request (sender, receiver, status, sent_at)
+    SELECT username, friendname, 'Pending', datetime('now') FROM friendship AS one_sided
+    WHERE NOT EXISTS (SELECT 1 FROM friendship
+        WHERE username = one_sided.friendname AND friendname = one_sided.username);",
+            ),
+            MigrationStep::Sql(
+                "DELETE FROM friendship WHERE (username, friendname) IN
+    (SELECT sender, receiver FROM friend_request WHERE status = 'Pending');",
+            ),
+        ],
+    },
+    Migration {
+        version: 11,
+        description: "Record events for the friends feed",
+        // Workout details are read from exerciseLog, so edited workouts show their new sets
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS feed_event (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    workout_id INTEGER,
+    duration_minutes INTEGER,
+    exercise_name TEXT,
+    mascot_name TEXT,
+    goal_type TEXT,
+    value FLOAT,
+
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS feed_event_username ON feed_event(username, id);",
+            ),
+        ],
+    },
+    Migration {
+        version: 12,
+        description: "Add kudos and comments on workouts",
+        // workout_id is unique across all users in exerciseLog, so it identifies the workout
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS workout_kudos (
+    workout_id INTEGER NOT NULL,
+    username TEXT NOT NULL,
+    given_at TEXT NOT NULL,
+    PRIMARY KEY (workout_id, username),
+
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS workout_comment (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workout_id INTEGER NOT NULL,
+    username TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+
+    FOREIGN KEY (username) REFERENCES users(username)
+    );",
+            ),
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS workout_comment_workout_id ON workout_comment(workout_id);",
+            ),
+        ],
+    },
+    Migration {
+        version: 13,
+        description: "Add challenges between friends",
+        // The stakes of both users are taken from coin_balance while the challenge runs
+        steps: &[MigrationStep::Sql(
+            "CREATE TABLE IF NOT EXISTS challenge (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    challenger TEXT NOT NULL,
+    opponent TEXT NOT NULL,
+    goal TEXT NOT NULL,
+    exercise_name TEXT,
+    target_weight FLOAT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    stake INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    winner TEXT,
+    created_at TEXT NOT NULL,
+
+    FOREIGN KEY (challenger) REFERENCES users(username),
+    FOREIGN KEY (opponent) REFERENCES users(username)
+    );",
+        )],
+    },
+    Migration {
+        version: 14,
+        description: "Add the profile visibility of users",
+        steps: &[MigrationStep::AddColumnIfMissing {
+            table: "users",
+            column: "profile_visibility",
+            column_type: "TEXT NOT NULL DEFAULT 'Public'",
+        }],
+    },
+    Migration {
+        version: 15,
+        description: "Add blocked users and reports of profiles",
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS user_block (
+    blocker TEXT NOT NULL,
+    blocked TEXT NOT NULL,
+    blocked_at TEXT NOT NULL,
+    PRIMARY KEY (blocker, blocked),
+
+    FOREIGN KEY (blocker) REFERENCES users(username),
+    FOREIGN KEY (blocked) REFERENCES users(username)
+    );",
+            ),
+            // The description is copied, so moderators see what was reported even after it changed
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS user_report (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reporter TEXT NOT NULL,
+    reported TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    reported_description TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+
+    FOREIGN KEY (reporter) REFERENCES users(username),
+    FOREIGN KEY (reported) REFERENCES users(username)
+    );",
+            ),
+        ],
+    },
+    Migration {
+        version: 16,
+        description: "Cascade renamed and deleted users to every table referencing them",
+        // Every table referencing users(username) is rebuilt, the indexes are created again.
+        // Reports keep the plain usernames, so moderators still see them after an account is deleted
+        steps: &[
+            MigrationStep::RebuildTable {
+                table: "user_mascot",
+                definition: "username TEXT NOT NULL,
+    mascot_name TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (username, mascot_name),
+
+    FOREIGN KEY (username) REFERENCES users (username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (mascot_name) REFERENCES mascot (mascot_name)",
+            },
+            MigrationStep::RebuildTable {
+                table: "exerciseLog",
+                definition: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL,
+    username TEXT NOT NULL,
+    reps INTEGER NOT NULL,
+    exercise_id INTEGER NOT NULL,
+    weight_in_kg FLOAT NOT NULL,
+    workout_id INTEGER NOT NULL,
+    start_time TEXT,
+    FOREIGN KEY (exercise_id) REFERENCES exercise(id),
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "user_preset",
+                definition: "username TEXT NOT NULL,
+    preset_id INTEGER NOT NULL,
+    times_preset_trained INTEGER NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (username, preset_id),
+
+    FOREIGN KEY (username) REFERENCES users (username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (preset_id) REFERENCES preset (id)",
+            },
+            MigrationStep::RebuildTable {
+                table: "friendship",
+                definition: "username TEXT NOT NULL,
+    friendname TEXT NOT NULL,
+    PRIMARY KEY (username, friendname),
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (friendname) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "logs",
+                definition: "username TEXT NOT NULL,
+    date TEXT NOT NULL,
+    value FLOAT NOT NULL,
+    log_type TEXT NOT NULL,
+
+    PRIMARY KEY(username, date, log_type),
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "user_goals",
+                definition: "username TEXT NOT NULL,
+    weekly_workouts FLOAT NOT NULL,
+    weight FLOAT NOT NULL,
+    water FLOAT NOT NULL,
+    steps FLOAT NOT NULL,
+    sleep FLOAT NOT NULL,
+
+    PRIMARY KEY (username),
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "refresh_tokens",
+                definition: "token_hash TEXT PRIMARY KEY,
+    username TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    expires_at INTEGER NOT NULL,
+    revoked INTEGER NOT NULL DEFAULT 0,
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "preset_history",
+                definition: "workout_id INTEGER PRIMARY KEY,
+    username TEXT NOT NULL,
+    preset_id INTEGER NOT NULL,
+    date TEXT NOT NULL,
+    duration_minutes INTEGER,
+
+    FOREIGN KEY (username) REFERENCES users (username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (preset_id) REFERENCES preset (id)",
+            },
+            MigrationStep::RebuildTable {
+                table: "exercise",
+                definition: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    exercise_force_name TEXT,
+    exercise_level_name TEXT NOT NULL,
+    exercise_equipment_name TEXT,
+    muscle_name TEXT NOT NULL,
+    instructions TEXT NOT NULL,
+    exercise_category_name TEXT NOT NULL,
+    created_by TEXT,
+    shared_with_friends INTEGER NOT NULL DEFAULT 0,
+
+    FOREIGN KEY (created_by) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "friend_request",
+                definition: "sender TEXT NOT NULL,
+    receiver TEXT NOT NULL,
+    status TEXT NOT NULL,
+    sent_at TEXT NOT NULL,
+    PRIMARY KEY (sender, receiver),
+
+    FOREIGN KEY (sender) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (receiver) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "feed_event",
+                definition: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    workout_id INTEGER,
+    duration_minutes INTEGER,
+    exercise_name TEXT,
+    mascot_name TEXT,
+    goal_type TEXT,
+    value FLOAT,
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "workout_kudos",
+                definition: "workout_id INTEGER NOT NULL,
+    username TEXT NOT NULL,
+    given_at TEXT NOT NULL,
+    PRIMARY KEY (workout_id, username),
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "workout_comment",
+                definition: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workout_id INTEGER NOT NULL,
+    username TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "challenge",
+                definition: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+    challenger TEXT NOT NULL,
+    opponent TEXT NOT NULL,
+    goal TEXT NOT NULL,
+    exercise_name TEXT,
+    target_weight FLOAT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    stake INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    winner TEXT,
+    created_at TEXT NOT NULL,
+
+    FOREIGN KEY (challenger) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (opponent) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (winner) REFERENCES users(username) ON UPDATE CASCADE ON DELETE SET NULL",
+            },
+            MigrationStep::RebuildTable {
+                table: "user_block",
+                definition: "blocker TEXT NOT NULL,
+    blocked TEXT NOT NULL,
+    blocked_at TEXT NOT NULL,
+    PRIMARY KEY (blocker, blocked),
+
+    FOREIGN KEY (blocker) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (blocked) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE",
+            },
+            MigrationStep::RebuildTable {
+                table: "user_report",
+                definition: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reporter TEXT NOT NULL,
+    reported TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    reported_description TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL",
+            },
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS exerciseLog_username_exercise_id ON exerciseLog(username, exercise_id);",
+            ),
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS refresh_tokens_session_id ON refresh_tokens(session_id);",
+            ),
+            MigrationStep::Sql(
+                "CREATE UNIQUE INDEX IF NOT EXISTS exercise_catalog_name ON exercise(name) WHERE created_by IS NULL;",
+            ),
+            MigrationStep::Sql(
+                "CREATE UNIQUE INDEX IF NOT EXISTS exercise_custom_name ON exercise(created_by, name) WHERE created_by IS NOT NULL;",
+            ),
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS feed_event_username ON feed_event(username, id);",
+            ),
+            MigrationStep::Sql(
+                "CREATE INDEX IF NOT EXISTS workout_comment_workout_id ON workout_comment(workout_id);",
+            ),
+        ],
+    },
+    Migration {
+        version: 17,
+        description: "Remember the days the daily coin reward was paid",
+        // Days which already have tracked sets count as rewarded
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS daily_reward (
+    username TEXT NOT NULL,
+    date TEXT NOT NULL,
+    PRIMARY KEY (username, date),
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE
+    );",
+            ),
+            MigrationStep::Sql(
+                "INSERT OR IGNORE INTO daily_reward (username, date)
+    SELECT DISTINCT username, date FROM exerciseLog
+    WHERE username IN (SELECT username FROM users);",
+            ),
+        ],
+    },
+    Migration {
+        version: 18,
+        description: "Hand out workout ids which are never reused",
+        // Feed events, reactions and preset trainings of a deleted workout must not
+        // be inherited by the next saved one
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS workout (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username TEXT NOT NULL,
+
+    FOREIGN KEY (username) REFERENCES users(username) ON UPDATE CASCADE ON DELETE CASCADE
+    );",
+            ),
+            MigrationStep::Sql(
+                "INSERT OR IGNORE INTO workout (id, username)
+    SELECT workout_id, MIN(username) FROM exerciseLog GROUP BY workout_id;",
+            ),
+        ],
+    },
+    Migration {
+        version: 19,
+        description: "Count changes of the exercise catalog",
+        // Triggers catch every change, also the ones of the import-exercises command.
+        // Rebuilding the exercise table drops them, so they have to be created again then
+        steps: &[
+            MigrationStep::Sql(
+                "CREATE TABLE IF NOT EXISTS exercise_catalog_version (version INTEGER NOT NULL);",
+            ),
+            MigrationStep::Sql(
+                "INSERT INTO exercise_catalog_version (version)
+    SELECT 0 WHERE NOT EXISTS (SELECT 1 FROM exercise_catalog_version);",
+            ),
+            MigrationStep::Sql(
+                "CREATE TRIGGER IF NOT EXISTS exercise_catalog_insert AFTER INSERT ON exercise
+    WHEN NEW.created_by IS NULL
+    BEGIN UPDATE exercise_catalog_version SET version = version + 1; END;",
+            ),
+            MigrationStep::Sql(
+                "CREATE TRIGGER IF NOT EXISTS exercise_catalog_update AFTER UPDATE ON exercise
+    WHEN OLD.created_by IS NULL OR NEW.created_by IS NULL
+    BEGIN UPDATE exercise_catalog_version SET version = version + 1; END;",
+            ),
+            MigrationStep::Sql(
+                "CREATE TRIGGER IF NOT EXISTS exercise_catalog_delete AFTER DELETE ON exercise
+    WHEN OLD.created_by IS NULL
+    BEGIN UPDATE exercise_catalog_version SET version = version + 1; END;",
+            ),
+        ],
+    },
+];
+
+/// A migration and when it was applied, None if it is still pending
+pub struct MigrationStatus {
+    pub migration: &'static Migration,
+    pub applied_at: Option<String>,
+}
+
+/// Applies every pending migration in order, each one in its own transaction. <br>
+/// Returns the newly applied migrations
+pub async fn run_migrations(pool: &SqlitePool) -> Result<Vec<&'static Migration>, sqlx::Error> {
+    let mut connection = pool.acquire().await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+    version INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    applied_at TEXT NOT NULL
+    );",
+    )
+    .execute(&mut *connection)
+    .await?;
+
+    let applied_migrations = get_applied_migrations(&mut connection).await?;
+    let pending_migrations: Vec<&'static Migration> = MIGRATIONS
+        .iter()
+        .filter(|migration| !applied_migrations.contains_key(&migration.version))
+        .collect();
+    if pending_migrations.is_empty() {
+        return Ok(pending_migrations);
+    }
+
+    // Rebuilding a table drops it, which must not cascade to the tables referencing it.
+    // The pragma has no effect inside a transaction, so it is set around them
+    let foreign_keys_enabled: bool = sqlx::query("PRAGMA foreign_keys")
+        .fetch_one(&mut *connection)
+        .await?
+        .get(0);
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *connection)
+        .await?;
+
+    let mut result = Ok(());
+    for migration in &pending_migrations {
+        result = apply_migration(&mut connection, migration).await;
+        if result.is_err() {
+            break;
+        }
+    }
+
+    if foreign_keys_enabled {
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *connection)
+            .await?;
+    }
+    result.map(|_| pending_migrations)
+}
+
+/// Status of every known migration. Doesn't change the database
+pub async fn get_migration_status(pool: &SqlitePool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let mut connection = pool.acquire().await?;
+
+    let migrations_table_exists: bool = sqlx::query(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations')",
+    )
+    .fetch_one(&mut *connection)
+    .await?
+    .get(0);
+    let mut applied_migrations = if migrations_table_exists {
+        get_applied_migrations(&mut connection).await?
+    } else {
+        BTreeMap::new()
+    };
+
+    Ok(MIGRATIONS
+        .iter()
+        .map(|migration| MigrationStatus {
+            migration,
+            applied_at: applied_migrations.remove(&migration.version),
+        })
+        .collect())
+}
+
+async fn get_applied_migrations(
+    connection: &mut SqliteConnection,
+) -> Result<BTreeMap<i64, String>, sqlx::Error> {
+    let rows = sqlx::query("SELECT version, applied_at FROM schema_migrations")
+        .fetch_all(&mut *connection)
+        .await?;
+
+    let applied_migrations: BTreeMap<i64, String> = rows
+        .iter()
+        .map(|row| (row.get("version"), row.get("applied_at")))
+        .collect();
+
+    // Running an older server would silently ignore the newer schema
+    if let Some(unknown_version) = applied_migrations.keys().find(|version| {
+        !MIGRATIONS
+            .iter()
+            .any(|migration| migration.version == **version)
+    }) {
+        return Err(sqlx::Error::Configuration(
+            format!("The database contains migration {unknown_version}, which this server doesn't know. Update the server!").into(),
+        ));
+    }
+    Ok(applied_migrations)
+}
+
+async fn apply_migration(
+    connection: &mut SqliteConnection,
+    migration: &Migration,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = connection.begin().await?;
+
+    for step in migration.steps {
+        match step {
+            MigrationStep::Sql(sql) => {
+                sqlx::query(sql).execute(&mut *transaction).await?;
+            }
+            MigrationStep::AddColumnIfMissing {
+                table,
+                column,
+                column_type,
+            } => add_column_if_missing(&mut transaction, table, column, column_type).await?,
+            MigrationStep::RebuildTable { table, definition } => {
+                rebuild_table(&mut transaction, table, definition).await?
+            }
+        }
+    }
+
+    sqlx::query(
+        "INSERT INTO schema_migrations (version, description, applied_at) VALUES (?, ?, datetime('now'))",
+    )
+    .bind(migration.version)
+    .bind(migration.description)
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await
+}
+
+async fn add_column_if_missing(
+    connection: &mut SqliteConnection,
+    table: &str,
+    column: &str,
+    column_type: &str,
+) -> Result<(), sqlx::Error> {
+    let column_exists: bool = sqlx::query(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?) WHERE name = ?) AS column_exists",
+    )
+    .bind(table)
+    .bind(column)
+    .fetch_one(&mut *connection)
+    .await?
+    .get("column_exists");
+
+    if !column_exists {
+        sqlx::query(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {column_type}"
+        ))
+        .execute(&mut *connection)
+        .await?;
+    }
+    Ok(())
+}
+
+async fn rebuild_table(
+    connection: &mut SqliteConnection,
+    table: &str,
+    definition: &str,
+) -> Result<(), sqlx::Error> {
+    let new_table = format!("{table}_new");
+    sqlx::query(&format!("CREATE TABLE {new_table} ({definition})"))
+        .execute(&mut *connection)
+        .await?;
+
+    let columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+        .bind(&new_table)
+        .fetch_all(&mut *connection)
+        .await?;
+    let columns = columns.join(", ");
+    sqlx::query(&format!(
+        "INSERT INTO {new_table} ({columns}) SELECT {columns} FROM {table}"
+    ))
+    .execute(&mut *connection)
+    .await?;
+    let violations = sqlx::query("SELECT rowid, parent FROM pragma_foreign_key_check(?)")
+        .bind(&new_table)
+        .fetch_all(&mut *connection)
+        .await?;
+    if !violations.is_empty() {
+        let violations: Vec<String> = violations
+            .iter()
+            .map(|row| {
+                format!(
+                    "row {} references a missing {}",
+                    row.get::<i64, _>("rowid"),
+                    row.get::<String, _>("parent")
+                )
+            })
+            .collect();
+        return Err(sqlx::Error::Configuration(
+            format!(
+                "Rows of {table} reference rows which don't exist, fix or delete them and migrate again: {}",
+                violations.join(", ")
+            )
+            .into(),
+        ));
+    }
+
+    sqlx::query(&format!("DROP TABLE {table}"))
+        .execute(&mut *connection)
+        .await?;
+    sqlx::query(&format!("ALTER TABLE {new_table} RENAME TO {table}"))
+        .execute(&mut *connection)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::user_mod::user_goals::GoalType;
+    use crate::server::database_mod::database_user_logs::{add_user_log, get_user_log};
+    use chrono::NaiveDate;
+
+    /// Tables of the first release which later migrations change, filled with a user
+    const FIRST_RELEASE_SNAPSHOT: &str = "
+    CREATE TABLE users (
+    username TEXT PRIMARY KEY,
+    user_password TEXT NOT NULL,
+    coin_balance INTEGER NOT NULL,
+    weight FLOAT NOT NULL,
+    height FLOAT NOT NULL,
+    gender TEXT NOT NULL,
+    favorite_mascot TEXT NOT NULL,
+    selected_mascot TEXT NOT NULL,
+    profile_picture TEXT,
+    description TEXT
+    );
+    CREATE TABLE exerciseLog (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL,
+    username TEXT NOT NULL,
+    reps INTEGER NOT NULL,
+    exercise_id INTEGER NOT NULL,
+    weight_in_kg FLOAT NOT NULL,
+    workout_id INTEGER NOT NULL,
+    FOREIGN KEY (username) REFERENCES users(username)
+    );
+    CREATE TABLE user_preset(
+    username TEXT NOT NULL,
+    preset_id INTEGER NOT NULL,
+    times_preset_trained INTEGER NOT NULL,
+    PRIMARY KEY (username, preset_id),
+    FOREIGN KEY (username) REFERENCES users (username)
+    );
+    CREATE TABLE friendship (
+    username TEXT NOT NULL,
+    friendname TEXT NOT NULL,
+    PRIMARY KEY (username, friendname),
+    FOREIGN KEY (username) REFERENCES users(username),
+    FOREIGN KEY (friendname) REFERENCES users(username)
+    );
+    CREATE TABLE exercise (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    exercise_force_name TEXT,
+    exercise_level_name TEXT NOT NULL,
+    exercise_equipment_name TEXT,
+    muscle_name TEXT NOT NULL,
+    instructions TEXT NOT NULL,
+    exercise_category_name TEXT NOT NULL
+    );
+    CREATE TABLE preset (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    preset_name TEXT NOT NULL,
+    preset_image TEXT NOT NULL,
+    number_of_exercises INTEGER NOT NULL,
+    estimated_duration INTEGER NOT NULL,
+    description TEXT
+    );
+    CREATE TABLE logs (
+    username TEXT NOT NULL,
+    date TEXT NOT NULL,
+    value FLOAT NOT NULL,
+    log_type TEXT NOT NULL,
+    PRIMARY KEY(username, date, log_type),
+    FOREIGN KEY (username) REFERENCES users(username)
+    );
+    INSERT INTO users VALUES ('anna', 'hash', 10, 60.5, 170.6, 'Female', 'Duck', 'Duck', NULL, 'hi');
+    INSERT INTO users VALUES ('ben', 'hash', 0, 80.0, 185.0, 'Male', 'Duck', 'Duck', NULL, NULL);
+    INSERT INTO exerciseLog (date, username, reps, exercise_id, weight_in_kg, workout_id)
+    VALUES ('2025-10-10', 'anna', 8, 1, 40.0, 1);
+    INSERT INTO exercise VALUES (1, 'Squat', 'push', 'beginner', 'barbell', 'quadriceps', 'go down', 'strength');
+    INSERT INTO preset VALUES (1, 'Legs', 'legs.png', 1, 30, NULL);
+    INSERT INTO user_preset VALUES ('anna', 1, 3);
+    INSERT INTO friendship VALUES ('anna', 'ben');
+    INSERT INTO logs VALUES ('anna', '2025-10-10', 2.5, 'Water');
+    ";
+
+    async fn snapshot_db(snapshot: &str) -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(snapshot).execute(&pool).await.unwrap();
+        pool
+    }
+
+    async fn column_type(pool: &SqlitePool, table: &str, column: &str) -> Option<String> {
+        sqlx::query("SELECT type FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+            .map(|row| row.get("type"))
+    }
+
+    #[test]
+    fn versions_start_at_one_and_increase_by_one() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i64 + 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn fresh_database_applies_every_migration_once() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+        let applied_migrations = run_migrations(&pool).await.unwrap();
+        assert_eq!(applied_migrations.len(), MIGRATIONS.len());
+        assert!(run_migrations(&pool).await.unwrap().is_empty());
+
+        let status = get_migration_status(&pool).await.unwrap();
+        assert!(status.iter().all(|status| status.applied_at.is_some()));
+        assert_eq!(
+            column_type(&pool, "users", "height").await.as_deref(),
+            Some("INTEGER")
+        );
+    }
+
+    #[tokio::test]
+    async fn status_of_unmigrated_database_is_pending() {
+        let pool = snapshot_db(FIRST_RELEASE_SNAPSHOT).await;
+
+        let status = get_migration_status(&pool).await.unwrap();
+        assert_eq!(status.len(), MIGRATIONS.len());
+        assert!(status.iter().all(|status| status.applied_at.is_none()));
+    }
+
+    #[tokio::test]
+    async fn mutual_friendships_are_kept_by_migration() {
+        let pool = snapshot_db(FIRST_RELEASE_SNAPSHOT).await;
+        sqlx::query("INSERT INTO friendship (username, friendname) VALUES ('ben', 'anna')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+
+        let friendships: i64 = sqlx::query("SELECT COUNT(*) FROM friendship")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(friendships, 2);
+        let friend_requests: i64 = sqlx::query("SELECT COUNT(*) FROM friend_request")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(friend_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn first_release_snapshot_is_migrated_without_losing_data() {
+        let pool = snapshot_db(FIRST_RELEASE_SNAPSHOT).await;
+
+        run_migrations(&pool).await.unwrap();
+
+        let anna = sqlx::query(
+            "SELECT height, typeof(height) AS height_type, description FROM users WHERE username = 'anna'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(anna.get::<i64, _>("height"), 171);
+        assert_eq!(anna.get::<String, _>("height_type"), "integer");
+        assert_eq!(anna.get::<String, _>("description"), "hi");
+
+        let friendships: i64 = sqlx::query("SELECT COUNT(*) FROM friendship")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(friendships, 0);
+        let friend_request: (String, String, String) =
+            sqlx::query_as("SELECT sender, receiver, status FROM friend_request")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            friend_request,
+            ("anna".to_string(), "ben".to_string(), "Pending".to_string())
+        );
+        let squat = sqlx::query("SELECT name, created_by FROM exercise WHERE id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(squat.get::<String, _>("name"), "Squat");
+        assert!(squat.get::<Option<String>, _>("created_by").is_none());
+        let times_trained: i64 =
+            sqlx::query("SELECT times_preset_trained FROM user_preset WHERE username = 'anna'")
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+                .get(0);
+        assert_eq!(times_trained, 3);
+        let violations = sqlx::query("PRAGMA foreign_key_check")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert!(violations.is_empty());
+        let foreign_keys_enabled: bool = sqlx::query("PRAGMA foreign_keys")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert!(foreign_keys_enabled);
+
+        assert!(
+            column_type(&pool, "exerciseLog", "start_time")
+                .await
+                .is_some()
+        );
+        assert!(
+            column_type(&pool, "user_preset", "position")
+                .await
+                .is_some()
+        );
+        assert!(
+            column_type(&pool, "preset_history", "preset_id")
+                .await
+                .is_some()
+        );
+        assert!(
+            column_type(&pool, "refresh_tokens", "token_hash")
+                .await
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn dangling_references_abort_the_migration() {
+        let pool = snapshot_db(FIRST_RELEASE_SNAPSHOT).await;
+        sqlx::query("INSERT INTO user_preset VALUES ('anna', 2, 1)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let error = run_migrations(&pool).await.err().unwrap();
+        assert!(error.to_string().contains("user_preset"));
+        assert!(error.to_string().contains("missing preset"));
+
+        let user_presets: i64 = sqlx::query("SELECT COUNT(*) FROM user_preset")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(user_presets, 2);
+        let status = get_migration_status(&pool).await.unwrap();
+        assert!(status.iter().any(|status| status.applied_at.is_none()));
+    }
+
+    #[tokio::test]
+    async fn logs_upsert_works_on_migrated_snapshot() {
+        let pool = snapshot_db(FIRST_RELEASE_SNAPSHOT).await;
+        run_migrations(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+
+        add_user_log(&pool, "anna", 3.0, date, GoalType::Water)
+            .await
+            .unwrap();
+        add_user_log(&pool, "anna", 7000.0, date, GoalType::Steps)
+            .await
+            .unwrap();
+
+        let water_log = get_user_log(&pool, "anna", GoalType::Water).await.unwrap();
+        assert_eq!(water_log.len(), 1);
+        assert_eq!(water_log[0].1, 3.0);
+        let step_log = get_user_log(&pool, "anna", GoalType::Steps).await.unwrap();
+        assert_eq!(step_log.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn columns_added_before_the_migrations_are_kept() {
+        let pool = snapshot_db(FIRST_RELEASE_SNAPSHOT).await;
+        sqlx::query("ALTER TABLE exerciseLog ADD COLUMN start_time TEXT")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE exerciseLog SET start_time = '08:30:00'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+
+        let start_time: String = sqlx::query("SELECT start_time FROM exerciseLog")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(start_time, "08:30:00");
+    }
+
+    #[tokio::test]
+    async fn unknown_migration_is_rejected() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, description, applied_at) VALUES (999, 'future', 'now')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(run_migrations(&pool).await.is_err());
+        assert!(get_migration_status(&pool).await.is_err());
+    }
+}
@@ -1,45 +1,165 @@
 use chrono::Utc;
+use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use rand::RngExt;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Access tokens are short-lived, the client renews them with its refresh token
+pub const ACCESS_TOKEN_LIFETIME_SECONDS: i64 = 15 * 60;
+pub const REFRESH_TOKEN_LIFETIME_SECONDS: i64 = 30 * 24 * 60 * 60;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     ///Subject of the JWT: In our case username
-    sub: String,
+    pub sub: String,
     ///Unix timestamp for an expiration date
-    exp: usize,
-    //Issued at
-    //iat: usize,
+    pub exp: usize,
+    ///Unix timestamp of the moment the token was issued
+    pub iat: usize,
+    ///Unique id of this token
+    pub jti: String,
+    ///Session the token belongs to. Logging out revokes the whole session
+    pub sid: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeJwtError {
+    Expired,
+    Invalid,
+}
+
+/// Secret every JWT is signed and validated with. Configured on server start
+/// and shared between all requests, see ServerConfig
+#[derive(Clone)]
+pub struct JwtSecret(Arc<str>);
+
+impl JwtSecret {
+    pub fn new(secret: &str) -> Self {
+        JwtSecret(Arc::from(secret))
+    }
+}
+
+pub fn create_jwt(username: String, session_id: &str, jwt_secret: &JwtSecret) -> String {
+    create_jwt_with_lifetime(
+        username,
+        session_id,
+        ACCESS_TOKEN_LIFETIME_SECONDS,
+        jwt_secret,
+    )
 }
 
-pub fn create_jwt(username: String) -> String {
-    let expiration_time = Utc::now().timestamp() + 1800;
+fn create_jwt_with_lifetime(
+    username: String,
+    session_id: &str,
+    lifetime_seconds: i64,
+    jwt_secret: &JwtSecret,
+) -> String {
+    let issued_at = Utc::now().timestamp();
 
     let claims = Claims {
         sub: username,
-        exp: expiration_time as usize,
+        exp: (issued_at + lifetime_seconds) as usize,
+        iat: issued_at as usize,
+        jti: create_random_token(),
+        sid: session_id.to_string(),
     };
 
     let encoded = encode::<Claims>(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret("123".as_ref()), //TODO HIDE
+        &EncodingKey::from_secret(jwt_secret.0.as_bytes()),
     );
     encoded.unwrap()
 }
 
-pub fn decode_jwt(token: &str) -> Option<String> {
-    //println!("Received {} token from client!", token);
-
+pub fn decode_jwt(token: &str, jwt_secret: &JwtSecret) -> Result<Claims, DecodeJwtError> {
     let mut validation = Validation::new(Algorithm::default());
-    validation.validate_exp = false; // Could be future enhancement
+    validation.leeway = 0;
+    validation.set_required_spec_claims(&["exp", "iat", "sub", "jti"]);
 
     let token_data = decode::<Claims>(
-        &token,
-        &DecodingKey::from_secret("123".as_ref()), //TODO HIDE
+        token,
+        &DecodingKey::from_secret(jwt_secret.0.as_bytes()),
         &validation,
     )
-    .ok()?;
-    //println!("The token data is valid if this is printed {:#?}", token_data);
-    Some(token_data.claims.sub)
+    .map_err(|err| match err.kind() {
+        ErrorKind::ExpiredSignature => DecodeJwtError::Expired,
+        _ => DecodeJwtError::Invalid,
+    })?;
+    Ok(token_data.claims)
+}
+
+/// 256 random bits, hex encoded. Used for refresh tokens, session ids and jwt ids
+pub fn create_random_token() -> String {
+    let bytes: [u8; 32] = rand::rng().random();
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Only the hash of a refresh token is stored, so a leaked database can't be used to log in
+pub fn hash_refresh_token(refresh_token: &str) -> String {
+    format!("{:x}", Sha256::digest(refresh_token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_secret() -> JwtSecret {
+        JwtSecret::new("a_test_secret_that_is_long_enough_")
+    }
+
+    #[test]
+    fn decode_valid_jwt() {
+        let jwt = create_jwt("testuser".to_string(), "session", &test_secret());
+        let claims = decode_jwt(&jwt, &test_secret()).unwrap();
+        assert_eq!(claims.sub, "testuser");
+        assert_eq!(claims.sid, "session");
+        assert_eq!(
+            claims.exp - claims.iat,
+            ACCESS_TOKEN_LIFETIME_SECONDS as usize
+        );
+    }
+    #[test]
+    fn expired_jwt_is_rejected() {
+        let jwt = create_jwt_with_lifetime("testuser".to_string(), "session", -1, &test_secret());
+        assert_eq!(
+            decode_jwt(&jwt, &test_secret()).unwrap_err(),
+            DecodeJwtError::Expired
+        );
+    }
+    #[test]
+    fn jwt_with_other_secret_is_rejected() {
+        let jwt = create_jwt("testuser".to_string(), "session", &test_secret());
+        let other_secret = JwtSecret::new("another_secret_that_is_long_enough_");
+        assert_eq!(
+            decode_jwt(&jwt, &other_secret).unwrap_err(),
+            DecodeJwtError::Invalid
+        );
+    }
+    #[test]
+    fn every_jwt_has_a_unique_id() {
+        let first = decode_jwt(
+            &create_jwt("testuser".to_string(), "session", &test_secret()),
+            &test_secret(),
+        )
+        .unwrap();
+        let second = decode_jwt(
+            &create_jwt("testuser".to_string(), "session", &test_secret()),
+            &test_secret(),
+        )
+        .unwrap();
+        assert_ne!(first.jti, second.jti);
+    }
+    #[test]
+    fn refresh_token_hash_is_stable() {
+        let refresh_token = create_random_token();
+        assert_eq!(refresh_token.len(), 64);
+        assert_eq!(
+            hash_refresh_token(&refresh_token),
+            hash_refresh_token(&refresh_token)
+        );
+        assert_ne!(hash_refresh_token(&refresh_token), refresh_token);
+    }
 }
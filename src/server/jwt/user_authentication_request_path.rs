@@ -1,15 +1,23 @@
-use crate::server::jwt::jwt_architecture::decode_jwt;
+use crate::server::database_mod::database_refresh_token::is_session_active;
+use crate::server::jwt::jwt_architecture::{DecodeJwtError, JwtSecret, decode_jwt};
 use axum::Json;
-use axum::extract::FromRequestParts;
+use axum::extract::{FromRef, FromRequestParts};
 use axum::http::request::Parts;
 use axum::response::{IntoResponse, Response};
+use chrono::Utc;
 use reqwest::StatusCode;
 use serde_json::json;
+use sqlx::SqlitePool;
 
 pub enum JWTAuthenticationError {
     MissingAuthorizationHeader,
     CouldNotFindToken,
     WrongToken,
+    /// The client is expected to refresh its tokens and repeat the request
+    ExpiredToken,
+    /// The session of the token was logged out
+    RevokedToken,
+    SessionLookupFailed,
 }
 impl IntoResponse for JWTAuthenticationError {
     fn into_response(self) -> Response {
@@ -25,6 +33,16 @@ impl IntoResponse for JWTAuthenticationError {
                 StatusCode::UNAUTHORIZED,
                 "Authentication token doesn't match",
             ),
+            JWTAuthenticationError::ExpiredToken => {
+                (StatusCode::UNAUTHORIZED, "Authentication token expired")
+            }
+            JWTAuthenticationError::RevokedToken => {
+                (StatusCode::UNAUTHORIZED, "Authentication token was revoked")
+            }
+            JWTAuthenticationError::SessionLookupFailed => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Could not check authentication session",
+            ),
         };
 
         let body = Json(json!({
@@ -38,10 +56,15 @@ pub struct UserAuthenticationRequestPath {
     pub username: String,
 }
 
-impl<S: Sync> FromRequestParts<S> for UserAuthenticationRequestPath {
+impl<S> FromRequestParts<S> for UserAuthenticationRequestPath
+where
+    JwtSecret: FromRef<S>,
+    SqlitePool: FromRef<S>,
+    S: Sync,
+{
     type Rejection = JWTAuthenticationError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let header = parts
             .headers
             .get("Authorization")
@@ -52,8 +75,22 @@ impl<S: Sync> FromRequestParts<S> for UserAuthenticationRequestPath {
             .strip_prefix("Token ")
             .ok_or(JWTAuthenticationError::CouldNotFindToken)?;
 
-        let username = decode_jwt(token).ok_or(JWTAuthenticationError::WrongToken)?;
+        let jwt_secret = JwtSecret::from_ref(state);
+        let claims = decode_jwt(token, &jwt_secret).map_err(|err| match err {
+            DecodeJwtError::Expired => JWTAuthenticationError::ExpiredToken,
+            DecodeJwtError::Invalid => JWTAuthenticationError::WrongToken,
+        })?;
+
+        let pool = SqlitePool::from_ref(state);
+        let session_active = is_session_active(&pool, &claims.sid, Utc::now().timestamp())
+            .await
+            .map_err(|_| JWTAuthenticationError::SessionLookupFailed)?;
+        if !session_active {
+            return Err(JWTAuthenticationError::RevokedToken);
+        }
 
-        Ok(UserAuthenticationRequestPath { username })
+        Ok(UserAuthenticationRequestPath {
+            username: claims.sub,
+        })
     }
 }
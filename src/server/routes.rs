@@ -1,7 +1,14 @@
+pub mod account;
+pub mod challenge;
+pub mod exercise_catalog;
 pub mod foreign_users;
+pub mod leaderboard;
 pub mod login;
 pub mod mascot_manager;
+pub mod social_feed;
 pub mod user_exercises;
 pub mod user_info;
+pub mod user_logs;
 pub mod user_presets;
 pub mod workout;
+pub mod workout_reaction;
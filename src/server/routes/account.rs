@@ -0,0 +1,198 @@
+use crate::common::user_mod::account::{
+    AccountChangeAnswer, AccountDeletion, AccountDeletionAnswer, PasswordChange, UsernameChange,
+};
+use crate::server::database_mod::database_account;
+use crate::server::jwt::jwt_architecture::JwtSecret;
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::routes::login::{hash_password, is_password_correct, start_session};
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::State;
+use sqlx::SqlitePool;
+
+/// Logs out every session, the client which changed the password gets a new one
+pub async fn change_password(
+    State(pool): State<SqlitePool>,
+    State(jwt_secret): State<JwtSecret>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(password_change): Json<PasswordChange>,
+) -> Result<Json<AccountChangeAnswer>, ApiError> {
+    if let Some(reason) = password_change.validation_error() {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    let username = user_authentication.username;
+    if !is_password_correct(&pool, &username, &password_change.current_password).await? {
+        return Ok(Json(AccountChangeAnswer::WrongPassword));
+    }
+
+    let password_hash = hash_password(&password_change.new_password)?;
+    database_account::update_password(&pool, &username, &password_hash).await?;
+    let auth_tokens = start_session(&pool, &jwt_secret, &username).await?;
+
+    println!("{}: Changed the password!", username);
+    Ok(Json(AccountChangeAnswer::Valid(auth_tokens)))
+}
+
+/// Logs out every session, the client which changed the username gets a new one
+pub async fn change_username(
+    State(pool): State<SqlitePool>,
+    State(jwt_secret): State<JwtSecret>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(username_change): Json<UsernameChange>,
+) -> Result<Json<AccountChangeAnswer>, ApiError> {
+    if let Some(reason) = username_change.validation_error() {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    let username = user_authentication.username;
+    if !is_password_correct(&pool, &username, &username_change.password).await? {
+        return Ok(Json(AccountChangeAnswer::WrongPassword));
+    }
+
+    let new_username = username_change.new_username;
+    if !database_account::rename_user(&pool, &username, &new_username).await? {
+        return Ok(Json(AccountChangeAnswer::UsernameAlreadyExists));
+    }
+    let auth_tokens = start_session(&pool, &jwt_secret, &new_username).await?;
+
+    println!("{}: Changed the username to {}!", username, new_username);
+    Ok(Json(AccountChangeAnswer::Valid(auth_tokens)))
+}
+
+/// Deletes the account with all its data, which also ends every session
+pub async fn delete_account(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(account_deletion): Json<AccountDeletion>,
+) -> Result<Json<AccountDeletionAnswer>, ApiError> {
+    let username = user_authentication.username;
+    if !is_password_correct(&pool, &username, &account_deletion.password).await? {
+        return Ok(Json(AccountDeletionAnswer::WrongPassword));
+    }
+
+    database_account::delete_user(&pool, &username).await?;
+
+    println!("{}: Deleted the account!", username);
+    Ok(Json(AccountDeletionAnswer::Deleted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::login::RequestValidRegisterAnswer;
+    use crate::server::database_mod::database::setup_test_db;
+    use crate::server::database_mod::database_refresh_token::is_session_active;
+    use crate::server::jwt::jwt_architecture::decode_jwt;
+    use crate::server::routes::login::{LoginRequest, register};
+    use chrono::Utc;
+
+    fn test_secret() -> JwtSecret {
+        JwtSecret::new("a_test_secret_that_is_long_enough_")
+    }
+
+    fn anna() -> UserAuthenticationRequestPath {
+        UserAuthenticationRequestPath {
+            username: "anna".to_string(),
+        }
+    }
+
+    async fn register_anna(pool: &SqlitePool) {
+        sqlx::query("INSERT INTO mascot (mascot_name, description) VALUES ('Duck', 'test')")
+            .execute(pool)
+            .await
+            .unwrap();
+        let answer = register(
+            State(pool.clone()),
+            State(test_secret()),
+            Json(LoginRequest {
+                username: "anna".to_string(),
+                password: "old".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(answer.0, RequestValidRegisterAnswer::Valid(_)));
+    }
+
+    async fn change_password_to(
+        pool: &SqlitePool,
+        current_password: &str,
+        new_password: &str,
+    ) -> AccountChangeAnswer {
+        let password_change = PasswordChange {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        };
+        change_password(
+            State(pool.clone()),
+            State(test_secret()),
+            anna(),
+            Json(password_change),
+        )
+        .await
+        .unwrap()
+        .0
+    }
+
+    #[tokio::test]
+    async fn password_is_only_changed_with_the_current_password() {
+        let pool = setup_test_db().await;
+        register_anna(&pool).await;
+
+        assert!(matches!(
+            change_password_to(&pool, "wrong", "new").await,
+            AccountChangeAnswer::WrongPassword
+        ));
+        let AccountChangeAnswer::Valid(auth_tokens) = change_password_to(&pool, "old", "new").await
+        else {
+            panic!("password wasn't changed");
+        };
+
+        assert!(is_password_correct(&pool, "anna", "new").await.unwrap());
+        assert!(!is_password_correct(&pool, "anna", "old").await.unwrap());
+        let sessions: Vec<String> = sqlx::query_scalar("SELECT session_id FROM refresh_tokens")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        let new_session = decode_jwt(&auth_tokens.access_token, &test_secret())
+            .unwrap()
+            .sid;
+        for session in sessions {
+            let active = is_session_active(&pool, &session, Utc::now().timestamp())
+                .await
+                .unwrap();
+            assert_eq!(active, session == new_session);
+        }
+    }
+
+    #[tokio::test]
+    async fn deleted_account_needs_the_password() {
+        let pool = setup_test_db().await;
+        register_anna(&pool).await;
+
+        let answer = delete_account(
+            State(pool.clone()),
+            anna(),
+            Json(AccountDeletion {
+                password: "wrong".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(answer.0, AccountDeletionAnswer::WrongPassword);
+
+        let answer = delete_account(
+            State(pool.clone()),
+            anna(),
+            Json(AccountDeletion {
+                password: "old".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(answer.0, AccountDeletionAnswer::Deleted);
+        assert!(matches!(
+            is_password_correct(&pool, "anna", "old").await,
+            Err(ApiError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::common::challenge::{ChallengeAnswer, ChallengeOverview, NewChallenge};
+use crate::common::user_mod::friend_request::FriendshipStatus;
+use crate::server::database_mod::{database_challenge, database_social, database_user};
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::State;
+use chrono::Local;
+use sqlx::SqlitePool;
+
+/// Challenges of the user, the ones which ended are resolved first
+pub async fn get_challenges(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ChallengeOverview>, ApiError> {
+    database_challenge::resolve_expired_challenges(&pool, Local::now().date_naive()).await?;
+    let overview = get_challenge_overview(&pool, &user_authentication.username).await?;
+    Ok(Json(overview))
+}
+
+/// Challenges a friend, the stake is taken from the coins of the challenger right away
+pub async fn create_challenge(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Json(new_challenge): Json<NewChallenge>,
+) -> Result<Json<ChallengeOverview>, ApiError> {
+    if let Some(reason) = new_challenge.validation_error(Local::now().date_naive()) {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    if database_social::get_friendship_status(
+        &pool,
+        &user_authentication.username,
+        &new_challenge.opponent,
+    )
+    .await?
+        != FriendshipStatus::Friends
+    {
+        return Err(ApiError::InvalidInput(
+            "You can only challenge your friends".to_string(),
+        ));
+    }
+    let created =
+        database_challenge::create_challenge(&pool, &user_authentication.username, &new_challenge)
+            .await?;
+    if !created {
+        return Err(ApiError::InvalidInput(
+            "Not enough coins for the stake".to_string(),
+        ));
+    }
+
+    println!(
+        "{}: Challenged {} to {}",
+        user_authentication.username,
+        new_challenge.opponent,
+        new_challenge.goal.description()
+    );
+    let overview = get_challenge_overview(&pool, &user_authentication.username).await?;
+    Ok(Json(overview))
+}
+
+/// Accepting takes the stake from the coins of the opponent, but only until the challenge starts
+pub async fn answer_challenge(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Json(answer): Json<ChallengeAnswer>,
+) -> Result<Json<ChallengeOverview>, ApiError> {
+    let rejection = database_challenge::answer_challenge(
+        &pool,
+        &user_authentication.username,
+        answer.challenge_id,
+        answer.accept,
+        Local::now().date_naive(),
+    )
+    .await?;
+    if let Some(reason) = rejection {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+
+    println!(
+        "{}: {} challenge {}",
+        user_authentication.username,
+        if answer.accept {
+            "Accepted"
+        } else {
+            "Declined"
+        },
+        answer.challenge_id
+    );
+    let overview = get_challenge_overview(&pool, &user_authentication.username).await?;
+    Ok(Json(overview))
+}
+
+async fn get_challenge_overview(
+    pool: &SqlitePool,
+    username: &str,
+) -> Result<ChallengeOverview, ApiError> {
+    Ok(ChallengeOverview {
+        challenges: database_challenge::get_challenges(pool, username).await?,
+        coin_balance: database_user::get_user_coin_balance(pool, username).await?,
+    })
+}
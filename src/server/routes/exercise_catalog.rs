@@ -0,0 +1,72 @@
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::State;
+use axum::http::header::{ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use sqlx::SqlitePool;
+
+/// Returns all exercises with their ETag. <br>
+/// If the If-None-Match header already contains the ETag, only 304 Not Modified is sent
+pub async fn get_exercise_catalog(
+    State(pool): State<SqlitePool>,
+    State(exercise_catalog): State<ExerciseCatalog>,
+    user_authentication: UserAuthenticationRequestPath,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let catalog = exercise_catalog.current(&pool).await?;
+    let etag = &catalog.etag;
+
+    if etag_matches(&headers, etag) {
+        println!(
+            "{}: Exercise Catalog is up to date!",
+            user_authentication.username
+        );
+        return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag.to_string())]).into_response());
+    }
+
+    println!(
+        "{}: Fetching Exercise Catalog!",
+        user_authentication.username
+    );
+
+    Ok(([(ETAG, etag.to_string())], Json(&catalog.exercises)).into_response())
+}
+
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|header_value| header_value.to_str().ok())
+        .flat_map(|header_value| header_value.split(','))
+        .any(|requested_etag| {
+            let requested_etag = requested_etag.trim();
+            requested_etag == "*" || requested_etag.trim_start_matches("W/") == etag
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers_with_if_none_match(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn matching_etag_in_list_is_found() {
+        let headers = headers_with_if_none_match("\"old\", W/\"current\"");
+        assert!(etag_matches(&headers, "\"current\""));
+        assert!(!etag_matches(&headers, "\"new\""));
+        assert!(!etag_matches(&HeaderMap::new(), "\"current\""));
+        assert!(etag_matches(
+            &headers_with_if_none_match("*"),
+            "\"current\""
+        ));
+    }
+}
@@ -1,6 +1,10 @@
-use crate::common::user_mod::friend_request::FriendRequest;
+use crate::common::user_mod::friend_request::{
+    FriendRequest, FriendRequestAnswer, FriendshipStatus,
+};
 use crate::common::user_mod::user::ForeignUser;
-use crate::server::database_mod::database_social;
+use crate::common::user_mod::user_report::UserReport;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use crate::server::database_mod::{database_moderation, database_social};
 use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
 use crate::server::server_main::ApiError;
 use axum::Json;
@@ -9,13 +13,27 @@ use sqlx::SqlitePool;
 
 pub async fn get_foreign_users(
     State(pool): State<SqlitePool>,
+    State(exercise_catalog): State<ExerciseCatalog>,
     user_authentication: UserAuthenticationRequestPath,
 ) -> Result<Json<Vec<ForeignUser>>, ApiError> {
-    let mut non_friend_users =
-        database_social::get_discovery_users(&pool, &user_authentication.username, 200).await?;
+    let mut non_friend_users = database_social::get_discovery_users(
+        &pool,
+        &exercise_catalog,
+        &user_authentication.username,
+        200,
+    )
+    .await?;
     let mut friends =
-        database_social::get_all_friends(&pool, &user_authentication.username).await?;
+        database_social::get_all_friends(&pool, &exercise_catalog, &user_authentication.username)
+            .await?;
+    let mut friend_request_senders = database_social::get_friend_request_senders(
+        &pool,
+        &exercise_catalog,
+        &user_authentication.username,
+    )
+    .await?;
 
+    friends.append(&mut friend_request_senders);
     friends.append(&mut non_friend_users);
 
     println!(
@@ -26,18 +44,55 @@ pub async fn get_foreign_users(
     Ok(Json(friends))
 }
 
-pub async fn add_friend(
+/// Returns the relationship after the request, Friends if the other user already sent one
+pub async fn send_friend_request(
     State(pool): State<SqlitePool>,
     user_authentication: UserAuthenticationRequestPath,
     Json(other_user): Json<FriendRequest>,
-) -> Result<(), ApiError> {
-    database_social::add_friend(&pool, &user_authentication.username, &other_user.username).await?;
+) -> Result<Json<FriendshipStatus>, ApiError> {
+    if other_user.username == user_authentication.username {
+        return Err(ApiError::InvalidInput(
+            "You can't send yourself a friend request".to_string(),
+        ));
+    }
+    let friendship_status = database_social::send_friend_request(
+        &pool,
+        &user_authentication.username,
+        &other_user.username,
+    )
+    .await?;
 
     println!(
-        "{}: Added {} as a friend",
+        "{}: Sent {} a friend request, they are now {friendship_status:?}",
         user_authentication.username, other_user.username
     );
 
+    Ok(Json(friendship_status))
+}
+
+pub async fn answer_friend_request(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(answer): Json<FriendRequestAnswer>,
+) -> Result<(), ApiError> {
+    database_social::answer_friend_request(
+        &pool,
+        &user_authentication.username,
+        &answer.username,
+        answer.accept,
+    )
+    .await?;
+
+    let answer_text = if answer.accept {
+        "Accepted"
+    } else {
+        "Declined"
+    };
+    println!(
+        "{}: {answer_text} the friend request of {}",
+        user_authentication.username, answer.username
+    );
+
     Ok(())
 }
 
@@ -56,3 +111,81 @@ pub async fn remove_friend(
 
     Ok(())
 }
+
+/// Removes any friendship, afterwards neither user finds the other one
+pub async fn block_user(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(other_user): Json<FriendRequest>,
+) -> Result<(), ApiError> {
+    if other_user.username == user_authentication.username {
+        return Err(ApiError::InvalidInput(
+            "You can't block yourself".to_string(),
+        ));
+    }
+    database_moderation::block_user(&pool, &user_authentication.username, &other_user.username)
+        .await?;
+
+    println!(
+        "{}: Blocked {}",
+        user_authentication.username, other_user.username
+    );
+
+    Ok(())
+}
+
+pub async fn unblock_user(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(other_user): Json<FriendRequest>,
+) -> Result<(), ApiError> {
+    let unblocked = database_moderation::unblock_user(
+        &pool,
+        &user_authentication.username,
+        &other_user.username,
+    )
+    .await?;
+    if !unblocked {
+        return Err(ApiError::NotFound);
+    }
+
+    println!(
+        "{}: Unblocked {}",
+        user_authentication.username, other_user.username
+    );
+
+    Ok(())
+}
+
+pub async fn get_blocked_users(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+) -> Result<Json<Vec<String>>, ApiError> {
+    let blocked_users =
+        database_moderation::get_blocked_users(&pool, &user_authentication.username).await?;
+    Ok(Json(blocked_users))
+}
+
+/// Saves the report for the moderators, see the `reports` server command
+pub async fn report_user(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+    Json(report): Json<UserReport>,
+) -> Result<(), ApiError> {
+    if let Some(reason) = report.validation_error() {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    if report.username == user_authentication.username {
+        return Err(ApiError::InvalidInput(
+            "You can't report yourself".to_string(),
+        ));
+    }
+    database_moderation::report_user(&pool, &user_authentication.username, &report).await?;
+
+    println!(
+        "{}: Reported the profile of {}",
+        user_authentication.username, report.username
+    );
+
+    Ok(())
+}
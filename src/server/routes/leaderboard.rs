@@ -0,0 +1,36 @@
+use crate::common::leaderboard::{LeaderboardEntry, LeaderboardQuery};
+use crate::server::database_mod::database_leaderboard;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::{Query, State};
+use chrono::Local;
+use sqlx::SqlitePool;
+
+/// The user and their friends ranked by the metric in the current week or month
+pub async fn get_leaderboard(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    State(exercise_catalog): State<ExerciseCatalog>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<Vec<LeaderboardEntry>>, ApiError> {
+    if let Some(reason) = query.validation_error() {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    let entries = database_leaderboard::get_leaderboard(
+        &pool,
+        &exercise_catalog,
+        &user_authentication.username,
+        &query,
+        Local::now().date_naive(),
+    )
+    .await?;
+
+    println!(
+        "{}: Fetching {} leaderboard of {}",
+        user_authentication.username, query.metric, query.period
+    );
+
+    Ok(Json(entries))
+}
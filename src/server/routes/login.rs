@@ -1,8 +1,16 @@
-use crate::common::login::{RequestValidRegisterAnswer, RequestValidUserAnswer};
+use crate::common::login::{
+    AuthTokens, RefreshTokenRequest, RequestValidRegisterAnswer, RequestValidUserAnswer,
+};
+use crate::server::database_mod::database_refresh_token::{
+    add_refresh_token, delete_expired_refresh_tokens, get_refresh_token, revoke_session,
+    rotate_refresh_token,
+};
 use crate::server::database_mod::database_user::{
     RequestPasswordAnswer, add_user, get_all_usernames, get_password,
 };
-use crate::server::jwt::jwt_architecture::create_jwt;
+use crate::server::jwt::jwt_architecture::{
+    JwtSecret, REFRESH_TOKEN_LIFETIME_SECONDS, create_jwt, create_random_token, hash_refresh_token,
+};
 use crate::server::server_main::ApiError;
 use argon2::{
     Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
@@ -10,6 +18,7 @@ use argon2::{
 };
 use axum::Json;
 use axum::extract::State;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
@@ -21,6 +30,7 @@ pub struct LoginRequest {
 
 pub async fn check_login(
     State(pool): State<SqlitePool>,
+    State(jwt_secret): State<JwtSecret>,
     Json(login_request): Json<LoginRequest>,
 ) -> Result<Json<RequestValidUserAnswer>, ApiError> {
     match get_password(&pool, &login_request.username).await? {
@@ -29,8 +39,11 @@ pub async fn check_login(
             let parsed_hash = PasswordHash::new(&password_hash)?;
             match argon2.verify_password(login_request.password.as_bytes(), &parsed_hash) {
                 Ok(_) => {
-                    let jwt = create_jwt(login_request.username);
-                    Ok(Json(RequestValidUserAnswer::Valid(jwt)))
+                    delete_expired_refresh_tokens(&pool, Utc::now().timestamp()).await?;
+                    let auth_tokens =
+                        start_session(&pool, &jwt_secret, &login_request.username).await?;
+                    println!("{}: Logged in!", login_request.username);
+                    Ok(Json(RequestValidUserAnswer::Valid(auth_tokens)))
                 }
                 Err(_) => Ok(Json(RequestValidUserAnswer::WrongPassword)),
             }
@@ -41,17 +54,218 @@ pub async fn check_login(
 
 pub async fn register(
     State(pool): State<SqlitePool>,
+    State(jwt_secret): State<JwtSecret>,
     Json(login_request): Json<LoginRequest>,
 ) -> Result<Json<RequestValidRegisterAnswer>, ApiError> {
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2.hash_password(login_request.password.as_bytes(), &salt)?;
+    let password_hash = hash_password(&login_request.password)?;
     let users = get_all_usernames(&pool).await?;
     if !users.contains(&login_request.username) || users.is_empty() {
-        add_user(&pool, &login_request.username, &password_hash.to_string()).await?;
-        let jwt = create_jwt(login_request.username);
-        Ok(Json(RequestValidRegisterAnswer::Valid(jwt)))
+        add_user(&pool, &login_request.username, &password_hash).await?;
+        let auth_tokens = start_session(&pool, &jwt_secret, &login_request.username).await?;
+        Ok(Json(RequestValidRegisterAnswer::Valid(auth_tokens)))
     } else {
         Ok(Json(RequestValidRegisterAnswer::UserAlreadyExists))
     }
 }
+
+/// Exchanges a refresh token for a new token pair. The used refresh token is revoked,
+/// presenting it again revokes the whole session as it was most likely stolen
+pub async fn refresh_tokens(
+    State(pool): State<SqlitePool>,
+    State(jwt_secret): State<JwtSecret>,
+    Json(refresh_request): Json<RefreshTokenRequest>,
+) -> Result<Json<AuthTokens>, ApiError> {
+    let invalid_refresh_token = || ApiError::Unauthorized("Invalid refresh token".to_string());
+
+    let old_token_hash = hash_refresh_token(&refresh_request.refresh_token);
+    let stored_token = get_refresh_token(&pool, &old_token_hash)
+        .await?
+        .ok_or_else(invalid_refresh_token)?;
+
+    if stored_token.revoked {
+        revoke_session(&pool, &stored_token.session_id).await?;
+        println!(
+            "{}: Revoked refresh token was reused, session was revoked!",
+            stored_token.username
+        );
+        return Err(invalid_refresh_token());
+    }
+    let now = Utc::now().timestamp();
+    if stored_token.expires_at <= now {
+        return Err(invalid_refresh_token());
+    }
+
+    let refresh_token = create_random_token();
+    let rotated = rotate_refresh_token(
+        &pool,
+        &old_token_hash,
+        &hash_refresh_token(&refresh_token),
+        now + REFRESH_TOKEN_LIFETIME_SECONDS,
+    )
+    .await?;
+    if !rotated {
+        return Err(invalid_refresh_token());
+    }
+
+    let access_token = create_jwt(
+        stored_token.username.clone(),
+        &stored_token.session_id,
+        &jwt_secret,
+    );
+    println!("{}: Refreshed session tokens!", stored_token.username);
+
+    Ok(Json(AuthTokens {
+        access_token,
+        refresh_token,
+    }))
+}
+
+/// Revokes the session of the refresh token. Unknown tokens are ignored
+pub async fn logout(
+    State(pool): State<SqlitePool>,
+    Json(logout_request): Json<RefreshTokenRequest>,
+) -> Result<(), ApiError> {
+    let token_hash = hash_refresh_token(&logout_request.refresh_token);
+    if let Some(stored_token) = get_refresh_token(&pool, &token_hash).await? {
+        revoke_session(&pool, &stored_token.session_id).await?;
+        println!("{}: Logged out!", stored_token.username);
+    }
+    Ok(())
+}
+
+/// Hashes the password with a new salt, as stored in users.user_password
+pub(crate) fn hash_password(password: &str) -> Result<String, ApiError> {
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(password_hash.to_string())
+}
+
+/// Whether `password` matches the stored one. NotFound if the user doesn't exist
+pub(crate) async fn is_password_correct(
+    pool: &SqlitePool,
+    username: &str,
+    password: &str,
+) -> Result<bool, ApiError> {
+    match get_password(pool, username).await? {
+        RequestPasswordAnswer::Password(password_hash) => {
+            let parsed_hash = PasswordHash::new(&password_hash)?;
+            Ok(Argon2::default()
+                .verify_password(password.as_bytes(), &parsed_hash)
+                .is_ok())
+        }
+        RequestPasswordAnswer::UserNotFound => Err(ApiError::NotFound),
+    }
+}
+
+/// Creates a new session for the user and returns its first token pair
+pub(crate) async fn start_session(
+    pool: &SqlitePool,
+    jwt_secret: &JwtSecret,
+    username: &str,
+) -> Result<AuthTokens, ApiError> {
+    let session_id = create_random_token();
+    let refresh_token = create_random_token();
+    add_refresh_token(
+        pool,
+        &hash_refresh_token(&refresh_token),
+        username,
+        &session_id,
+        Utc::now().timestamp() + REFRESH_TOKEN_LIFETIME_SECONDS,
+    )
+    .await?;
+
+    Ok(AuthTokens {
+        access_token: create_jwt(username.to_string(), &session_id, jwt_secret),
+        refresh_token,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::database_mod::database::setup_test_db;
+    use crate::server::jwt::jwt_architecture::decode_jwt;
+
+    fn test_secret() -> JwtSecret {
+        JwtSecret::new("a_test_secret_that_is_long_enough_")
+    }
+
+    async fn register_test_user(pool: &SqlitePool) -> AuthTokens {
+        sqlx::query("INSERT INTO mascot (mascot_name, description) VALUES ('Duck', 'test')")
+            .execute(pool)
+            .await
+            .unwrap();
+        let answer = register(
+            State(pool.clone()),
+            State(test_secret()),
+            Json(LoginRequest {
+                username: "testuser".to_string(),
+                password: "123".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        match answer.0 {
+            RequestValidRegisterAnswer::Valid(auth_tokens) => auth_tokens,
+            RequestValidRegisterAnswer::UserAlreadyExists => panic!("user already exists"),
+        }
+    }
+
+    async fn refresh(pool: &SqlitePool, refresh_token: &str) -> Result<AuthTokens, ApiError> {
+        refresh_tokens(
+            State(pool.clone()),
+            State(test_secret()),
+            Json(RefreshTokenRequest {
+                refresh_token: refresh_token.to_string(),
+            }),
+        )
+        .await
+        .map(|json| json.0)
+    }
+
+    #[tokio::test]
+    async fn refresh_rotates_tokens_of_the_same_session() {
+        let pool = setup_test_db().await;
+        let first_tokens = register_test_user(&pool).await;
+        let second_tokens = refresh(&pool, &first_tokens.refresh_token).await.unwrap();
+
+        assert_ne!(first_tokens.refresh_token, second_tokens.refresh_token);
+        let first_claims = decode_jwt(&first_tokens.access_token, &test_secret()).unwrap();
+        let second_claims = decode_jwt(&second_tokens.access_token, &test_secret()).unwrap();
+        assert_eq!(second_claims.sub, "testuser");
+        assert_eq!(first_claims.sid, second_claims.sid);
+    }
+    #[tokio::test]
+    async fn reusing_refresh_token_revokes_session() {
+        let pool = setup_test_db().await;
+        let first_tokens = register_test_user(&pool).await;
+        let second_tokens = refresh(&pool, &first_tokens.refresh_token).await.unwrap();
+
+        assert!(matches!(
+            refresh(&pool, &first_tokens.refresh_token).await,
+            Err(ApiError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            refresh(&pool, &second_tokens.refresh_token).await,
+            Err(ApiError::Unauthorized(_))
+        ));
+    }
+    #[tokio::test]
+    async fn logout_revokes_refresh_token() {
+        let pool = setup_test_db().await;
+        let tokens = register_test_user(&pool).await;
+        logout(
+            State(pool.clone()),
+            Json(RefreshTokenRequest {
+                refresh_token: tokens.refresh_token.clone(),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            refresh(&pool, &tokens.refresh_token).await,
+            Err(ApiError::Unauthorized(_))
+        ));
+    }
+}
@@ -1,29 +1,34 @@
-use crate::common::mascot_mod::mascot::Mascot;
-use crate::common::mascot_mod::mascot_data_transfer::MascotDataServerClientTransfer;
-use crate::common::mascot_mod::mascot_trait::MascotTrait;
-use crate::server::database_mod::database_mascot::add_mascot_to_user;
-use crate::server::database_mod::database_user::{get_user_coin_balance, update_user_coin_balance};
-use crate::server::database_mod::{database_mascot, database_user};
+use crate::common::mascot_mod::mascot::{Mascot, MascotRarity};
+use crate::common::mascot_mod::mascot_data_transfer::{
+    MascotDataServerClientTransfer, MascotRollAnswer,
+};
+use crate::server::database_mod::{database_feed, database_mascot, database_user};
 use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
 use crate::server::server_main::ApiError;
 use axum::Json;
 use axum::extract::State;
 use sqlx::SqlitePool;
 
-pub async fn buy_mascot(
+/// Buys a random mascot of the requested rarity, see database_mascot::roll_mascot
+pub async fn roll_mascot(
     user_authentication: UserAuthenticationRequestPath,
     State(pool): State<SqlitePool>,
-    Json(mascot): Json<Mascot>,
-) -> Result<(), ApiError> {
-    add_mascot_to_user(&pool, &user_authentication.username, &mascot.to_string()).await?;
-    let current_coins = get_user_coin_balance(&pool, &user_authentication.username).await?;
-    update_user_coin_balance(
-        &pool,
-        &user_authentication.username,
-        current_coins - mascot.get_prize(),
-    )
-    .await?;
-    Ok(())
+    Json(rarity): Json<MascotRarity>,
+) -> Result<Json<MascotRollAnswer>, ApiError> {
+    let answer = database_mascot::roll_mascot(&pool, &user_authentication.username, rarity).await?;
+
+    if let MascotRollAnswer::Rolled { mascot, .. } = &answer {
+        let result =
+            database_feed::add_mascot_bought_event(&pool, &user_authentication.username, *mascot)
+                .await;
+        database_feed::log_failed_feed_event(&user_authentication.username, result);
+        println!(
+            "{}: Bought mascot {}!",
+            user_authentication.username, mascot
+        );
+    }
+
+    Ok(Json(answer))
 }
 pub async fn get_mascot_data(
     State(pool): State<SqlitePool>,
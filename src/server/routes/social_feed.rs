@@ -0,0 +1,38 @@
+use crate::common::social_feed::{DEFAULT_FEED_PAGE_SIZE, FeedPage, MAX_FEED_PAGE_SIZE};
+use crate::server::database_mod::database_feed;
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::{Query, State};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    /// `next_before` of the previous page, the newest events are returned if missing
+    before: Option<i64>,
+    limit: Option<u32>,
+}
+
+/// Events of the friends of the user, newest first
+pub async fn get_friends_feed(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Query(feed_query): Query<FeedQuery>,
+) -> Result<Json<FeedPage>, ApiError> {
+    let limit = feed_query
+        .limit
+        .unwrap_or(DEFAULT_FEED_PAGE_SIZE)
+        .clamp(1, MAX_FEED_PAGE_SIZE);
+    let feed_page = database_feed::get_friends_feed(
+        &pool,
+        &user_authentication.username,
+        feed_query.before,
+        limit,
+    )
+    .await?;
+
+    println!("{}: Fetching friends feed!", user_authentication.username);
+
+    Ok(Json(feed_page))
+}
@@ -2,19 +2,71 @@ use axum::Json;
 use axum::extract::State;
 use sqlx::SqlitePool;
 
+use crate::common::exercise_mod::custom_exercise::{CustomExercise, NewCustomExercise};
+use crate::common::exercise_mod::exercise::ExerciseSets;
+use crate::server::database_mod::database_custom_exercise::{
+    add_custom_exercise, get_custom_exercises,
+};
+use crate::server::database_mod::database_exercise::get_exercise_sets;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
 use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
 use crate::server::server_main::ApiError;
-use crate::{
-    common::exercise_mod::exercise::Exercise, server::database_mod::database::get_exercises_stats,
-};
 
-pub async fn get_user_exercises(
+/// Only the sets of the user, the exercises themselves come from /exercises/catalog
+pub async fn get_user_exercise_sets(
+    State(pool): State<SqlitePool>,
+    user_authentication: UserAuthenticationRequestPath,
+) -> Result<Json<Vec<ExerciseSets>>, ApiError> {
+    let exercise_sets = get_exercise_sets(&pool, &user_authentication.username).await?;
+
+    println!("{}: Fetching Exercise Sets!", user_authentication.username);
+
+    Ok(Json(exercise_sets))
+}
+
+/// The custom exercises the user can track next to the catalog
+pub async fn get_user_custom_exercises(
     State(pool): State<SqlitePool>,
     user_authentication: UserAuthenticationRequestPath,
-) -> Result<Json<Vec<Exercise>>, ApiError> {
-    let exercises = get_exercises_stats(&pool, &user_authentication.username).await?;
+) -> Result<Json<Vec<CustomExercise>>, ApiError> {
+    let custom_exercises = get_custom_exercises(&pool, &user_authentication.username).await?;
+
+    println!(
+        "{}: Fetching Custom Exercises!",
+        user_authentication.username
+    );
+
+    Ok(Json(custom_exercises))
+}
+
+/// Returns the new exercise with the id the server gave it
+pub async fn create_custom_exercise(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    State(exercise_catalog): State<ExerciseCatalog>,
+    Json(new_custom_exercise): Json<NewCustomExercise>,
+) -> Result<Json<CustomExercise>, ApiError> {
+    let username = &user_authentication.username;
+    let catalog = exercise_catalog.current(&pool).await?;
+    let custom_exercises = get_custom_exercises(&pool, username).await?;
+    let existing_names = catalog
+        .exercises
+        .iter()
+        .chain(
+            custom_exercises
+                .iter()
+                .map(|custom_exercise| &custom_exercise.general_exercise_info),
+        )
+        .map(|general_exercise_info| general_exercise_info.name.as_str());
 
-    println!("{}: Fetching Exercise Data!", user_authentication.username);
+    if let Some(reason) = new_custom_exercise.validation_error(existing_names) {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    let custom_exercise = add_custom_exercise(&pool, username, &new_custom_exercise).await?;
 
-    Ok(Json(exercises))
+    println!(
+        "{username}: Custom exercise {} was created!",
+        custom_exercise.general_exercise_info.name
+    );
+    Ok(Json(custom_exercise))
 }
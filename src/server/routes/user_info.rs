@@ -1,6 +1,7 @@
 use axum::extract::State;
 
 use crate::common::user_mod::user::UserInformation;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
 use crate::server::database_mod::{database_user, database_user_goals};
 use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
 use crate::server::server_main::ApiError;
@@ -9,10 +10,15 @@ use sqlx::SqlitePool;
 
 pub async fn get_user_info(
     State(pool): State<SqlitePool>,
+    State(exercise_catalog): State<ExerciseCatalog>,
     user_authentication: UserAuthenticationRequestPath,
 ) -> Result<Json<UserInformation>, ApiError> {
-    let user_info =
-        database_user::get_user_information(&pool, &user_authentication.username).await?;
+    let user_info = database_user::get_user_information(
+        &pool,
+        &exercise_catalog,
+        &user_authentication.username,
+    )
+    .await?;
 
     println!(
         "{}: Fetching UserInformation Data!",
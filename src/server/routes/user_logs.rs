@@ -0,0 +1,110 @@
+use crate::common::user_mod::user_goals::GoalType;
+use crate::common::user_mod::user_log::{Log, UserLogEntry};
+use crate::server::database_mod::database_feed;
+use crate::server::database_mod::database_user_logs;
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::{Path, State};
+use chrono::{Local, NaiveDate};
+use sqlx::SqlitePool;
+
+pub async fn get_user_logs(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(goal_type): Path<String>,
+) -> Result<Json<Log>, ApiError> {
+    let goal_type = parse_log_type(&goal_type)?;
+    let log =
+        database_user_logs::get_user_log(&pool, &user_authentication.username, goal_type).await?;
+    println!(
+        "{}: Fetching {goal_type} log!",
+        user_authentication.username
+    );
+
+    Ok(Json(log))
+}
+
+/// Inserts the entry or overwrites the value already logged on that date
+pub async fn upsert_user_log(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(goal_type): Path<String>,
+    Json(entry): Json<UserLogEntry>,
+) -> Result<(), ApiError> {
+    let goal_type = parse_log_type(&goal_type)?;
+    if let Some(error) = entry.validation_error(&goal_type, Local::now().date_naive()) {
+        return Err(ApiError::InvalidInput(error.to_string()));
+    }
+
+    let previous_value = database_user_logs::get_user_log_value(
+        &pool,
+        &user_authentication.username,
+        entry.date,
+        goal_type,
+    )
+    .await?;
+    database_user_logs::add_user_log(
+        &pool,
+        &user_authentication.username,
+        entry.value,
+        entry.date,
+        goal_type,
+    )
+    .await?;
+    let result = database_feed::add_goal_reached_event_if_new(
+        &pool,
+        &user_authentication.username,
+        goal_type,
+        previous_value,
+        entry.value,
+    )
+    .await;
+    database_feed::log_failed_feed_event(&user_authentication.username, result);
+    println!(
+        "{}: {goal_type} log of {} was saved in the database!",
+        user_authentication.username, entry.date
+    );
+
+    Ok(())
+}
+
+pub async fn delete_user_log(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path((goal_type, date)): Path<(String, NaiveDate)>,
+) -> Result<(), ApiError> {
+    let goal_type = parse_log_type(&goal_type)?;
+    let deleted =
+        database_user_logs::delete_user_log(&pool, &user_authentication.username, date, goal_type)
+            .await?;
+    if !deleted {
+        return Err(ApiError::NotFound);
+    }
+    println!(
+        "{}: {goal_type} log of {date} was deleted!",
+        user_authentication.username
+    );
+
+    Ok(())
+}
+
+fn parse_log_type(path: &str) -> Result<GoalType, ApiError> {
+    GoalType::from_log_path(path)
+        .ok_or_else(|| ApiError::InvalidInput(format!("'{path}' is not a log type")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_logged_goal_types_are_accepted() {
+        assert_eq!(parse_log_type("weight").unwrap(), GoalType::Weight);
+        assert_eq!(parse_log_type("water").unwrap(), GoalType::Water);
+        assert_eq!(parse_log_type("steps").unwrap(), GoalType::Steps);
+        assert_eq!(parse_log_type("sleep").unwrap(), GoalType::Sleep);
+        assert!(parse_log_type("weekly_workouts").is_err());
+        assert!(parse_log_type("Water").is_err());
+    }
+}
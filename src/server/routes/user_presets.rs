@@ -1,8 +1,10 @@
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, State};
 use sqlx::SqlitePool;
 
+use crate::common::exercise_mod::general_exercise::Id;
 use crate::common::workout_preset::WorkoutPreset;
+use crate::server::database_mod::database_preset;
 use crate::server::database_mod::database_preset::{
     add_preset, add_preset_to_user, get_presets_for_user,
 };
@@ -18,13 +20,87 @@ pub async fn get_user_presets(
     Ok(Json(presets))
 }
 
+/// Returns the id of the new preset
 pub async fn save_preset(
     user_authentication: UserAuthenticationRequestPath,
     State(pool): State<SqlitePool>,
     Json(preset): Json<WorkoutPreset>,
-) -> Result<(), ApiError> {
+) -> Result<Json<Id>, ApiError> {
     println!("{}: Preset received", user_authentication.username);
+    validate_preset(&preset)?;
     let preset_id = add_preset(&pool, &preset, 10).await?;
     add_preset_to_user(&pool, &user_authentication.username, preset_id).await?;
+    Ok(Json(preset_id as Id))
+}
+
+pub async fn update_preset(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(preset_id): Path<Id>,
+    Json(preset): Json<WorkoutPreset>,
+) -> Result<(), ApiError> {
+    validate_preset(&preset)?;
+    let updated = database_preset::update_preset(
+        &pool,
+        &user_authentication.username,
+        preset_id as i64,
+        &preset,
+    )
+    .await?;
+    if !updated {
+        return Err(ApiError::NotFound);
+    }
+    println!(
+        "{}: Preset {preset_id} was updated!",
+        user_authentication.username
+    );
+    Ok(())
+}
+
+pub async fn delete_preset(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(preset_id): Path<Id>,
+) -> Result<(), ApiError> {
+    let deleted =
+        database_preset::delete_preset(&pool, &user_authentication.username, preset_id as i64)
+            .await?;
+    if !deleted {
+        return Err(ApiError::NotFound);
+    }
+    println!(
+        "{}: Preset {preset_id} was deleted!",
+        user_authentication.username
+    );
+    Ok(())
+}
+
+/// Receives the ids of all presets of the user in the order they should be shown
+pub async fn reorder_presets(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Json(preset_ids): Json<Vec<Id>>,
+) -> Result<(), ApiError> {
+    let preset_ids: Vec<i64> = preset_ids.into_iter().map(i64::from).collect();
+    let reordered =
+        database_preset::reorder_presets(&pool, &user_authentication.username, &preset_ids).await?;
+    if !reordered {
+        return Err(ApiError::InvalidInput(
+            "The new order has to contain every preset exactly once".to_string(),
+        ));
+    }
+    println!("{}: Preset order was saved!", user_authentication.username);
+    Ok(())
+}
+
+fn validate_preset(preset: &WorkoutPreset) -> Result<(), ApiError> {
+    if preset.name.trim().is_empty() {
+        return Err(ApiError::InvalidInput("Presets need a name".to_string()));
+    }
+    if preset.exercises.is_empty() {
+        return Err(ApiError::InvalidInput(
+            "Presets need at least one exercise".to_string(),
+        ));
+    }
     Ok(())
 }
@@ -1,18 +1,44 @@
 use crate::common::exercise_mod::general_exercise::Id;
-use crate::server::database_mod::database::add_workout_to_exercise_log;
-use crate::server::database_mod::database_user::{get_user_coin_balance, update_user_coin_balance};
+use crate::common::workout::{PresetTraining, SavedWorkout, is_plausible_workout_duration};
+use crate::server::database_mod::database_exercise;
+use crate::server::database_mod::database_exercise::add_workout_with_daily_reward;
+use crate::server::database_mod::database_feed;
 use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
 use crate::server::server_main::ApiError;
 use axum::Json;
-use axum::extract::State;
-use chrono::Local;
+use axum::extract::{Path, State};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct WorkoutJson {
     workout: Vec<ExerciseJson>,
-    first_workout: bool,
+    /// Today if missing. Ignored when an existing workout is updated
+    date: Option<NaiveDate>,
+    start_time: Option<NaiveTime>,
+    /// Set if a new workout was started from a preset
+    #[serde(default)]
+    preset_training: Option<PresetTraining>,
+    /// Offset of the client's clock, the server's timezone is assumed if missing
+    #[serde(default)]
+    utc_offset_minutes: Option<i32>,
+}
+
+///coins you receive each day you have done a workout
+pub const DAILY_COIN_REWARD: u32 = 5;
+/// Largest offset of a real timezone, UTC+14
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+/// Workouts backdated further than this don't pay the daily reward,
+/// otherwise coins could be farmed by filling up old days
+pub const REWARDED_BACKDATE_DAYS: i64 = 7;
+
+/// A saved workout together with the date it was tracked on
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoggedWorkoutJson {
+    pub date: NaiveDate,
+    pub start_time: Option<NaiveTime>,
+    pub workout: Vec<ExerciseJson>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -32,20 +58,330 @@ pub async fn save_workout(
     user_authentication: UserAuthenticationRequestPath,
     State(pool): State<SqlitePool>,
     Json(workout): Json<WorkoutJson>,
-) -> Result<Json<Id>, ApiError> {
-    let workout_id = add_workout_to_exercise_log(
+) -> Result<Json<SavedWorkout>, ApiError> {
+    let now = client_now(Utc::now(), workout.utc_offset_minutes)?;
+    let date = workout.date.unwrap_or(now.date());
+    if is_in_future(date, workout.start_time, now) {
+        return Err(ApiError::InvalidInput(
+            "Workouts can not be logged in the future".to_string(),
+        ));
+    }
+    let daily_reward = if now.date() - date <= Duration::days(REWARDED_BACKDATE_DAYS) {
+        DAILY_COIN_REWARD
+    } else {
+        0
+    };
+
+    if let Some(reason) = workout
+        .preset_training
+        .and_then(|preset_training| preset_training.validation_error())
+    {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+
+    let saved_workout = add_workout_with_daily_reward(
         &pool,
         &user_authentication.username,
         workout.workout,
-        Local::now().date_naive(),
+        date,
+        workout.start_time,
+        daily_reward,
+        workout.preset_training,
     )
     .await?;
+    println!("{}: Workout received", user_authentication.username);
+
+    let duration_minutes = workout
+        .preset_training
+        .and_then(|preset_training| preset_training.duration_minutes)
+        .or_else(|| workout_duration_minutes(date, workout.start_time, now));
+    let result = database_feed::add_workout_events(
+        &pool,
+        &user_authentication.username,
+        saved_workout.workout_id,
+        duration_minutes,
+    )
+    .await;
+    database_feed::log_failed_feed_event(&user_authentication.username, result);
+
+    Ok(Json(saved_workout))
+}
+
+/// Only known if the workout is saved on the day it was started
+fn workout_duration_minutes(
+    date: NaiveDate,
+    start_time: Option<NaiveTime>,
+    now: NaiveDateTime,
+) -> Option<u32> {
+    let start_time = start_time.filter(|_| date == now.date())?;
+    let duration_minutes = (now.time() - start_time).num_minutes().max(0) as u32;
+    Some(duration_minutes)
+        .filter(|&duration_minutes| is_plausible_workout_duration(duration_minutes))
+}
 
-    if workout.first_workout {
-        let current_coins = get_user_coin_balance(&pool, &user_authentication.username).await?;
-        update_user_coin_balance(&pool, &user_authentication.username, current_coins + 5).await?;
+/// The current time in the timezone of the client, so dates are checked the way the user sees them
+fn client_now(
+    now_utc: DateTime<Utc>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<NaiveDateTime, ApiError> {
+    let Some(utc_offset_minutes) = utc_offset_minutes else {
+        return Ok(now_utc.with_timezone(&Local).naive_local());
+    };
+    if utc_offset_minutes.abs() > MAX_UTC_OFFSET_MINUTES {
+        return Err(ApiError::InvalidInput(
+            "The UTC offset is not a real timezone".to_string(),
+        ));
     }
-    println!("{}: Workout received", user_authentication.username);
+    let offset = FixedOffset::east_opt(utc_offset_minutes * 60).ok_or(ApiError::InternalError)?;
+    Ok(now_utc.with_timezone(&offset).naive_local())
+}
+
+fn is_in_future(date: NaiveDate, start_time: Option<NaiveTime>, now: NaiveDateTime) -> bool {
+    match start_time {
+        Some(start_time) => date.and_time(start_time) > now,
+        None => date > now.date(),
+    }
+}
+
+pub async fn get_workout(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+) -> Result<Json<LoggedWorkoutJson>, ApiError> {
+    let workout = database_exercise::get_workout(&pool, &user_authentication.username, workout_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(workout))
+}
+
+/// Replaces the sets of the workout, its date stays the same.
+/// A workout without sets has to be deleted instead
+pub async fn update_workout(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+    Json(workout): Json<WorkoutJson>,
+) -> Result<(), ApiError> {
+    if workout
+        .workout
+        .iter()
+        .all(|exercise| exercise.sets.is_empty())
+    {
+        return Err(ApiError::InvalidInput(
+            "A workout needs at least one set, delete it instead".to_string(),
+        ));
+    }
+    let updated = database_exercise::update_workout(
+        &pool,
+        &user_authentication.username,
+        workout_id,
+        workout.workout,
+    )
+    .await?;
+    if !updated {
+        return Err(ApiError::NotFound);
+    }
+    println!(
+        "{}: Workout {workout_id} updated",
+        user_authentication.username
+    );
+    Ok(())
+}
+
+pub async fn delete_workout(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+) -> Result<(), ApiError> {
+    let deleted =
+        database_exercise::delete_workout(&pool, &user_authentication.username, workout_id).await?;
+    if !deleted {
+        return Err(ApiError::NotFound);
+    }
+    println!(
+        "{}: Workout {workout_id} deleted",
+        user_authentication.username
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::server_communication::exercise_communicator::SetJson;
+    use crate::server::database_mod::database::{setup_test_db, test_values_for_db};
+
+    #[test]
+    fn future_workouts_are_detected() {
+        let now = NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let today = now.date();
+        let tomorrow = today.succ_opt().unwrap();
+        let yesterday = today.pred_opt().unwrap();
+        let morning = NaiveTime::from_hms_opt(8, 0, 0);
+        let evening = NaiveTime::from_hms_opt(20, 0, 0);
+
+        assert!(!is_in_future(today, None, now));
+        assert!(!is_in_future(today, morning, now));
+        assert!(is_in_future(today, evening, now));
+        assert!(!is_in_future(yesterday, evening, now));
+        assert!(is_in_future(tomorrow, None, now));
+    }
+
+    #[test]
+    fn dates_are_checked_in_the_timezone_of_the_client() {
+        let now_utc = NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap()
+            .and_utc();
+        let client_ahead = client_now(now_utc, Some(10 * 60)).unwrap();
+        let client_behind = client_now(now_utc, Some(-8 * 60)).unwrap();
+        let next_day = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        let early_morning = NaiveTime::from_hms_opt(5, 30, 0);
 
-    Ok(Json(workout_id))
+        assert_eq!(client_ahead.date(), next_day);
+        assert!(!is_in_future(next_day, early_morning, client_ahead));
+        assert!(is_in_future(next_day, None, client_behind));
+        assert!(matches!(
+            client_now(now_utc, Some(15 * 60)),
+            Err(ApiError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn duration_is_only_known_for_workouts_saved_on_their_day() {
+        let now = NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let yesterday = now.date().pred_opt().unwrap();
+        let morning = NaiveTime::from_hms_opt(9, 15, 0);
+
+        assert_eq!(workout_duration_minutes(now.date(), morning, now), Some(45));
+        assert_eq!(workout_duration_minutes(now.date(), None, now), None);
+        assert_eq!(workout_duration_minutes(yesterday, morning, now), None);
+        let midnight = NaiveTime::from_hms_opt(0, 0, 0);
+        let late_evening = now.date().and_hms_opt(23, 0, 0).unwrap();
+        assert_eq!(
+            workout_duration_minutes(now.date(), midnight, late_evening),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn workout_is_saved_even_if_the_feed_event_fails() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        sqlx::query("DROP TABLE feed_event")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let workout = WorkoutJson {
+            workout: vec![ExerciseJson {
+                name: "Squat".to_string(),
+                sets: vec![SetJson {
+                    weight: 60.0,
+                    reps: 5,
+                }],
+            }],
+            date: None,
+            start_time: None,
+            preset_training: None,
+            utc_offset_minutes: None,
+        };
+
+        let saved = save_workout(
+            UserAuthenticationRequestPath {
+                username: "testuser".to_string(),
+            },
+            State(pool.clone()),
+            Json(workout),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            database_exercise::get_workout(&pool, "testuser", saved.workout_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn implausible_preset_training_durations_are_rejected() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+
+        for duration_minutes in [0, 3 * 24 * 60] {
+            let workout = WorkoutJson {
+                workout: Vec::new(),
+                date: None,
+                start_time: None,
+                preset_training: Some(PresetTraining {
+                    preset_id: 1,
+                    duration_minutes: Some(duration_minutes),
+                }),
+                utc_offset_minutes: None,
+            };
+            let result = save_workout(
+                UserAuthenticationRequestPath {
+                    username: "testuser".to_string(),
+                },
+                State(pool.clone()),
+                Json(workout),
+            )
+            .await;
+
+            assert!(matches!(result, Err(ApiError::InvalidInput(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn workout_can_not_be_updated_without_sets() {
+        let pool = setup_test_db().await;
+        test_values_for_db(&pool).await.unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let squats = || ExerciseJson {
+            name: "Squat".to_string(),
+            sets: vec![SetJson {
+                weight: 60.0,
+                reps: 5,
+            }],
+        };
+        let saved =
+            add_workout_with_daily_reward(&pool, "testuser", vec![squats()], date, None, 0, None)
+                .await
+                .unwrap();
+        let empty_workout = WorkoutJson {
+            workout: vec![ExerciseJson {
+                sets: Vec::new(),
+                ..squats()
+            }],
+            date: None,
+            start_time: None,
+            preset_training: None,
+            utc_offset_minutes: None,
+        };
+
+        let result = update_workout(
+            UserAuthenticationRequestPath {
+                username: "testuser".to_string(),
+            },
+            State(pool.clone()),
+            Path(saved.workout_id),
+            Json(empty_workout),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiError::InvalidInput(_))));
+        let workout = database_exercise::get_workout(&pool, "testuser", saved.workout_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(workout.workout[0].sets.len(), 1);
+    }
 }
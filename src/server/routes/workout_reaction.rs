@@ -0,0 +1,155 @@
+use crate::common::exercise_mod::general_exercise::Id;
+use crate::common::user_mod::friend_request::FriendshipStatus;
+use crate::common::workout_reaction::{
+    NewWorkoutComment, WorkoutComment, WorkoutReactions, WorkoutSummary,
+};
+use crate::server::database_mod::{database_social, database_workout_reaction};
+use crate::server::jwt::user_authentication_request_path::UserAuthenticationRequestPath;
+use crate::server::server_main::ApiError;
+use axum::Json;
+use axum::extract::{Path, State};
+use sqlx::SqlitePool;
+
+/// Workouts shown with their reactions on a profile
+const PROFILE_WORKOUT_COUNT: u32 = 3;
+
+pub async fn get_workout_reactions(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+) -> Result<Json<WorkoutReactions>, ApiError> {
+    ensure_workout_is_visible(&pool, &user_authentication.username, workout_id).await?;
+    let reactions = database_workout_reaction::get_workout_reactions(&pool, workout_id).await?;
+    Ok(Json(reactions))
+}
+
+pub async fn give_kudos(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+) -> Result<(), ApiError> {
+    ensure_workout_is_visible(&pool, &user_authentication.username, workout_id).await?;
+    database_workout_reaction::add_kudos(&pool, &user_authentication.username, workout_id).await?;
+    println!(
+        "{}: Gave kudos to workout {workout_id}",
+        user_authentication.username
+    );
+    Ok(())
+}
+
+pub async fn take_back_kudos(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+) -> Result<(), ApiError> {
+    let removed =
+        database_workout_reaction::remove_kudos(&pool, &user_authentication.username, workout_id)
+            .await?;
+    if !removed {
+        return Err(ApiError::NotFound);
+    }
+    Ok(())
+}
+
+pub async fn add_comment(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(workout_id): Path<Id>,
+    Json(new_comment): Json<NewWorkoutComment>,
+) -> Result<Json<WorkoutComment>, ApiError> {
+    if let Some(reason) = new_comment.validation_error() {
+        return Err(ApiError::InvalidInput(reason.to_string()));
+    }
+    ensure_workout_is_visible(&pool, &user_authentication.username, workout_id).await?;
+    let comment = database_workout_reaction::add_comment(
+        &pool,
+        &user_authentication.username,
+        workout_id,
+        &new_comment,
+    )
+    .await?;
+    println!(
+        "{}: Commented on workout {workout_id}",
+        user_authentication.username
+    );
+    Ok(Json(comment))
+}
+
+/// Only the author of the comment and the owner of the workout can delete it
+pub async fn delete_comment(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path((workout_id, comment_id)): Path<(Id, i64)>,
+) -> Result<(), ApiError> {
+    let deleted = database_workout_reaction::delete_comment(
+        &pool,
+        &user_authentication.username,
+        workout_id,
+        comment_id,
+    )
+    .await?;
+    if !deleted {
+        return Err(ApiError::Unauthorized(
+            "Only the author or the owner of the workout can delete a comment".to_string(),
+        ));
+    }
+    println!(
+        "{}: Deleted comment {comment_id} of workout {workout_id}",
+        user_authentication.username
+    );
+    Ok(())
+}
+
+/// Reactions on the workouts of the logged-in user, workouts without any are left out
+pub async fn get_reactions_on_own_workouts(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<WorkoutReactions>>, ApiError> {
+    let reactions = database_workout_reaction::get_reactions_on_own_workouts(
+        &pool,
+        &user_authentication.username,
+    )
+    .await?;
+    Ok(Json(reactions))
+}
+
+/// Latest workouts of a friend or the user themselves together with their reactions
+pub async fn get_profile_workouts(
+    user_authentication: UserAuthenticationRequestPath,
+    State(pool): State<SqlitePool>,
+    Path(username): Path<String>,
+) -> Result<Json<Vec<WorkoutSummary>>, ApiError> {
+    if username != user_authentication.username
+        && database_social::get_friendship_status(&pool, &user_authentication.username, &username)
+            .await?
+            != FriendshipStatus::Friends
+    {
+        return Err(ApiError::Unauthorized(
+            "Only friends can see the workouts of a user".to_string(),
+        ));
+    }
+    let summaries = database_workout_reaction::get_recent_workout_summaries(
+        &pool,
+        &username,
+        PROFILE_WORKOUT_COUNT,
+    )
+    .await?;
+    println!(
+        "{}: Fetching workouts of {username}",
+        user_authentication.username
+    );
+    Ok(Json(summaries))
+}
+
+/// NotFound unless the workout belongs to the user or one of their friends
+async fn ensure_workout_is_visible(
+    pool: &SqlitePool,
+    username: &str,
+    workout_id: Id,
+) -> Result<(), ApiError> {
+    if database_workout_reaction::is_workout_visible(pool, username, workout_id).await? {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound)
+    }
+}
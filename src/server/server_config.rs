@@ -0,0 +1,345 @@
+use clap::{Parser, Subcommand};
+use serde::Deserialize;
+use std::fmt::{Debug, Formatter};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+/// Config file which is loaded if no other path is given via `--config` or `BUFFBUDDIES_CONFIG`
+pub const DEFAULT_CONFIG_FILE_PATH: &str = "server_config.toml";
+pub const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:3000";
+pub const DEFAULT_DATABASE_PATH: &str = "database/database.db";
+/// HS256 keys shorter than the hash output (32 bytes) weaken the signature
+pub const MIN_JWT_SECRET_LENGTH: usize = 32;
+
+/// Command line flags of the server binary.
+/// Every flag can alternatively be set through the named environment variable.
+/// Flags take precedence over environment variables, which take precedence over the config file.
+#[derive(Parser, Debug, Default)]
+#[command(name = "server", about = "Runs the BuffBuddies server")]
+pub struct ServerArgs {
+    /// Path to a TOML config file
+    #[arg(long, env = "BUFFBUDDIES_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// Socket address the server listens on, e.g. 0.0.0.0:3000
+    #[arg(long, env = "BUFFBUDDIES_BIND_ADDRESS")]
+    pub bind_address: Option<String>,
+    /// Path to the SQLite database file, created if missing
+    #[arg(long, env = "BUFFBUDDIES_DATABASE_PATH")]
+    pub database_path: Option<PathBuf>,
+    /// Secret used to sign the jsonwebtokens of logged-in users
+    #[arg(long, env = "BUFFBUDDIES_JWT_SECRET", hide_env_values = true)]
+    pub jwt_secret: Option<String>,
+    /// Runs a maintenance command instead of the server
+    #[command(subcommand)]
+    pub command: Option<ServerCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ServerCommand {
+    /// Shows or applies the database migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Imports or updates the exercises of a folder in the free-exercise-db format
+    ImportExercises {
+        /// Folder containing `<name>.json` files or `<name>/exercise.json` folders
+        folder: PathBuf,
+    },
+    /// Lists the open reports of user profiles
+    Reports,
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum MigrateAction {
+    /// Lists every migration and whether it was applied
+    Status,
+    /// Applies all pending migrations
+    Up,
+}
+
+/// Content of the TOML config file. Every field is optional so that it can be
+/// provided by a flag or an environment variable instead
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfigFile {
+    pub bind_address: Option<String>,
+    pub database_path: Option<PathBuf>,
+    pub jwt_secret: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ServerConfigError {
+    /// The config file was explicitly requested but doesn't exist
+    ConfigFileNotFound(PathBuf),
+    CouldNotReadConfigFile(PathBuf, std::io::Error),
+    InvalidConfigFile(PathBuf, toml::de::Error),
+    InvalidBindAddress(String),
+    EmptyDatabasePath,
+    MissingJwtSecret,
+    JwtSecretTooShort,
+}
+impl ServerConfigError {
+    pub fn to_error_message(&self) -> String {
+        match self {
+            ServerConfigError::ConfigFileNotFound(path) => {
+                format!("Config file {} doesn't exist!", path.display())
+            }
+            ServerConfigError::CouldNotReadConfigFile(path, err) => {
+                format!("Could not read config file {}: {err}", path.display())
+            }
+            ServerConfigError::InvalidConfigFile(path, err) => {
+                format!("Config file {} is invalid: {err}", path.display())
+            }
+            ServerConfigError::InvalidBindAddress(address) => {
+                format!("{address} is not a valid bind address! Expected e.g. 0.0.0.0:3000")
+            }
+            ServerConfigError::EmptyDatabasePath => "The database path can't be empty!".to_string(),
+            ServerConfigError::MissingJwtSecret => "No jwt secret configured! Set jwt_secret in \
+                the config file, BUFFBUDDIES_JWT_SECRET or --jwt-secret"
+                .to_string(),
+            ServerConfigError::JwtSecretTooShort => {
+                format!("The jwt secret needs at least {MIN_JWT_SECRET_LENGTH} characters!")
+            }
+        }
+    }
+}
+
+/// Validated configuration the server is started with
+#[derive(Clone)]
+pub struct ServerConfig {
+    pub bind_address: SocketAddr,
+    pub database_path: PathBuf,
+    pub jwt_secret: String,
+}
+
+/// Never print the jwt secret
+impl Debug for ServerConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerConfig")
+            .field("bind_address", &self.bind_address)
+            .field("database_path", &self.database_path)
+            .field("jwt_secret", &"<hidden>")
+            .finish()
+    }
+}
+
+impl ServerConfigFile {
+    /// Loads the config file at `path`. <br>
+    /// If no path is given the DEFAULT_CONFIG_FILE_PATH is used, but only if that file exists
+    pub fn load(path: Option<&Path>) -> Result<Self, ServerConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path, true),
+            None => (Path::new(DEFAULT_CONFIG_FILE_PATH), false),
+        };
+
+        if !path.exists() {
+            return if required {
+                Err(ServerConfigError::ConfigFileNotFound(path.to_path_buf()))
+            } else {
+                Ok(ServerConfigFile::default())
+            };
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| ServerConfigError::CouldNotReadConfigFile(path.to_path_buf(), err))?;
+
+        toml::from_str(&content)
+            .map_err(|err| ServerConfigError::InvalidConfigFile(path.to_path_buf(), err))
+    }
+}
+
+impl ServerConfig {
+    /// Loads the config file referenced by `args` and merges it with the flags/environment variables
+    pub fn load(args: ServerArgs) -> Result<Self, ServerConfigError> {
+        let config_file = ServerConfigFile::load(args.config.as_deref())?;
+        Self::from_sources(args, config_file)
+    }
+
+    /// Merges both sources, `args` overriding `config_file`, and validates the result.
+    /// The jwt secret has no default and has to be provided by one of the sources
+    pub fn from_sources(
+        args: ServerArgs,
+        config_file: ServerConfigFile,
+    ) -> Result<Self, ServerConfigError> {
+        let bind_address_string = args
+            .bind_address
+            .or(config_file.bind_address)
+            .unwrap_or(DEFAULT_BIND_ADDRESS.to_string());
+        let bind_address = bind_address_string
+            .trim()
+            .parse::<SocketAddr>()
+            .map_err(|_| ServerConfigError::InvalidBindAddress(bind_address_string))?;
+
+        let database_path =
+            database_path_from_sources(args.database_path, config_file.database_path)?;
+
+        let jwt_secret = args
+            .jwt_secret
+            .or(config_file.jwt_secret)
+            .filter(|secret| !secret.trim().is_empty())
+            .ok_or(ServerConfigError::MissingJwtSecret)?;
+        if jwt_secret.chars().count() < MIN_JWT_SECRET_LENGTH {
+            return Err(ServerConfigError::JwtSecretTooShort);
+        }
+
+        Ok(ServerConfig {
+            bind_address,
+            database_path,
+            jwt_secret,
+        })
+    }
+
+    /// Only loads the database path, as commands like `migrate` don't need a jwt secret
+    pub fn load_database_path(args: ServerArgs) -> Result<PathBuf, ServerConfigError> {
+        let config_file = ServerConfigFile::load(args.config.as_deref())?;
+        database_path_from_sources(args.database_path, config_file.database_path)
+    }
+}
+
+fn database_path_from_sources(
+    args_database_path: Option<PathBuf>,
+    config_file_database_path: Option<PathBuf>,
+) -> Result<PathBuf, ServerConfigError> {
+    let database_path = args_database_path
+        .or(config_file_database_path)
+        .unwrap_or(PathBuf::from(DEFAULT_DATABASE_PATH));
+    if database_path.as_os_str().is_empty() {
+        return Err(ServerConfigError::EmptyDatabasePath);
+    }
+    Ok(database_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SECRET: &str = "a_test_secret_that_is_long_enough_";
+
+    fn args_with_secret() -> ServerArgs {
+        ServerArgs {
+            jwt_secret: Some(TEST_SECRET.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn defaults_are_used_without_other_sources() {
+        let config =
+            ServerConfig::from_sources(args_with_secret(), ServerConfigFile::default()).unwrap();
+        assert_eq!(config.bind_address, DEFAULT_BIND_ADDRESS.parse().unwrap());
+        assert_eq!(config.database_path, PathBuf::from(DEFAULT_DATABASE_PATH));
+        assert_eq!(config.jwt_secret, TEST_SECRET);
+    }
+    #[test]
+    fn missing_secret_has_no_default() {
+        let result = ServerConfig::from_sources(ServerArgs::default(), ServerConfigFile::default());
+        assert!(matches!(result, Err(ServerConfigError::MissingJwtSecret)));
+    }
+    #[test]
+    fn blank_secret_counts_as_missing() {
+        let args = ServerArgs {
+            jwt_secret: Some("   ".to_string()),
+            ..Default::default()
+        };
+        let result = ServerConfig::from_sources(args, ServerConfigFile::default());
+        assert!(matches!(result, Err(ServerConfigError::MissingJwtSecret)));
+    }
+    #[test]
+    fn short_secret_is_rejected() {
+        let args = ServerArgs {
+            jwt_secret: Some("123".to_string()),
+            ..Default::default()
+        };
+        let result = ServerConfig::from_sources(args, ServerConfigFile::default());
+        assert!(matches!(result, Err(ServerConfigError::JwtSecretTooShort)));
+    }
+    #[test]
+    fn args_override_config_file() {
+        let args = ServerArgs {
+            bind_address: Some("127.0.0.1:4000".to_string()),
+            ..args_with_secret()
+        };
+        let config_file = ServerConfigFile {
+            bind_address: Some("0.0.0.0:5000".to_string()),
+            database_path: Some(PathBuf::from("staging.db")),
+            jwt_secret: Some("a_different_secret_from_the_config_file".to_string()),
+        };
+        let config = ServerConfig::from_sources(args, config_file).unwrap();
+        assert_eq!(config.bind_address, "127.0.0.1:4000".parse().unwrap());
+        assert_eq!(config.database_path, PathBuf::from("staging.db"));
+        assert_eq!(config.jwt_secret, TEST_SECRET);
+    }
+    #[test]
+    fn invalid_bind_address_is_rejected() {
+        let args = ServerArgs {
+            bind_address: Some("localhost".to_string()),
+            ..args_with_secret()
+        };
+        let result = ServerConfig::from_sources(args, ServerConfigFile::default());
+        assert!(matches!(
+            result,
+            Err(ServerConfigError::InvalidBindAddress(_))
+        ));
+    }
+    #[test]
+    fn empty_database_path_is_rejected() {
+        let args = ServerArgs {
+            database_path: Some(PathBuf::new()),
+            ..args_with_secret()
+        };
+        let result = ServerConfig::from_sources(args, ServerConfigFile::default());
+        assert!(matches!(result, Err(ServerConfigError::EmptyDatabasePath)));
+    }
+    #[test]
+    fn parse_config_file() {
+        let config_file: ServerConfigFile = toml::from_str(
+            r#"
+            bind_address = "127.0.0.1:3001"
+            database_path = "database/test.db"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config_file,
+            ServerConfigFile {
+                bind_address: Some("127.0.0.1:3001".to_string()),
+                database_path: Some(PathBuf::from("database/test.db")),
+                jwt_secret: None,
+            }
+        );
+    }
+    #[test]
+    fn unknown_config_file_keys_are_rejected() {
+        let result = toml::from_str::<ServerConfigFile>("jwt_secrett = \"typo\"");
+        assert!(result.is_err());
+    }
+    #[test]
+    fn migrate_command_is_parsed_without_secret() {
+        let args = ServerArgs::parse_from(["server", "--database-path", "old.db", "migrate", "up"]);
+        assert!(matches!(
+            args.command,
+            Some(ServerCommand::Migrate {
+                action: MigrateAction::Up
+            })
+        ));
+        assert_eq!(
+            ServerConfig::load_database_path(args).unwrap(),
+            PathBuf::from("old.db")
+        );
+    }
+    #[test]
+    fn explicit_missing_config_file_is_an_error() {
+        let result = ServerConfigFile::load(Some(Path::new("does/not/exist.toml")));
+        assert!(matches!(
+            result,
+            Err(ServerConfigError::ConfigFileNotFound(_))
+        ));
+    }
+    #[test]
+    fn debug_output_hides_secret() {
+        let config =
+            ServerConfig::from_sources(args_with_secret(), ServerConfigFile::default()).unwrap();
+        assert!(!format!("{config:?}").contains(TEST_SECRET));
+    }
+}
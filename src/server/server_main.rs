@@ -1,28 +1,80 @@
-use crate::server::database_mod::database;
-use crate::server::database_mod::database::init_db;
-use crate::server::routes::foreign_users::{add_friend, get_foreign_users, remove_friend};
-use crate::server::routes::login::{check_login, register};
-use crate::server::routes::mascot_manager::{buy_mascot, get_mascot_data, select_mascot};
-use crate::server::routes::user_exercises::get_user_exercises;
+use crate::server::database_mod::exercise_catalog::ExerciseCatalog;
+use crate::server::database_mod::import_exercises::import_exercises;
+use crate::server::database_mod::migrations::{get_migration_status, run_migrations};
+use crate::server::database_mod::{database, database_challenge, database_moderation};
+use crate::server::jwt::jwt_architecture::JwtSecret;
+use crate::server::routes::account::{change_password, change_username, delete_account};
+use crate::server::routes::challenge::{answer_challenge, create_challenge, get_challenges};
+use crate::server::routes::exercise_catalog::get_exercise_catalog;
+use crate::server::routes::foreign_users::{
+    answer_friend_request, block_user, get_blocked_users, get_foreign_users, remove_friend,
+    report_user, send_friend_request, unblock_user,
+};
+use crate::server::routes::leaderboard::get_leaderboard;
+use crate::server::routes::login::{check_login, logout, refresh_tokens, register};
+use crate::server::routes::mascot_manager::{get_mascot_data, roll_mascot, select_mascot};
+use crate::server::routes::social_feed::get_friends_feed;
+use crate::server::routes::user_exercises::{
+    create_custom_exercise, get_user_custom_exercises, get_user_exercise_sets,
+};
 use crate::server::routes::user_info::{get_user_info, update_user_info};
-use crate::server::routes::user_presets::{get_user_presets, save_preset};
-use crate::server::routes::workout::save_workout;
+use crate::server::routes::user_logs::{delete_user_log, get_user_logs, upsert_user_log};
+use crate::server::routes::user_presets::{
+    delete_preset, get_user_presets, reorder_presets, save_preset, update_preset,
+};
+use crate::server::routes::workout::{delete_workout, get_workout, save_workout, update_workout};
+use crate::server::routes::workout_reaction::{
+    add_comment, delete_comment, get_profile_workouts, get_reactions_on_own_workouts,
+    get_workout_reactions, give_kudos, take_back_kudos,
+};
+use crate::server::server_config::{MigrateAction, ServerArgs, ServerCommand, ServerConfig};
+use axum::extract::FromRef;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post, put};
 use axum::{Json, Router};
+use chrono::Local;
+use clap::Parser;
 use database::init_pool;
 use serde_json::json;
 use sqlx::SqlitePool;
+use std::path::Path;
+use std::time::Duration;
 use tokio;
 
+const CHALLENGE_RESOLUTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// State shared by all routes. Handlers extract the parts they need via FromRef
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: SqlitePool,
+    pub jwt_secret: JwtSecret,
+    pub exercise_catalog: ExerciseCatalog,
+}
+impl FromRef<AppState> for SqlitePool {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.pool.clone()
+    }
+}
+impl FromRef<AppState> for JwtSecret {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.jwt_secret.clone()
+    }
+}
+impl FromRef<AppState> for ExerciseCatalog {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.exercise_catalog.clone()
+    }
+}
+
 #[derive(Debug)]
-#[allow(dead_code)] //TODO: construct variants `NotFound`, `InvalidInput`, and `InternalError`
+#[allow(dead_code)] //TODO: construct variant `InternalError`
 pub enum ApiError {
     NotFound,
     InvalidInput(String),
     InternalError,
     DatabaseError,
+    Unauthorized(String),
     HashError(argon2::password_hash::Error),
 }
 impl From<sqlx::Error> for ApiError {
@@ -52,6 +104,7 @@ impl IntoResponse for ApiError {
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Database error".to_string(),
             ),
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
             ApiError::HashError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Hash error".to_string()),
         };
 
@@ -64,40 +117,137 @@ impl IntoResponse for ApiError {
 }
 
 pub async fn server_main() {
-    let pool = create_database().await.expect("DB init failed");
+    let mut args = ServerArgs::parse();
+    match args.command.take() {
+        Some(ServerCommand::Migrate { action }) => run_migrate_command(args, action).await,
+        Some(ServerCommand::ImportExercises { folder }) => {
+            run_import_exercises_command(args, &folder).await
+        }
+        Some(ServerCommand::Reports) => run_reports_command(args).await,
+        None => run_server(args).await,
+    }
+}
+
+async fn run_server(args: ServerArgs) {
+    let config = match ServerConfig::load(args) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Invalid server configuration: {}", err.to_error_message());
+            std::process::exit(1);
+        }
+    };
+
+    let pool = create_database(&config.database_path)
+        .await
+        .expect("DB init failed");
 
     //test_database(&pool).await.expect("test_db_failed");
 
     println!("Launching Server!");
-    let app = create_app(pool);
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
+    tokio::spawn(resolve_challenges_periodically(pool.clone()));
+    let app = create_app(pool, &config.jwt_secret);
+    let listener = tokio::net::TcpListener::bind(config.bind_address)
         .await
         .expect("failed to bind tcp listener");
-    println!("Server running on http://localhost:3000");
+    println!("Server running on http://{}", config.bind_address);
 
     axum::serve(listener, app)
         .await
         .expect("failed to start server")
 }
 
-pub fn create_app(pool: SqlitePool) -> Router {
+pub fn create_app(pool: SqlitePool, jwt_secret: &str) -> Router {
+    let app_state = AppState {
+        pool,
+        jwt_secret: JwtSecret::new(jwt_secret),
+        exercise_catalog: ExerciseCatalog::default(),
+    };
+
     Router::new()
         .route("/server", get(health_check))
         .route("/user/login", post(check_login))
         .route("/user/register", post(register))
-        .route("/mascot/buy", post(buy_mascot))
+        .route("/user/token/refresh", post(refresh_tokens))
+        .route("/user/logout", post(logout))
+        .route("/mascot/roll", post(roll_mascot))
         .route("/mascot/get", get(get_mascot_data))
         .route("/mascot/select", post(select_mascot))
         .route("/workout/save", post(save_workout))
+        .route(
+            "/workout/{id}",
+            get(get_workout).put(update_workout).delete(delete_workout),
+        )
         .route("/preset/save", post(save_preset))
-        .route("/user/exercises", get(get_user_exercises))
+        .route("/preset/{id}", put(update_preset).delete(delete_preset))
+        .route("/exercises/catalog", get(get_exercise_catalog))
+        .route("/user/exercises/sets", get(get_user_exercise_sets))
+        .route(
+            "/user/exercises/custom",
+            get(get_user_custom_exercises).post(create_custom_exercise),
+        )
         .route("/user/presets", get(get_user_presets))
+        .route("/user/presets/order", put(reorder_presets))
         .route("/user/info/get", get(get_user_info))
         .route("/user/info/update", post(update_user_info))
+        .route("/user/account/password", post(change_password))
+        .route("/user/account/username", post(change_username))
+        .route("/user/account/delete", post(delete_account))
+        .route(
+            "/user/logs/{goal_type}",
+            get(get_user_logs).put(upsert_user_log),
+        )
+        .route("/user/logs/{goal_type}/{date}", delete(delete_user_log))
         .route("/user/foreign/get", get(get_foreign_users))
-        .route("/user/foreign/add_friend", post(add_friend))
+        .route(
+            "/user/foreign/send_friend_request",
+            post(send_friend_request),
+        )
+        .route(
+            "/user/foreign/answer_friend_request",
+            post(answer_friend_request),
+        )
         .route("/user/foreign/remove_friend", post(remove_friend))
-        .with_state(pool)
+        .route("/user/foreign/block", post(block_user))
+        .route("/user/foreign/unblock", post(unblock_user))
+        .route("/user/foreign/blocked", get(get_blocked_users))
+        .route("/user/foreign/report", post(report_user))
+        .route("/social/feed", get(get_friends_feed))
+        .route("/social/leaderboard", get(get_leaderboard))
+        .route(
+            "/social/challenges",
+            get(get_challenges).post(create_challenge),
+        )
+        .route("/social/challenges/answer", post(answer_challenge))
+        .route("/social/workouts/{username}", get(get_profile_workouts))
+        .route("/workout/{id}/reactions", get(get_workout_reactions))
+        .route(
+            "/workout/{id}/kudos",
+            post(give_kudos).delete(take_back_kudos),
+        )
+        .route("/workout/{id}/comments", post(add_comment))
+        .route(
+            "/workout/{id}/comments/{comment_id}",
+            delete(delete_comment),
+        )
+        .route(
+            "/user/workouts/reactions",
+            get(get_reactions_on_own_workouts),
+        )
+        .with_state(app_state)
+}
+
+/// Resolves expired challenges every hour, so winners are paid without opening the Social tab
+async fn resolve_challenges_periodically(pool: SqlitePool) {
+    let mut interval = tokio::time::interval(CHALLENGE_RESOLUTION_INTERVAL);
+    loop {
+        interval.tick().await;
+        match database_challenge::resolve_expired_challenges(&pool, Local::now().date_naive()).await
+        {
+            Ok(0) => {}
+            Ok(resolved) => println!("Resolved {resolved} expired challenges"),
+            Err(err) => eprintln!("Resolving challenges failed: {err}"),
+        }
+    }
 }
 
 async fn health_check() -> impl IntoResponse {
@@ -107,8 +257,113 @@ async fn health_check() -> impl IntoResponse {
     }))
 }
 
-pub async fn create_database() -> Result<SqlitePool, sqlx::Error> {
-    let pool = init_pool().await?;
-    init_db(&pool).await?;
+/// Opens the database and applies all pending migrations
+pub async fn create_database(database_path: &Path) -> Result<SqlitePool, sqlx::Error> {
+    let pool = init_pool(database_path).await?;
+    for migration in run_migrations(&pool).await? {
+        println!(
+            "Applied migration {}: {}",
+            migration.version, migration.description
+        );
+    }
     Ok(pool)
 }
+
+async fn run_migrate_command(args: ServerArgs, action: MigrateAction) {
+    let database_path = match ServerConfig::load_database_path(args) {
+        Ok(database_path) => database_path,
+        Err(err) => {
+            eprintln!("Invalid server configuration: {}", err.to_error_message());
+            std::process::exit(1);
+        }
+    };
+
+    let result = match init_pool(&database_path).await {
+        Ok(pool) => match action {
+            MigrateAction::Status => print_migration_status(&pool).await,
+            MigrateAction::Up => create_database(&database_path).await.map(|_| {
+                println!("Database {} is up to date!", database_path.display());
+            }),
+        },
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        eprintln!("Migration failed: {err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run_import_exercises_command(args: ServerArgs, folder: &Path) {
+    let database_path = match ServerConfig::load_database_path(args) {
+        Ok(database_path) => database_path,
+        Err(err) => {
+            eprintln!("Invalid server configuration: {}", err.to_error_message());
+            std::process::exit(1);
+        }
+    };
+    let pool = match create_database(&database_path).await {
+        Ok(pool) => pool,
+        Err(err) => {
+            eprintln!("Could not open the database: {err}");
+            std::process::exit(1);
+        }
+    };
+
+    match import_exercises(&pool, folder).await {
+        Ok(report) => println!("{}", report.to_summary()),
+        Err(err) => {
+            eprintln!("{}", err.to_error_message());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints the open reports of user profiles for the moderators
+async fn run_reports_command(args: ServerArgs) {
+    let database_path = match ServerConfig::load_database_path(args) {
+        Ok(database_path) => database_path,
+        Err(err) => {
+            eprintln!("Invalid server configuration: {}", err.to_error_message());
+            std::process::exit(1);
+        }
+    };
+    let reports = match create_database(&database_path).await {
+        Ok(pool) => database_moderation::get_open_reports(&pool).await,
+        Err(err) => Err(err),
+    };
+
+    match reports {
+        Ok(reports) if reports.is_empty() => println!("There are no open reports!"),
+        Ok(reports) => {
+            for report in reports {
+                println!(
+                    "#{} {} reported {} on {}: {}\n    Description: {}",
+                    report.id,
+                    report.reporter,
+                    report.reported,
+                    report.created_at,
+                    report.reason,
+                    report.reported_description
+                );
+            }
+        }
+        Err(err) => {
+            eprintln!("Could not read the reports: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn print_migration_status(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    for status in get_migration_status(pool).await? {
+        let state = match &status.applied_at {
+            Some(applied_at) => format!("applied {applied_at}"),
+            None => "pending".to_string(),
+        };
+        println!(
+            "{:>3} {:<28} {}",
+            status.migration.version, state, status.migration.description
+        );
+    }
+    Ok(())
+}
@@ -9,6 +9,9 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 
+/// Only used by the test server, never use it for a real deployment
+pub const TEST_JWT_SECRET: &str = "buff_buddies_integration_test_jwt_secret";
+
 #[allow(dead_code)]
 pub fn client_setup() -> App {
     App::default()
@@ -35,7 +38,7 @@ pub async fn server_main_test(shutdown: oneshot::Receiver<()>) {
     let pool = setup_test_db().await;
     setup_test_mascots(&pool).await;
     //TODO add test exercises cause memory only database starts empty
-    let app = create_app(pool);
+    let app = create_app(pool, TEST_JWT_SECRET);
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
         .await
         .expect("failed to bind tcp listener");
@@ -85,12 +88,17 @@ pub async fn logged_in_setup() -> (App, oneshot::Sender<()>) {
     let _ = app.update(Message::Login(LoginMessage::UsernameEntered(
         "12345".to_string(),
     )));
-    let jwt = valid_register(app.login_state.try_login().unwrap())
+    let auth_tokens = valid_register(app.api_client.clone(), app.login_state.try_login().unwrap())
         .await
         .unwrap();
-    app.jsonwebtoken = Some(jwt);
+    app.api_client.set_auth_tokens(Some(auth_tokens));
     app.screen = Tab::Loading;
-    let user_data = request_login_data(app.jsonwebtoken.clone()).await.unwrap();
+    let user_data = request_login_data(
+        app.api_client.clone(),
+        app.exercise_catalog_cache_path.clone(),
+    )
+    .await
+    .unwrap();
     match Arc::try_unwrap(user_data) {
         Ok(data) => {
             app.update_app_on_login(data);